@@ -5,6 +5,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_prost_build::configure()
         .build_server(true)
         .build_client(true)
+        // Lets the audit log record request payloads as JSON.
+        .type_attribute(".coop.v1", "#[derive(serde::Serialize)]")
         .compile_protos(&["../../proto/coop/v1/coop.proto"], &["../../proto"])?;
 
     // Derive version from git tags so local builds show the real version
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Asciicast v2 (`.cast`) encoding and decoding.
+//!
+//! A cast file is a JSON header line followed by one `[time, code, data]`
+//! array per line, where `time` is seconds since the recording started and
+//! `code` is `"o"` (output), `"i"` (input), `"r"` (resize, `"COLSxROWS"`)
+//! or `"m"` (marker).
+
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::record::RecordingEntry;
+use crate::screen::{Screen, ScreenSnapshot};
+
+/// Asciicast v2 header (first line of a `.cast` file).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CastHeader {
+    pub version: u32,
+    pub width: u16,
+    pub height: u16,
+    /// Unix timestamp (seconds) of the recording start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl CastHeader {
+    pub fn new(width: u16, height: u16, timestamp_ms: u64) -> Self {
+        let timestamp = (timestamp_ms > 0).then_some(timestamp_ms / 1000);
+        Self { version: 2, width, height, timestamp, title: None }
+    }
+}
+
+/// A single timed event, serialized as `[time, code, data]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastEvent {
+    /// Seconds since the recording started.
+    pub time: f64,
+    pub code: String,
+    pub data: String,
+}
+
+impl CastEvent {
+    pub fn output(ts_ms: u64, data: impl Into<String>) -> Self {
+        Self { time: ts_ms as f64 / 1000.0, code: "o".to_owned(), data: data.into() }
+    }
+
+    pub fn resize(ts_ms: u64, cols: u16, rows: u16) -> Self {
+        Self { time: ts_ms as f64 / 1000.0, code: "r".to_owned(), data: format!("{cols}x{rows}") }
+    }
+
+    pub fn marker(ts_ms: u64, label: impl Into<String>) -> Self {
+        Self { time: ts_ms as f64 / 1000.0, code: "m".to_owned(), data: label.into() }
+    }
+
+    /// Event time in whole milliseconds.
+    pub fn ts_ms(&self) -> u64 {
+        (self.time.max(0.0) * 1000.0).round() as u64
+    }
+}
+
+impl Serialize for CastEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.time, &self.code, &self.data).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CastEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (time, code, data) = <(f64, String, String)>::deserialize(deserializer)?;
+        Ok(Self { time, code, data })
+    }
+}
+
+/// A parsed cast file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cast {
+    pub header: CastHeader,
+    pub events: Vec<CastEvent>,
+}
+
+impl Cast {
+    /// Parse a `.cast` file. Blank lines are skipped; malformed event lines
+    /// are an error.
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let mut lines = text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+        let (_, first) = lines.next().ok_or_else(|| anyhow::anyhow!("empty cast file"))?;
+        let header: CastHeader =
+            serde_json::from_str(first).map_err(|e| anyhow::anyhow!("invalid cast header: {e}"))?;
+        if header.version != 2 {
+            anyhow::bail!("unsupported asciicast version {}", header.version);
+        }
+        let events = lines
+            .map(|(n, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| anyhow::anyhow!("invalid cast event on line {}: {e}", n + 1))
+            })
+            .collect::<anyhow::Result<Vec<CastEvent>>>()?;
+        Ok(Self { header, events })
+    }
+
+    /// Serialize to `.cast` text (header line + one line per event).
+    pub fn to_text(&self) -> String {
+        let mut out = header_line(&self.header);
+        for event in &self.events {
+            out.push_str(&event_line(event));
+        }
+        out
+    }
+
+    /// Replay the cast through a virtual terminal, producing one recording
+    /// entry per distinct screen.
+    ///
+    /// Output events become `"output"` entries (deduplicated by screen
+    /// content), markers become `"marker"` entries with the label as detail,
+    /// and resizes become `"resize"` entries.
+    pub fn to_entries(&self) -> Vec<RecordingEntry> {
+        let mut screen = Screen::new(self.header.width, self.header.height);
+        let mut entries = Vec::new();
+        let mut last_hash = 0;
+        let push = |entries: &mut Vec<RecordingEntry>, ts, kind: &str, detail, snap| {
+            let seq = entries.len() as u64 + 1;
+            entries.push(RecordingEntry { ts, seq, kind: kind.to_owned(), detail, screen: snap });
+        };
+
+        for event in &self.events {
+            let ts = event.ts_ms();
+            match event.code.as_str() {
+                "o" => {
+                    screen.feed(event.data.as_bytes());
+                    let snap = screen.snapshot();
+                    let h = snapshot_hash(&snap);
+                    if h != last_hash {
+                        last_hash = h;
+                        push(&mut entries, ts, "output", serde_json::json!({}), snap);
+                    }
+                }
+                "r" => {
+                    if let Some((cols, rows)) = parse_size(&event.data) {
+                        screen.resize(cols, rows);
+                        let detail = serde_json::json!({ "cols": cols, "rows": rows });
+                        push(&mut entries, ts, "resize", detail, screen.snapshot());
+                    }
+                }
+                "m" => {
+                    let detail = serde_json::json!({ "label": event.data });
+                    push(&mut entries, ts, "marker", detail, screen.snapshot());
+                }
+                _ => {}
+            }
+        }
+        entries
+    }
+}
+
+/// Format the header as a newline-terminated line.
+pub fn header_line(header: &CastHeader) -> String {
+    let mut line = serde_json::to_string(header).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+/// Format an event as a newline-terminated line.
+pub fn event_line(event: &CastEvent) -> String {
+    let mut line = serde_json::to_string(event).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+/// Synthesize a cast from `recording.jsonl` contents.
+///
+/// Used when no raw-output track was kept: each snapshot entry becomes a
+/// full-screen redraw at its timestamp, and its kind is emitted as a marker.
+pub fn from_recording(jsonl: &str) -> anyhow::Result<Cast> {
+    let mut lines = jsonl.lines().filter(|l| !l.trim().is_empty());
+    let first = lines.next().ok_or_else(|| anyhow::anyhow!("empty recording"))?;
+    let header: serde_json::Value = serde_json::from_str(first)?;
+    let width = header["cols"].as_u64().unwrap_or(80) as u16;
+    let height = header["rows"].as_u64().unwrap_or(24) as u16;
+    let timestamp = header["timestamp"].as_u64().unwrap_or(0);
+
+    let mut events = Vec::new();
+    for line in lines {
+        let Ok(entry) = serde_json::from_str::<RecordingEntry>(line) else {
+            continue;
+        };
+        events.push(CastEvent::marker(entry.ts, entry.kind.clone()));
+        events.push(CastEvent::output(entry.ts, redraw(&entry.screen)));
+    }
+    Ok(Cast { header: CastHeader::new(width, height, timestamp), events })
+}
+
+/// Render a snapshot as a clear-and-redraw escape sequence.
+fn redraw(snap: &ScreenSnapshot) -> String {
+    let lines = if snap.ansi.is_empty() { &snap.lines } else { &snap.ansi };
+    let mut out = String::from("\x1b[H\x1b[2J");
+    out.push_str(&lines.join("\r\n"));
+    out.push_str(&format!("\x1b[{};{}H", snap.cursor.row + 1, snap.cursor.col + 1));
+    out
+}
+
+/// Split raw bytes into a decoded string and an incomplete trailing UTF-8
+/// sequence to carry into the next chunk.
+///
+/// Invalid bytes elsewhere are replaced with U+FFFD.
+pub fn split_utf8(buf: &[u8]) -> (String, Vec<u8>) {
+    let tail = crate::screen::incomplete_utf8_tail_len(buf);
+    let (head, rest) = buf.split_at(buf.len() - tail);
+    (String::from_utf8_lossy(head).into_owned(), rest.to_vec())
+}
+
+fn parse_size(data: &str) -> Option<(u16, u16)> {
+    let (cols, rows) = data.split_once('x')?;
+    Some((cols.trim().parse().ok()?, rows.trim().parse().ok()?))
+}
+
+fn snapshot_hash(snap: &ScreenSnapshot) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    snap.lines.hash(&mut hasher);
+    snap.cursor.row.hash(&mut hasher);
+    snap.cursor.col.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+#[path = "asciicast_tests.rs"]
+mod tests;
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::{split_utf8, Cast, CastEvent, CastHeader};
+
+const SAMPLE: &str = r#"{"version": 2, "width": 20, "height": 4, "timestamp": 1700000000}
+[0.5, "o", "hello\r\n"]
+[1.25, "m", "checkpoint"]
+[2.0, "o", "world"]
+[2.0, "o", ""]
+[3.0, "r", "30x5"]
+"#;
+
+#[test]
+fn parse_header_and_events() -> anyhow::Result<()> {
+    let cast = Cast::parse(SAMPLE)?;
+    assert_eq!(
+        cast.header,
+        CastHeader {
+            version: 2,
+            width: 20,
+            height: 4,
+            timestamp: Some(1_700_000_000),
+            title: None
+        }
+    );
+    assert_eq!(cast.events.len(), 5);
+    assert_eq!(cast.events[1], CastEvent::marker(1250, "checkpoint"));
+    assert_eq!(cast.events[0].ts_ms(), 500);
+    Ok(())
+}
+
+#[test]
+fn roundtrip_through_text() -> anyhow::Result<()> {
+    let cast = Cast::parse(SAMPLE)?;
+    assert_eq!(Cast::parse(&cast.to_text())?, cast);
+    Ok(())
+}
+
+#[yare::parameterized(
+    empty = { "" },
+    wrong_version = { r#"{"version": 1, "width": 80, "height": 24}"# },
+    bad_event = { "{\"version\": 2, \"width\": 80, \"height\": 24}\n[1.0, \"o\"]\n" },
+)]
+fn parse_rejects(text: &str) {
+    assert!(Cast::parse(text).is_err());
+}
+
+#[test]
+fn to_entries_replays_screen() -> anyhow::Result<()> {
+    let entries = Cast::parse(SAMPLE)?.to_entries();
+    let kinds: Vec<&str> = entries.iter().map(|e| e.kind.as_str()).collect();
+    // The empty output event does not change the screen, so it is deduplicated.
+    assert_eq!(kinds, vec!["output", "marker", "output", "resize"]);
+    assert_eq!(entries[0].screen.lines[0], "hello");
+    assert_eq!(entries[1].detail["label"], "checkpoint");
+    assert_eq!(entries[2].screen.lines[1], "world");
+    assert_eq!(entries[2].ts, 2000);
+    assert_eq!((entries[3].screen.cols, entries[3].screen.rows), (30, 5));
+    let seqs: Vec<u64> = entries.iter().map(|e| e.seq).collect();
+    assert_eq!(seqs, vec![1, 2, 3, 4]);
+    Ok(())
+}
+
+#[test]
+fn from_recording_synthesizes_redraws() -> anyhow::Result<()> {
+    let jsonl = concat!(
+        r#"{"version":1,"cols":40,"rows":10,"timestamp":1700000000500}"#,
+        "\n",
+        r#"{"ts":1500,"seq":1,"kind":"state","detail":{},"screen":{"lines":["idle"],"ansi":[],"cols":40,"rows":10,"alt_screen":false,"cursor":{"row":0,"col":4},"sequence":3}}"#,
+        "\n",
+    );
+    let cast = super::from_recording(jsonl)?;
+    assert_eq!((cast.header.width, cast.header.height), (40, 10));
+    assert_eq!(cast.header.timestamp, Some(1_700_000_000));
+    assert_eq!(cast.events.len(), 2);
+    assert_eq!(cast.events[0], CastEvent::marker(1500, "state"));
+    assert_eq!(cast.events[1].time, 1.5);
+    assert!(cast.events[1].data.starts_with("\x1b[H\x1b[2J"));
+
+    // The synthesized cast imports back to the same screen text.
+    let entries = cast.to_entries();
+    assert_eq!(entries.last().map(|e| e.screen.lines[0].as_str()), Some("idle"));
+    Ok(())
+}
+
+#[yare::parameterized(
+    ascii = { b"abc" as &[u8], "abc", b"" as &[u8] },
+    split_two_byte = { &[b'a', 0xC3], "a", &[0xC3] },
+    split_three_byte = { &[0xE2, 0x94], "", &[0xE2, 0x94] },
+    complete = { "é".as_bytes(), "é", b"" },
+    invalid_middle = { &[b'a', 0xFF, b'b'], "a\u{FFFD}b", b"" },
+)]
+fn split_utf8_holds_back_partial(input: &[u8], text: &str, rest: &[u8]) {
+    let (t, r) = split_utf8(input);
+    assert_eq!(t, text);
+    assert_eq!(r, rest);
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Append-only audit log of mutating API calls.
+//!
+//! Every input, nudge, respond, signal, switch, config write and similar call
+//! is appended to `audit.jsonl` in the session directory with the caller's
+//! identity, the transport and a redacted copy of the request payload.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+/// File name of the audit log within the session directory.
+pub const AUDIT_FILE: &str = "audit.jsonl";
+
+/// Strings and arrays longer than this are elided from payloads.
+const MAX_PAYLOAD_VALUE_LEN: usize = 4096;
+
+/// Transport an audited call arrived on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditTransport {
+    Http,
+    Ws,
+    Grpc,
+}
+
+impl AuditTransport {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Ws => "ws",
+            Self::Grpc => "grpc",
+        }
+    }
+}
+
+/// A single audited call.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp_ms: u64,
+    /// Token name, or the peer address when no token was presented.
+    pub caller: String,
+    pub transport: AuditTransport,
+    /// Transport-native operation name (e.g. `POST /api/v1/agent/nudge`,
+    /// `nudge`, `Nudge`).
+    pub operation: String,
+    /// Request payload with secrets redacted and large values elided.
+    pub payload: Value,
+}
+
+/// Audit catchup response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditCatchupResponse {
+    pub entries: Vec<AuditEntry>,
+}
+
+/// File-backed append-only audit log.
+///
+/// Like [`EventLog`](crate::event_log::EventLog), catchup reads the file
+/// rather than an in-memory buffer. Sequence numbers continue from the
+/// highest one in the existing file so they stay unique across restarts.
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    /// Next sequence number. Held while an entry is appended so entries land
+    /// in the file in sequence order.
+    seq: Mutex<u64>,
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+impl AuditLog {
+    /// Create an audit log. If `session_dir` is `None` (tests/attach mode),
+    /// nothing is written and catchup always returns empty.
+    pub fn new(session_dir: Option<&std::path::Path>) -> Self {
+        let path = session_dir.map(|dir| {
+            let _ = std::fs::create_dir_all(dir);
+            dir.join(AUDIT_FILE)
+        });
+        let log = Self { path, seq: Mutex::new(0) };
+        let next = log.read_entries().iter().map(|e| e.seq + 1).max().unwrap_or(0);
+        *log.seq.lock() = next;
+        log
+    }
+
+    /// Append an entry for a call. `payload` is redacted before writing.
+    pub fn record(
+        &self,
+        caller: &str,
+        transport: AuditTransport,
+        operation: impl Into<String>,
+        payload: Value,
+    ) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        let payload = redact(payload);
+        let mut seq = self.seq.lock();
+        let entry = AuditEntry {
+            seq: *seq,
+            timestamp_ms: now_ms(),
+            caller: caller.to_owned(),
+            transport,
+            operation: operation.into(),
+            payload,
+        };
+        let Ok(mut line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        line.push('\n');
+        let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) else {
+            return;
+        };
+        let _ = file.write_all(line.as_bytes());
+        *seq += 1;
+    }
+
+    /// Read entries with seq >= `since_seq`.
+    pub fn catchup(&self, since_seq: u64) -> Vec<AuditEntry> {
+        self.read_entries().into_iter().filter(|e| e.seq >= since_seq).collect()
+    }
+
+    fn read_entries(&self) -> Vec<AuditEntry> {
+        let Some(ref path) = self.path else {
+            return vec![];
+        };
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return vec![];
+        };
+        contents.lines().filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok()).collect()
+    }
+}
+
+/// Whether an object key names a secret whose value must not be logged.
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    ["credential", "token", "secret", "password", "api_key", "apikey"]
+        .iter()
+        .any(|needle| key.contains(needle))
+}
+
+/// Redact secret-named fields and elide oversized strings and arrays
+/// (raw input, upload data).
+pub fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let v = if is_secret_key(&k) { Value::from("[redacted]") } else { redact(v) };
+                    (k, v)
+                })
+                .collect(),
+        ),
+        Value::Array(items) if items.len() > MAX_PAYLOAD_VALUE_LEN => {
+            Value::from(format!("[{} items elided]", items.len()))
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        Value::String(s) if s.len() > MAX_PAYLOAD_VALUE_LEN => {
+            Value::from(format!("[{} bytes elided]", s.len()))
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+#[path = "audit_tests.rs"]
+mod tests;
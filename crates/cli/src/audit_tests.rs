@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use serde_json::json;
+
+use super::{redact, AuditLog, AuditTransport};
+
+#[test]
+fn record_and_catchup() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let log = AuditLog::new(Some(dir.path()));
+    log.record("dashboard", AuditTransport::Http, "POST /api/v1/input", json!({"text": "ls"}));
+    log.record("ops", AuditTransport::Grpc, "Nudge", json!({"message": "go"}));
+
+    let all = log.catchup(0);
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].seq, 0);
+    assert_eq!(all[0].caller, "dashboard");
+    assert_eq!(all[0].payload, json!({"text": "ls"}));
+    assert_eq!(all[1].transport, AuditTransport::Grpc);
+
+    let tail = log.catchup(1);
+    assert_eq!(tail.len(), 1);
+    assert_eq!(tail[0].operation, "Nudge");
+    Ok(())
+}
+
+#[test]
+fn seq_continues_after_reopen() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    AuditLog::new(Some(dir.path())).record("a", AuditTransport::Ws, "shutdown", json!({}));
+
+    let reopened = AuditLog::new(Some(dir.path()));
+    reopened.record("b", AuditTransport::Ws, "shutdown", json!({}));
+    let seqs: Vec<u64> = reopened.catchup(0).iter().map(|e| e.seq).collect();
+    assert_eq!(seqs, [0, 1]);
+    Ok(())
+}
+
+#[test]
+fn seq_continues_from_the_highest_entry() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let lines = [
+        json!({"seq": 7, "timestamp_ms": 0, "caller": "a", "transport": "http", "operation": "x", "payload": {}}),
+        json!({"seq": 3, "timestamp_ms": 0, "caller": "a", "transport": "http", "operation": "x", "payload": {}}),
+    ];
+    let contents: String = lines.iter().map(|l| format!("{l}\n")).collect();
+    std::fs::write(dir.path().join(super::AUDIT_FILE), contents)?;
+
+    let log = AuditLog::new(Some(dir.path()));
+    log.record("b", AuditTransport::Ws, "shutdown", json!({}));
+    assert_eq!(log.catchup(8).iter().map(|e| e.seq).collect::<Vec<_>>(), [8]);
+    Ok(())
+}
+
+#[test]
+fn concurrent_records_land_in_seq_order() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let log = AuditLog::new(Some(dir.path()));
+    std::thread::scope(|scope| {
+        for t in 0..8 {
+            let log = &log;
+            scope.spawn(move || {
+                for _ in 0..25 {
+                    log.record(
+                        &format!("t{t}"),
+                        AuditTransport::Http,
+                        "POST /api/v1/input",
+                        json!({}),
+                    );
+                }
+            });
+        }
+    });
+
+    let seqs: Vec<u64> = log.catchup(0).iter().map(|e| e.seq).collect();
+    assert_eq!(seqs, (0..200).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn no_session_dir_records_nothing() {
+    let log = AuditLog::new(None);
+    log.record("a", AuditTransport::Http, "POST /api/v1/shutdown", json!({}));
+    assert!(log.catchup(0).is_empty());
+}
+
+#[test]
+fn redacts_secrets_and_large_values() {
+    let payload = json!({
+        "profile": "main",
+        "credentials": {"ANTHROPIC_API_KEY": "sk-123"},
+        "profiles": [{"name": "a", "credentials": {"X": "y"}}],
+        "auth_token": "t",
+        "data": "x".repeat(5000),
+        "text": "hello",
+    });
+    assert_eq!(
+        redact(payload),
+        json!({
+            "profile": "main",
+            "credentials": "[redacted]",
+            "profiles": [{"name": "a", "credentials": "[redacted]"}],
+            "auth_token": "[redacted]",
+            "data": "[5000 bytes elided]",
+            "text": "hello",
+        })
+    );
+}
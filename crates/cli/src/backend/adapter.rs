@@ -240,6 +240,258 @@ impl Backend for TmuxBackend {
     }
 }
 
+/// Compatibility backend that attaches to an existing GNU screen session.
+///
+/// Output is read by tailing a screen logfile (`log on`), seeded with a
+/// `hardcopy` of the current window so the first frame is not blank.
+/// Input is delivered with `stuff`.
+pub struct ScreenBackend {
+    session: String,
+    socket_dir: Option<std::path::PathBuf>,
+    poll_interval: Duration,
+    workdir: tempfile::TempDir,
+}
+
+impl ScreenBackend {
+    /// Create a new `ScreenBackend` for the given screen session.
+    ///
+    /// Validates the session exists via `screen -X select .`.
+    pub fn new(session: String) -> anyhow::Result<Self> {
+        Self::with_socket_dir(session, None)
+    }
+
+    /// Create a new `ScreenBackend` targeting a specific socket directory.
+    ///
+    /// When `socket_dir` is `Some`, every screen invocation runs with
+    /// `SCREENDIR=<path>` to address isolated sessions instead of the
+    /// user's default.
+    pub fn with_socket_dir(
+        session: String,
+        socket_dir: Option<std::path::PathBuf>,
+    ) -> anyhow::Result<Self> {
+        let mut cmd = std::process::Command::new("screen");
+        if let Some(ref d) = socket_dir {
+            cmd.env("SCREENDIR", d);
+        }
+        cmd.args(["-S", &session, "-X", "select", "."])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null());
+
+        match cmd.status() {
+            Ok(s) if s.success() => {}
+            Ok(_) => anyhow::bail!("screen session '{session}' does not exist"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                anyhow::bail!("screen is not installed or not in PATH")
+            }
+            Err(e) => return Err(anyhow::Error::new(e).context("failed to check screen session")),
+        }
+
+        let workdir = tempfile::Builder::new().prefix("coop-screen-").tempdir()?;
+        Ok(Self { session, socket_dir, poll_interval: Duration::from_millis(250), workdir })
+    }
+
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Returns the session name.
+    pub fn session(&self) -> &str {
+        &self.session
+    }
+
+    /// Build a `std::process::Command` for `screen -S <session> -X <args>`.
+    fn screen_cmd(&self, args: &[&str]) -> std::process::Command {
+        let mut cmd = std::process::Command::new("screen");
+        if let Some(ref d) = self.socket_dir {
+            cmd.env("SCREENDIR", d);
+        }
+        cmd.args(["-S", &self.session, "-X"])
+            .args(args)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null());
+        cmd
+    }
+
+    /// Run `screen -S <session> -X <args>`, returning whether it succeeded.
+    async fn screen_x(&self, args: &[&str]) -> bool {
+        let mut cmd = tokio::process::Command::from(self.screen_cmd(args));
+        matches!(cmd.status().await, Ok(s) if s.success())
+    }
+
+    /// Capture the current window via `hardcopy` as a clear-and-redraw frame.
+    ///
+    /// Screen writes the file asynchronously after the command returns, so
+    /// the read is retried briefly.
+    async fn hardcopy_frame(&self) -> Option<Bytes> {
+        let path = self.workdir.path().join("hardcopy");
+        let path_str = path.to_string_lossy().into_owned();
+        if !self.screen_x(&["hardcopy", &path_str]).await {
+            return None;
+        }
+        for _ in 0..20 {
+            if let Ok(text) = tokio::fs::read_to_string(&path).await {
+                let body = text.trim_end_matches('\n').replace('\n', "\r\n");
+                return Some(Bytes::from(format!("\x1b[H\x1b[2J{body}")));
+            }
+            tokio::time::sleep(Duration::from_millis(25)).await;
+        }
+        None
+    }
+}
+
+impl Backend for ScreenBackend {
+    fn run(
+        &mut self,
+        output_tx: mpsc::Sender<Bytes>,
+        mut input_rx: mpsc::Receiver<BackendInput>,
+        mut resize_rx: mpsc::Receiver<(u16, u16)>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<ExitStatus>> + Send + '_>> {
+        Box::pin(async move {
+            let gone = ExitStatus { code: None, signal: None };
+            let log_path = self.workdir.path().join("screenlog");
+            let log_str = log_path.to_string_lossy().into_owned();
+
+            let ok = self.screen_x(&["logfile", &log_str]).await
+                && self.screen_x(&["logfile", "flush", "0"]).await
+                && self.screen_x(&["log", "on"]).await;
+            if !ok {
+                return Ok(gone);
+            }
+
+            if let Some(frame) = self.hardcopy_frame().await {
+                if output_tx.send(frame).await.is_err() {
+                    self.screen_x(&["log", "off"]).await;
+                    return Ok(gone);
+                }
+            }
+
+            let mut interval = tokio::time::interval(self.poll_interval);
+            let mut offset: u64 = 0;
+
+            let status = loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        let chunk = read_from(&log_path, offset).await;
+                        if chunk.is_empty() {
+                            // Nothing new — confirm the session is still there.
+                            if !self.screen_x(&["select", "."]).await {
+                                break gone;
+                            }
+                            continue;
+                        }
+                        offset += chunk.len() as u64;
+                        if output_tx.send(Bytes::from(chunk)).await.is_err() {
+                            break gone;
+                        }
+                    }
+                    data = input_rx.recv() => {
+                        match data {
+                            Some(BackendInput::Write(bytes)) => {
+                                let text = stuff_escape(&bytes);
+                                if !self.screen_x(&["stuff", &text]).await {
+                                    break gone;
+                                }
+                            }
+                            Some(BackendInput::Drain(tx)) => {
+                                let _ = tx.send(());
+                            }
+                            None => break gone,
+                        }
+                    }
+                    resize = resize_rx.recv() => {
+                        if let Some((cols, rows)) = resize {
+                            let _ = self.screen_x(&[
+                                "width", "-w", &cols.to_string(), &rows.to_string(),
+                            ]).await;
+                        }
+                    }
+                }
+            };
+
+            // Best effort: the session may already be gone.
+            self.screen_x(&["log", "off"]).await;
+            Ok(status)
+        })
+    }
+
+    fn resize(&self, cols: u16, rows: u16) -> anyhow::Result<()> {
+        let status =
+            self.screen_cmd(&["width", "-w", &cols.to_string(), &rows.to_string()]).status()?;
+
+        if !status.success() {
+            anyhow::bail!("screen width failed");
+        }
+        Ok(())
+    }
+
+    fn child_pid(&self) -> Option<u32> {
+        let mut cmd = std::process::Command::new("screen");
+        if let Some(ref d) = self.socket_dir {
+            cmd.env("SCREENDIR", d);
+        }
+        // `screen -ls` exits non-zero even when sessions are listed, so
+        // only the output is inspected.
+        let output = cmd.args(["-ls", &self.session]).output().ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let server_pid = parse_session_pid(&text, &self.session)?;
+
+        // The window process is a child of the screen server; fall back to
+        // the server itself when the child list is unavailable.
+        let children =
+            std::fs::read_to_string(format!("/proc/{server_pid}/task/{server_pid}/children"))
+                .unwrap_or_default();
+        children.split_whitespace().next().and_then(|p| p.parse().ok()).or(Some(server_pid))
+    }
+}
+
+/// Read everything in `path` past `offset`. Missing files read as empty.
+async fn read_from(path: &std::path::Path, offset: u64) -> Vec<u8> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let Ok(mut file) = tokio::fs::File::open(path).await else {
+        return Vec::new();
+    };
+    if file.seek(std::io::SeekFrom::Start(offset)).await.is_err() {
+        return Vec::new();
+    }
+    let mut buf = Vec::new();
+    let _ = file.read_to_end(&mut buf).await;
+    buf
+}
+
+/// Escape raw bytes for `screen -X stuff`.
+///
+/// Screen's command parser interprets `^X` and backslash escapes, so any
+/// byte outside a conservative printable set is written as a `\ooo` octal
+/// escape, which screen decodes back to the original byte.
+pub fn stuff_escape(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for &b in bytes {
+        let plain = b.is_ascii_alphanumeric()
+            || matches!(b, b' ' | b'.' | b',' | b'-' | b'_' | b'/' | b':' | b'=' | b'+' | b'@');
+        if plain {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("\\{b:03o}"));
+        }
+    }
+    out
+}
+
+/// Find the screen server PID for `session` in `screen -ls` output.
+///
+/// Sessions are listed as `\t<pid>.<name>\t(<state>)`; `session` may be
+/// either the bare name or the full `<pid>.<name>` form.
+pub fn parse_session_pid(ls_output: &str, session: &str) -> Option<u32> {
+    ls_output.lines().find_map(|line| {
+        let id = line.split_whitespace().next()?;
+        let (pid, name) = id.split_once('.')?;
+        let pid: u32 = pid.parse().ok()?;
+        (name == session || id == session).then_some(pid)
+    })
+}
+
 #[cfg(test)]
 #[path = "adapter_tests.rs"]
 mod tests;
@@ -38,3 +38,29 @@ fn parse_no_colon() {
     let result: Result<AdapterSpec, _> = "tmux".parse();
     assert!(result.is_err());
 }
+
+#[test]
+fn stuff_escape_passes_plain_text() {
+    assert_eq!(stuff_escape(b"echo hello"), "echo hello");
+}
+
+#[test]
+fn stuff_escape_octal_encodes_specials() {
+    assert_eq!(stuff_escape(b"a\r"), "a\\015");
+    assert_eq!(stuff_escape(b"^C\\$"), "\\136C\\134\\044");
+    assert_eq!(stuff_escape(b"\x1b[A"), "\\033\\133A");
+    assert_eq!(stuff_escape("é".as_bytes()), "\\303\\251");
+}
+
+#[test]
+fn parse_session_pid_matches_name() {
+    let ls = "There are screens on:\n\t4242.agent\t(Detached)\n\t99.other\t(Attached)\n2 Sockets in /run/screen/S-me.\n";
+    assert_eq!(parse_session_pid(ls, "agent"), Some(4242));
+    assert_eq!(parse_session_pid(ls, "99.other"), Some(99));
+    assert_eq!(parse_session_pid(ls, "missing"), None);
+}
+
+#[test]
+fn parse_session_pid_no_sockets() {
+    assert_eq!(parse_session_pid("No Sockets found in /run/screen/S-me.\n", "agent"), None);
+}
@@ -0,0 +1,349 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Per-session usage budgets.
+//!
+//! The `budget` section of the `--agent-config` file (or
+//! `PUT /api/v1/config/budget` at runtime) caps the session's cost, output
+//! tokens and API time. A watcher task checks every usage update: crossing a
+//! `warn_at_percent` threshold broadcasts a `warning` event, and crossing a
+//! limit broadcasts `exceeded`. The session loop then runs the configured
+//! action (park, open the stop gate, or shut down).
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use utoipa::ToSchema;
+
+use crate::transport::Store;
+use crate::usage::SessionUsage;
+
+/// Reason reported in `AgentState::Parked` by the `park` action.
+pub const BUDGET_PARK_REASON: &str = "budget_exceeded";
+
+/// Budget section of the agent config file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BudgetConfig {
+    /// Maximum cost in USD, measured against the estimated cost so agents
+    /// that only report tokens are capped too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_usd: Option<f64>,
+    /// Maximum output tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    /// Maximum cumulative API time in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_api_secs: Option<u64>,
+    /// Broadcast a `warning` event when usage crosses these percentages of a limit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warn_at_percent: Vec<u32>,
+    /// What to do once a limit is exceeded.
+    #[serde(default)]
+    pub action: BudgetAction,
+}
+
+impl BudgetConfig {
+    /// Whether any limit is set.
+    pub fn is_enabled(&self) -> bool {
+        self.max_cost_usd.is_some()
+            || self.max_output_tokens.is_some()
+            || self.max_api_secs.is_some()
+    }
+
+    /// Current usage measured against each configured limit.
+    pub fn limits(&self, usage: &SessionUsage) -> Vec<LimitStatus> {
+        let limits = [
+            (BudgetLimit::CostUsd, self.max_cost_usd, usage.estimated_cost_usd),
+            (
+                BudgetLimit::OutputTokens,
+                self.max_output_tokens.map(|m| m as f64),
+                usage.output_tokens as f64,
+            ),
+            (
+                BudgetLimit::ApiSecs,
+                self.max_api_secs.map(|m| m as f64),
+                usage.total_api_ms as f64 / 1000.0,
+            ),
+        ];
+        limits
+            .into_iter()
+            .filter_map(|(limit, max, used)| {
+                let max = max?;
+                let percent = if max > 0.0 { used / max * 100.0 } else { 100.0 };
+                Some(LimitStatus { limit, max, used, percent })
+            })
+            .collect()
+    }
+}
+
+/// What happens when a budget limit is exceeded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAction {
+    /// Only broadcast the `exceeded` event.
+    #[default]
+    Warn,
+    /// Interrupt a working agent, report it as `Parked` and drop its input
+    /// until the budget is raised.
+    Park,
+    /// Switch the stop hook to `allow` so it stops blocking the agent from
+    /// finishing.
+    OpenStopGate,
+    /// Shut the session down (gracefully, like `POST /api/v1/shutdown`).
+    Shutdown,
+}
+
+impl BudgetAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Warn => "warn",
+            Self::Park => "park",
+            Self::OpenStopGate => "open_stop_gate",
+            Self::Shutdown => "shutdown",
+        }
+    }
+}
+
+/// A budgeted quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetLimit {
+    CostUsd,
+    OutputTokens,
+    ApiSecs,
+}
+
+impl BudgetLimit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CostUsd => "cost_usd",
+            Self::OutputTokens => "output_tokens",
+            Self::ApiSecs => "api_secs",
+        }
+    }
+}
+
+/// Usage measured against one limit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LimitStatus {
+    pub limit: BudgetLimit,
+    pub max: f64,
+    pub used: f64,
+    pub percent: f64,
+}
+
+/// Budget state reported alongside session usage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BudgetStatus {
+    pub limits: Vec<LimitStatus>,
+    pub action: BudgetAction,
+    /// Highest `warn_at_percent` threshold crossed so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warned_percent: Option<u32>,
+    /// The limit that was exceeded, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exceeded: Option<BudgetLimit>,
+}
+
+/// Kind of budget event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetEventKind {
+    /// Usage crossed a `warn_at_percent` threshold.
+    Warning,
+    /// Usage reached a limit; `action` runs.
+    Exceeded,
+    /// The budget was raised above current usage after being exceeded.
+    Cleared,
+}
+
+impl BudgetEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Exceeded => "exceeded",
+            Self::Cleared => "cleared",
+        }
+    }
+}
+
+/// A budget event, broadcast to WebSocket and NATS consumers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetEvent {
+    pub kind: BudgetEventKind,
+    /// The limit closest to (or over) its maximum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<LimitStatus>,
+    /// Threshold crossed (for `warning` events).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u32>,
+    pub action: BudgetAction,
+}
+
+struct Tracker {
+    config: BudgetConfig,
+    warned_percent: Option<u32>,
+    exceeded: Option<BudgetLimit>,
+}
+
+/// Runtime state for usage budgets.
+pub struct BudgetState {
+    tracker: Mutex<Tracker>,
+    /// Broadcast channel for budget events.
+    pub budget_tx: broadcast::Sender<BudgetEvent>,
+}
+
+impl BudgetState {
+    pub fn new(config: BudgetConfig) -> Self {
+        let (budget_tx, _) = broadcast::channel(16);
+        Self {
+            tracker: Mutex::new(Tracker { config, warned_percent: None, exceeded: None }),
+            budget_tx,
+        }
+    }
+
+    /// Current budget configuration.
+    pub fn config(&self) -> BudgetConfig {
+        self.tracker.lock().config.clone()
+    }
+
+    /// Replace the configuration and re-check `usage` against it. Lifting an
+    /// exceeded budget broadcasts `cleared`; lowering a limit below current
+    /// usage broadcasts `exceeded` right away.
+    pub fn set_config(&self, config: BudgetConfig, usage: &SessionUsage) {
+        let mut tracker = self.tracker.lock();
+        tracker.config = config;
+        let limits = tracker.config.limits(usage);
+        let top = limits.iter().map(|l| l.percent).fold(0.0, f64::max);
+        tracker.warned_percent = tracker.warned_percent.filter(|&w| top >= f64::from(w));
+        let cleared = tracker.exceeded.is_some() && !limits.iter().any(|l| l.percent >= 100.0);
+        if cleared {
+            tracker.exceeded = None;
+            let action = tracker.config.action;
+            self.emit(BudgetEvent {
+                kind: BudgetEventKind::Cleared,
+                limit: None,
+                threshold: None,
+                action,
+            });
+        }
+        self.check(&mut tracker, limits);
+    }
+
+    /// Check a usage snapshot, broadcasting any newly crossed thresholds.
+    pub fn observe(&self, usage: &SessionUsage) {
+        let mut tracker = self.tracker.lock();
+        let limits = tracker.config.limits(usage);
+        self.check(&mut tracker, limits);
+    }
+
+    /// Budget status for `usage`, or `None` when no limit is configured.
+    pub fn status(&self, usage: &SessionUsage) -> Option<BudgetStatus> {
+        let tracker = self.tracker.lock();
+        if !tracker.config.is_enabled() {
+            return None;
+        }
+        Some(BudgetStatus {
+            limits: tracker.config.limits(usage),
+            action: tracker.config.action,
+            warned_percent: tracker.warned_percent,
+            exceeded: tracker.exceeded,
+        })
+    }
+
+    /// The `exceeded` event for a budget that is already over its limit, so
+    /// a session loop that starts after it was broadcast (usage restored on
+    /// resume, or a new session after a switch) still runs the action.
+    pub fn exceeded_event(&self) -> Option<BudgetEvent> {
+        let tracker = self.tracker.lock();
+        tracker.exceeded?;
+        Some(BudgetEvent {
+            kind: BudgetEventKind::Exceeded,
+            limit: None,
+            threshold: None,
+            action: tracker.config.action,
+        })
+    }
+
+    /// Whether an exceeded budget should keep the agent parked.
+    pub fn keeps_parked(&self) -> bool {
+        let tracker = self.tracker.lock();
+        tracker.exceeded.is_some() && tracker.config.action == BudgetAction::Park
+    }
+
+    fn check(&self, tracker: &mut Tracker, limits: Vec<LimitStatus>) {
+        let Some(top) = limits.iter().max_by(|a, b| a.percent.total_cmp(&b.percent)) else {
+            return;
+        };
+        let action = tracker.config.action;
+        let crossed = tracker
+            .config
+            .warn_at_percent
+            .iter()
+            .copied()
+            .filter(|&p| top.percent >= f64::from(p))
+            .filter(|&p| tracker.warned_percent.is_none_or(|w| p > w))
+            .max();
+        if let Some(threshold) = crossed {
+            tracker.warned_percent = Some(threshold);
+            self.emit(BudgetEvent {
+                kind: BudgetEventKind::Warning,
+                limit: Some(top.clone()),
+                threshold: Some(threshold),
+                action,
+            });
+        }
+        if tracker.exceeded.is_none() && top.percent >= 100.0 {
+            tracker.exceeded = Some(top.limit);
+            self.emit(BudgetEvent {
+                kind: BudgetEventKind::Exceeded,
+                limit: Some(top.clone()),
+                threshold: None,
+                action,
+            });
+        }
+    }
+
+    fn emit(&self, event: BudgetEvent) {
+        // Ignore send errors (no receivers is fine).
+        let _ = self.budget_tx.send(event);
+    }
+}
+
+impl std::fmt::Debug for BudgetState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tracker = self.tracker.lock();
+        f.debug_struct("BudgetState")
+            .field("config", &tracker.config)
+            .field("exceeded", &tracker.exceeded)
+            .finish()
+    }
+}
+
+/// Spawn the task that checks every usage update against the budget.
+pub fn spawn_watcher(store: Arc<Store>, shutdown: CancellationToken) {
+    let mut usage_rx = store.usage.usage_tx.subscribe();
+    tokio::spawn(async move {
+        // Usage restored from a previous run may already be over budget.
+        store.budget.observe(&store.usage.snapshot().await);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                event = usage_rx.recv() => match event {
+                    Ok(event) => store.budget.observe(&event.cumulative),
+                    Err(broadcast::error::RecvError::Lagged(_)) => {
+                        store.budget.observe(&store.usage.snapshot().await);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+#[path = "budget_tests.rs"]
+mod tests;
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+fn usage(cost: f64, output_tokens: u64) -> SessionUsage {
+    SessionUsage {
+        total_cost_usd: cost,
+        estimated_cost_usd: cost,
+        output_tokens,
+        ..Default::default()
+    }
+}
+
+fn drain(rx: &mut broadcast::Receiver<BudgetEvent>) -> Vec<(BudgetEventKind, Option<u32>)> {
+    std::iter::from_fn(|| rx.try_recv().ok()).map(|e| (e.kind, e.threshold)).collect()
+}
+
+#[test]
+fn deserialize_from_agent_config() -> anyhow::Result<()> {
+    let config: BudgetConfig = serde_json::from_value(serde_json::json!({
+        "max_cost_usd": 25.0,
+        "max_api_secs": 3600,
+        "warn_at_percent": [50, 80],
+        "action": "open_stop_gate",
+    }))?;
+    assert_eq!(config.max_cost_usd, Some(25.0));
+    assert_eq!(config.max_output_tokens, None);
+    assert_eq!(config.action, BudgetAction::OpenStopGate);
+    assert!(config.is_enabled());
+    assert!(!BudgetConfig::default().is_enabled());
+    Ok(())
+}
+
+#[test]
+fn warns_once_per_threshold_then_exceeds() {
+    let state = BudgetState::new(BudgetConfig {
+        max_cost_usd: Some(10.0),
+        warn_at_percent: vec![50, 80],
+        action: BudgetAction::Park,
+        ..Default::default()
+    });
+    let mut rx = state.budget_tx.subscribe();
+
+    state.observe(&usage(4.0, 0));
+    assert!(drain(&mut rx).is_empty());
+    state.observe(&usage(5.5, 0));
+    state.observe(&usage(6.0, 0));
+    assert_eq!(drain(&mut rx), vec![(BudgetEventKind::Warning, Some(50))]);
+    assert!(!state.keeps_parked());
+
+    state.observe(&usage(12.0, 0));
+    state.observe(&usage(13.0, 0));
+    assert_eq!(
+        drain(&mut rx),
+        vec![(BudgetEventKind::Warning, Some(80)), (BudgetEventKind::Exceeded, None)]
+    );
+    assert!(state.keeps_parked());
+}
+
+#[test]
+fn cost_limit_uses_the_estimated_cost() {
+    let state = BudgetState::new(BudgetConfig {
+        max_cost_usd: Some(1.0),
+        action: BudgetAction::Park,
+        ..Default::default()
+    });
+    let mut rx = state.budget_tx.subscribe();
+
+    // Codex and Gemini report tokens only; the cost comes from the price table.
+    let tokens_only =
+        SessionUsage { total_cost_usd: 0.0, estimated_cost_usd: 1.25, ..Default::default() };
+    state.observe(&tokens_only);
+    assert_eq!(drain(&mut rx), vec![(BudgetEventKind::Exceeded, None)]);
+    assert!(state.keeps_parked());
+}
+
+#[test]
+fn status_reports_the_tightest_limit() -> anyhow::Result<()> {
+    let state = BudgetState::new(BudgetConfig {
+        max_cost_usd: Some(10.0),
+        max_output_tokens: Some(1_000),
+        ..Default::default()
+    });
+    state.observe(&usage(1.0, 1_500));
+
+    let status = state.status(&usage(1.0, 1_500)).ok_or_else(|| anyhow::anyhow!("no status"))?;
+    assert_eq!(status.limits.len(), 2);
+    assert_eq!(status.limits[1].limit, BudgetLimit::OutputTokens);
+    assert!((status.limits[1].percent - 150.0).abs() < f64::EPSILON);
+    assert_eq!(status.exceeded, Some(BudgetLimit::OutputTokens));
+    assert!(BudgetState::new(BudgetConfig::default()).status(&usage(1.0, 0)).is_none());
+    Ok(())
+}
+
+#[test]
+fn raising_the_limit_clears_and_lowering_exceeds() {
+    let limited = |max| BudgetConfig {
+        max_output_tokens: Some(max),
+        action: BudgetAction::Park,
+        ..Default::default()
+    };
+    let state = BudgetState::new(limited(100));
+    let mut rx = state.budget_tx.subscribe();
+    let used = usage(0.0, 150);
+
+    state.observe(&used);
+    state.set_config(limited(200), &used);
+    assert!(!state.keeps_parked());
+    state.set_config(limited(120), &used);
+    assert_eq!(
+        drain(&mut rx),
+        vec![
+            (BudgetEventKind::Exceeded, None),
+            (BudgetEventKind::Cleared, None),
+            (BudgetEventKind::Exceeded, None),
+        ]
+    );
+}
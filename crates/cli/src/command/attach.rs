@@ -151,12 +151,12 @@ impl AttachState {
 }
 
 /// RAII guard that restores terminal attributes on drop.
-struct RawModeGuard {
+pub(crate) struct RawModeGuard {
     original: termios::Termios,
 }
 
 impl RawModeGuard {
-    fn enter() -> anyhow::Result<Self> {
+    pub(crate) fn enter() -> anyhow::Result<Self> {
         let stdin = std::io::stdin();
         let original = termios::tcgetattr(&stdin)?;
         let mut raw = original.clone();
@@ -176,7 +176,7 @@ impl Drop for RawModeGuard {
     }
 }
 
-fn terminal_size() -> Option<(u16, u16)> {
+pub(crate) fn terminal_size() -> Option<(u16, u16)> {
     let ws = rustix::termios::tcgetwinsize(std::io::stdout()).ok()?;
     if ws.ws_col > 0 && ws.ws_row > 0 {
         Some((ws.ws_col, ws.ws_row))
@@ -1,10 +1,11 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
-//! CLI subcommands: `attach`, `open`, `send`, `cred`, `peek`.
+//! CLI subcommands: `attach`, `open`, `send`, `cred`, `peek`, `replay`.
 
 pub mod attach;
 pub mod cred;
 pub mod open;
 pub mod peek;
+pub mod replay;
 pub mod send;
@@ -0,0 +1,583 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! `coop replay` — play back a session recording in the terminal.
+//!
+//! Reads a `recording.jsonl` (or an asciicast `.cast` file) and steps through
+//! its entries offline, rendering each screen snapshot with its kind and
+//! detail on a status line. Playback can be paused, sped up, seeked, and
+//! jumped to the next state transition or hook event.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use super::attach::{terminal_size, RawModeGuard};
+use crate::asciicast::Cast;
+use crate::record::RecordingEntry;
+
+/// CLI arguments for `coop replay`.
+#[derive(Debug, clap::Args)]
+pub struct ReplayArgs {
+    /// Recording file (`recording.jsonl` or an asciicast `.cast`).
+    pub file: PathBuf,
+
+    /// Playback speed multiplier.
+    #[arg(long, default_value_t = 1.0)]
+    pub speed: f64,
+
+    /// Start position: a time offset (`90`, `1m30s`, `1h`) or `#SEQ`.
+    #[arg(long)]
+    pub seek: Option<String>,
+
+    /// Longest pause between entries in seconds, before speed (0 = no cap).
+    #[arg(long, default_value_t = 2.0)]
+    pub max_wait: f64,
+
+    /// Print every entry and exit instead of playing interactively.
+    #[arg(long)]
+    pub print: bool,
+
+    /// Show plain text (no ANSI escape codes).
+    #[arg(long)]
+    pub plain: bool,
+}
+
+/// Key bindings shown on the status line.
+const HELP: &str = "space pause  ←/→ step  s/S state  h/H hook  +/- speed  g/G start/end  q quit";
+
+/// Hide cursor, disable autowrap (long ANSI lines are clipped, not wrapped).
+const ENTER: &[u8] = b"\x1b[?1049h\x1b[?25l\x1b[?7l\x1b[2J\x1b[H";
+const LEAVE: &[u8] = b"\x1b[?7h\x1b[?25h\x1b[?1049l";
+
+const MIN_SPEED: f64 = 0.125;
+const MAX_SPEED: f64 = 64.0;
+
+/// A loaded recording.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub cols: u16,
+    pub rows: u16,
+    pub entries: Vec<RecordingEntry>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&text)
+    }
+
+    /// Parse `recording.jsonl` or asciicast v2 text, detected from the
+    /// header line.
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let mut lines = text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+        let (_, first) = lines.next().ok_or_else(|| anyhow::anyhow!("empty recording"))?;
+        let header: serde_json::Value = serde_json::from_str(first)
+            .map_err(|e| anyhow::anyhow!("invalid recording header: {e}"))?;
+
+        if header.get("width").is_some() {
+            let cast = Cast::parse(text)?;
+            let entries = cast.to_entries();
+            return Ok(Self { cols: cast.header.width, rows: cast.header.height, entries });
+        }
+
+        let cols = header.get("cols").and_then(|v| v.as_u64()).unwrap_or(80) as u16;
+        let rows = header.get("rows").and_then(|v| v.as_u64()).unwrap_or(24) as u16;
+        let mut entries = Vec::new();
+        for (n, line) in lines {
+            let entry: RecordingEntry = serde_json::from_str(line)
+                .map_err(|e| anyhow::anyhow!("invalid entry on line {}: {e}", n + 1))?;
+            entries.push(entry);
+        }
+        Ok(Self { cols, rows, entries })
+    }
+}
+
+/// A playback start position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seek {
+    /// Milliseconds since the recording started.
+    Time(u64),
+    /// Recording sequence number.
+    Seq(u64),
+}
+
+/// Parse a seek position: `#SEQ`, plain seconds (`90`, `1.5`), or unit
+/// suffixed components (`1m30s`, `2h`, `500ms`).
+pub fn parse_seek(s: &str) -> anyhow::Result<Seek> {
+    let s = s.trim();
+    let invalid = || anyhow::anyhow!("invalid seek position: {s}");
+    if s.is_empty() {
+        return Err(invalid());
+    }
+    if let Some(seq) = s.strip_prefix('#') {
+        let seq = seq.parse().map_err(|_| anyhow::anyhow!("invalid sequence number: {s}"))?;
+        return Ok(Seek::Seq(seq));
+    }
+    if let Ok(secs) = s.parse::<f64>() {
+        if secs.is_finite() && secs >= 0.0 {
+            return Ok(Seek::Time((secs * 1000.0).round() as u64));
+        }
+    }
+
+    let mut total_ms: u64 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let scale = match &rest[..unit_len] {
+            "h" => 3_600_000,
+            "m" => 60_000,
+            "s" => 1_000,
+            "ms" => 1,
+            _ => return Err(invalid()),
+        };
+        total_ms = total_ms.saturating_add(value.saturating_mul(scale));
+        rest = &rest[unit_len..];
+    }
+    Ok(Seek::Time(total_ms))
+}
+
+/// Playback cursor over a recording's entries.
+#[derive(Debug)]
+pub struct Player {
+    entries: Vec<RecordingEntry>,
+    pos: usize,
+    speed: f64,
+    paused: bool,
+    max_wait: Option<Duration>,
+}
+
+impl Player {
+    pub fn new(entries: Vec<RecordingEntry>) -> Self {
+        Self { entries, pos: 0, speed: 1.0, paused: false, max_wait: None }
+    }
+
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+        self
+    }
+
+    /// Cap the (unscaled) gap between entries.
+    pub fn with_max_wait(mut self, max_wait: Option<Duration>) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    pub fn current(&self) -> Option<&RecordingEntry> {
+        self.entries.get(self.pos)
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.pos + 1 >= self.entries.len()
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed * 2.0).min(MAX_SPEED);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = (self.speed / 2.0).max(MIN_SPEED);
+    }
+
+    /// Move to the first entry at or after `seek`, or the last entry if the
+    /// position is past the end.
+    pub fn seek(&mut self, seek: Seek) {
+        let found = self.entries.iter().position(|e| match seek {
+            Seek::Time(ms) => e.ts >= ms,
+            Seek::Seq(seq) => e.seq >= seq,
+        });
+        self.pos = found.unwrap_or(self.entries.len().saturating_sub(1));
+    }
+
+    pub fn first(&mut self) {
+        self.pos = 0;
+    }
+
+    pub fn last(&mut self) {
+        self.pos = self.entries.len().saturating_sub(1);
+    }
+
+    /// Step forward one entry. Returns false at the end.
+    pub fn forward(&mut self) -> bool {
+        if self.at_end() {
+            return false;
+        }
+        self.pos += 1;
+        true
+    }
+
+    /// Step back one entry. Returns false at the start.
+    pub fn back(&mut self) -> bool {
+        if self.pos == 0 {
+            return false;
+        }
+        self.pos -= 1;
+        true
+    }
+
+    /// Jump to the next entry of `kind`. Returns false (and stays put) if
+    /// there is none.
+    pub fn next_kind(&mut self, kind: &str) -> bool {
+        let found = self.entries.iter().skip(self.pos + 1).position(|e| e.kind == kind);
+        match found {
+            Some(offset) => {
+                self.pos += offset + 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Jump to the previous entry of `kind`. Returns false (and stays put)
+    /// if there is none.
+    pub fn prev_kind(&mut self, kind: &str) -> bool {
+        let found =
+            self.entries[..self.pos.min(self.entries.len())].iter().rposition(|e| e.kind == kind);
+        match found {
+            Some(pos) => {
+                self.pos = pos;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Wall-clock wait before advancing to the next entry, or `None` when
+    /// paused or at the end.
+    pub fn delay(&self) -> Option<Duration> {
+        if self.paused || self.at_end() {
+            return None;
+        }
+        let gap = self.entries[self.pos + 1].ts.saturating_sub(self.entries[self.pos].ts);
+        let mut gap = Duration::from_millis(gap);
+        if let Some(max) = self.max_wait {
+            gap = gap.min(max);
+        }
+        Some(gap.div_f64(self.speed))
+    }
+}
+
+/// One-line description of an entry's detail.
+pub fn summarize(entry: &RecordingEntry) -> String {
+    let detail = &entry.detail;
+    let field = |key: &str| detail.get(key).and_then(|v| v.as_str()).unwrap_or("?");
+    match entry.kind.as_str() {
+        "state" => {
+            let mut s = format!("{} → {}", field("prev"), field("next"));
+            if let Some(cause) = detail.get("cause").and_then(|v| v.as_str()) {
+                if !cause.is_empty() {
+                    s.push_str(&format!(" ({cause})"));
+                }
+            }
+            s
+        }
+        "hook" => {
+            let json = detail.get("json");
+            let event = json
+                .and_then(|j| j.get("event"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("?")
+                .to_owned();
+            let tool = json.and_then(|j| j.pointer("/data/tool_name")).and_then(|v| v.as_str());
+            match tool {
+                Some(tool) => format!("{event} {tool}"),
+                None => event,
+            }
+        }
+        "resize" => format!("{}x{}", entry.screen.cols, entry.screen.rows),
+        "marker" => field("label").to_owned(),
+        _ => match detail {
+            serde_json::Value::Object(map) if map.is_empty() => String::new(),
+            serde_json::Value::Null => String::new(),
+            other => other.to_string(),
+        },
+    }
+}
+
+/// Format milliseconds as `mm:ss.t` (or `h:mm:ss.t` past an hour).
+pub fn format_ts(ms: u64) -> String {
+    let tenths = (ms % 1000) / 100;
+    let secs = ms / 1000;
+    let (h, m, s) = (secs / 3600, (secs / 60) % 60, secs % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}.{tenths}")
+    } else {
+        format!("{m:02}:{s:02}.{tenths}")
+    }
+}
+
+/// Entry header: `#seq +mm:ss.t kind summary`.
+fn entry_title(entry: &RecordingEntry) -> String {
+    let summary = summarize(entry);
+    let mut title = format!("#{} +{} {}", entry.seq, format_ts(entry.ts), entry.kind);
+    if !summary.is_empty() {
+        title.push(' ');
+        title.push_str(&summary);
+    }
+    title
+}
+
+/// Playback key actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Quit,
+    TogglePause,
+    Next,
+    Prev,
+    NextState,
+    PrevState,
+    NextHook,
+    PrevHook,
+    Faster,
+    Slower,
+    First,
+    Last,
+}
+
+/// Decode raw terminal input into key actions. Unknown bytes are ignored.
+pub fn parse_keys(buf: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < buf.len() {
+        if buf[i..].starts_with(b"\x1b[C") {
+            keys.push(Key::Next);
+            i += 3;
+            continue;
+        }
+        if buf[i..].starts_with(b"\x1b[D") {
+            keys.push(Key::Prev);
+            i += 3;
+            continue;
+        }
+        let key = match buf[i] {
+            b'q' | 0x03 => Some(Key::Quit),
+            b' ' => Some(Key::TogglePause),
+            b'n' | b'l' => Some(Key::Next),
+            b'p' => Some(Key::Prev),
+            b's' => Some(Key::NextState),
+            b'S' => Some(Key::PrevState),
+            b'h' => Some(Key::NextHook),
+            b'H' => Some(Key::PrevHook),
+            b'+' | b'=' => Some(Key::Faster),
+            b'-' | b'_' => Some(Key::Slower),
+            b'g' => Some(Key::First),
+            b'G' => Some(Key::Last),
+            _ => None,
+        };
+        keys.extend(key);
+        i += 1;
+    }
+    keys
+}
+
+/// Run the `coop replay` subcommand. Returns a process exit code.
+pub async fn run(args: &ReplayArgs) -> i32 {
+    let recording = match Recording::load(&args.file) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 1;
+        }
+    };
+    if recording.entries.is_empty() {
+        eprintln!("error: {} has no entries", args.file.display());
+        return 1;
+    }
+    if !(args.speed.is_finite() && args.speed > 0.0) {
+        eprintln!("error: --speed must be positive");
+        return 2;
+    }
+
+    let max_wait = (args.max_wait > 0.0).then(|| Duration::from_secs_f64(args.max_wait));
+    let mut player = Player::new(recording.entries).with_speed(args.speed).with_max_wait(max_wait);
+    if let Some(ref seek) = args.seek {
+        match parse_seek(seek) {
+            Ok(seek) => player.seek(seek),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return 2;
+            }
+        }
+    }
+
+    let interactive = std::io::IsTerminal::is_terminal(&std::io::stdin())
+        && std::io::IsTerminal::is_terminal(&std::io::stdout());
+    if args.print || !interactive {
+        print_all(&mut player, args.plain);
+        return 0;
+    }
+    play(player, args.plain).await
+}
+
+/// Print entries from the current position to the end.
+fn print_all(player: &mut Player, plain: bool) {
+    let mut stdout = std::io::stdout().lock();
+    loop {
+        if let Some(entry) = player.current() {
+            let _ = writeln!(stdout, "--- {} ---", entry_title(entry));
+            let lines = if plain { &entry.screen.lines } else { &entry.screen.ansi };
+            for line in lines {
+                let _ = writeln!(stdout, "{}", line.trim_end());
+            }
+        }
+        if !player.forward() {
+            break;
+        }
+    }
+}
+
+/// Interactive playback loop.
+async fn play(mut player: Player, plain: bool) -> i32 {
+    let raw_guard = match RawModeGuard::enter() {
+        Ok(g) => g,
+        Err(e) => {
+            eprintln!("error: failed to enter raw mode: {e}");
+            return 1;
+        }
+    };
+
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(ENTER);
+
+    let (stdin_tx, mut stdin_rx) = mpsc::channel::<Vec<u8>>(64);
+    std::thread::spawn(move || {
+        use std::io::Read;
+        let mut handle = std::io::stdin().lock();
+        let mut buf = [0u8; 256];
+        loop {
+            match handle.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if stdin_tx.blocking_send(buf[..n].to_vec()).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    let mut sigwinch =
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::window_change()).ok();
+
+    render(&mut stdout, &player, plain);
+    loop {
+        let delay = player.delay();
+        let sleep = tokio::time::sleep(delay.unwrap_or(Duration::MAX));
+        tokio::select! {
+            _ = sleep, if delay.is_some() => {
+                player.forward();
+            }
+            input = stdin_rx.recv() => {
+                let Some(input) = input else { break };
+                let mut quit = false;
+                for key in parse_keys(&input) {
+                    match key {
+                        Key::Quit => quit = true,
+                        Key::TogglePause => player.toggle_pause(),
+                        Key::Next => { player.forward(); }
+                        Key::Prev => { player.back(); }
+                        Key::NextState => { player.next_kind("state"); }
+                        Key::PrevState => { player.prev_kind("state"); }
+                        Key::NextHook => { player.next_kind("hook"); }
+                        Key::PrevHook => { player.prev_kind("hook"); }
+                        Key::Faster => player.faster(),
+                        Key::Slower => player.slower(),
+                        Key::First => player.first(),
+                        Key::Last => player.last(),
+                    }
+                }
+                if quit {
+                    break;
+                }
+            }
+            _ = async {
+                match sigwinch.as_mut() {
+                    Some(s) => { s.recv().await; }
+                    None => std::future::pending::<()>().await,
+                }
+            } => {
+                let _ = stdout.write_all(b"\x1b[2J");
+            }
+        }
+        render(&mut stdout, &player, plain);
+    }
+
+    let _ = stdout.write_all(LEAVE);
+    let _ = stdout.flush();
+    drop(raw_guard);
+    0
+}
+
+/// Draw the current entry's screen and the status line.
+fn render(stdout: &mut std::io::Stdout, player: &Player, plain: bool) {
+    let Some(entry) = player.current() else {
+        return;
+    };
+    let (cols, rows) = terminal_size().unwrap_or((80, 24));
+    let content_rows = rows.saturating_sub(1) as usize;
+    let lines = if plain { &entry.screen.lines } else { &entry.screen.ansi };
+
+    let mut out = String::from("\x1b[?2026h");
+    for row in 0..content_rows {
+        out.push_str(&format!("\x1b[{};1H\x1b[0m\x1b[2K", row + 1));
+        if let Some(line) = lines.get(row) {
+            out.push_str(line);
+        }
+    }
+
+    let state = if player.is_paused() {
+        "paused"
+    } else if player.at_end() {
+        "end"
+    } else {
+        ""
+    };
+    let status = format!(
+        " [replay] {}/{} {} | {}x {} | {HELP}",
+        player.pos() + 1,
+        player.len(),
+        entry_title(entry),
+        player.speed(),
+        state,
+    );
+    let max = cols as usize;
+    let status =
+        if status.len() > max { &status[..status.floor_char_boundary(max)] } else { &status };
+    out.push_str(&format!("\x1b[{rows};1H\x1b[0m\x1b[7m{status:<max$}\x1b[0m\x1b[?2026l"));
+    let _ = stdout.write_all(out.as_bytes());
+    let _ = stdout.flush();
+}
+
+#[cfg(test)]
+#[path = "replay_tests.rs"]
+mod tests;
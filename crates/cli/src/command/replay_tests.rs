@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::time::Duration;
+
+use super::{format_ts, parse_keys, parse_seek, summarize, Key, Player, Recording, Seek};
+use crate::record::RecordingEntry;
+use crate::screen::Screen;
+
+fn entry(seq: u64, ts: u64, kind: &str, detail: serde_json::Value) -> RecordingEntry {
+    RecordingEntry { ts, seq, kind: kind.to_owned(), detail, screen: Screen::new(10, 2).snapshot() }
+}
+
+fn sample() -> Vec<RecordingEntry> {
+    let empty = serde_json::json!({});
+    vec![
+        entry(
+            1,
+            0,
+            "state",
+            serde_json::json!({"prev":"starting","next":"working","cause":"hook"}),
+        ),
+        entry(2, 1_000, "hook", serde_json::json!({"hook_seq":0,"json":{"event":"stop"}})),
+        entry(3, 5_000, "screen", empty.clone()),
+        entry(4, 60_000, "state", serde_json::json!({"prev":"working","next":"idle","cause":""})),
+        entry(5, 61_000, "screen", empty),
+    ]
+}
+
+#[yare::parameterized(
+    seconds = { "90", Seek::Time(90_000) },
+    fractional = { "1.5", Seek::Time(1_500) },
+    minutes_seconds = { "1m30s", Seek::Time(90_000) },
+    hours = { "2h", Seek::Time(7_200_000) },
+    millis = { "250ms", Seek::Time(250) },
+    seq = { "#42", Seek::Seq(42) },
+    padded = { "  10s ", Seek::Time(10_000) },
+)]
+fn parse_seek_valid(input: &str, expected: Seek) -> anyhow::Result<()> {
+    assert_eq!(parse_seek(input)?, expected);
+    Ok(())
+}
+
+#[yare::parameterized(
+    empty = { "" },
+    bad_unit = { "5d" },
+    no_number = { "m" },
+    bad_seq = { "#x" },
+    negative = { "-3" },
+)]
+fn parse_seek_invalid(input: &str) {
+    assert!(parse_seek(input).is_err());
+}
+
+#[test]
+fn seek_by_time_and_seq() {
+    let mut player = Player::new(sample());
+    player.seek(Seek::Time(2_000));
+    assert_eq!(player.pos(), 2);
+    player.seek(Seek::Seq(4));
+    assert_eq!(player.pos(), 3);
+    // Past the end clamps to the last entry.
+    player.seek(Seek::Time(999_000));
+    assert_eq!(player.pos(), 4);
+}
+
+#[test]
+fn step_and_jump_by_kind() {
+    let mut player = Player::new(sample());
+    assert!(!player.back());
+    assert!(player.next_kind("state"));
+    assert_eq!(player.pos(), 3);
+    assert!(!player.next_kind("state"));
+    assert_eq!(player.pos(), 3);
+    assert!(player.prev_kind("hook"));
+    assert_eq!(player.pos(), 1);
+    assert!(!player.prev_kind("hook"));
+    assert!(player.prev_kind("state"));
+    assert_eq!(player.pos(), 0);
+
+    player.last();
+    assert!(player.at_end());
+    assert!(!player.forward());
+    player.first();
+    assert!(player.forward());
+    assert_eq!(player.pos(), 1);
+}
+
+#[test]
+fn delay_scales_with_speed_and_cap() {
+    let mut player = Player::new(sample()).with_speed(2.0);
+    assert_eq!(player.delay(), Some(Duration::from_millis(500)));
+
+    player.seek(Seek::Seq(3));
+    assert_eq!(player.delay(), Some(Duration::from_millis(27_500)));
+
+    let mut capped = Player::new(sample()).with_max_wait(Some(Duration::from_secs(2)));
+    capped.seek(Seek::Seq(3));
+    assert_eq!(capped.delay(), Some(Duration::from_secs(2)));
+
+    capped.toggle_pause();
+    assert_eq!(capped.delay(), None);
+    capped.toggle_pause();
+    capped.last();
+    assert_eq!(capped.delay(), None);
+}
+
+#[test]
+fn speed_is_clamped() {
+    let mut player = Player::new(sample()).with_speed(1000.0);
+    assert_eq!(player.speed(), 64.0);
+    for _ in 0..20 {
+        player.slower();
+    }
+    assert_eq!(player.speed(), 0.125);
+    player.faster();
+    assert_eq!(player.speed(), 0.25);
+}
+
+#[test]
+fn summarize_entries() {
+    let entries = sample();
+    assert_eq!(summarize(&entries[0]), "starting → working (hook)");
+    assert_eq!(summarize(&entries[1]), "stop");
+    assert_eq!(summarize(&entries[2]), "");
+    assert_eq!(summarize(&entries[3]), "working → idle");
+
+    let tool = entry(
+        6,
+        0,
+        "hook",
+        serde_json::json!({"json":{"event":"pre_tool_use","data":{"tool_name":"Bash"}}}),
+    );
+    assert_eq!(summarize(&tool), "pre_tool_use Bash");
+}
+
+#[yare::parameterized(
+    zero = { 0, "00:00.0" },
+    tenths = { 1_250, "00:01.2" },
+    minutes = { 61_000, "01:01.0" },
+    hours = { 3_723_400, "1:02:03.4" },
+)]
+fn format_ts_cases(ms: u64, expected: &str) {
+    assert_eq!(format_ts(ms), expected);
+}
+
+#[test]
+fn parse_keys_decodes_arrows_and_letters() {
+    assert_eq!(
+        parse_keys(b"\x1b[C \x1b[Dsh+-Gq?"),
+        vec![
+            Key::Next,
+            Key::TogglePause,
+            Key::Prev,
+            Key::NextState,
+            Key::NextHook,
+            Key::Faster,
+            Key::Slower,
+            Key::Last,
+            Key::Quit,
+        ]
+    );
+}
+
+#[test]
+fn recording_parses_jsonl() -> anyhow::Result<()> {
+    let mut text = String::from("{\"version\":1,\"cols\":100,\"rows\":30,\"timestamp\":0}\n\n");
+    for e in sample() {
+        text.push_str(&serde_json::to_string(&e)?);
+        text.push('\n');
+    }
+    let recording = Recording::parse(&text)?;
+    assert_eq!((recording.cols, recording.rows), (100, 30));
+    assert_eq!(recording.entries, sample());
+    Ok(())
+}
+
+#[test]
+fn recording_parses_asciicast() -> anyhow::Result<()> {
+    let text = "{\"version\":2,\"width\":20,\"height\":3}\n[0.5,\"o\",\"hi\"]\n[1.0,\"m\",\"x\"]\n";
+    let recording = Recording::parse(text)?;
+    assert_eq!((recording.cols, recording.rows), (20, 3));
+    let kinds: Vec<&str> = recording.entries.iter().map(|e| e.kind.as_str()).collect();
+    assert_eq!(kinds, vec!["output", "marker"]);
+    Ok(())
+}
+
+#[test]
+fn recording_rejects_bad_entry() {
+    let text = "{\"version\":1,\"cols\":80,\"rows\":24,\"timestamp\":0}\nnot json\n";
+    let err = Recording::parse(text).err().map(|e| e.to_string()).unwrap_or_default();
+    assert!(err.contains("line 2"), "{err}");
+}
@@ -78,6 +78,10 @@ pub struct Config {
     #[arg(long, env = "COOP_AUTH_TOKEN")]
     pub auth_token: Option<String>,
 
+    /// Named API tokens with per-route scopes (TOML or JSON).
+    #[arg(long, env = "COOP_AUTH_TOKENS_FILE")]
+    pub auth_tokens_file: Option<PathBuf>,
+
     /// Agent type (claude, codex, gemini, unknown). Auto-detected from command if omitted.
     #[arg(long, env = "COOP_AGENT")]
     pub agent: Option<String>,
@@ -86,7 +90,15 @@ pub struct Config {
     #[arg(long, env = "COOP_AGENT_CONFIG")]
     pub agent_config: Option<PathBuf>,
 
-    /// Attach to an existing session (e.g. tmux:session-name).
+    /// Declarative driver definition (TOML or JSON) for `--agent unknown`.
+    #[arg(long, env = "COOP_DRIVER_CONFIG")]
+    pub driver_config: Option<PathBuf>,
+
+    /// Per-model token prices (TOML or JSON) layered over the built-in list.
+    #[arg(long, env = "COOP_PRICING_FILE")]
+    pub pricing_file: Option<PathBuf>,
+
+    /// Attach to an existing session (e.g. tmux:session-name, screen:session-name).
     #[arg(long, env = "COOP_ATTACH")]
     pub attach: Option<String>,
 
@@ -102,6 +114,11 @@ pub struct Config {
     #[arg(long, env = "COOP_RING_SIZE", default_value = "1048576")]
     pub ring_size: usize,
 
+    /// Spill raw output to a compressed on-disk log in the session directory
+    /// so replay can reach back past the ring buffer.
+    #[arg(long, env = "COOP_RING_SPILL")]
+    pub ring_spill: bool,
+
     /// TERM environment variable for the child process.
     #[arg(long, env = "TERM", default_value = "xterm-256color")]
     pub term: String,
@@ -114,6 +131,11 @@ pub struct Config {
     #[arg(long, env = "COOP_LOG_LEVEL", default_value = "info")]
     pub log_level: String,
 
+    /// OTLP/gRPC collector endpoint for trace export (e.g. http://localhost:4317).
+    /// Tracing export is disabled when unset.
+    #[arg(long, env = "COOP_OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
+
     /// Resume a previous session. Accepts a .jsonl log path, a workspace
     /// path (e.g. /Users/me/myapp), or a project directory name.
     #[arg(long, env = "COOP_RESUME", value_name = "HINT")]
@@ -127,6 +149,10 @@ pub struct Config {
     #[arg(long, env = "COOP_RECORD")]
     pub record: bool,
 
+    /// Also keep a timed raw-output track (asciicast v2) while recording.
+    #[arg(long, env = "COOP_RECORD_OUTPUT")]
+    pub record_output: bool,
+
     /// NATS server URL (e.g. nats://localhost:4222). Enables NATS publishing when set.
     #[arg(long, env = "COOP_NATS_URL")]
     pub nats_url: Option<String>,
@@ -207,6 +233,8 @@ pub struct Config {
     #[clap(skip)]
     pub tmux_poll_ms: Option<u64>,
     #[clap(skip)]
+    pub gnu_screen_poll_ms: Option<u64>,
+    #[clap(skip)]
     pub reap_poll_ms: Option<u64>,
     #[clap(skip)]
     pub input_delay_ms: Option<u64>,
@@ -276,6 +304,11 @@ impl Config {
         // Validate groom level
         let groom = self.groom_level()?;
 
+        // Declarative definitions only drive otherwise-unknown agents
+        if self.driver_config.is_some() && self.agent_enum()? != AgentType::Unknown {
+            anyhow::bail!("--driver-config is only supported with --agent unknown");
+        }
+
         // --resume is only valid with --agent claude and cannot combine with --attach
         if self.resume.is_some() {
             if self.agent_enum()? != AgentType::Claude {
@@ -316,6 +349,7 @@ impl Config {
     duration_field!(screen_poll, screen_poll_ms, "COOP_SCREEN_POLL_MS", 3_000);
     duration_field!(log_poll, log_poll_ms, "COOP_LOG_POLL_MS", 3_000);
     duration_field!(tmux_poll, tmux_poll_ms, "COOP_TMUX_POLL_MS", 1_000);
+    duration_field!(gnu_screen_poll, gnu_screen_poll_ms, "COOP_GNU_SCREEN_POLL_MS", 250);
     duration_field!(reap_poll, reap_poll_ms, "COOP_REAP_POLL_MS", 50);
     duration_field!(input_delay, input_delay_ms, "COOP_INPUT_DELAY_MS", 200);
     duration_field!(
@@ -343,18 +377,24 @@ impl Config {
             host: "127.0.0.1".into(),
             port_grpc: None,
             auth_token: None,
+            auth_tokens_file: None,
             agent: None,
             agent_config: None,
+            driver_config: None,
+            pricing_file: None,
             attach: None,
             cols: 80,
             rows: 24,
             ring_size: 4096,
+            ring_spill: false,
             term: "xterm-256color".into(),
             port_health: None,
             log_format: "json".into(),
             log_level: "debug".into(),
+            otlp_endpoint: None,
             resume: None,
             record: false,
+            record_output: false,
             nats_url: None,
             nats_prefix: "coop.events".into(),
             nats_token: None,
@@ -379,6 +419,7 @@ impl Config {
             screen_poll_ms: Some(50),
             log_poll_ms: Some(50),
             tmux_poll_ms: Some(50),
+            gnu_screen_poll_ms: Some(50),
             reap_poll_ms: Some(10),
             input_delay_ms: Some(10),
             input_delay_per_byte_ms: Some(0),
@@ -444,6 +485,15 @@ pub struct AgentFileConfig {
     /// For Gemini, inserted as `mcpServers` in the settings file.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mcp: Option<serde_json::Value>,
+    /// Auto-respond rules for tool permission prompts (applied with `--groom auto`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<crate::policy::PolicyConfig>,
+    /// Escalation steps fired while the agent stays idle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle: Option<crate::idle::IdleConfig>,
+    /// Usage limits and what to do when they are exceeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<crate::budget::BudgetConfig>,
 }
 
 /// Load and parse the agent config file at `path`.
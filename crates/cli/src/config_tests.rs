@@ -42,6 +42,8 @@ fn valid_config_with_attach() -> anyhow::Result<()> {
     no_command          = { &["coop", "--port", "8080"], "agent command is required" },
     both_cmd_and_attach = { &["coop", "--port", "8080", "--attach", "tmux:sess", "--", "echo"],
                             "cannot specify both" },
+    driver_config_known = { &["coop", "--port", "8080", "--driver-config", "d.toml", "--", "claude"],
+                            "--driver-config is only supported" },
 )]
 fn invalid_config(args: &[&str], expected_substr: &str) {
     let config = parse(args);
@@ -190,6 +192,7 @@ fn env_duration_defaults() {
     assert_eq!(config.screen_poll(), Duration::from_secs(3));
     assert_eq!(config.log_poll(), Duration::from_secs(3));
     assert_eq!(config.tmux_poll(), Duration::from_secs(1));
+    assert_eq!(config.gnu_screen_poll(), Duration::from_millis(250));
     assert_eq!(config.reap_poll(), Duration::from_millis(50));
     assert_eq!(config.input_delay(), Duration::from_millis(200));
     assert_eq!(config.input_delay_per_byte(), Duration::from_millis(1));
@@ -209,6 +212,7 @@ fn test_config_uses_fast_timings() {
     assert_eq!(config.screen_poll(), Duration::from_millis(50));
     assert_eq!(config.log_poll(), Duration::from_millis(50));
     assert_eq!(config.tmux_poll(), Duration::from_millis(50));
+    assert_eq!(config.gnu_screen_poll(), Duration::from_millis(50));
     assert_eq!(config.reap_poll(), Duration::from_millis(10));
     assert_eq!(config.input_delay(), Duration::from_millis(10));
     assert_eq!(config.input_delay_per_byte(), Duration::ZERO);
@@ -52,6 +52,11 @@ impl ClaudeDriver {
             detectors.push(Box::new(stream::new_hook_detector(receiver, raw_hook_tx)));
         }
 
+        // Usage is counted by the session log when one is watched, otherwise
+        // from stdout; never both.
+        let (log_usage, stdout_usage) =
+            if session_log_path.is_some() { (usage, None) } else { (None, usage) };
+
         // Tier 2: Session log watching
         if let Some(log_path) = session_log_path {
             detectors.push(Box::new(LogDetector {
@@ -60,7 +65,7 @@ impl ClaudeDriver {
                 poll_interval: config.log_poll(),
                 last_message: last_message.clone(),
                 raw_message_tx: raw_message_tx.clone(),
-                usage,
+                usage: log_usage,
             }));
         }
 
@@ -70,6 +75,7 @@ impl ClaudeDriver {
                 stdout_rx,
                 last_message,
                 raw_message_tx,
+                stdout_usage,
             )));
         }
 
@@ -186,10 +186,14 @@ impl Detector for LogDetector {
 /// Parses structured JSONL from Claude's stdout stream (used when Claude is
 /// invoked with `--print --output-format stream-json`). Classifies each entry
 /// with `parse_claude_state` and extracts assistant message text.
+///
+/// Usage is counted from `assistant` entries only: the closing `result`
+/// entry repeats the turn's totals and would double-count them.
 pub fn new_stdout_detector(
     stdout_rx: mpsc::Receiver<Bytes>,
     last_message: Option<Arc<RwLock<Option<String>>>>,
     raw_message_tx: Option<broadcast::Sender<RawMessageEvent>>,
+    usage: Option<Arc<UsageState>>,
 ) -> impl Detector {
     use crate::driver::stdout_detect::StdoutDetector;
     StdoutDetector {
@@ -202,6 +206,13 @@ pub fn new_stdout_detector(
         extract_message: Some(Box::new(extract_assistant_text)),
         last_message,
         raw_message_tx,
+        extract_usage: Some(Box::new(|json| {
+            if json.get("type").and_then(|v| v.as_str()) != Some("assistant") {
+                return None;
+            }
+            crate::usage::extract_usage_delta(json)
+        })),
+        usage,
     }
 }
 
@@ -1,11 +1,14 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
+use std::sync::Arc;
+
 use bytes::Bytes;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 use crate::driver::{AgentState, Detector};
+use crate::usage::UsageState;
 
 use super::LogDetector;
 
@@ -106,7 +109,9 @@ async fn log_detector_skips_non_assistant_lines() -> anyhow::Result<()> {
 #[tokio::test]
 async fn stdout_detector_parses_jsonl_bytes() -> anyhow::Result<()> {
     let (bytes_tx, bytes_rx) = mpsc::channel(32);
-    let detector = Box::new(super::new_stdout_detector(bytes_rx, None, None));
+    let usage = Arc::new(UsageState::new());
+    let detector =
+        Box::new(super::new_stdout_detector(bytes_rx, None, None, Some(Arc::clone(&usage))));
     assert_eq!(detector.tier(), 3);
 
     let (state_tx, mut state_rx) = mpsc::channel(32);
@@ -117,22 +122,32 @@ async fn stdout_detector_parses_jsonl_bytes() -> anyhow::Result<()> {
         detector.run(state_tx, shutdown_clone).await;
     });
 
-    // Send a JSONL line as raw bytes
+    // Send JSONL lines as raw bytes. The result entry repeats the turn's
+    // totals and must not be counted again.
     bytes_tx
-        .send(Bytes::from(
-            "{\"type\":\"assistant\",\"message\":{\"content\":[{\"type\":\"tool_use\",\"name\":\"Bash\",\"input\":{}}]}}\n",
-        ))
+        .send(Bytes::from(concat!(
+            "{\"type\":\"result\",\"subtype\":\"success\",\"usage\":{\"input_tokens\":40,\"output_tokens\":9}}\n",
+            "{\"type\":\"assistant\",\"message\":{\"model\":\"claude-sonnet-4-5\",\"usage\":{\"input_tokens\":40,\"output_tokens\":9},\"content\":[{\"type\":\"tool_use\",\"name\":\"Bash\",\"input\":{}}]}}\n",
+        )))
         .await?;
 
-    let state = tokio::time::timeout(std::time::Duration::from_secs(2), state_rx.recv()).await;
+    let mut state = None;
+    while let Ok(Some((next, _cause, _))) =
+        tokio::time::timeout(std::time::Duration::from_secs(2), state_rx.recv()).await
+    {
+        if next == AgentState::Working {
+            state = Some(next); // tool_use → Working
+            break;
+        }
+    }
 
     shutdown.cancel();
     let _ = handle.await;
 
-    match state {
-        Ok(Some((AgentState::Working, _cause, _))) => {} // tool_use → Working
-        other => anyhow::bail!("expected Working, got {other:?}"),
-    }
+    anyhow::ensure!(state.is_some(), "expected Working");
+    let snap = usage.snapshot().await;
+    assert_eq!((snap.input_tokens, snap.output_tokens, snap.request_count), (40, 9, 1));
+    assert!(snap.by_model.contains_key("claude-sonnet-4-5"));
     Ok(())
 }
 
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use tokio::sync::{broadcast, mpsc, RwLock};
+
+use crate::driver::Detector;
+use crate::event::RawMessageEvent;
+use crate::usage::UsageState;
+
+use super::parse::{
+    extract_agent_message, extract_codex_usage, format_codex_cause, parse_codex_state,
+};
+
+/// Create a Tier 3 stdout detector for Codex.
+///
+/// Parses structured JSONL from Codex's stdout stream (used when Codex is
+/// invoked as `codex exec --json`). Classifies each event with
+/// `parse_codex_state`, extracts completed agent message text, and counts
+/// token usage from `turn.completed` events, attributed to `model` (see
+/// [`super::setup::resolve_model`]).
+pub fn new_stdout_detector(
+    stdout_rx: mpsc::Receiver<Bytes>,
+    last_message: Option<Arc<RwLock<Option<String>>>>,
+    raw_message_tx: Option<broadcast::Sender<RawMessageEvent>>,
+    usage: Option<Arc<UsageState>>,
+    model: Option<String>,
+) -> impl Detector {
+    use crate::driver::stdout_detect::StdoutDetector;
+    StdoutDetector {
+        stdout_rx,
+        classify: Box::new(|json| {
+            let state = parse_codex_state(json)?;
+            let cause = format_codex_cause(json);
+            Some((state, cause))
+        }),
+        extract_message: Some(Box::new(extract_agent_message)),
+        last_message,
+        raw_message_tx,
+        extract_usage: Some(Box::new(move |json| {
+            let mut delta = extract_codex_usage(json)?;
+            delta.model = model.clone();
+            Some(delta)
+        })),
+        usage,
+    }
+}
+
+#[cfg(test)]
+#[path = "detect_tests.rs"]
+mod tests;
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use tokio::sync::{mpsc, RwLock};
+use tokio_util::sync::CancellationToken;
+
+use crate::driver::{AgentState, Detector};
+use crate::usage::UsageState;
+
+#[tokio::test]
+async fn stdout_detector_parses_exec_json() -> anyhow::Result<()> {
+    let (bytes_tx, bytes_rx) = mpsc::channel(32);
+    let last_message = Arc::new(RwLock::new(None));
+    let usage = Arc::new(UsageState::new());
+    let detector = Box::new(super::new_stdout_detector(
+        bytes_rx,
+        Some(Arc::clone(&last_message)),
+        None,
+        Some(Arc::clone(&usage)),
+        Some("gpt-5".to_owned()),
+    ));
+    assert_eq!(detector.tier(), 3);
+
+    let (state_tx, mut state_rx) = mpsc::channel(32);
+    let shutdown = CancellationToken::new();
+    let sd = shutdown.clone();
+    let handle = tokio::spawn(async move {
+        detector.run(state_tx, sd).await;
+    });
+
+    bytes_tx
+        .send(Bytes::from(concat!(
+            "{\"type\":\"item.completed\",\"item\":{\"id\":\"item_0\",\"type\":\"agent_message\",\"text\":\"Listed files.\"}}\n",
+            "{\"type\":\"turn.completed\",\"usage\":{\"input_tokens\":10,\"cached_input_tokens\":0,\"output_tokens\":5}}\n",
+        )))
+        .await?;
+
+    let mut states = Vec::new();
+    for _ in 0..2 {
+        match tokio::time::timeout(std::time::Duration::from_secs(5), state_rx.recv()).await {
+            Ok(Some((state, cause, _))) => states.push((state, cause)),
+            other => anyhow::bail!("expected state, got {other:?}"),
+        }
+    }
+
+    shutdown.cancel();
+    let _ = handle.await;
+
+    assert_eq!(states[0], (AgentState::Working, "stdout:item(agent_message)".to_owned()));
+    assert_eq!(states[1], (AgentState::Idle, "stdout:idle".to_owned()));
+    assert_eq!(last_message.read().await.as_deref(), Some("Listed files."));
+    let snap = usage.snapshot().await;
+    assert_eq!((snap.input_tokens, snap.output_tokens, snap.request_count), (10, 5, 1));
+    assert!(snap.by_model.contains_key("gpt-5"));
+    assert!(snap.estimated_cost_usd > 0.0);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::time::Duration;
+
+use crate::driver::{NudgeStep, QuestionAnswer, RespondEncoder};
+
+pub use crate::driver::nudge::SafeNudgeEncoder as CodexNudgeEncoder;
+
+/// Encodes prompt responses for Codex CLI's terminal input.
+pub struct CodexRespondEncoder {
+    /// Delay between keystrokes in multi-step sequences.
+    pub input_delay: Duration,
+}
+
+impl Default for CodexRespondEncoder {
+    fn default() -> Self {
+        Self { input_delay: Duration::from_millis(200) }
+    }
+}
+
+impl CodexRespondEncoder {
+    /// Select option `n` in a Codex list picker: the digit moves the
+    /// selection, Enter confirms it.
+    fn select(&self, option: u32) -> Vec<NudgeStep> {
+        vec![
+            NudgeStep {
+                bytes: format!("{option}").into_bytes(),
+                delay_after: Some(self.input_delay),
+            },
+            NudgeStep { bytes: b"\r".to_vec(), delay_after: None },
+        ]
+    }
+}
+
+impl RespondEncoder for CodexRespondEncoder {
+    fn encode_permission(&self, option: u32) -> Vec<NudgeStep> {
+        // Approval overlay: 1 = proceed, 2 = don't ask again, 3 = deny with feedback.
+        self.select(option)
+    }
+
+    fn encode_plan(&self, option: u32, feedback: Option<&str>) -> Vec<NudgeStep> {
+        // Codex has no dedicated plan dialog. Feedback is typed into the
+        // composer after the selection so the agent sees it on its next turn.
+        let mut steps = self.select(option);
+        if let Some(text) = feedback {
+            if let Some(last) = steps.last_mut() {
+                last.delay_after = Some(self.input_delay);
+            }
+            steps.push(NudgeStep {
+                bytes: text.as_bytes().to_vec(),
+                delay_after: Some(self.input_delay),
+            });
+            steps.push(NudgeStep { bytes: b"\r".to_vec(), delay_after: None });
+        }
+        steps
+    }
+
+    fn encode_question(
+        &self,
+        answers: &[QuestionAnswer],
+        _total_questions: usize,
+    ) -> Vec<NudgeStep> {
+        // Codex asks questions in plain chat; answer the first one inline.
+        let answer = match answers.first() {
+            Some(a) => a,
+            None => return vec![],
+        };
+
+        if let Some(n) = answer.option {
+            return self.select(n);
+        }
+
+        if let Some(ref text) = answer.text {
+            return vec![
+                NudgeStep { bytes: text.as_bytes().to_vec(), delay_after: Some(self.input_delay) },
+                NudgeStep { bytes: b"\r".to_vec(), delay_after: None },
+            ];
+        }
+
+        vec![]
+    }
+
+    fn encode_setup(&self, option: u32) -> Vec<NudgeStep> {
+        self.select(option)
+    }
+}
+
+#[cfg(test)]
+#[path = "encoding_tests.rs"]
+mod tests;
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::time::Duration;
+
+use crate::driver::{NudgeEncoder, QuestionAnswer, RespondEncoder};
+
+use super::{CodexNudgeEncoder, CodexRespondEncoder};
+
+#[test]
+fn nudge_encodes_message_then_enter() {
+    let encoder = CodexNudgeEncoder {
+        input_delay: Duration::from_millis(200),
+        input_delay_per_byte: Duration::from_millis(1),
+    };
+    let steps = encoder.encode("Rebase on main");
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, b"Rebase on main");
+    assert_eq!(steps[0].delay_after, Some(Duration::from_millis(200)));
+    assert_eq!(steps[1].bytes, b"\r");
+    assert!(steps[1].delay_after.is_none());
+}
+
+#[yare::parameterized(
+    proceed = { 1, b"1" as &[u8] },
+    always = { 2, b"2" },
+    deny = { 3, b"3" },
+)]
+fn permission_selects_then_confirms(option: u32, digit: &[u8]) {
+    let encoder = CodexRespondEncoder::default();
+    let steps = encoder.encode_permission(option);
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, digit);
+    assert_eq!(steps[0].delay_after, Some(Duration::from_millis(200)));
+    assert_eq!(steps[1].bytes, b"\r");
+    assert!(steps[1].delay_after.is_none());
+}
+
+#[test]
+fn plan_without_feedback_is_a_selection() {
+    let encoder = CodexRespondEncoder::default();
+    let steps = encoder.encode_plan(1, None);
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, b"1");
+    assert_eq!(steps[1].bytes, b"\r");
+}
+
+#[test]
+fn plan_with_feedback_types_into_composer() {
+    let encoder = CodexRespondEncoder::default();
+    let steps = encoder.encode_plan(3, Some("Keep the schema"));
+    assert_eq!(steps.len(), 4);
+    assert_eq!(steps[0].bytes, b"3");
+    assert_eq!(steps[1].bytes, b"\r");
+    assert_eq!(steps[1].delay_after, Some(Duration::from_millis(200)));
+    assert_eq!(steps[2].bytes, b"Keep the schema");
+    assert_eq!(steps[3].bytes, b"\r");
+    assert!(steps[3].delay_after.is_none());
+}
+
+#[test]
+fn question_with_option_number() {
+    let encoder = CodexRespondEncoder::default();
+    let answers = [QuestionAnswer { option: Some(2), text: None }];
+    let steps = encoder.encode_question(&answers, 1);
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, b"2");
+    assert_eq!(steps[1].bytes, b"\r");
+}
+
+#[test]
+fn question_with_freeform_text() {
+    let encoder = CodexRespondEncoder::default();
+    let answers = [QuestionAnswer { option: None, text: Some("Use Redis".to_string()) }];
+    let steps = encoder.encode_question(&answers, 1);
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, b"Use Redis");
+    assert_eq!(steps[1].bytes, b"\r");
+}
+
+#[test]
+fn question_without_answers_is_empty() {
+    let encoder = CodexRespondEncoder::default();
+    assert!(encoder.encode_question(&[], 0).is_empty());
+}
+
+#[test]
+fn setup_selects_then_confirms() {
+    let encoder = CodexRespondEncoder::default();
+    let steps = encoder.encode_setup(1);
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, b"1");
+    assert_eq!(steps[1].bytes, b"\r");
+}
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+pub mod detect;
+pub mod encoding;
+pub mod parse;
+pub mod screen;
+pub mod setup;
+
+use crate::config::Config;
+
+use super::{Detector, DetectorSinks};
+use encoding::{CodexNudgeEncoder, CodexRespondEncoder};
+
+/// Codex CLI agent driver.
+///
+/// Provides encoding for nudge/respond actions and detection tiers
+/// for monitoring Codex's agent state. Codex has no hook mechanism,
+/// so detection relies on structured stdout and the rendered screen.
+pub struct CodexDriver {
+    pub nudge: CodexNudgeEncoder,
+    pub respond: CodexRespondEncoder,
+    pub detectors: Vec<Box<dyn Detector>>,
+}
+
+impl CodexDriver {
+    /// Build a new driver from config.
+    ///
+    /// Constructs detectors based on available tiers:
+    /// - Tier 3 (StdoutDetector): if `sinks.stdout_rx` is provided
+    pub fn new(config: &Config, sinks: DetectorSinks) -> anyhow::Result<Self> {
+        let DetectorSinks { last_message, raw_message_tx, stdout_rx, usage, .. } = sinks;
+        let mut detectors: Vec<Box<dyn Detector>> = Vec::new();
+
+        // Tier 3: Structured stdout JSONL (`codex exec --json`)
+        if let Some(stdout_rx) = stdout_rx {
+            detectors.push(Box::new(detect::new_stdout_detector(
+                stdout_rx,
+                last_message,
+                raw_message_tx,
+                usage,
+                setup::resolve_model(&config.command, &setup::codex_home()),
+            )));
+        }
+
+        Ok(Self {
+            nudge: CodexNudgeEncoder {
+                input_delay: config.input_delay(),
+                input_delay_per_byte: config.input_delay_per_byte(),
+            },
+            respond: CodexRespondEncoder { input_delay: config.input_delay() },
+            detectors,
+        })
+    }
+}
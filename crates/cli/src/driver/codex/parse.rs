@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use serde_json::Value;
+
+use crate::driver::AgentState;
+use crate::usage::UsageDelta;
+
+/// Extract a semantic cause string from a Codex `--json` JSONL event.
+///
+/// Item events carry the item type so tool activity is distinguishable
+/// from plain message streaming (e.g. `stdout:item(command_execution)`).
+pub fn format_codex_cause(json: &Value) -> String {
+    match json.get("type").and_then(|v| v.as_str()) {
+        Some("turn.completed") => "stdout:idle".to_owned(),
+        Some("turn.failed" | "error") => "stdout:error".to_owned(),
+        Some(t) if t.starts_with("item.") => {
+            match json.get("item").and_then(|i| i.get("type")).and_then(|v| v.as_str()) {
+                Some(kind) => format!("stdout:item({kind})"),
+                None => "stdout:working".to_owned(),
+            }
+        }
+        _ => "stdout:working".to_owned(),
+    }
+}
+
+/// Parse a Codex `exec --json` JSONL event into an [`AgentState`].
+///
+/// Handles the thread/turn/item event stream:
+/// - `thread.started`, `turn.started`, `item.*` -> `Working`
+/// - `turn.completed` -> `Idle`
+/// - `turn.failed` -> `Error { detail }` (from `error.message`)
+/// - `error` -> `Error { detail }` (from `message`)
+///
+/// Returns `None` if the entry cannot be classified.
+pub fn parse_codex_state(json: &Value) -> Option<AgentState> {
+    match json.get("type").and_then(|v| v.as_str()) {
+        Some("turn.completed") => Some(AgentState::Idle),
+        Some("turn.failed") => {
+            let detail = json
+                .get("error")
+                .and_then(|e| e.get("message"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+            Some(AgentState::Error { detail })
+        }
+        Some("error") => {
+            let detail =
+                json.get("message").and_then(|v| v.as_str()).unwrap_or("unknown").to_string();
+            Some(AgentState::Error { detail })
+        }
+        Some(
+            "thread.started" | "turn.started" | "item.started" | "item.updated" | "item.completed",
+        ) => Some(AgentState::Working),
+        _ => None,
+    }
+}
+
+/// Extract the text of a completed agent message, if this event is one.
+///
+/// Only `item.completed` events with an `agent_message` item carry the
+/// final text; streaming updates are ignored.
+pub fn extract_agent_message(json: &Value) -> Option<String> {
+    if json.get("type").and_then(|v| v.as_str()) != Some("item.completed") {
+        return None;
+    }
+    let item = json.get("item")?;
+    if item.get("type").and_then(|v| v.as_str()) != Some("agent_message") {
+        return None;
+    }
+    let text = item.get("text").and_then(|v| v.as_str())?;
+    if text.is_empty() {
+        return None;
+    }
+    Some(text.to_owned())
+}
+
+/// Extract token usage from a `turn.completed` event.
+///
+/// Codex reports `input_tokens` inclusive of `cached_input_tokens`; the
+/// cached share is split out into cache reads. Exec JSON doesn't name the
+/// model; the stdout detector fills it in from the Codex config.
+pub fn extract_codex_usage(json: &Value) -> Option<UsageDelta> {
+    if json.get("type").and_then(|v| v.as_str()) != Some("turn.completed") {
+        return None;
+    }
+    let usage = json.get("usage")?;
+    let field = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    let (input, cached, output) =
+        (field("input_tokens"), field("cached_input_tokens"), field("output_tokens"));
+    if input == 0 && output == 0 {
+        return None;
+    }
+    Some(UsageDelta {
+        input_tokens: input.saturating_sub(cached),
+        output_tokens: output,
+        cache_read_input_tokens: cached,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+#[path = "parse_tests.rs"]
+mod tests;
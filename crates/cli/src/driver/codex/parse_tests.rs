@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use serde_json::json;
+
+use crate::driver::AgentState;
+
+use super::{extract_agent_message, extract_codex_usage, format_codex_cause, parse_codex_state};
+
+#[yare::parameterized(
+    thread_started = {
+        json!({"type": "thread.started", "thread_id": "0199a213-81c0-7800-8aa1-bbab2a035a53"}),
+        Some(AgentState::Working)
+    },
+    turn_started = {
+        json!({"type": "turn.started"}),
+        Some(AgentState::Working)
+    },
+    command_started = {
+        json!({"type": "item.started", "item": {"id": "item_1", "type": "command_execution", "command": "bash -lc ls", "aggregated_output": "", "exit_code": null, "status": "in_progress"}}),
+        Some(AgentState::Working)
+    },
+    agent_message = {
+        json!({"type": "item.completed", "item": {"id": "item_3", "type": "agent_message", "text": "Done."}}),
+        Some(AgentState::Working)
+    },
+    turn_completed = {
+        json!({"type": "turn.completed", "usage": {"input_tokens": 24763, "cached_input_tokens": 24448, "output_tokens": 122}}),
+        Some(AgentState::Idle)
+    },
+    turn_failed = {
+        json!({"type": "turn.failed", "error": {"message": "stream disconnected"}}),
+        Some(AgentState::Error { detail: "stream disconnected".to_string() })
+    },
+    error_event = {
+        json!({"type": "error", "message": "rate limit exceeded"}),
+        Some(AgentState::Error { detail: "rate limit exceeded".to_string() })
+    },
+    error_without_message = {
+        json!({"type": "error"}),
+        Some(AgentState::Error { detail: "unknown".to_string() })
+    },
+    unknown_type = {
+        json!({"type": "custom_event"}),
+        None
+    },
+    missing_type = {
+        json!({"data": "something"}),
+        None
+    },
+)]
+fn state_from_exec_json(entry: serde_json::Value, expected: Option<AgentState>) {
+    assert_eq!(parse_codex_state(&entry), expected);
+}
+
+#[yare::parameterized(
+    idle = { json!({"type": "turn.completed"}), "stdout:idle" },
+    failed = { json!({"type": "turn.failed", "error": {"message": "x"}}), "stdout:error" },
+    item = { json!({"type": "item.started", "item": {"type": "command_execution"}}), "stdout:item(command_execution)" },
+    item_without_type = { json!({"type": "item.updated"}), "stdout:working" },
+    turn_started = { json!({"type": "turn.started"}), "stdout:working" },
+)]
+fn cause_from_exec_json(entry: serde_json::Value, expected: &str) {
+    assert_eq!(format_codex_cause(&entry), expected);
+}
+
+#[test]
+fn extracts_completed_agent_message() {
+    let entry = json!({"type": "item.completed", "item": {"id": "item_3", "type": "agent_message", "text": "All tests pass."}});
+    assert_eq!(extract_agent_message(&entry).as_deref(), Some("All tests pass."));
+}
+
+#[test]
+fn ignores_non_message_items() {
+    let reasoning =
+        json!({"type": "item.completed", "item": {"type": "reasoning", "text": "thinking"}});
+    let started =
+        json!({"type": "item.started", "item": {"type": "agent_message", "text": "partial"}});
+    let empty = json!({"type": "item.completed", "item": {"type": "agent_message", "text": ""}});
+    assert_eq!(extract_agent_message(&reasoning), None);
+    assert_eq!(extract_agent_message(&started), None);
+    assert_eq!(extract_agent_message(&empty), None);
+}
+
+#[test]
+fn extracts_turn_usage_with_cached_split() -> anyhow::Result<()> {
+    let entry = json!({"type": "turn.completed", "usage": {"input_tokens": 24763, "cached_input_tokens": 24448, "output_tokens": 122}});
+    let delta = extract_codex_usage(&entry).ok_or_else(|| anyhow::anyhow!("no usage"))?;
+    assert_eq!(delta.input_tokens, 315);
+    assert_eq!(delta.cache_read_input_tokens, 24448);
+    assert_eq!(delta.output_tokens, 122);
+    assert_eq!(delta.model, None);
+
+    let started = json!({"type": "turn.started", "usage": {"input_tokens": 1}});
+    assert!(extract_codex_usage(&started).is_none());
+    Ok(())
+}
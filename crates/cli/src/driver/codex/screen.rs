@@ -0,0 +1,265 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::config::Config;
+use crate::driver::{AgentState, Detector, DetectorEmission, PromptContext, PromptKind};
+use crate::screen::ScreenSnapshot;
+
+/// Composer prompt glyph Codex renders at the start of its input line (U+203A).
+const COMPOSER_PROMPT: char = '\u{203a}';
+
+/// Tier 5 detector: classifies Codex's rendered terminal screen.
+///
+/// Codex exposes no hooks, so approval overlays, the workspace trust
+/// dialog, and the working/idle composer are all read from the screen.
+pub struct CodexScreenDetector {
+    snapshot_fn: Arc<dyn Fn() -> ScreenSnapshot + Send + Sync>,
+    poll: Duration,
+}
+
+impl CodexScreenDetector {
+    pub fn new(
+        config: &Config,
+        snapshot_fn: Arc<dyn Fn() -> ScreenSnapshot + Send + Sync>,
+    ) -> Self {
+        Self { snapshot_fn, poll: config.screen_poll() }
+    }
+}
+
+impl Detector for CodexScreenDetector {
+    fn run(
+        self: Box<Self>,
+        state_tx: mpsc::Sender<DetectorEmission>,
+        shutdown: CancellationToken,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async move {
+            let mut interval = tokio::time::interval(self.poll);
+            let mut last_state: Option<AgentState> = None;
+
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+
+                let snapshot = (self.snapshot_fn)();
+                match classify_codex_screen(&snapshot) {
+                    Some((state, cause)) => {
+                        if last_state.as_ref() != Some(&state) {
+                            debug!(cause, state = state.as_str(), "screen detected");
+                            let _ = state_tx.send((state.clone(), cause, None)).await;
+                            last_state = Some(state);
+                        }
+                    }
+                    None => last_state = None,
+                }
+            }
+        })
+    }
+
+    fn tier(&self) -> u8 {
+        5
+    }
+}
+
+/// Classification of an interactive dialog screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DialogKind {
+    /// Command or patch approval overlay, with the tool name it gates.
+    Approval(&'static str),
+    /// Workspace trust — emitted as `Prompt(Permission)` with subtype `"trust"`.
+    Trust,
+    /// Onboarding/setup dialog — emitted as `Prompt(Setup)` with a subtype string.
+    Setup(&'static str),
+}
+
+/// Signal phrases for a dialog screen, paired with its classification.
+/// A match requires [`DIALOG_SIGNAL_THRESHOLD`] or more signals on screen.
+type DialogScreen = (DialogKind, &'static [&'static str]);
+
+const DIALOG_SCREENS: &[DialogScreen] = &[
+    (
+        DialogKind::Approval("shell"),
+        &[
+            "Would you like to run the following command?",
+            "don't ask again for this command",
+            "Press enter to confirm or esc to cancel",
+        ],
+    ),
+    (
+        DialogKind::Approval("apply_patch"),
+        &[
+            "Would you like to make the following edits?",
+            "don't ask again for these files",
+            "Press enter to confirm or esc to cancel",
+        ],
+    ),
+    (
+        DialogKind::Trust,
+        &[
+            "You are running Codex in",
+            "allow Codex to work in this folder",
+            "ask me to approve edits and commands",
+        ],
+    ),
+    (
+        DialogKind::Setup("login_method"),
+        &["Sign in with ChatGPT", "Provide your own API key", "Press Enter to continue"],
+    ),
+];
+
+/// Minimum number of signals that must match to identify a dialog screen.
+const DIALOG_SIGNAL_THRESHOLD: usize = 2;
+
+/// Classify Codex's screen, returning the state and a cause string.
+///
+/// Priority: dialogs (approval, trust, login) > working indicator > idle
+/// composer. Returns `None` for unrecognised screens.
+pub(crate) fn classify_codex_screen(snapshot: &ScreenSnapshot) -> Option<(AgentState, String)> {
+    match classify_interactive_dialog(&snapshot.lines) {
+        Some(DialogKind::Approval(tool)) => {
+            let options = parse_options_from_screen(&snapshot.lines);
+            let mut ctx = PromptContext::new(PromptKind::Permission)
+                .with_subtype("tool")
+                .with_tool(tool)
+                .with_options(options)
+                .with_ready();
+            ctx.input = extract_approval_command(&snapshot.lines);
+            return Some((AgentState::Prompt { prompt: ctx }, "screen:permission".to_owned()));
+        }
+        Some(DialogKind::Trust) => {
+            let options = parse_options_from_screen(&snapshot.lines);
+            return Some((
+                AgentState::Prompt {
+                    prompt: PromptContext::new(PromptKind::Permission)
+                        .with_subtype("trust")
+                        .with_options(options)
+                        .with_ready(),
+                },
+                "screen:permission".to_owned(),
+            ));
+        }
+        Some(DialogKind::Setup(subtype)) => {
+            let options = parse_options_from_screen(&snapshot.lines);
+            return Some((
+                AgentState::Prompt {
+                    prompt: PromptContext::new(PromptKind::Setup)
+                        .with_subtype(subtype)
+                        .with_options(options)
+                        .with_ready(),
+                },
+                "screen:setup".to_owned(),
+            ));
+        }
+        None => {}
+    }
+
+    // The status line reads `• Working (4s • esc to interrupt)` while a turn
+    // is running. The composer stays visible underneath, so this check must
+    // run before the idle check.
+    if snapshot.lines.iter().any(|l| l.contains("esc to interrupt")) {
+        return Some((AgentState::Working, "screen:working".to_owned()));
+    }
+
+    // Idle composer: `› ` followed by placeholder or draft text. Option
+    // markers in list pickers (`› 1. Yes`) are excluded by the digit check.
+    for line in snapshot.lines.iter().rev() {
+        let trimmed = line.trim();
+        if let Some(after) = trimmed.strip_prefix(COMPOSER_PROMPT) {
+            let is_option = after.trim_start().as_bytes().first().is_some_and(u8::is_ascii_digit);
+            if !is_option {
+                return Some((AgentState::Idle, "screen:idle".to_owned()));
+            }
+        }
+    }
+
+    None
+}
+
+/// Classify the screen as an interactive dialog, if any.
+fn classify_interactive_dialog(lines: &[String]) -> Option<DialogKind> {
+    for (kind, signals) in DIALOG_SCREENS {
+        let hits =
+            signals.iter().filter(|phrase| lines.iter().any(|l| l.contains(**phrase))).count();
+        if hits >= DIALOG_SIGNAL_THRESHOLD {
+            return Some(*kind);
+        }
+    }
+    None
+}
+
+/// Extract the command shown in an exec approval overlay (`$ cmd`).
+fn extract_approval_command(lines: &[String]) -> Option<String> {
+    lines.iter().find_map(|l| l.trim().strip_prefix("$ ").map(|cmd| cmd.trim().to_owned()))
+}
+
+/// Parse numbered option labels from Codex list pickers.
+///
+/// Codex renders the selected option with a `›` marker and the rest
+/// indented:
+/// ```text
+/// › 1. Yes, proceed (y)
+///   2. Yes, and don't ask again for this command (a)
+///   3. No, and tell Codex what to do differently (esc)
+/// ```
+///
+/// Scans bottom-up, skipping footer hints, and stops at the first
+/// non-option line above the block. Returns options in ascending order.
+pub fn parse_options_from_screen(lines: &[String]) -> Vec<String> {
+    let mut options: Vec<(u32, String)> = Vec::new();
+    let mut found_any = false;
+
+    for line in lines.iter().rev() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || is_hint_line(trimmed) {
+            continue;
+        }
+
+        if let Some((num, label)) = parse_numbered_option(trimmed) {
+            options.push((num, label));
+            found_any = true;
+        } else if found_any {
+            break;
+        }
+    }
+
+    options.sort_by_key(|(num, _)| *num);
+    options.into_iter().map(|(_, label)| label).collect()
+}
+
+/// Try to parse a line as a numbered option: `[› ] N. label`.
+fn parse_numbered_option(trimmed: &str) -> Option<(u32, String)> {
+    let s = trimmed.strip_prefix(COMPOSER_PROMPT).unwrap_or(trimmed);
+    let s = s.trim_start();
+
+    let digit_end = s.find(|c: char| !c.is_ascii_digit())?;
+    if digit_end == 0 {
+        return None;
+    }
+    let num: u32 = s[..digit_end].parse().ok()?;
+    let label = s[digit_end..].strip_prefix(". ")?.trim_end();
+    if label.is_empty() {
+        return None;
+    }
+
+    Some((num, label.to_owned()))
+}
+
+/// Footer lines below a picker (e.g. "Press enter to confirm or esc to cancel").
+fn is_hint_line(trimmed: &str) -> bool {
+    trimmed.starts_with("Press enter") || trimmed.starts_with("Press Enter")
+}
+
+#[cfg(test)]
+#[path = "screen_tests.rs"]
+mod tests;
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use crate::driver::{AgentState, PromptKind};
+use crate::screen::{CursorPosition, ScreenSnapshot};
+
+use super::{classify_codex_screen, parse_options_from_screen};
+
+fn fixture_lines(text: &str) -> Vec<String> {
+    text.lines().map(String::from).collect()
+}
+
+fn snapshot(lines: Vec<String>) -> ScreenSnapshot {
+    ScreenSnapshot {
+        lines,
+        ansi: vec![],
+        cols: 80,
+        rows: 24,
+        alt_screen: false,
+        cursor: CursorPosition { row: 0, col: 0 },
+        sequence: 1,
+    }
+}
+
+fn classify_fixture(text: &str) -> Option<(AgentState, String)> {
+    classify_codex_screen(&snapshot(fixture_lines(text)))
+}
+
+#[test]
+fn exec_approval_emits_tool_permission() -> anyhow::Result<()> {
+    let (state, cause) = classify_fixture(include_str!("fixtures/exec_approval.screen.txt"))
+        .ok_or_else(|| anyhow::anyhow!("expected a state"))?;
+    let prompt = state.prompt().ok_or_else(|| anyhow::anyhow!("expected Prompt"))?;
+    assert_eq!(cause, "screen:permission");
+    assert_eq!(prompt.kind, PromptKind::Permission);
+    assert_eq!(prompt.subtype.as_deref(), Some("tool"));
+    assert_eq!(prompt.tool.as_deref(), Some("shell"));
+    assert_eq!(prompt.input.as_deref(), Some("touch /usr/local/bin/test_permission_file"));
+    assert_eq!(prompt.options.len(), 3);
+    assert!(prompt.ready);
+    Ok(())
+}
+
+#[test]
+fn edit_approval_emits_apply_patch_permission() -> anyhow::Result<()> {
+    let (state, _) = classify_fixture(include_str!("fixtures/edit_approval.screen.txt"))
+        .ok_or_else(|| anyhow::anyhow!("expected a state"))?;
+    let prompt = state.prompt().ok_or_else(|| anyhow::anyhow!("expected Prompt"))?;
+    assert_eq!(prompt.tool.as_deref(), Some("apply_patch"));
+    assert!(prompt.input.is_none());
+    Ok(())
+}
+
+#[test]
+fn trust_folder_emits_trust_permission() -> anyhow::Result<()> {
+    let (state, _) = classify_fixture(include_str!("fixtures/trust_folder.screen.txt"))
+        .ok_or_else(|| anyhow::anyhow!("expected a state"))?;
+    let prompt = state.prompt().ok_or_else(|| anyhow::anyhow!("expected Prompt"))?;
+    assert_eq!(prompt.kind, PromptKind::Permission);
+    assert_eq!(prompt.subtype.as_deref(), Some("trust"));
+    assert_eq!(
+        prompt.options,
+        vec![
+            "Yes, allow Codex to work in this folder without asking for approval",
+            "No, ask me to approve edits and commands",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn idle_composer_emits_idle() {
+    let result = classify_fixture(include_str!("fixtures/idle.screen.txt"));
+    assert_eq!(result, Some((AgentState::Idle, "screen:idle".to_owned())));
+}
+
+#[test]
+fn working_indicator_beats_composer() {
+    let result = classify_fixture(include_str!("fixtures/working.screen.txt"));
+    assert_eq!(result, Some((AgentState::Working, "screen:working".to_owned())));
+}
+
+#[test]
+fn unrecognised_screen_emits_nothing() {
+    let lines = vec!["Reading files...".to_owned(), String::new()];
+    assert_eq!(classify_codex_screen(&snapshot(lines)), None);
+}
+
+#[test]
+fn parse_options_exec_approval() {
+    let lines = fixture_lines(include_str!("fixtures/exec_approval.screen.txt"));
+    assert_eq!(
+        parse_options_from_screen(&lines),
+        vec![
+            "Yes, proceed (y)",
+            "Yes, and don't ask again for this command (a)",
+            "No, and tell Codex what to do differently (esc)",
+        ]
+    );
+}
+
+#[test]
+fn parse_options_empty_screen() {
+    assert!(parse_options_from_screen(&[]).is_empty());
+}
+
+#[test]
+fn parse_options_ignores_composer() {
+    let lines = fixture_lines(include_str!("fixtures/idle.screen.txt"));
+    assert!(parse_options_from_screen(&lines).is_empty());
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Pre-spawn preparation for `--agent codex` sessions.
+//!
+//! Codex has no hook mechanism coop can inject into, so setup only
+//! allocates a session directory (for transcripts, recordings, and event
+//! logs) and exports `COOP_URL` to the child.
+
+use std::path::{Path, PathBuf};
+
+use crate::driver::SessionSetup;
+
+/// Prepare a Codex session setup.
+pub fn prepare(coop_url: &str) -> anyhow::Result<SessionSetup> {
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let session_dir = crate::driver::coop_session_dir(&session_id)?;
+
+    Ok(SessionSetup {
+        session_id,
+        hook_pipe_path: None,
+        session_log_path: None,
+        session_dir,
+        env_vars: vec![("COOP_URL".to_string(), coop_url.to_string())],
+        extra_args: vec![],
+    })
+}
+
+/// Return Codex's home directory.
+///
+/// Respects `CODEX_HOME` if set, otherwise defaults to `$HOME/.codex`.
+pub fn codex_home() -> PathBuf {
+    if let Ok(dir) = std::env::var("CODEX_HOME") {
+        return PathBuf::from(dir);
+    }
+    let home = std::env::var("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".codex")
+}
+
+/// Resolve the model Codex will run, since its exec JSON never names it.
+///
+/// A `-m` / `--model` argument wins; otherwise the `model` key of
+/// `<codex_home>/config.toml`. Returns `None` when neither is set.
+pub fn resolve_model(command: &[String], codex_home: &Path) -> Option<String> {
+    let mut args = command.iter();
+    while let Some(arg) = args.next() {
+        if arg == "-m" || arg == "--model" {
+            return args.next().cloned();
+        }
+        if let Some(model) = arg.strip_prefix("--model=") {
+            return Some(model.to_owned());
+        }
+    }
+    let contents = std::fs::read_to_string(codex_home.join("config.toml")).ok()?;
+    let config: toml::Value = toml::from_str(&contents).ok()?;
+    config.get("model")?.as_str().map(str::to_owned)
+}
+
+#[cfg(test)]
+#[path = "setup_tests.rs"]
+mod tests;
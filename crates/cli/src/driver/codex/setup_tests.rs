@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::resolve_model;
+
+#[test]
+fn prepare_creates_session_dir() -> anyhow::Result<()> {
+    let setup = super::prepare("http://127.0.0.1:0")?;
+    assert!(setup.session_dir.is_dir());
+    assert!(setup.session_dir.ends_with(&setup.session_id));
+    Ok(())
+}
+
+#[test]
+fn prepare_has_no_hooks_or_log() -> anyhow::Result<()> {
+    let setup = super::prepare("http://127.0.0.1:0")?;
+    assert!(setup.hook_pipe_path.is_none());
+    assert!(setup.session_log_path.is_none());
+    assert!(setup.extra_args.is_empty());
+    assert!(setup.env_vars.iter().any(|(k, v)| k == "COOP_URL" && v == "http://127.0.0.1:0"));
+    Ok(())
+}
+
+#[yare::parameterized(
+    short_flag = { &["codex", "exec", "-m", "gpt-5-mini", "--json"], Some("gpt-5-mini") },
+    long_flag = { &["codex", "--model", "o3", "exec"], Some("o3") },
+    long_equals = { &["codex", "exec", "--model=gpt-5-codex"], Some("gpt-5-codex") },
+    from_config = { &["codex", "exec", "--json"], Some("gpt-5") },
+)]
+fn resolve_model_prefers_args_over_config(
+    command: &[&str],
+    expected: Option<&str>,
+) -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("config.toml"), "model = \"gpt-5\"\n")?;
+    let command: Vec<String> = command.iter().map(|s| (*s).to_owned()).collect();
+    assert_eq!(resolve_model(&command, dir.path()).as_deref(), expected);
+    Ok(())
+}
+
+#[test]
+fn resolve_model_without_args_or_config() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let command = vec!["codex".to_owned(), "exec".to_owned()];
+    assert_eq!(resolve_model(&command, dir.path()), None);
+
+    std::fs::write(dir.path().join("config.toml"), "approval_policy = \"never\"\n")?;
+    assert_eq!(resolve_model(&command, dir.path()), None);
+    Ok(())
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::time::Duration;
+
+use crate::driver::{NudgeEncoder, NudgeStep, QuestionAnswer, RespondEncoder};
+
+use super::{KeySpec, NudgeSpec};
+
+/// Nudge encoder driven by a definition's `[nudge]` table: optional prefix,
+/// the message, a scaled delay, then the submit sequence.
+pub struct DeclarativeNudgeEncoder {
+    pub prefix: Vec<u8>,
+    pub submit: Vec<u8>,
+    /// Base delay between typing the message and submitting it.
+    pub input_delay: Duration,
+    /// Per-byte delay added for messages longer than 256 bytes.
+    pub input_delay_per_byte: Duration,
+}
+
+impl DeclarativeNudgeEncoder {
+    pub fn new(spec: &NudgeSpec, input_delay: Duration, input_delay_per_byte: Duration) -> Self {
+        Self {
+            prefix: spec.prefix.clone().unwrap_or_default().into_bytes(),
+            submit: spec.submit.clone().unwrap_or_else(|| "\r".to_owned()).into_bytes(),
+            input_delay,
+            input_delay_per_byte,
+        }
+    }
+}
+
+impl NudgeEncoder for DeclarativeNudgeEncoder {
+    fn encode(&self, message: &str) -> Vec<NudgeStep> {
+        let mut steps = Vec::new();
+        if !self.prefix.is_empty() {
+            steps.push(NudgeStep { bytes: self.prefix.clone(), delay_after: None });
+        }
+        let delay = crate::driver::compute_nudge_delay(
+            self.input_delay,
+            self.input_delay_per_byte,
+            message.len(),
+        );
+        steps.push(NudgeStep { bytes: message.as_bytes().to_vec(), delay_after: Some(delay) });
+        if !self.submit.is_empty() {
+            steps.push(NudgeStep { bytes: self.submit.clone(), delay_after: None });
+        }
+        steps
+    }
+}
+
+/// Respond encoder driven by a definition's `[keys]` table.
+pub struct DeclarativeRespondEncoder {
+    pub option: String,
+    pub accept: Option<String>,
+    pub deny: Option<String>,
+    pub submit: Vec<u8>,
+    /// Delay between a selection or typed text and the submit sequence.
+    pub input_delay: Duration,
+}
+
+impl DeclarativeRespondEncoder {
+    pub fn new(spec: &KeySpec, input_delay: Duration) -> Self {
+        Self {
+            option: spec.option.clone().unwrap_or_else(|| "{n}".to_owned()),
+            accept: spec.accept.clone(),
+            deny: spec.deny.clone(),
+            submit: spec.submit.clone().unwrap_or_else(|| "\r".to_owned()).into_bytes(),
+            input_delay,
+        }
+    }
+
+    /// Key sequence that selects option `n`.
+    fn key_for(&self, n: u32) -> Vec<u8> {
+        self.option.replace("{n}", &n.to_string()).into_bytes()
+    }
+
+    /// Key sequence that answers a permission prompt with option `n`:
+    /// `accept` for 1 and `deny` for 3 (what `accept: true` / `false`
+    /// resolve to), the option template for anything else.
+    fn permission_key(&self, n: u32) -> Vec<u8> {
+        let over = match n {
+            1 => self.accept.as_ref(),
+            3 => self.deny.as_ref(),
+            _ => None,
+        };
+        match over {
+            Some(seq) => seq.clone().into_bytes(),
+            None => self.key_for(n),
+        }
+    }
+
+    /// Bytes followed by the submit sequence (if any), delayed in between.
+    fn submitted(&self, bytes: Vec<u8>) -> Vec<NudgeStep> {
+        if self.submit.is_empty() {
+            return vec![NudgeStep { bytes, delay_after: None }];
+        }
+        vec![
+            NudgeStep { bytes, delay_after: Some(self.input_delay) },
+            NudgeStep { bytes: self.submit.clone(), delay_after: None },
+        ]
+    }
+}
+
+impl RespondEncoder for DeclarativeRespondEncoder {
+    fn encode_permission(&self, option: u32) -> Vec<NudgeStep> {
+        self.submitted(self.permission_key(option))
+    }
+
+    fn encode_plan(&self, option: u32, feedback: Option<&str>) -> Vec<NudgeStep> {
+        let mut steps = self.submitted(self.key_for(option));
+        if let Some(text) = feedback {
+            if let Some(last) = steps.last_mut() {
+                last.delay_after = Some(self.input_delay);
+            }
+            steps.extend(self.submitted(text.as_bytes().to_vec()));
+        }
+        steps
+    }
+
+    fn encode_question(
+        &self,
+        answers: &[QuestionAnswer],
+        _total_questions: usize,
+    ) -> Vec<NudgeStep> {
+        // Declarative drivers model single-question prompts; take the first answer.
+        let answer = match answers.first() {
+            Some(a) => a,
+            None => return vec![],
+        };
+
+        if let Some(n) = answer.option {
+            return self.submitted(self.key_for(n));
+        }
+
+        if let Some(ref text) = answer.text {
+            return self.submitted(text.as_bytes().to_vec());
+        }
+
+        vec![]
+    }
+
+    fn encode_setup(&self, option: u32) -> Vec<NudgeStep> {
+        self.submitted(self.key_for(option))
+    }
+}
+
+#[cfg(test)]
+#[path = "encoding_tests.rs"]
+mod tests;
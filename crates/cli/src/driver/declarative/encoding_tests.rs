@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::time::Duration;
+
+use crate::driver::{NudgeEncoder, QuestionAnswer, RespondEncoder};
+
+use super::super::{KeySpec, NudgeSpec};
+use super::{DeclarativeNudgeEncoder, DeclarativeRespondEncoder};
+
+const DELAY: Duration = Duration::from_millis(200);
+
+fn yes_no() -> DeclarativeRespondEncoder {
+    let keys =
+        KeySpec { accept: Some("y".to_owned()), deny: Some("n".to_owned()), ..Default::default() };
+    DeclarativeRespondEncoder::new(&keys, DELAY)
+}
+
+#[test]
+fn nudge_defaults_to_message_then_enter() {
+    let encoder = DeclarativeNudgeEncoder::new(&NudgeSpec::default(), DELAY, Duration::ZERO);
+    let steps = encoder.encode("Fix the tests");
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, b"Fix the tests");
+    assert_eq!(steps[0].delay_after, Some(DELAY));
+    assert_eq!(steps[1].bytes, b"\r");
+    assert!(steps[1].delay_after.is_none());
+}
+
+#[test]
+fn nudge_with_prefix_and_custom_submit() {
+    let spec = NudgeSpec { prefix: Some("\u{15}".to_owned()), submit: Some("\x1b\r".to_owned()) };
+    let encoder = DeclarativeNudgeEncoder::new(&spec, DELAY, Duration::ZERO);
+    let steps = encoder.encode("hi");
+    assert_eq!(steps.len(), 3);
+    assert_eq!(steps[0].bytes, b"\x15");
+    assert_eq!(steps[1].bytes, b"hi");
+    assert_eq!(steps[2].bytes, b"\x1b\r");
+}
+
+#[yare::parameterized(
+    accept = { 1, b"y" as &[u8] },
+    explicit_second = { 2, b"2" },
+    deny = { 3, b"n" },
+    explicit_fourth = { 4, b"4" },
+)]
+fn permission_uses_accept_and_deny(option: u32, key: &[u8]) {
+    let steps = yes_no().encode_permission(option);
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, key);
+    assert_eq!(steps[0].delay_after, Some(DELAY));
+    assert_eq!(steps[1].bytes, b"\r");
+}
+
+#[test]
+fn permission_default_option_template() {
+    let encoder = DeclarativeRespondEncoder::new(&KeySpec::default(), DELAY);
+    let steps = encoder.encode_permission(3);
+    assert_eq!(steps[0].bytes, b"3");
+    assert_eq!(steps[1].bytes, b"\r");
+}
+
+#[test]
+fn empty_submit_sends_single_keypress() {
+    let keys = KeySpec {
+        option: Some("\x1b[{n}~".to_owned()),
+        submit: Some(String::new()),
+        ..Default::default()
+    };
+    let steps = DeclarativeRespondEncoder::new(&keys, DELAY).encode_setup(2);
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0].bytes, b"\x1b[2~");
+    assert!(steps[0].delay_after.is_none());
+}
+
+#[test]
+fn plan_with_feedback_types_text() {
+    let steps = yes_no().encode_plan(4, Some("Use a smaller diff"));
+    assert_eq!(steps.len(), 4);
+    assert_eq!(steps[0].bytes, b"4");
+    assert_eq!(steps[1].bytes, b"\r");
+    assert_eq!(steps[1].delay_after, Some(DELAY));
+    assert_eq!(steps[2].bytes, b"Use a smaller diff");
+    assert_eq!(steps[3].bytes, b"\r");
+    assert!(steps[3].delay_after.is_none());
+}
+
+#[test]
+fn question_option_and_text() {
+    let encoder = yes_no();
+    let steps = encoder.encode_question(&[QuestionAnswer { option: Some(1), text: None }], 1);
+    assert_eq!(steps[0].bytes, b"1");
+
+    let steps = encoder
+        .encode_question(&[QuestionAnswer { option: None, text: Some("main".to_owned()) }], 1);
+    assert_eq!(steps[0].bytes, b"main");
+    assert_eq!(steps[1].bytes, b"\r");
+
+    assert!(encoder.encode_question(&[], 1).is_empty());
+}
+
+#[yare::parameterized(
+    first = { 1, b"1" as &[u8] },
+    second = { 2, b"2" },
+    third = { 3, b"3" },
+)]
+fn three_option_question_selects_each_option(option: u32, key: &[u8]) {
+    let steps = yes_no().encode_question(&[QuestionAnswer { option: Some(option), text: None }], 1);
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].bytes, key);
+    assert_eq!(steps[1].bytes, b"\r");
+}
+
+#[yare::parameterized(
+    first = { 1, b"\x1b[1~" as &[u8] },
+    second = { 2, b"\x1b[2~" },
+    third = { 3, b"\x1b[3~" },
+)]
+fn three_option_question_uses_option_template(option: u32, key: &[u8]) {
+    let keys = KeySpec {
+        option: Some("\x1b[{n}~".to_owned()),
+        accept: Some("y".to_owned()),
+        deny: Some("n".to_owned()),
+        ..Default::default()
+    };
+    let encoder = DeclarativeRespondEncoder::new(&keys, DELAY);
+    let steps = encoder.encode_question(&[QuestionAnswer { option: Some(option), text: None }], 1);
+    assert_eq!(steps[0].bytes, key);
+}
@@ -0,0 +1,260 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Data-driven driver built from a TOML or JSON definition file.
+//!
+//! Lets new agent CLIs be onboarded without writing Rust: the definition
+//! supplies screen regexes per prompt kind, key sequences for responses,
+//! nudge encoding, and stdout JSONL field mappings.
+//!
+//! ```toml
+//! name = "aider"
+//!
+//! [screen]
+//! idle = ['^> $']
+//! working = ['Thinking']
+//! error = ['^Error:']
+//!
+//! [[screen.prompts]]
+//! kind = "permission"
+//! subtype = "tool"
+//! tool = "shell"
+//! patterns = ['Run shell command\?']
+//!
+//! [keys]
+//! accept = "y"
+//! deny = "n"
+//! ```
+
+pub mod encoding;
+pub mod screen;
+pub mod stdout;
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::driver::{Detector, DetectorSinks, OptionParser, PromptKind};
+use crate::screen::ScreenSnapshot;
+
+use self::encoding::{DeclarativeNudgeEncoder, DeclarativeRespondEncoder};
+use self::screen::{DeclarativeScreenDetector, ScreenRules};
+use self::stdout::StdoutRules;
+
+/// Top-level contents of a declarative driver definition.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DriverSpec {
+    /// Display name of the agent CLI (informational).
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub screen: ScreenSpec,
+    #[serde(default)]
+    pub keys: KeySpec,
+    #[serde(default)]
+    pub nudge: NudgeSpec,
+    /// Stdout JSONL field mappings. `None` disables the Tier 3 detector.
+    #[serde(default)]
+    pub stdout: Option<StdoutSpec>,
+}
+
+/// Screen regexes, matched against rendered terminal lines.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ScreenSpec {
+    /// Matched against the last non-empty line; a match means idle.
+    #[serde(default)]
+    pub idle: Vec<String>,
+    /// Matched against every line; a match means working.
+    #[serde(default)]
+    pub working: Vec<String>,
+    /// Matched against every line; the matching line becomes the error detail.
+    #[serde(default)]
+    pub error: Vec<String>,
+    /// Prompt rules, checked in order before any other classification.
+    #[serde(default)]
+    pub prompts: Vec<PromptRuleSpec>,
+    /// Regex for numbered option lines with two capture groups: the option
+    /// number and its label. Defaults to `N. label` / `N) label` with an
+    /// optional `›`, `❯` or `>` selection marker.
+    #[serde(default)]
+    pub options: Option<String>,
+}
+
+/// A screen rule that classifies the agent as presenting a prompt.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PromptRuleSpec {
+    pub kind: PromptKind,
+    #[serde(default)]
+    pub subtype: Option<String>,
+    #[serde(default)]
+    pub tool: Option<String>,
+    /// Every pattern must match some line for the rule to apply.
+    pub patterns: Vec<String>,
+    /// Regex whose first capture group is reported as the prompt input.
+    #[serde(default)]
+    pub input: Option<String>,
+}
+
+/// Key sequences used to answer prompts.
+///
+/// Sequences are written verbatim, so control characters are expressed
+/// with string escapes (`"\r"`, `"\u001b"`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct KeySpec {
+    /// Sequence that selects option N; `{n}` is replaced by the number.
+    /// Defaults to `"{n}"`.
+    #[serde(default)]
+    pub option: Option<String>,
+    /// Overrides `option` when accepting a permission prompt (option 1).
+    #[serde(default)]
+    pub accept: Option<String>,
+    /// Overrides `option` when rejecting a permission prompt (option 3,
+    /// what `accept: false` resolves to).
+    #[serde(default)]
+    pub deny: Option<String>,
+    /// Sent after a selection or typed text. Defaults to `"\r"`; set to
+    /// `""` for pickers that act on a single keypress.
+    #[serde(default)]
+    pub submit: Option<String>,
+}
+
+/// How nudge messages are typed into the agent.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NudgeSpec {
+    /// Sent before the message (e.g. `"\u0015"` to clear the input line).
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Sent after the message. Defaults to `"\r"`.
+    #[serde(default)]
+    pub submit: Option<String>,
+}
+
+/// Mapping from stdout JSONL entries to agent states.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StdoutSpec {
+    /// Dotted path to the field that discriminates entry types.
+    #[serde(default = "default_stdout_field")]
+    pub field: String,
+    /// Discriminator values that mean working.
+    #[serde(default)]
+    pub working: Vec<String>,
+    /// Discriminator values that mean idle.
+    #[serde(default)]
+    pub idle: Vec<String>,
+    /// Discriminator values that mean error.
+    #[serde(default)]
+    pub error: Vec<String>,
+    /// Dotted path to the error detail. Falls back to the discriminator value.
+    #[serde(default)]
+    pub error_detail: Option<String>,
+    /// Dotted path to the assistant message text.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Discriminator values to extract `message` from. Empty means any entry.
+    #[serde(default)]
+    pub message_on: Vec<String>,
+    /// Token usage mapping (`[stdout.usage]`).
+    #[serde(default)]
+    pub usage: Option<UsageSpec>,
+}
+
+/// Dotted paths to token usage fields in stdout entries. Entries where
+/// neither token count is present (or both are zero) are skipped.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UsageSpec {
+    /// Discriminator values that carry usage. Empty means any entry.
+    #[serde(default)]
+    pub on: Vec<String>,
+    #[serde(default)]
+    pub input_tokens: Option<String>,
+    #[serde(default)]
+    pub output_tokens: Option<String>,
+    #[serde(default)]
+    pub cache_read_tokens: Option<String>,
+    #[serde(default)]
+    pub cache_write_tokens: Option<String>,
+    #[serde(default)]
+    pub cost_usd: Option<String>,
+    #[serde(default)]
+    pub duration_ms: Option<String>,
+    /// Path to the model name, for the per-model breakdown.
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+fn default_stdout_field() -> String {
+    "type".to_owned()
+}
+
+/// Load a driver definition. Files ending in `.toml` are parsed as TOML,
+/// everything else as JSON.
+pub fn load_driver_spec(path: &Path) -> anyhow::Result<DriverSpec> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read driver config {}", path.display()))?;
+    let is_toml = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+    let spec = if is_toml {
+        toml::from_str(&contents).map_err(anyhow::Error::from)
+    } else {
+        serde_json::from_str(&contents).map_err(anyhow::Error::from)
+    };
+    spec.with_context(|| format!("invalid driver config {}", path.display()))
+}
+
+/// Driver assembled from a [`DriverSpec`].
+pub struct DeclarativeDriver {
+    pub nudge: DeclarativeNudgeEncoder,
+    pub respond: DeclarativeRespondEncoder,
+    pub detectors: Vec<Box<dyn Detector>>,
+    pub option_parser: OptionParser,
+}
+
+impl DeclarativeDriver {
+    /// Compile the definition and build its detectors.
+    ///
+    /// Always includes a Tier 5 screen detector. A Tier 3 stdout detector
+    /// is added when the definition maps stdout and `sinks.stdout_rx` is set.
+    pub fn new(
+        config: &Config,
+        spec: &DriverSpec,
+        snapshot_fn: Arc<dyn Fn() -> ScreenSnapshot + Send + Sync>,
+        sinks: DetectorSinks,
+    ) -> anyhow::Result<Self> {
+        let rules = Arc::new(ScreenRules::compile(&spec.screen)?);
+        let mut detectors: Vec<Box<dyn Detector>> = Vec::new();
+
+        if let (Some(stdout_spec), Some(stdout_rx)) = (spec.stdout.as_ref(), sinks.stdout_rx) {
+            let stdout_rules = StdoutRules::new(stdout_spec.clone());
+            detectors.push(Box::new(stdout::new_stdout_detector(
+                stdout_rules,
+                stdout_rx,
+                sinks.last_message,
+                sinks.raw_message_tx,
+                sinks.usage,
+            )));
+        }
+
+        detectors.push(Box::new(
+            DeclarativeScreenDetector::new(Arc::clone(&rules), snapshot_fn)
+                .with_poll_interval(config.screen_poll()),
+        ));
+
+        let option_rules = Arc::clone(&rules);
+        Ok(Self {
+            nudge: DeclarativeNudgeEncoder::new(
+                &spec.nudge,
+                config.input_delay(),
+                config.input_delay_per_byte(),
+            ),
+            respond: DeclarativeRespondEncoder::new(&spec.keys, config.input_delay()),
+            detectors,
+            option_parser: Arc::new(move |lines: &[String]| option_rules.parse_options(lines)),
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "mod_tests.rs"]
+mod tests;
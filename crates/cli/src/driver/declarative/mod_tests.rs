@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::driver::{DetectorSinks, PromptKind};
+
+use super::{load_driver_spec, DeclarativeDriver, DriverSpec};
+
+#[test]
+fn fixture_toml_parses() -> anyhow::Result<()> {
+    let spec: DriverSpec = toml::from_str(include_str!("fixtures/aider.toml"))?;
+    assert_eq!(spec.name.as_deref(), Some("aider"));
+    assert_eq!(spec.screen.idle.len(), 1);
+    assert_eq!(spec.screen.prompts.len(), 2);
+    assert_eq!(spec.screen.prompts[0].kind, PromptKind::Permission);
+    assert_eq!(spec.screen.prompts[0].tool.as_deref(), Some("shell"));
+    assert_eq!(spec.keys.accept.as_deref(), Some("y"));
+    assert_eq!(spec.nudge.prefix.as_deref(), Some("\u{15}"));
+    let stdout = spec.stdout.ok_or_else(|| anyhow::anyhow!("expected stdout mapping"))?;
+    assert_eq!(stdout.field, "type");
+    assert_eq!(stdout.message_on, vec!["assistant.done".to_owned()]);
+    Ok(())
+}
+
+#[test]
+fn load_selects_format_by_extension() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let toml_path = dir.path().join("driver.toml");
+    std::fs::write(&toml_path, "[screen]\nworking = ['Thinking']\n")?;
+    assert_eq!(load_driver_spec(&toml_path)?.screen.working, vec!["Thinking".to_owned()]);
+
+    let json_path = dir.path().join("driver.json");
+    std::fs::write(
+        &json_path,
+        r#"{"screen": {"prompts": [{"kind": "setup", "subtype": "login", "patterns": ["Log in"]}]}}"#,
+    )?;
+    let spec = load_driver_spec(&json_path)?;
+    assert_eq!(spec.screen.prompts[0].kind, PromptKind::Setup);
+    assert!(spec.stdout.is_none());
+    Ok(())
+}
+
+#[test]
+fn load_reports_invalid_file() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("driver.toml");
+    std::fs::write(&path, "[screen\n")?;
+    let err = load_driver_spec(&path).err().ok_or_else(|| anyhow::anyhow!("expected error"))?;
+    assert!(err.to_string().contains("invalid driver config"));
+    Ok(())
+}
+
+#[test]
+fn driver_rejects_invalid_regex() {
+    let mut spec = DriverSpec::default();
+    spec.screen.idle = vec!["[unclosed".to_owned()];
+    let snapshot_fn = Arc::new(|| crate::screen::Screen::new(80, 24).snapshot());
+    let result =
+        DeclarativeDriver::new(&Config::test(), &spec, snapshot_fn, DetectorSinks::default());
+    assert!(result.is_err());
+}
+
+#[yare::parameterized(
+    screen_only = { false, &[5] },
+    with_stdout = { true, &[3, 5] },
+)]
+fn driver_tiers(stdout: bool, expected: &[u8]) -> anyhow::Result<()> {
+    let spec: DriverSpec = toml::from_str(include_str!("fixtures/aider.toml"))?;
+    let mut sinks = DetectorSinks::default();
+    if stdout {
+        let (_tx, rx) = tokio::sync::mpsc::channel(1);
+        sinks = sinks.with_stdout_rx(rx);
+    }
+    let snapshot_fn = Arc::new(|| crate::screen::Screen::new(80, 24).snapshot());
+    let driver = DeclarativeDriver::new(&Config::test(), &spec, snapshot_fn, sinks)?;
+    let tiers: Vec<u8> = driver.detectors.iter().map(|d| d.tier()).collect();
+    assert_eq!(tiers, expected);
+    Ok(())
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use regex::Regex;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::driver::{AgentState, Detector, DetectorEmission, PromptContext, PromptKind};
+use crate::screen::ScreenSnapshot;
+
+use super::ScreenSpec;
+
+/// Default option line regex: `[›❯>] N. label` or `N) label`.
+const DEFAULT_OPTION_PATTERN: &str = r"^\s*(?:[›❯>]\s*)?(\d+)[.)]\s+(\S.*?)\s*$";
+
+/// Compiled prompt rule.
+#[derive(Debug)]
+pub struct PromptRule {
+    pub kind: PromptKind,
+    pub subtype: Option<String>,
+    pub tool: Option<String>,
+    pub patterns: Vec<Regex>,
+    pub input: Option<Regex>,
+}
+
+/// Compiled screen regexes from a [`ScreenSpec`].
+#[derive(Debug)]
+pub struct ScreenRules {
+    pub idle: Vec<Regex>,
+    pub working: Vec<Regex>,
+    pub error: Vec<Regex>,
+    pub prompts: Vec<PromptRule>,
+    pub options: Regex,
+}
+
+fn compile_all(patterns: &[String]) -> anyhow::Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|p| Regex::new(p).with_context(|| format!("invalid screen pattern: {p}")))
+        .collect()
+}
+
+impl ScreenRules {
+    pub fn compile(spec: &ScreenSpec) -> anyhow::Result<Self> {
+        let prompts = spec
+            .prompts
+            .iter()
+            .map(|rule| {
+                if rule.patterns.is_empty() {
+                    anyhow::bail!("prompt rule for {} has no patterns", rule.kind.as_str());
+                }
+                Ok(PromptRule {
+                    kind: rule.kind,
+                    subtype: rule.subtype.clone(),
+                    tool: rule.tool.clone(),
+                    patterns: compile_all(&rule.patterns)?,
+                    input: rule.input.as_deref().map(Regex::new).transpose()?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let options_src = spec.options.as_deref().unwrap_or(DEFAULT_OPTION_PATTERN);
+        let options = Regex::new(options_src)
+            .with_context(|| format!("invalid option pattern: {options_src}"))?;
+        if options.captures_len() < 3 {
+            anyhow::bail!("option pattern must capture the number and the label");
+        }
+
+        Ok(Self {
+            idle: compile_all(&spec.idle)?,
+            working: compile_all(&spec.working)?,
+            error: compile_all(&spec.error)?,
+            prompts,
+            options,
+        })
+    }
+
+    /// Parse numbered option labels from screen lines.
+    ///
+    /// Scans bottom-up for a contiguous block of option lines (blank lines
+    /// allowed) and returns labels in ascending option order.
+    pub fn parse_options(&self, lines: &[String]) -> Vec<String> {
+        let mut options: Vec<(u32, String)> = Vec::new();
+
+        for line in lines.iter().rev() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parsed = self.options.captures(line).and_then(|caps| {
+                let num: u32 = caps.get(1)?.as_str().parse().ok()?;
+                let label = caps.get(2)?.as_str().trim();
+                (!label.is_empty()).then(|| (num, label.to_owned()))
+            });
+            match parsed {
+                Some(opt) => options.push(opt),
+                None if !options.is_empty() => break,
+                None => {}
+            }
+        }
+
+        options.sort_by_key(|(num, _)| *num);
+        options.into_iter().map(|(_, label)| label).collect()
+    }
+}
+
+/// Classify the screen, returning the state and a cause string.
+///
+/// Priority: prompt rules (in definition order) > error > idle (last
+/// non-empty line) > working. Returns `None` if nothing matches.
+pub fn classify(rules: &ScreenRules, snapshot: &ScreenSnapshot) -> Option<(AgentState, String)> {
+    let lines = &snapshot.lines;
+
+    for rule in &rules.prompts {
+        let matched = rule.patterns.iter().all(|pat| lines.iter().any(|l| pat.is_match(l)));
+        if !matched {
+            continue;
+        }
+        let mut ctx =
+            PromptContext::new(rule.kind).with_options(rules.parse_options(lines)).with_ready();
+        ctx.subtype = rule.subtype.clone();
+        ctx.tool = rule.tool.clone();
+        ctx.input = rule.input.as_ref().and_then(|re| {
+            lines.iter().find_map(|l| re.captures(l)?.get(1).map(|m| m.as_str().to_owned()))
+        });
+        let cause = format!("screen:{}", rule.kind.as_str());
+        return Some((AgentState::Prompt { prompt: ctx }, cause));
+    }
+
+    for line in lines {
+        if rules.error.iter().any(|pat| pat.is_match(line)) {
+            return Some((AgentState::Error { detail: line.clone() }, "screen:error".to_owned()));
+        }
+    }
+
+    if let Some(line) = lines.iter().rev().find(|l| !l.trim().is_empty()) {
+        if rules.idle.iter().any(|pat| pat.is_match(line)) {
+            return Some((AgentState::Idle, "screen:idle".to_owned()));
+        }
+    }
+
+    if lines.iter().any(|line| rules.working.iter().any(|pat| pat.is_match(line))) {
+        return Some((AgentState::Working, "screen:working".to_owned()));
+    }
+
+    None
+}
+
+/// Tier 5 detector that classifies the rendered screen with the
+/// definition's regex rules.
+pub struct DeclarativeScreenDetector {
+    rules: Arc<ScreenRules>,
+    snapshot_fn: Arc<dyn Fn() -> ScreenSnapshot + Send + Sync>,
+    poll_interval: Duration,
+}
+
+impl DeclarativeScreenDetector {
+    pub fn new(
+        rules: Arc<ScreenRules>,
+        snapshot_fn: Arc<dyn Fn() -> ScreenSnapshot + Send + Sync>,
+    ) -> Self {
+        Self { rules, snapshot_fn, poll_interval: Duration::from_secs(2) }
+    }
+
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+}
+
+impl Detector for DeclarativeScreenDetector {
+    fn run(
+        self: Box<Self>,
+        state_tx: mpsc::Sender<DetectorEmission>,
+        shutdown: CancellationToken,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            let mut last_state: Option<AgentState> = None;
+
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+
+                let snapshot = (self.snapshot_fn)();
+                match classify(&self.rules, &snapshot) {
+                    Some((state, cause)) => {
+                        if last_state.as_ref() != Some(&state) {
+                            let _ = state_tx.send((state.clone(), cause, None)).await;
+                            last_state = Some(state);
+                        }
+                    }
+                    None => last_state = None,
+                }
+            }
+        })
+    }
+
+    fn tier(&self) -> u8 {
+        5
+    }
+}
+
+#[cfg(test)]
+#[path = "screen_tests.rs"]
+mod tests;
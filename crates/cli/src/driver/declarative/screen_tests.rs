@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+
+use crate::driver::{AgentState, Detector, PromptKind};
+use crate::screen::{CursorPosition, ScreenSnapshot};
+
+use super::super::{DriverSpec, ScreenSpec};
+use super::{classify, DeclarativeScreenDetector, ScreenRules};
+
+fn make_snapshot(lines: &[&str]) -> ScreenSnapshot {
+    ScreenSnapshot {
+        lines: lines.iter().map(|l| l.to_string()).collect(),
+        ansi: vec![],
+        cols: 80,
+        rows: 24,
+        alt_screen: false,
+        cursor: CursorPosition { row: 0, col: 0 },
+        sequence: 0,
+    }
+}
+
+fn aider_rules() -> anyhow::Result<ScreenRules> {
+    let spec: DriverSpec = toml::from_str(include_str!("fixtures/aider.toml"))?;
+    ScreenRules::compile(&spec.screen)
+}
+
+fn basic_rules() -> anyhow::Result<ScreenRules> {
+    ScreenRules::compile(&ScreenSpec {
+        idle: vec![r"^\$ $".to_owned()],
+        working: vec!["Compiling".to_owned(), "Building".to_owned()],
+        error: vec![r"^error:".to_owned(), r"^ERROR".to_owned()],
+        ..Default::default()
+    })
+}
+
+#[test]
+fn compile_rejects_invalid_regex() {
+    let spec = ScreenSpec { idle: vec![r"[invalid".to_owned()], ..Default::default() };
+    assert!(ScreenRules::compile(&spec).is_err());
+}
+
+#[test]
+fn compile_rejects_option_pattern_without_groups() {
+    let spec = ScreenSpec { options: Some(r"^\d+\.".to_owned()), ..Default::default() };
+    assert!(ScreenRules::compile(&spec).is_err());
+}
+
+#[yare::parameterized(
+    error = { &["some output", "error: something failed", "$ "], "screen:error" },
+    idle = { &["some output", "$ "], "screen:idle" },
+    idle_trailing_blank = { &["$ ", "", "   "], "screen:idle" },
+    working = { &["   Compiling foo v0.1.0", ""], "screen:working" },
+)]
+fn classify_basic(lines: &[&str], expected_cause: &str) -> anyhow::Result<()> {
+    let rules = basic_rules()?;
+    let (_, cause) = classify(&rules, &make_snapshot(lines))
+        .ok_or_else(|| anyhow::anyhow!("expected a state"))?;
+    assert_eq!(cause, expected_cause);
+    Ok(())
+}
+
+#[test]
+fn classify_returns_none_when_no_match() -> anyhow::Result<()> {
+    let rules = basic_rules()?;
+    assert_eq!(classify(&rules, &make_snapshot(&["hello world", "> "])), None);
+    Ok(())
+}
+
+#[test]
+fn classify_error_carries_line() -> anyhow::Result<()> {
+    let rules = basic_rules()?;
+    let (state, _) = classify(&rules, &make_snapshot(&["error: disk full"]))
+        .ok_or_else(|| anyhow::anyhow!("expected a state"))?;
+    assert_eq!(state, AgentState::Error { detail: "error: disk full".to_owned() });
+    Ok(())
+}
+
+#[test]
+fn classify_prompt_rule_with_input() -> anyhow::Result<()> {
+    let rules = aider_rules()?;
+    let snapshot =
+        make_snapshot(&["$ ls -la", "Run shell command? (Y)es/(N)o/(D)on't ask again [Yes]: "]);
+    let (state, cause) =
+        classify(&rules, &snapshot).ok_or_else(|| anyhow::anyhow!("expected a state"))?;
+    let prompt = state.prompt().ok_or_else(|| anyhow::anyhow!("expected Prompt"))?;
+    assert_eq!(cause, "screen:permission");
+    assert_eq!(prompt.kind, PromptKind::Permission);
+    assert_eq!(prompt.subtype.as_deref(), Some("tool"));
+    assert_eq!(prompt.tool.as_deref(), Some("shell"));
+    assert_eq!(prompt.input.as_deref(), Some("ls -la"));
+    assert!(prompt.ready);
+    Ok(())
+}
+
+#[test]
+fn classify_prompt_rules_apply_in_order() -> anyhow::Result<()> {
+    let rules = aider_rules()?;
+    let snapshot = make_snapshot(&["Add file to the chat? (Y)es/(N)o [Yes]: "]);
+    let (state, cause) =
+        classify(&rules, &snapshot).ok_or_else(|| anyhow::anyhow!("expected a state"))?;
+    assert_eq!(cause, "screen:question");
+    assert_eq!(state.prompt().map(|p| p.kind), Some(PromptKind::Question));
+    Ok(())
+}
+
+#[test]
+fn prompt_rule_requires_every_pattern() -> anyhow::Result<()> {
+    let spec = ScreenSpec {
+        prompts: vec![super::super::PromptRuleSpec {
+            kind: PromptKind::Setup,
+            subtype: Some("login".to_owned()),
+            tool: None,
+            patterns: vec!["Log in".to_owned(), "API key".to_owned()],
+            input: None,
+        }],
+        ..Default::default()
+    };
+    let rules = ScreenRules::compile(&spec)?;
+    assert_eq!(classify(&rules, &make_snapshot(&["Log in to continue"])), None);
+    let (state, _) = classify(&rules, &make_snapshot(&["Log in", "Paste your API key"]))
+        .ok_or_else(|| anyhow::anyhow!("expected a state"))?;
+    assert_eq!(state.prompt().and_then(|p| p.subtype.as_deref()), Some("login"));
+    Ok(())
+}
+
+#[test]
+fn parse_options_default_pattern() -> anyhow::Result<()> {
+    let rules = basic_rules()?;
+    let lines: Vec<String> = [
+        "Allow this action?",
+        "› 1. Yes",
+        "  2. Yes, always",
+        "",
+        "  3) No",
+        "Press enter to confirm",
+    ]
+    .iter()
+    .map(|l| l.to_string())
+    .collect();
+    // Non-option lines below the block (footer hints) are skipped.
+    let options = rules.parse_options(&lines);
+    assert_eq!(options, vec!["Yes", "Yes, always", "No"]);
+    Ok(())
+}
+
+#[test]
+fn parse_options_custom_pattern() -> anyhow::Result<()> {
+    let spec = ScreenSpec { options: Some(r"^\[(\d+)\] (.+)$".to_owned()), ..Default::default() };
+    let rules = ScreenRules::compile(&spec)?;
+    let lines = vec!["Pick one".to_owned(), "[1] Apply".to_owned(), "[2] Skip".to_owned()];
+    assert_eq!(rules.parse_options(&lines), vec!["Apply", "Skip"]);
+    Ok(())
+}
+
+#[test]
+fn parse_options_empty_screen() -> anyhow::Result<()> {
+    let rules = basic_rules()?;
+    assert!(rules.parse_options(&["$ ".to_owned()]).is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn detector_emits_on_change() -> anyhow::Result<()> {
+    let rules = Arc::new(basic_rules()?);
+    let detector = Box::new(
+        DeclarativeScreenDetector::new(rules, Arc::new(|| make_snapshot(&["Building..."])))
+            .with_poll_interval(std::time::Duration::from_millis(10)),
+    );
+    assert_eq!(detector.tier(), 5);
+
+    let (state_tx, mut state_rx) = tokio::sync::mpsc::channel(8);
+    let shutdown = tokio_util::sync::CancellationToken::new();
+    let handle = tokio::spawn(detector.run(state_tx, shutdown.clone()));
+
+    let emission = tokio::time::timeout(std::time::Duration::from_secs(2), state_rx.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("channel closed"))?;
+    assert_eq!((emission.0, emission.1), (AgentState::Working, "screen:working".to_owned()));
+
+    // Unchanged screen does not re-emit.
+    let again = tokio::time::timeout(std::time::Duration::from_millis(100), state_rx.recv()).await;
+    assert!(again.is_err());
+
+    shutdown.cancel();
+    let _ = handle.await;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use serde_json::Value;
+use tokio::sync::{broadcast, mpsc, RwLock};
+
+use crate::driver::{AgentState, Detector};
+use crate::event::RawMessageEvent;
+use crate::usage::{UsageDelta, UsageState};
+
+use super::StdoutSpec;
+
+/// Field mappings from a definition's `[stdout]` table.
+#[derive(Debug, Clone)]
+pub struct StdoutRules {
+    spec: StdoutSpec,
+}
+
+/// Look up a dotted path (`item.text`, `content.0.text`) in a JSON value.
+pub fn lookup<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(json, |value, key| match value {
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    })
+}
+
+impl StdoutRules {
+    pub fn new(spec: StdoutSpec) -> Self {
+        Self { spec }
+    }
+
+    fn discriminator<'a>(&self, json: &'a Value) -> Option<&'a str> {
+        lookup(json, &self.spec.field)?.as_str()
+    }
+
+    /// Classify a stdout entry by its discriminator field.
+    pub fn classify(&self, json: &Value) -> Option<(AgentState, String)> {
+        let kind = self.discriminator(json)?;
+        let matches = |values: &[String]| values.iter().any(|v| v == kind);
+
+        if matches(&self.spec.error) {
+            let detail = self
+                .spec
+                .error_detail
+                .as_deref()
+                .and_then(|path| lookup(json, path)?.as_str())
+                .unwrap_or(kind)
+                .to_owned();
+            return Some((AgentState::Error { detail }, "stdout:error".to_owned()));
+        }
+        if matches(&self.spec.idle) {
+            return Some((AgentState::Idle, "stdout:idle".to_owned()));
+        }
+        if matches(&self.spec.working) {
+            return Some((AgentState::Working, "stdout:working".to_owned()));
+        }
+        None
+    }
+
+    /// Extract assistant message text, if the entry carries one.
+    pub fn extract_message(&self, json: &Value) -> Option<String> {
+        let path = self.spec.message.as_deref()?;
+        if !self.spec.message_on.is_empty() {
+            let kind = self.discriminator(json)?;
+            if !self.spec.message_on.iter().any(|v| v == kind) {
+                return None;
+            }
+        }
+        let text = lookup(json, path)?.as_str()?;
+        (!text.is_empty()).then(|| text.to_owned())
+    }
+
+    /// Extract token usage, if the definition maps it and the entry carries it.
+    pub fn extract_usage(&self, json: &Value) -> Option<UsageDelta> {
+        let spec = self.spec.usage.as_ref()?;
+        if !spec.on.is_empty() {
+            let kind = self.discriminator(json)?;
+            if !spec.on.iter().any(|v| v == kind) {
+                return None;
+            }
+        }
+        let field = |path: &Option<String>| lookup(json, path.as_deref()?);
+        let count = |path: &Option<String>| field(path).and_then(Value::as_u64).unwrap_or(0);
+        let (input, output) = (count(&spec.input_tokens), count(&spec.output_tokens));
+        if input == 0 && output == 0 {
+            return None;
+        }
+        Some(UsageDelta {
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_input_tokens: count(&spec.cache_read_tokens),
+            cache_creation_input_tokens: count(&spec.cache_write_tokens),
+            cost_usd: field(&spec.cost_usd).and_then(Value::as_f64).unwrap_or(0.0),
+            duration_api_ms: count(&spec.duration_ms),
+            model: field(&spec.model).and_then(Value::as_str).map(str::to_owned),
+            ..Default::default()
+        })
+    }
+}
+
+/// Create a Tier 3 stdout detector from declarative field mappings.
+pub fn new_stdout_detector(
+    rules: StdoutRules,
+    stdout_rx: mpsc::Receiver<Bytes>,
+    last_message: Option<Arc<RwLock<Option<String>>>>,
+    raw_message_tx: Option<broadcast::Sender<RawMessageEvent>>,
+    usage: Option<Arc<UsageState>>,
+) -> impl Detector {
+    use crate::driver::stdout_detect::StdoutDetector;
+    let rules = Arc::new(rules);
+    let extract_rules = Arc::clone(&rules);
+    let usage_rules = Arc::clone(&rules);
+    StdoutDetector {
+        stdout_rx,
+        classify: Box::new(move |json| rules.classify(json)),
+        extract_message: Some(Box::new(move |json| extract_rules.extract_message(json))),
+        last_message,
+        raw_message_tx,
+        extract_usage: Some(Box::new(move |json| usage_rules.extract_usage(json))),
+        usage,
+    }
+}
+
+#[cfg(test)]
+#[path = "stdout_tests.rs"]
+mod tests;
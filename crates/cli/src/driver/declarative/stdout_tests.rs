@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use serde_json::json;
+use tokio::sync::{mpsc, RwLock};
+use tokio_util::sync::CancellationToken;
+
+use crate::driver::{AgentState, Detector};
+
+use super::super::DriverSpec;
+use super::{lookup, new_stdout_detector, StdoutRules};
+
+fn aider_rules() -> anyhow::Result<StdoutRules> {
+    let spec: DriverSpec = toml::from_str(include_str!("fixtures/aider.toml"))?;
+    let stdout = spec.stdout.ok_or_else(|| anyhow::anyhow!("expected stdout mapping"))?;
+    Ok(StdoutRules::new(stdout))
+}
+
+#[test]
+fn lookup_walks_objects_and_arrays() {
+    let value = json!({"message": {"content": [{"text": "hi"}]}});
+    assert_eq!(lookup(&value, "message.content.0.text"), Some(&json!("hi")));
+    assert_eq!(lookup(&value, "message.content.1.text"), None);
+    assert_eq!(lookup(&value, "missing"), None);
+}
+
+#[yare::parameterized(
+    working = { json!({"type": "assistant.delta"}), Some((AgentState::Working, "stdout:working")) },
+    idle = { json!({"type": "assistant.done", "content": "Done."}), Some((AgentState::Idle, "stdout:idle")) },
+    unmapped = { json!({"type": "system"}), None },
+    no_field = { json!({"kind": "error"}), None },
+)]
+fn classify_maps_discriminator(
+    entry: serde_json::Value,
+    expected: Option<(AgentState, &str)>,
+) -> anyhow::Result<()> {
+    let result = aider_rules()?.classify(&entry);
+    assert_eq!(result, expected.map(|(s, c)| (s, c.to_owned())));
+    Ok(())
+}
+
+#[test]
+fn classify_error_detail() -> anyhow::Result<()> {
+    let rules = aider_rules()?;
+    let entry = json!({"type": "error", "error": {"message": "rate limited"}});
+    assert_eq!(
+        rules.classify(&entry),
+        Some((AgentState::Error { detail: "rate limited".to_owned() }, "stdout:error".to_owned()))
+    );
+    // Without a detail, the discriminator value is used.
+    assert_eq!(
+        rules.classify(&json!({"type": "error"})),
+        Some((AgentState::Error { detail: "error".to_owned() }, "stdout:error".to_owned()))
+    );
+    Ok(())
+}
+
+#[test]
+fn extract_message_respects_message_on() -> anyhow::Result<()> {
+    let rules = aider_rules()?;
+    assert_eq!(
+        rules.extract_message(&json!({"type": "assistant.done", "content": "Done."})),
+        Some("Done.".to_owned())
+    );
+    assert_eq!(rules.extract_message(&json!({"type": "assistant.delta", "content": "Do"})), None);
+    assert_eq!(rules.extract_message(&json!({"type": "assistant.done", "content": ""})), None);
+    Ok(())
+}
+
+#[test]
+fn extract_usage_follows_mapping() -> anyhow::Result<()> {
+    let rules = aider_rules()?;
+    let entry = json!({
+        "type": "usage",
+        "model": "gpt-4o",
+        "tokens": {"sent": 2300, "received": 180},
+        "cost": {"message": 0.0075},
+    });
+    let delta = rules.extract_usage(&entry).ok_or_else(|| anyhow::anyhow!("no usage"))?;
+    assert_eq!((delta.input_tokens, delta.output_tokens), (2300, 180));
+    assert!((delta.cost_usd - 0.0075).abs() < f64::EPSILON);
+    assert_eq!(delta.model.as_deref(), Some("gpt-4o"));
+
+    // Other entry types and entries without counts are skipped.
+    let done = json!({"type": "assistant.done", "tokens": {"sent": 1}});
+    assert!(rules.extract_usage(&done).is_none());
+    assert!(rules.extract_usage(&json!({"type": "usage"})).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn stdout_detector_classifies_and_records_message() -> anyhow::Result<()> {
+    let (bytes_tx, bytes_rx) = mpsc::channel(32);
+    let last_message = Arc::new(RwLock::new(None));
+    let detector = Box::new(new_stdout_detector(
+        aider_rules()?,
+        bytes_rx,
+        Some(Arc::clone(&last_message)),
+        None,
+        None,
+    ));
+    assert_eq!(detector.tier(), 3);
+
+    let (state_tx, mut state_rx) = mpsc::channel(32);
+    let shutdown = CancellationToken::new();
+    let handle = tokio::spawn(detector.run(state_tx, shutdown.clone()));
+
+    bytes_tx
+        .send(Bytes::from(concat!(
+            "{\"type\":\"assistant.delta\",\"content\":\"Do\"}\n",
+            "{\"type\":\"assistant.done\",\"content\":\"Done.\"}\n",
+        )))
+        .await?;
+
+    let mut states = Vec::new();
+    for _ in 0..2 {
+        match tokio::time::timeout(std::time::Duration::from_secs(5), state_rx.recv()).await {
+            Ok(Some((state, cause, _))) => states.push((state, cause)),
+            other => anyhow::bail!("expected state, got {other:?}"),
+        }
+    }
+
+    shutdown.cancel();
+    let _ = handle.await;
+
+    assert_eq!(states[0], (AgentState::Working, "stdout:working".to_owned()));
+    assert_eq!(states[1], (AgentState::Idle, "stdout:idle".to_owned()));
+    assert_eq!(last_message.read().await.as_deref(), Some("Done."));
+    Ok(())
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
-use super::{disruption_option, PromptContext, PromptKind};
+use super::{disruption_option, structured_stdout, AgentType, PromptContext, PromptKind};
 
 fn prompt(kind: PromptKind, subtype: Option<&str>) -> PromptContext {
     let ctx = PromptContext::new(kind).with_ready();
@@ -94,3 +94,53 @@ fn elicitation_setup_no_subtype() {
 fn elicitation_permission_no_subtype() {
     assert_eq!(disruption_option(&prompt(PromptKind::Permission, None)), None);
 }
+
+// -- Driver builders --
+
+#[test]
+fn codex_driver_tiers_without_stdout() -> anyhow::Result<()> {
+    let config = crate::config::Config::test();
+    let ctx = super::build_codex_driver(
+        &config,
+        std::sync::Arc::new(|| None),
+        std::sync::Arc::new(|| 0),
+        std::sync::Arc::new(|| crate::screen::Screen::new(80, 24).snapshot()),
+        super::DetectorSinks::default(),
+    )?;
+    let tiers: Vec<u8> = ctx.detectors.iter().map(|d| d.tier()).collect();
+    assert_eq!(tiers, vec![4, 5]);
+    assert!(ctx.nudge_encoder.is_some());
+    assert!(ctx.respond_encoder.is_some());
+    assert!(ctx.option_parser.is_some());
+    Ok(())
+}
+
+#[test]
+fn codex_driver_adds_stdout_tier() -> anyhow::Result<()> {
+    let config = crate::config::Config::test();
+    let (_tx, rx) = tokio::sync::mpsc::channel(1);
+    let ctx = super::build_codex_driver(
+        &config,
+        std::sync::Arc::new(|| None),
+        std::sync::Arc::new(|| 0),
+        std::sync::Arc::new(|| crate::screen::Screen::new(80, 24).snapshot()),
+        super::DetectorSinks::default().with_stdout_rx(rx),
+    )?;
+    let tiers: Vec<u8> = ctx.detectors.iter().map(|d| d.tier()).collect();
+    assert_eq!(tiers, vec![3, 4, 5]);
+    Ok(())
+}
+
+#[yare::parameterized(
+    codex_exec_json = { AgentType::Codex, &["codex", "exec", "--json", "fix it"], true },
+    codex_tui = { AgentType::Codex, &["codex"], false },
+    gemini_stream = { AgentType::Gemini, &["gemini", "-o", "stream-json", "-p", "hi"], true },
+    gemini_tui = { AgentType::Gemini, &["gemini"], false },
+    claude_stream = { AgentType::Claude, &["claude", "--print", "--output-format=stream-json"], true },
+    claude_text = { AgentType::Claude, &["claude", "--print", "--output-format", "text"], false },
+    unknown = { AgentType::Unknown, &["aider"], true },
+)]
+fn structured_stdout_modes(agent: AgentType, command: &[&str], expected: bool) {
+    let command: Vec<String> = command.iter().map(|a| (*a).to_owned()).collect();
+    assert_eq!(structured_stdout(agent, &command), expected);
+}
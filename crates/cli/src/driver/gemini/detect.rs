@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
+use std::sync::Arc;
+
 use bytes::Bytes;
 use tokio::sync::{broadcast, mpsc};
 
@@ -9,8 +11,9 @@ use crate::driver::hook_recv::HookReceiver;
 use crate::driver::HookEvent;
 use crate::driver::{AgentState, Detector, PromptContext, PromptKind};
 use crate::event::{RawHookEvent, RawMessageEvent};
+use crate::usage::UsageState;
 
-use super::parse::{format_gemini_cause, parse_gemini_state};
+use super::parse::{format_gemini_cause, parse_gemini_state, GeminiUsageExtractor};
 
 /// Map a Gemini hook event to an `(AgentState, cause)` pair.
 ///
@@ -57,10 +60,12 @@ pub fn new_hook_detector(
 /// Parses structured JSONL from Gemini's stdout stream (used when Gemini is
 /// invoked with `--output-format stream-json`). Receives raw PTY bytes from
 /// a channel, feeds them through a JSONL parser, and classifies each parsed
-/// entry with `parse_gemini_state`.
+/// entry with `parse_gemini_state`. Token usage is read from `result` stats
+/// via [`GeminiUsageExtractor`].
 pub fn new_stdout_detector(
     stdout_rx: mpsc::Receiver<Bytes>,
     raw_message_tx: Option<broadcast::Sender<RawMessageEvent>>,
+    usage: Option<Arc<UsageState>>,
 ) -> impl Detector {
     use crate::driver::stdout_detect::StdoutDetector;
     StdoutDetector {
@@ -73,6 +78,11 @@ pub fn new_stdout_detector(
         extract_message: None,
         last_message: None,
         raw_message_tx,
+        extract_usage: {
+            let mut extractor = GeminiUsageExtractor::default();
+            Some(Box::new(move |json| extractor.extract(json)))
+        },
+        usage,
     }
 }
 
@@ -1,16 +1,19 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
+use std::sync::Arc;
+
 use bytes::Bytes;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 use crate::driver::{AgentState, Detector};
+use crate::usage::UsageState;
 
 #[tokio::test]
 async fn stdout_detector_parses_gemini_stream_json() -> anyhow::Result<()> {
     let (bytes_tx, bytes_rx) = mpsc::channel(32);
-    let detector = Box::new(super::new_stdout_detector(bytes_rx, None));
+    let detector = Box::new(super::new_stdout_detector(bytes_rx, None, None));
     assert_eq!(detector.tier(), 3);
 
     let (state_tx, mut state_rx) = mpsc::channel(32);
@@ -43,7 +46,8 @@ async fn stdout_detector_parses_gemini_stream_json() -> anyhow::Result<()> {
 #[tokio::test]
 async fn stdout_detector_detects_result_as_idle() -> anyhow::Result<()> {
     let (bytes_tx, bytes_rx) = mpsc::channel(32);
-    let detector = Box::new(super::new_stdout_detector(bytes_rx, None));
+    let usage = Arc::new(UsageState::new());
+    let detector = Box::new(super::new_stdout_detector(bytes_rx, None, Some(Arc::clone(&usage))));
 
     let (state_tx, mut state_rx) = mpsc::channel(32);
     let shutdown = CancellationToken::new();
@@ -54,20 +58,28 @@ async fn stdout_detector_detects_result_as_idle() -> anyhow::Result<()> {
     });
 
     bytes_tx
-        .send(Bytes::from(
-            "{\"type\":\"result\",\"status\":\"success\",\"timestamp\":\"2025-10-10T12:00:00.000Z\"}\n",
-        ))
+        .send(Bytes::from(concat!(
+            "{\"type\":\"init\",\"session_id\":\"abc123\",\"model\":\"gemini-2.5-flash\"}\n",
+            "{\"type\":\"result\",\"status\":\"success\",\"stats\":{\"input_tokens\":90,\"output_tokens\":12},\"timestamp\":\"2025-10-10T12:00:00.000Z\"}\n",
+        )))
         .await?;
 
-    let state = tokio::time::timeout(std::time::Duration::from_secs(5), state_rx.recv()).await;
+    let mut states = Vec::new();
+    for _ in 0..2 {
+        match tokio::time::timeout(std::time::Duration::from_secs(5), state_rx.recv()).await {
+            Ok(Some((state, _cause, _))) => states.push(state),
+            other => anyhow::bail!("expected state, got {other:?}"),
+        }
+    }
 
     shutdown.cancel();
     let _ = handle.await;
 
-    match state {
-        Ok(Some((AgentState::Idle, _cause, _))) => {}
-        other => anyhow::bail!("expected Idle, got {other:?}"),
-    }
+    assert_eq!(states, vec![AgentState::Working, AgentState::Idle]);
+    let snap = usage.snapshot().await;
+    assert_eq!(snap.output_tokens, 12);
+    let flash = snap.by_model.get("gemini-2.5-flash").ok_or_else(|| anyhow::anyhow!("no model"))?;
+    assert_eq!((flash.input_tokens, flash.request_count), (90, 1));
     Ok(())
 }
 
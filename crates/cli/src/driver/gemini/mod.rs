@@ -37,7 +37,7 @@ impl GeminiDriver {
         hook_pipe_path: Option<&Path>,
         sinks: DetectorSinks,
     ) -> anyhow::Result<Self> {
-        let DetectorSinks { raw_hook_tx, raw_message_tx, stdout_rx, .. } = sinks;
+        let DetectorSinks { raw_hook_tx, raw_message_tx, stdout_rx, usage, .. } = sinks;
         let mut detectors: Vec<Box<dyn Detector>> = Vec::new();
 
         // Tier 1: Hook events (highest confidence)
@@ -48,7 +48,7 @@ impl GeminiDriver {
 
         // Tier 3: Structured stdout JSONL
         if let Some(stdout_rx) = stdout_rx {
-            detectors.push(Box::new(detect::new_stdout_detector(stdout_rx, raw_message_tx)));
+            detectors.push(Box::new(detect::new_stdout_detector(stdout_rx, raw_message_tx, usage)));
         }
 
         // Sort by tier (lowest number = highest priority)
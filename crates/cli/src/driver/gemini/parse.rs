@@ -4,6 +4,7 @@
 use serde_json::Value;
 
 use crate::driver::AgentState;
+use crate::usage::UsageDelta;
 
 /// Extract a semantic cause string from a Gemini stream-json JSONL event.
 pub fn format_gemini_cause(json: &Value) -> String {
@@ -35,6 +36,50 @@ pub fn parse_gemini_state(json: &Value) -> Option<AgentState> {
     }
 }
 
+/// Extracts token usage from Gemini stream-json events.
+///
+/// Token counts arrive in the `stats` of each `result` event; the model is
+/// only named by the `init` event, so it is remembered for later results.
+#[derive(Debug, Default)]
+pub struct GeminiUsageExtractor {
+    model: Option<String>,
+}
+
+impl GeminiUsageExtractor {
+    /// Observe one event, returning a delta for `result` events with stats.
+    ///
+    /// `stats.input_tokens` includes cached prompt tokens; the `cached` share
+    /// (when reported) is split out into cache reads.
+    pub fn extract(&mut self, json: &Value) -> Option<UsageDelta> {
+        match json.get("type").and_then(|v| v.as_str()) {
+            Some("init") => {
+                if let Some(model) = json.get("model").and_then(|v| v.as_str()) {
+                    self.model = Some(model.to_owned());
+                }
+                None
+            }
+            Some("result") => {
+                let stats = json.get("stats")?;
+                let field = |key: &str| stats.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+                let (input, cached, output) =
+                    (field("input_tokens"), field("cached"), field("output_tokens"));
+                if input == 0 && output == 0 {
+                    return None;
+                }
+                Some(UsageDelta {
+                    input_tokens: input.saturating_sub(cached),
+                    output_tokens: output,
+                    cache_read_input_tokens: cached,
+                    duration_api_ms: field("duration_ms"),
+                    model: self.model.clone(),
+                    ..Default::default()
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 #[path = "parse_tests.rs"]
 mod tests;
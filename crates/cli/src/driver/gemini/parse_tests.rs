@@ -5,7 +5,7 @@ use serde_json::json;
 
 use crate::driver::AgentState;
 
-use super::parse_gemini_state;
+use super::{parse_gemini_state, GeminiUsageExtractor};
 
 #[yare::parameterized(
     init_event = {
@@ -56,3 +56,32 @@ use super::parse_gemini_state;
 fn state_from_stream_json(entry: serde_json::Value, expected: Option<AgentState>) {
     assert_eq!(parse_gemini_state(&entry), expected);
 }
+
+#[test]
+fn usage_from_result_stats_tagged_with_init_model() -> anyhow::Result<()> {
+    let mut extractor = GeminiUsageExtractor::default();
+    let init = json!({"type": "init", "session_id": "abc123", "model": "gemini-2.5-pro"});
+    let result = json!({
+        "type": "result",
+        "status": "success",
+        "stats": {"total_tokens": 1450, "input_tokens": 1200, "output_tokens": 250, "cached": 800, "duration_ms": 5300, "tool_calls": 2},
+    });
+
+    assert!(extractor.extract(&init).is_none());
+    let delta = extractor.extract(&result).ok_or_else(|| anyhow::anyhow!("no usage"))?;
+    assert_eq!(delta.input_tokens, 400);
+    assert_eq!(delta.cache_read_input_tokens, 800);
+    assert_eq!(delta.output_tokens, 250);
+    assert_eq!(delta.duration_api_ms, 5300);
+    assert_eq!(delta.model.as_deref(), Some("gemini-2.5-pro"));
+    Ok(())
+}
+
+#[test]
+fn usage_skips_results_without_tokens() {
+    let mut extractor = GeminiUsageExtractor::default();
+    let bare = json!({"type": "result", "status": "success"});
+    let empty = json!({"type": "result", "status": "error", "stats": {"total_tokens": 0}});
+    assert!(extractor.extract(&bare).is_none());
+    assert!(extractor.extract(&empty).is_none());
+}
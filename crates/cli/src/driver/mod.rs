@@ -2,7 +2,9 @@
 // Copyright (c) 2026 Alfred Jean LLC
 
 pub mod claude;
+pub mod codex;
 pub mod composite;
+pub mod declarative;
 pub mod error_category;
 pub mod gemini;
 pub mod hook_detect;
@@ -11,7 +13,6 @@ pub mod jsonl_stdout;
 pub mod log_watch;
 pub mod nudge;
 pub mod process;
-pub mod screen_parse;
 pub mod stdout_detect;
 pub mod unknown;
 
@@ -28,6 +29,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio_util::sync::CancellationToken;
+use utoipa::ToSchema;
 
 use crate::event::{RawHookEvent, RawMessageEvent};
 use crate::usage::UsageState;
@@ -68,7 +70,7 @@ pub enum AgentState {
 }
 
 /// Distinguishes the type of prompt the agent is presenting.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PromptKind {
     Permission,
@@ -89,7 +91,7 @@ impl PromptKind {
 }
 
 /// Contextual information about a prompt the agent is presenting.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct PromptContext {
     /// Prompt type: permission, plan, question, setup.
     #[serde(rename = "type")]
@@ -177,7 +179,7 @@ impl PromptContext {
 }
 
 /// A single question within a multi-question dialog.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct QuestionContext {
     pub question: String,
     pub options: Vec<String>,
@@ -417,6 +419,25 @@ pub fn coop_session_dir(session_id: &str) -> anyhow::Result<PathBuf> {
     Ok(dir)
 }
 
+/// Whether `command` runs the agent in a structured output mode that writes
+/// JSONL to stdout (Tier 3): `codex exec --json`, `gemini --output-format
+/// stream-json`, or `claude --print --output-format stream-json`. The
+/// unknown agent always qualifies; its declarative definition decides
+/// whether stdout is mapped.
+pub fn structured_stdout(agent: AgentType, command: &[String]) -> bool {
+    let has = |flag: &str| command.iter().any(|a| a == flag);
+    let stream_json = has("--output-format=stream-json")
+        || command
+            .windows(2)
+            .any(|w| matches!(w[0].as_str(), "--output-format" | "-o") && w[1] == "stream-json");
+    match agent {
+        AgentType::Codex => has("exec") && has("--json"),
+        AgentType::Claude => stream_json && (has("--print") || has("-p")),
+        AgentType::Gemini => stream_json,
+        AgentType::Unknown => true,
+    }
+}
+
 /// Return environment variables for hook communication with the agent child process.
 pub fn hook_env_vars(pipe_path: &Path, coop_url: &str) -> Vec<(String, String)> {
     vec![
@@ -476,6 +497,33 @@ pub fn build_gemini_driver(
     })
 }
 
+/// Build a Codex-specific driver (Tier 3 stdout + Tier 4 process monitor +
+/// Tier 5 screen).
+pub fn build_codex_driver(
+    config: &crate::config::Config,
+    child_pid_fn: Arc<dyn Fn() -> Option<u32> + Send + Sync>,
+    ring_total_written_fn: Arc<dyn Fn() -> u64 + Send + Sync>,
+    snapshot_fn: Arc<dyn Fn() -> crate::screen::ScreenSnapshot + Send + Sync>,
+    sinks: DetectorSinks,
+) -> anyhow::Result<DriverContext> {
+    let driver = codex::CodexDriver::new(config, sinks)?;
+    let mut detectors = driver.detectors;
+    // Tier 4: ProcessMonitor fallback for basic Working/Exited detection
+    detectors.push(Box::new(
+        process::ProcessMonitor::new(child_pid_fn, ring_total_written_fn)
+            .with_poll_interval(config.process_poll()),
+    ));
+    // Tier 5: approval overlays and idle composer are only visible on screen
+    detectors.push(Box::new(codex::screen::CodexScreenDetector::new(config, snapshot_fn)));
+    detectors.sort_by_key(|d| d.tier());
+    Ok(DriverContext {
+        nudge_encoder: Some(Arc::new(driver.nudge)),
+        respond_encoder: Some(Arc::new(driver.respond)),
+        detectors,
+        option_parser: Some(Arc::new(codex::screen::parse_options_from_screen)),
+    })
+}
+
 #[cfg(test)]
 #[path = "driver_tests.rs"]
 mod driver_tests;
@@ -4,7 +4,8 @@
 //! Generic stdout-based JSONL detector shared by all agent drivers.
 //!
 //! Each agent provides a classify function from parsed JSON to
-//! `(AgentState, cause)` pairs; the select loop is identical.
+//! `(AgentState, cause)` pairs, and optionally a usage extractor; the select
+//! loop is identical.
 
 use std::future::Future;
 use std::pin::Pin;
@@ -17,6 +18,7 @@ use tokio_util::sync::CancellationToken;
 use crate::driver::jsonl_stdout::JsonlParser;
 use crate::driver::{AgentState, Detector, DetectorEmission};
 use crate::event::RawMessageEvent;
+use crate::usage::{UsageDelta, UsageState};
 
 /// Classifies a parsed JSON entry into an `(AgentState, cause)` pair.
 type ClassifyFn = Box<dyn Fn(&serde_json::Value) -> Option<(AgentState, String)> + Send>;
@@ -24,6 +26,10 @@ type ClassifyFn = Box<dyn Fn(&serde_json::Value) -> Option<(AgentState, String)>
 /// Extracts the last assistant message text from a parsed JSON entry.
 type ExtractMessageFn = Box<dyn Fn(&serde_json::Value) -> Option<String> + Send>;
 
+/// Extracts token usage from a parsed JSON entry. `FnMut` so an extractor can
+/// carry context between entries (e.g. the model announced at startup).
+type ExtractUsageFn = Box<dyn FnMut(&serde_json::Value) -> Option<UsageDelta> + Send>;
+
 /// Tier 3 detector that parses JSONL from an agent's stdout stream,
 /// classifying each entry via caller-supplied closures.
 pub struct StdoutDetector {
@@ -36,6 +42,10 @@ pub struct StdoutDetector {
     pub last_message: Option<Arc<RwLock<Option<String>>>>,
     /// Optional sender for raw message JSON broadcast.
     pub raw_message_tx: Option<broadcast::Sender<RawMessageEvent>>,
+    /// Optional extractor for per-response token usage.
+    pub extract_usage: Option<ExtractUsageFn>,
+    /// Usage tracking state fed by `extract_usage`.
+    pub usage: Option<Arc<UsageState>>,
 }
 
 impl Detector for StdoutDetector {
@@ -51,6 +61,8 @@ impl Detector for StdoutDetector {
             let extract_message = self.extract_message;
             let last_message = self.last_message;
             let raw_message_tx = self.raw_message_tx;
+            let mut extract_usage = self.extract_usage;
+            let usage = self.usage;
 
             loop {
                 tokio::select! {
@@ -72,6 +84,13 @@ impl Detector for StdoutDetector {
                                             }
                                         }
                                     }
+                                    if let (Some(extract), Some(u)) =
+                                        (extract_usage.as_mut(), usage.as_ref())
+                                    {
+                                        if let Some(delta) = extract(&json) {
+                                            u.accumulate(delta).await;
+                                        }
+                                    }
                                     if let Some((state, cause)) = classify(&json) {
                                         let _ = state_tx.send((state, cause, None)).await;
                                     }
@@ -3,32 +3,66 @@
 
 use std::sync::Arc;
 
-use super::{build_detectors, nudge_encoder, respond_encoder};
+use super::build_driver;
 use crate::config::Config;
+use crate::driver::DetectorSinks;
+use crate::screen::ScreenSnapshot;
+
+fn blank_snapshot() -> Arc<dyn Fn() -> ScreenSnapshot + Send + Sync> {
+    Arc::new(|| crate::screen::Screen::new(80, 24).snapshot())
+}
 
 #[test]
-fn build_detectors_without_config_returns_one_tier() -> anyhow::Result<()> {
+fn build_driver_without_config_returns_one_tier() -> anyhow::Result<()> {
     let config = Config::test();
-    let detectors = build_detectors(&config, Arc::new(|| None), Arc::new(|| 0), None)?;
-    assert_eq!(detectors.len(), 1);
-    assert_eq!(detectors[0].tier(), 4);
+    let driver = build_driver(
+        &config,
+        Arc::new(|| None),
+        Arc::new(|| 0),
+        blank_snapshot(),
+        DetectorSinks::default(),
+    )?;
+    assert_eq!(driver.detectors.len(), 1);
+    assert_eq!(driver.detectors[0].tier(), 4);
+    assert!(driver.nudge_encoder.is_none());
+    assert!(driver.respond_encoder.is_none());
+    assert!(driver.option_parser.is_none());
     Ok(())
 }
 
 #[test]
-fn build_detectors_config_without_snapshot_fn_errors() {
+fn build_driver_missing_config_errors() {
     let mut config = Config::test();
-    config.agent_config = Some("/nonexistent/config.json".into());
-    let result = build_detectors(&config, Arc::new(|| None), Arc::new(|| 0), None);
+    config.driver_config = Some("/nonexistent/driver.toml".into());
+    let result = build_driver(
+        &config,
+        Arc::new(|| None),
+        Arc::new(|| 0),
+        blank_snapshot(),
+        DetectorSinks::default(),
+    );
     assert!(result.is_err());
 }
 
 #[test]
-fn nudge_encoder_returns_none() {
-    assert!(nudge_encoder().is_none());
-}
+fn build_driver_with_definition_adds_screen_tier() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("driver.toml");
+    std::fs::write(&path, "[screen]\nidle = ['^> $']\n")?;
 
-#[test]
-fn respond_encoder_returns_none() {
-    assert!(respond_encoder().is_none());
+    let mut config = Config::test();
+    config.driver_config = Some(path);
+    let driver = build_driver(
+        &config,
+        Arc::new(|| None),
+        Arc::new(|| 0),
+        blank_snapshot(),
+        DetectorSinks::default(),
+    )?;
+    let tiers: Vec<u8> = driver.detectors.iter().map(|d| d.tier()).collect();
+    assert_eq!(tiers, vec![4, 5]);
+    assert!(driver.nudge_encoder.is_some());
+    assert!(driver.respond_encoder.is_some());
+    assert!(driver.option_parser.is_some());
+    Ok(())
 }
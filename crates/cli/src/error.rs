@@ -10,11 +10,13 @@ pub enum ErrorCode {
     NotReady,
     Exited,
     Unauthorized,
+    Forbidden,
     BadRequest,
     NoDriver,
     AgentBusy,
     NoPrompt,
     SwitchInProgress,
+    BudgetExceeded,
     Internal,
 }
 
@@ -24,11 +26,13 @@ impl ErrorCode {
             Self::NotReady => 503,
             Self::Exited => 410,
             Self::Unauthorized => 401,
+            Self::Forbidden => 403,
             Self::BadRequest => 400,
             Self::NoDriver => 404,
             Self::AgentBusy => 409,
             Self::NoPrompt => 409,
             Self::SwitchInProgress => 409,
+            Self::BudgetExceeded => 409,
             Self::Internal => 500,
         }
     }
@@ -38,11 +42,13 @@ impl ErrorCode {
             Self::NotReady => "NOT_READY",
             Self::Exited => "EXITED",
             Self::Unauthorized => "UNAUTHORIZED",
+            Self::Forbidden => "FORBIDDEN",
             Self::BadRequest => "BAD_REQUEST",
             Self::NoDriver => "NO_DRIVER",
             Self::AgentBusy => "AGENT_BUSY",
             Self::NoPrompt => "NO_PROMPT",
             Self::SwitchInProgress => "SWITCH_IN_PROGRESS",
+            Self::BudgetExceeded => "BUDGET_EXCEEDED",
             Self::Internal => "INTERNAL",
         }
     }
@@ -55,11 +61,13 @@ impl ErrorCode {
             Self::NotReady => tonic::Code::Unavailable,
             Self::Exited => tonic::Code::NotFound,
             Self::Unauthorized => tonic::Code::Unauthenticated,
+            Self::Forbidden => tonic::Code::PermissionDenied,
             Self::BadRequest => tonic::Code::InvalidArgument,
             Self::NoDriver => tonic::Code::Unimplemented,
             Self::AgentBusy => tonic::Code::FailedPrecondition,
             Self::NoPrompt => tonic::Code::FailedPrecondition,
             Self::SwitchInProgress => tonic::Code::FailedPrecondition,
+            Self::BudgetExceeded => tonic::Code::FailedPrecondition,
             Self::Internal => tonic::Code::Internal,
         };
         tonic::Status::new(code, message)
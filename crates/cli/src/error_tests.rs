@@ -7,10 +7,12 @@ use super::*;
     not_ready = { ErrorCode::NotReady, tonic::Code::Unavailable },
     exited = { ErrorCode::Exited, tonic::Code::NotFound },
     unauthorized = { ErrorCode::Unauthorized, tonic::Code::Unauthenticated },
+    forbidden = { ErrorCode::Forbidden, tonic::Code::PermissionDenied },
     bad_request = { ErrorCode::BadRequest, tonic::Code::InvalidArgument },
     no_driver = { ErrorCode::NoDriver, tonic::Code::Unimplemented },
     agent_busy = { ErrorCode::AgentBusy, tonic::Code::FailedPrecondition },
     no_prompt = { ErrorCode::NoPrompt, tonic::Code::FailedPrecondition },
+    budget_exceeded = { ErrorCode::BudgetExceeded, tonic::Code::FailedPrecondition },
     internal = { ErrorCode::Internal, tonic::Code::Internal },
 )]
 fn to_grpc_status(error_code: ErrorCode, expected: tonic::Code) {
@@ -38,10 +38,12 @@ pub enum InputEvent {
     WaitForDrain(tokio::sync::oneshot::Sender<()>),
 }
 
-/// A prompt response was delivered to the agent's terminal (auto-dismiss or API).
-#[derive(Debug, Clone, Serialize)]
+/// A prompt response was delivered to the agent's terminal (auto-dismiss,
+/// policy or API), or the respond policy left a prompt for the orchestrator.
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct PromptOutcome {
-    /// How the response was triggered: `"groom"` (auto-dismiss) or `"api"`.
+    /// How the response was triggered: `"groom"` (auto-dismiss), `"policy"`
+    /// (respond policy) or `"api"`.
     pub source: String,
     /// Prompt type responded to (e.g. `"setup"`, `"permission"`).
     pub r#type: String,
@@ -49,6 +51,10 @@ pub struct PromptOutcome {
     pub subtype: Option<String>,
     /// Option number selected (e.g. 1 for "Yes"), or `None` for Enter-only.
     pub option: Option<u32>,
+    /// Policy decision (`"allow"`, `"deny"`, `"ask"`) when `source` is `"policy"`.
+    pub decision: Option<String>,
+    /// Label of the policy rule that matched, `None` for the default action.
+    pub rule: Option<String>,
 }
 
 /// Raw hook event JSON from the hook FIFO pipe.
@@ -11,6 +11,7 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::event::{RawHookEvent, TransitionEvent};
 
@@ -28,7 +29,7 @@ pub struct EventLog {
 }
 
 /// A serialized state transition entry.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TransitionEntry {
     pub prev: String,
     pub next: String,
@@ -40,7 +41,7 @@ pub struct TransitionEntry {
 }
 
 /// A serialized hook event entry.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HookEntry {
     pub hook_seq: u64,
     pub json: serde_json::Value,
@@ -48,7 +49,8 @@ pub struct HookEntry {
 }
 
 /// Catchup response combining both event types.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = EventCatchupResponse)]
 pub struct CatchupResponse {
     pub state_events: Vec<TransitionEntry>,
     pub hook_events: Vec<HookEntry>,
@@ -133,17 +135,28 @@ impl EventLog {
 
     /// Read hook events with hook_seq > `since_hook_seq`.
     pub fn catchup_hooks(&self, since_hook_seq: u64) -> Vec<HookEntry> {
+        self.read_hooks().into_iter().filter(|e| e.hook_seq > since_hook_seq).collect()
+    }
+
+    /// Read hook events with hook_seq >= `from` (inclusive, unlike
+    /// [`catchup_hooks`](Self::catchup_hooks), so `0` reads every event).
+    pub fn hooks_from(&self, from: u64) -> Vec<HookEntry> {
+        self.read_hooks().into_iter().filter(|e| e.hook_seq >= from).collect()
+    }
+
+    /// Number of hook events logged so far (the next hook_seq).
+    pub fn hook_count(&self) -> u64 {
+        self.hook_seq.load(Ordering::Relaxed)
+    }
+
+    fn read_hooks(&self) -> Vec<HookEntry> {
         let Some(ref path) = self.hook_path else {
             return vec![];
         };
         let Ok(contents) = std::fs::read_to_string(path) else {
             return vec![];
         };
-        contents
-            .lines()
-            .filter_map(|line| serde_json::from_str::<HookEntry>(line).ok())
-            .filter(|e| e.hook_seq > since_hook_seq)
-            .collect()
+        contents.lines().filter_map(|line| serde_json::from_str::<HookEntry>(line).ok()).collect()
     }
 }
 
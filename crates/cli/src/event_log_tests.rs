@@ -65,6 +65,23 @@ fn push_and_catchup_hooks() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn hooks_from_is_inclusive() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let log = EventLog::new(Some(tmp.path()));
+
+    log.push_hook(&RawHookEvent { json: serde_json::json!({"event": "a"}) });
+    log.push_hook(&RawHookEvent { json: serde_json::json!({"event": "b"}) });
+
+    assert_eq!(log.hook_count(), 2);
+    assert_eq!(log.hooks_from(0).len(), 2);
+    let caught = log.hooks_from(1);
+    assert_eq!(caught.len(), 1);
+    assert_eq!(caught[0].json["event"], "b");
+    assert!(log.hooks_from(2).is_empty());
+    Ok(())
+}
+
 #[test]
 fn catchup_empty_when_no_events() -> anyhow::Result<()> {
     let tmp = tempfile::tempdir()?;
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Idle-timeout escalation.
+//!
+//! The `idle` section of the `--agent-config` file lists steps that fire as
+//! the agent stays idle: each step's `after_secs` is measured from when the
+//! agent went idle, so steps escalate (nudge at 10 minutes, alert at 30).
+//! The session loop fires the steps; this module holds the config, the
+//! message templates and the broadcast channel observers subscribe to.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::stop::StopMode;
+
+/// Idle section of the agent config file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdleConfig {
+    /// Escalation steps, fired in order of `after_secs`.
+    #[serde(default)]
+    pub steps: Vec<IdleStep>,
+}
+
+/// A single escalation step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdleStep {
+    /// Seconds since the agent went idle before this step fires.
+    pub after_secs: u64,
+    #[serde(flatten)]
+    pub action: IdleAction,
+}
+
+impl IdleStep {
+    pub fn after(&self) -> Duration {
+        Duration::from_secs(self.after_secs)
+    }
+}
+
+/// What an escalation step does.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum IdleAction {
+    /// Nudge the agent. `message` may use `{idle_secs}` and `{idle_mins}`.
+    Nudge { message: String },
+    /// Change the stop hook mode (and optionally its prompt).
+    StopMode {
+        mode: StopMode,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prompt: Option<String>,
+    },
+    /// Report the agent as parked until `resume_after_secs` have passed.
+    Park { resume_after_secs: u64 },
+    /// Only broadcast the `idle:step` event (published to NATS as `<prefix>.idle`).
+    Alert {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+    /// Switch to a registered credential profile.
+    Switch { profile: String },
+    /// Shut the session down.
+    Shutdown,
+}
+
+impl IdleAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nudge { .. } => "nudge",
+            Self::StopMode { .. } => "stop_mode",
+            Self::Park { .. } => "park",
+            Self::Alert { .. } => "alert",
+            Self::Switch { .. } => "switch",
+            Self::Shutdown => "shutdown",
+        }
+    }
+}
+
+/// Expand `{idle_secs}` and `{idle_mins}` in a step message.
+pub fn render_message(template: &str, idle: Duration) -> String {
+    let secs = idle.as_secs();
+    template
+        .replace("{idle_secs}", &secs.to_string())
+        .replace("{idle_mins}", &(secs / 60).to_string())
+}
+
+/// An escalation step that fired, broadcast to WebSocket and NATS consumers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdleEvent {
+    /// Index of the step in the configured order.
+    pub step: usize,
+    /// Action name (e.g. `"nudge"`, `"alert"`).
+    pub action: String,
+    /// How long the agent had been idle when the step fired.
+    pub idle_secs: u64,
+    /// Rendered message for `nudge` and `alert` steps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Runtime state for idle escalation.
+pub struct IdleState {
+    /// Steps sorted by `after_secs`.
+    pub steps: Vec<IdleStep>,
+    /// Broadcast channel for fired steps.
+    pub idle_tx: broadcast::Sender<IdleEvent>,
+}
+
+impl IdleState {
+    pub fn new(config: IdleConfig) -> Self {
+        let (idle_tx, _) = broadcast::channel(16);
+        let mut steps = config.steps;
+        steps.sort_by_key(|s| s.after_secs);
+        Self { steps, idle_tx }
+    }
+
+    /// Broadcast a fired step to all subscribers.
+    pub fn emit(&self, step: usize, action: &IdleAction, idle: Duration, message: Option<String>) {
+        let event = IdleEvent {
+            step,
+            action: action.as_str().to_owned(),
+            idle_secs: idle.as_secs(),
+            message,
+        };
+        // Ignore send errors (no receivers is fine).
+        let _ = self.idle_tx.send(event);
+    }
+}
+
+impl std::fmt::Debug for IdleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdleState").field("steps", &self.steps.len()).finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "idle_tests.rs"]
+mod tests;
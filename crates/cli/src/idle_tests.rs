@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::time::Duration;
+
+use super::{render_message, IdleAction, IdleConfig, IdleState};
+use crate::stop::StopMode;
+
+#[test]
+fn parses_every_action() -> anyhow::Result<()> {
+    let config: IdleConfig = serde_json::from_value(serde_json::json!({
+        "steps": [
+            { "after_secs": 600, "action": "nudge", "message": "still there?" },
+            { "after_secs": 900, "action": "stop_mode", "mode": "gate", "prompt": "wrap up" },
+            { "after_secs": 1200, "action": "park", "resume_after_secs": 3600 },
+            { "after_secs": 1800, "action": "alert" },
+            { "after_secs": 2400, "action": "switch", "profile": "backup" },
+            { "after_secs": 3600, "action": "shutdown" },
+        ],
+    }))?;
+    let actions: Vec<_> = config.steps.iter().map(|s| s.action.clone()).collect();
+    assert_eq!(
+        actions,
+        vec![
+            IdleAction::Nudge { message: "still there?".to_owned() },
+            IdleAction::StopMode { mode: StopMode::Gate, prompt: Some("wrap up".to_owned()) },
+            IdleAction::Park { resume_after_secs: 3600 },
+            IdleAction::Alert { message: None },
+            IdleAction::Switch { profile: "backup".to_owned() },
+            IdleAction::Shutdown,
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn rejects_unknown_action() {
+    let result = serde_json::from_value::<IdleConfig>(serde_json::json!({
+        "steps": [{ "after_secs": 60, "action": "page" }],
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn state_sorts_steps_by_delay() -> anyhow::Result<()> {
+    let config: IdleConfig = serde_json::from_value(serde_json::json!({
+        "steps": [
+            { "after_secs": 1800, "action": "alert" },
+            { "after_secs": 600, "action": "nudge", "message": "poke" },
+        ],
+    }))?;
+    let state = IdleState::new(config);
+    let delays: Vec<_> = state.steps.iter().map(|s| s.after_secs).collect();
+    assert_eq!(delays, vec![600, 1800]);
+    Ok(())
+}
+
+#[test]
+fn renders_idle_placeholders() {
+    let text = render_message("idle {idle_mins}m ({idle_secs}s)", Duration::from_secs(630));
+    assert_eq!(text, "idle 10m (630s)");
+}
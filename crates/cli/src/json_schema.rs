@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Minimal JSON Schema validator for stop-hook signal bodies.
+//!
+//! Supports the keywords orchestrators need to pin down a completion report:
+//! `type`, `enum`, `const`, `required`, `properties`, `additionalProperties`,
+//! `items`, `minItems`/`maxItems`, `minLength`/`maxLength`, `pattern`,
+//! `minimum`/`maximum` and `exclusiveMinimum`/`exclusiveMaximum`, plus the
+//! `true`/`false` schemas. Other keywords (`description`, `title`, ...) are
+//! ignored. Validation collects every violation rather than stopping at the
+//! first, so a rejected agent can fix its report in one go.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+/// A single schema violation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Violation {
+    /// JSON Pointer to the offending value (`""` for the whole body).
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Validate `instance` against `schema`, returning every violation found.
+pub fn validate(schema: &Value, instance: &Value) -> Vec<Violation> {
+    let mut out = Vec::new();
+    validate_at(schema, instance, "", &mut out);
+    out
+}
+
+/// Check that `schema` is usable: an object or boolean whose supported
+/// keywords have the right shape and whose patterns compile.
+pub fn check_schema(schema: &Value) -> Result<(), String> {
+    check_at(schema, "")
+}
+
+fn check_at(schema: &Value, path: &str) -> Result<(), String> {
+    let obj = match schema {
+        Value::Bool(_) => return Ok(()),
+        Value::Object(obj) => obj,
+        _ => return Err(format!("{}: schema must be an object or boolean", display(path))),
+    };
+    let here = display(path);
+    if let Some(ty) = obj.get("type") {
+        let names: Vec<&Value> = match ty {
+            Value::Array(items) => items.iter().collect(),
+            other => vec![other],
+        };
+        for name in names {
+            match name.as_str() {
+                Some(n) if TYPES.contains(&n) => {}
+                _ => return Err(format!("{here}: unknown type {name}")),
+            }
+        }
+    }
+    if let Some(pattern) = obj.get("pattern") {
+        let pattern =
+            pattern.as_str().ok_or_else(|| format!("{here}: pattern must be a string"))?;
+        regex::Regex::new(pattern).map_err(|e| format!("{here}: invalid pattern: {e}"))?;
+    }
+    if let Some(required) = obj.get("required") {
+        if !required.as_array().is_some_and(|r| r.iter().all(Value::is_string)) {
+            return Err(format!("{here}: required must be an array of strings"));
+        }
+    }
+    if let Some(values) = obj.get("enum") {
+        if !values.is_array() {
+            return Err(format!("{here}: enum must be an array"));
+        }
+    }
+    for key in
+        ["minItems", "maxItems", "minLength", "maxLength"].iter().filter(|k| obj.contains_key(**k))
+    {
+        if !obj[*key].is_u64() {
+            return Err(format!("{here}: {key} must be a non-negative integer"));
+        }
+    }
+    for key in ["minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum"]
+        .iter()
+        .filter(|k| obj.contains_key(**k))
+    {
+        if !obj[*key].is_number() {
+            return Err(format!("{here}: {key} must be a number"));
+        }
+    }
+    if let Some(props) = obj.get("properties") {
+        let props =
+            props.as_object().ok_or_else(|| format!("{here}: properties must be an object"))?;
+        for (name, sub) in props {
+            check_at(sub, &format!("{path}/properties/{}", escape(name)))?;
+        }
+    }
+    if let Some(sub) = obj.get("additionalProperties") {
+        check_at(sub, &format!("{path}/additionalProperties"))?;
+    }
+    if let Some(sub) = obj.get("items") {
+        check_at(sub, &format!("{path}/items"))?;
+    }
+    Ok(())
+}
+
+const TYPES: &[&str] = &["null", "boolean", "object", "array", "number", "integer", "string"];
+
+fn validate_at(schema: &Value, instance: &Value, path: &str, out: &mut Vec<Violation>) {
+    let obj = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            out.push(violation(path, "no value is allowed here".to_owned()));
+            return;
+        }
+        Value::Object(obj) => obj,
+        _ => return,
+    };
+
+    if let Some(ty) = obj.get("type") {
+        let allowed: Vec<&str> = match ty {
+            Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+        if !allowed.iter().any(|t| is_type(instance, t)) {
+            out.push(violation(
+                path,
+                format!("expected {}, got {}", allowed.join(" or "), type_name(instance)),
+            ));
+            // Further keywords would only repeat the type mismatch.
+            return;
+        }
+    }
+
+    if let Some(values) = obj.get("enum").and_then(Value::as_array) {
+        if !values.contains(instance) {
+            let list: Vec<String> = values.iter().map(short).collect();
+            out.push(violation(
+                path,
+                format!("value {} is not one of: {}", short(instance), list.join(", ")),
+            ));
+        }
+    }
+    if let Some(expected) = obj.get("const") {
+        if expected != instance {
+            out.push(violation(path, format!("value must be {}", short(expected))));
+        }
+    }
+
+    match instance {
+        Value::Object(map) => {
+            if let Some(required) = obj.get("required").and_then(Value::as_array) {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(name) {
+                        out.push(violation(path, format!("missing required field: {name}")));
+                    }
+                }
+            }
+            let props = obj.get("properties").and_then(Value::as_object);
+            for (name, value) in map {
+                let child = format!("{path}/{}", escape(name));
+                match props.and_then(|p| p.get(name)) {
+                    Some(sub) => validate_at(sub, value, &child, out),
+                    None => match obj.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            out.push(violation(&child, "unexpected field".to_owned()));
+                        }
+                        Some(sub) => validate_at(sub, value, &child, out),
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = obj.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    out.push(violation(path, format!("expected at least {min} items")));
+                }
+            }
+            if let Some(max) = obj.get("maxItems").and_then(Value::as_u64) {
+                if items.len() as u64 > max {
+                    out.push(violation(path, format!("expected at most {max} items")));
+                }
+            }
+            if let Some(sub) = obj.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(sub, item, &format!("{path}/{i}"), out);
+                }
+            }
+        }
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = obj.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    out.push(violation(path, format!("expected at least {min} characters")));
+                }
+            }
+            if let Some(max) = obj.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    out.push(violation(path, format!("expected at most {max} characters")));
+                }
+            }
+            if let Some(pattern) = obj.get("pattern").and_then(Value::as_str) {
+                if let Ok(re) = regex::Regex::new(pattern) {
+                    if !re.is_match(s) {
+                        out.push(violation(path, format!("does not match pattern {pattern:?}")));
+                    }
+                }
+            }
+        }
+        Value::Number(n) => {
+            if let Some(x) = n.as_f64() {
+                let bound = |key: &str| obj.get(key).and_then(Value::as_f64);
+                if let Some(min) = bound("minimum").filter(|&min| x < min) {
+                    out.push(violation(path, format!("must be at least {min}")));
+                }
+                if let Some(max) = bound("maximum").filter(|&max| x > max) {
+                    out.push(violation(path, format!("must be at most {max}")));
+                }
+                if let Some(min) = bound("exclusiveMinimum").filter(|&min| x <= min) {
+                    out.push(violation(path, format!("must be greater than {min}")));
+                }
+                if let Some(max) = bound("exclusiveMaximum").filter(|&max| x >= max) {
+                    out.push(violation(path, format!("must be less than {max}")));
+                }
+            }
+        }
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+fn is_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        "string" => value.is_string(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+    }
+}
+
+/// Render a value for a message: strings keep their quotes, large values
+/// are abbreviated.
+fn short(value: &Value) -> String {
+    match value {
+        Value::Object(_) => "{...}".to_owned(),
+        Value::Array(_) => "[...]".to_owned(),
+        other => other.to_string(),
+    }
+}
+
+fn violation(path: &str, message: String) -> Violation {
+    Violation { path: path.to_owned(), message }
+}
+
+/// JSON Pointer escaping for a single reference token.
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn display(path: &str) -> &str {
+    if path.is_empty() {
+        "schema"
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+#[path = "json_schema_tests.rs"]
+mod tests;
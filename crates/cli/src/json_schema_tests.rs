@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use serde_json::{json, Value};
+
+use super::{check_schema, validate, Violation};
+
+fn paths(schema: &Value, instance: &Value) -> Vec<String> {
+    validate(schema, instance).into_iter().map(|v| v.path).collect()
+}
+
+#[yare::parameterized(
+    string_ok = { json!({"type": "string"}), json!("x"), true },
+    string_bad = { json!({"type": "string"}), json!(1), false },
+    integer_ok = { json!({"type": "integer"}), json!(3), true },
+    integer_float = { json!({"type": "integer"}), json!(3.5), false },
+    union = { json!({"type": ["string", "null"]}), json!(null), true },
+    enum_bad = { json!({"enum": ["a", "b"]}), json!("c"), false },
+    const_ok = { json!({"const": 1}), json!(1), true },
+    pattern_ok = { json!({"pattern": "^PR-[0-9]+$"}), json!("PR-12"), true },
+    pattern_bad = { json!({"pattern": "^PR-[0-9]+$"}), json!("pr12"), false },
+    min_length = { json!({"minLength": 2}), json!("x"), false },
+    maximum = { json!({"maximum": 10}), json!(11), false },
+    exclusive_minimum = { json!({"exclusiveMinimum": 0}), json!(0), false },
+    max_items = { json!({"maxItems": 1}), json!([1, 2]), false },
+    required_present = { json!({"required": ["a"]}), json!({"a": 1}), true },
+    required_null = { json!({"required": ["a"]}), json!({"a": null}), true },
+    required_absent = { json!({"required": ["a"]}), json!({"b": 1}), false },
+    false_schema = { json!(false), json!("anything"), false },
+    true_schema = { json!(true), json!({"a": 1}), true },
+)]
+fn keywords(schema: Value, instance: Value, valid: bool) {
+    assert_eq!(validate(&schema, &instance).is_empty(), valid, "{schema} vs {instance}");
+}
+
+#[test]
+fn collects_every_violation_with_pointers() {
+    let schema = json!({
+        "type": "object",
+        "required": ["status", "report"],
+        "properties": {
+            "status": {"enum": ["done", "failed"]},
+            "report": {
+                "type": "object",
+                "required": ["tests"],
+                "properties": {"files": {"type": "array", "items": {"type": "string"}}},
+                "additionalProperties": false,
+            },
+        },
+    });
+    let body = json!({"status": "bogus", "report": {"files": ["a.rs", 7], "extra/x": 1}});
+
+    assert_eq!(
+        paths(&schema, &body),
+        vec!["/report", "/report/extra~1x", "/report/files/1", "/status"]
+    );
+    assert_eq!(
+        validate(&schema, &body)[0],
+        Violation {
+            path: "/report".to_owned(),
+            message: "missing required field: tests".to_owned()
+        }
+    );
+}
+
+#[test]
+fn type_mismatch_reports_once() {
+    let schema = json!({"type": "string", "minLength": 3, "pattern": "^a"});
+    let violations = validate(&schema, &json!(42));
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].to_string(), "expected string, got number");
+}
+
+#[yare::parameterized(
+    bad_type = { json!({"type": "text"}) },
+    bad_pattern = { json!({"pattern": "("}) },
+    bad_required = { json!({"required": "status"}) },
+    bad_nested = { json!({"properties": {"a": {"minLength": -1}}}) },
+    not_a_schema = { json!("string") },
+)]
+fn check_schema_rejects(schema: Value) {
+    assert!(check_schema(&schema).is_err(), "{schema}");
+}
+
+#[test]
+fn check_schema_accepts_nested() {
+    let schema = json!({
+        "type": "object",
+        "properties": {"pr": {"type": "string", "pattern": "^[0-9]+$"}},
+        "additionalProperties": {"type": "integer", "minimum": 0},
+        "description": "ignored",
+    });
+    assert_eq!(check_schema(&schema), Ok(()));
+}
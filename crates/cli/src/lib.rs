@@ -1,14 +1,24 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
+pub mod asciicast;
+pub mod audit;
 pub mod backend;
+pub mod budget;
 pub mod command;
 pub mod config;
 pub mod driver;
 pub mod error;
 pub mod event;
 pub mod event_log;
+pub mod idle;
+pub mod json_schema;
+pub mod metrics;
 pub mod mux_client;
+pub mod nudge_queue;
+pub mod persist;
+pub mod policy;
+pub mod pricing;
 pub mod profile;
 pub mod record;
 pub mod rendering_test_support;
@@ -17,9 +27,11 @@ pub mod ring;
 pub mod run;
 pub mod screen;
 pub mod session;
+pub mod spill;
 pub mod start;
 pub mod stop;
 pub mod switch;
+pub mod telemetry;
 pub mod test_support;
 pub mod transcript;
 pub mod transport;
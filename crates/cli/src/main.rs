@@ -107,6 +107,8 @@ enum Commands {
     Cred(coop::command::cred::CredArgs),
     /// Peek at session screens from the mux.
     Peek(coop::command::peek::PeekArgs),
+    /// Play back a session recording in the terminal.
+    Replay(coop::command::replay::ReplayArgs),
 }
 
 #[tokio::main]
@@ -133,6 +135,9 @@ async fn main() {
         Some(Commands::Peek(args)) => {
             std::process::exit(coop::command::peek::run(&args).await);
         }
+        Some(Commands::Replay(args)) => {
+            std::process::exit(coop::command::replay::run(&args).await);
+        }
         None => {
             let config = cli.config;
 
@@ -141,15 +146,15 @@ async fn main() {
                 std::process::exit(2);
             }
 
-            match coop::run::run(config).await {
-                Ok(result) => {
-                    std::process::exit(result.status.code.unwrap_or(1));
-                }
+            let code = match coop::run::run(config).await {
+                Ok(result) => result.status.code.unwrap_or(1),
                 Err(e) => {
                     error!("fatal: {e:#}");
-                    std::process::exit(1);
+                    1
                 }
-            }
+            };
+            coop::telemetry::shutdown();
+            std::process::exit(code);
         }
     }
 }
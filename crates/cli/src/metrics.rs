@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Prometheus metrics for `GET /metrics`.
+//!
+//! Event counters (transitions, nudges) are accumulated here as they happen;
+//! point-in-time gauges (agent state, ring bytes, usage) are read from the
+//! store at scrape time. Output uses the Prometheus text exposition format.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the nudge delivery latency histogram buckets.
+const NUDGE_LATENCY_BUCKETS: &[f64] = &[0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// How a nudge request ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NudgeResult {
+    /// Written to the PTY (or handed to respond for plan/question prompts).
+    Delivered,
+    /// Soft failure: the agent was in a state that doesn't accept nudges.
+    Rejected,
+    /// Hard failure: not ready or no driver.
+    Error,
+}
+
+impl NudgeResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Rejected => "rejected",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Event counters accumulated over the life of the process.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// State transitions keyed by `(tier, cause)`.
+    transitions: Mutex<BTreeMap<(u8, String), u64>>,
+    nudges_delivered: AtomicU64,
+    nudges_rejected: AtomicU64,
+    nudges_error: AtomicU64,
+    nudge_enter_retries: AtomicU64,
+    nudge_latency: Histogram,
+}
+
+impl Metrics {
+    /// Count a state transition detected at `tier` with `cause`.
+    pub fn record_transition(&self, tier: u8, cause: &str) {
+        let Ok(mut transitions) = self.transitions.lock() else {
+            return;
+        };
+        *transitions.entry((tier, cause.to_owned())).or_default() += 1;
+    }
+
+    /// Count a nudge request. Latency is only observed for delivered nudges.
+    pub fn record_nudge(&self, result: NudgeResult, elapsed: Duration) {
+        let counter = match result {
+            NudgeResult::Delivered => {
+                self.nudge_latency.observe(elapsed);
+                &self.nudges_delivered
+            }
+            NudgeResult::Rejected => &self.nudges_rejected,
+            NudgeResult::Error => &self.nudges_error,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count an Enter re-send by the nudge safety net.
+    pub fn record_enter_retry(&self) {
+        self.nudge_enter_retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Write the accumulated counters in exposition format.
+    pub fn write_to(&self, out: &mut Exposition) {
+        out.header("coop_state_transitions_total", "counter", "Agent state transitions.");
+        if let Ok(transitions) = self.transitions.lock() {
+            for ((tier, cause), count) in transitions.iter() {
+                let tier = tier.to_string();
+                out.sample(
+                    "coop_state_transitions_total",
+                    &[("tier", &tier), ("cause", cause)],
+                    count,
+                );
+            }
+        }
+
+        out.header("coop_nudges_total", "counter", "Nudge requests by result.");
+        for (result, counter) in [
+            (NudgeResult::Delivered, &self.nudges_delivered),
+            (NudgeResult::Rejected, &self.nudges_rejected),
+            (NudgeResult::Error, &self.nudges_error),
+        ] {
+            let count = counter.load(Ordering::Relaxed);
+            out.sample("coop_nudges_total", &[("result", result.as_str())], count);
+        }
+
+        out.header(
+            "coop_nudge_enter_retries_total",
+            "counter",
+            "Enter keys re-sent after a nudge was not confirmed in time.",
+        );
+        out.sample(
+            "coop_nudge_enter_retries_total",
+            &[],
+            self.nudge_enter_retries.load(Ordering::Relaxed),
+        );
+
+        out.header(
+            "coop_nudge_delivery_seconds",
+            "histogram",
+            "Time from nudge request to PTY delivery, including input gate wait.",
+        );
+        self.nudge_latency.write_to("coop_nudge_delivery_seconds", out);
+    }
+}
+
+/// Fixed-bucket latency histogram.
+#[derive(Debug)]
+struct Histogram {
+    /// Per-bucket (non-cumulative) counts; the last slot is `+Inf`.
+    buckets: Vec<AtomicU64>,
+    sum_micros: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: (0..=NUDGE_LATENCY_BUCKETS.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_micros: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Histogram {
+    fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let idx = NUDGE_LATENCY_BUCKETS
+            .iter()
+            .position(|le| secs <= *le)
+            .unwrap_or(NUDGE_LATENCY_BUCKETS.len());
+        if let Some(bucket) = self.buckets.get(idx) {
+            bucket.fetch_add(1, Ordering::Relaxed);
+        }
+        self.sum_micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn write_to(&self, name: &str, out: &mut Exposition) {
+        let bucket_name = format!("{name}_bucket");
+        let mut cumulative = 0;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let le = NUDGE_LATENCY_BUCKETS.get(i).map_or("+Inf".to_owned(), |le| le.to_string());
+            out.sample(&bucket_name, &[("le", &le)], cumulative);
+        }
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        out.sample(&format!("{name}_sum"), &[], sum);
+        out.sample(&format!("{name}_count"), &[], self.count.load(Ordering::Relaxed));
+    }
+}
+
+/// Builder for a Prometheus text exposition document.
+#[derive(Debug, Default)]
+pub struct Exposition {
+    buf: String,
+}
+
+impl Exposition {
+    /// Start a metric family with its `# HELP` and `# TYPE` lines.
+    pub fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.buf, "# HELP {name} {help}");
+        let _ = writeln!(self.buf, "# TYPE {name} {kind}");
+    }
+
+    /// Write a single sample line.
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        self.buf.push_str(name);
+        if !labels.is_empty() {
+            self.buf.push('{');
+            for (i, (key, val)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.buf.push(',');
+                }
+                let _ = write!(self.buf, "{key}=\"{}\"", escape_label(val));
+            }
+            self.buf.push('}');
+        }
+        let _ = writeln!(self.buf, " {value}");
+    }
+
+    /// Convenience for a family with a single unlabelled sample.
+    pub fn single(&mut self, name: &str, kind: &str, help: &str, value: impl Display) {
+        self.header(name, kind, help);
+        self.sample(name, &[], value);
+    }
+
+    pub fn finish(self) -> String {
+        self.buf
+    }
+}
+
+/// Escape a label value per the exposition format.
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+#[path = "metrics_tests.rs"]
+mod tests;
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::time::Duration;
+
+use super::{Exposition, Metrics, NudgeResult};
+
+fn render(metrics: &Metrics) -> String {
+    let mut out = Exposition::default();
+    metrics.write_to(&mut out);
+    out.finish()
+}
+
+#[test]
+fn transitions_counted_by_tier_and_cause() {
+    let metrics = Metrics::default();
+    metrics.record_transition(1, "hook:idle");
+    metrics.record_transition(1, "hook:idle");
+    metrics.record_transition(3, "screen:working");
+
+    let text = render(&metrics);
+    assert!(text.contains("# TYPE coop_state_transitions_total counter\n"));
+    assert!(text.contains("coop_state_transitions_total{tier=\"1\",cause=\"hook:idle\"} 2\n"));
+    assert!(text.contains("coop_state_transitions_total{tier=\"3\",cause=\"screen:working\"} 1\n"));
+}
+
+#[test]
+fn nudge_results_and_latency_histogram() {
+    let metrics = Metrics::default();
+    metrics.record_nudge(NudgeResult::Delivered, Duration::from_millis(30));
+    metrics.record_nudge(NudgeResult::Delivered, Duration::from_secs(20));
+    metrics.record_nudge(NudgeResult::Rejected, Duration::ZERO);
+
+    let text = render(&metrics);
+    assert!(text.contains("coop_nudges_total{result=\"delivered\"} 2\n"));
+    assert!(text.contains("coop_nudges_total{result=\"rejected\"} 1\n"));
+    assert!(text.contains("coop_nudges_total{result=\"error\"} 0\n"));
+    // Buckets are cumulative; rejected nudges are not observed.
+    assert!(text.contains("coop_nudge_delivery_seconds_bucket{le=\"0.01\"} 0\n"));
+    assert!(text.contains("coop_nudge_delivery_seconds_bucket{le=\"0.05\"} 1\n"));
+    assert!(text.contains("coop_nudge_delivery_seconds_bucket{le=\"10\"} 1\n"));
+    assert!(text.contains("coop_nudge_delivery_seconds_bucket{le=\"+Inf\"} 2\n"));
+    assert!(text.contains("coop_nudge_delivery_seconds_sum 20.03\n"));
+    assert!(text.contains("coop_nudge_delivery_seconds_count 2\n"));
+}
+
+#[test]
+fn label_values_are_escaped() {
+    let mut out = Exposition::default();
+    out.sample("m", &[("cause", "a\"b\\c\nd")], 1);
+    assert_eq!(out.finish(), "m{cause=\"a\\\"b\\\\c\\nd\"} 1\n");
+}
@@ -0,0 +1,355 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Persistent nudge queue.
+//!
+//! A plain nudge is fire-and-forget: when the agent is at a prompt, errored
+//! or parked it comes back `delivered: false`. Queued nudges are instead
+//! kept (in `nudge_queue.json` in the session directory) and delivered one at
+//! a time whenever the agent is idle, highest priority first, honouring each
+//! message's `not_before` and expiry. Messages with the same dedup key
+//! replace each other while pending.
+
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Notify};
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::driver::AgentState;
+use crate::error::ErrorCode;
+use crate::persist::write_json_atomic;
+use crate::transport::handler::handle_nudge;
+use crate::transport::Store;
+
+/// File name of the queue snapshot within the session directory.
+pub const NUDGE_QUEUE_FILE: &str = "nudge_queue.json";
+
+/// A nudge to enqueue.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct NewNudge {
+    pub message: String,
+    /// Higher priorities are delivered first; equal priorities in FIFO order.
+    #[serde(default)]
+    pub priority: i32,
+    /// Do not deliver before this time (epoch ms).
+    #[serde(default)]
+    pub not_before_ms: Option<u64>,
+    /// Drop the message if it is still queued at this time (epoch ms).
+    #[serde(default)]
+    pub expires_at_ms: Option<u64>,
+    /// Replaces any pending message with the same key.
+    #[serde(default)]
+    pub dedup_key: Option<String>,
+}
+
+/// A pending nudge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct QueuedNudge {
+    pub id: String,
+    pub message: String,
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_key: Option<String>,
+    pub enqueued_at_ms: u64,
+}
+
+impl QueuedNudge {
+    fn is_due(&self, now_ms: u64) -> bool {
+        self.not_before_ms.is_none_or(|t| t <= now_ms)
+    }
+
+    fn is_expired(&self, now_ms: u64) -> bool {
+        self.expires_at_ms.is_some_and(|t| t <= now_ms)
+    }
+}
+
+/// What happened to a queued nudge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NudgeQueueEventKind {
+    Queued,
+    Delivered,
+    Cancelled,
+    Expired,
+}
+
+impl NudgeQueueEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Delivered => "delivered",
+            Self::Cancelled => "cancelled",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+/// Broadcast whenever the queue changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NudgeQueueEvent {
+    pub kind: NudgeQueueEventKind,
+    pub nudge: QueuedNudge,
+}
+
+/// Runtime state for the nudge queue.
+pub struct NudgeQueue {
+    path: Option<PathBuf>,
+    pending: Mutex<Pending>,
+    /// Wakes the delivery task when a message is enqueued.
+    changed: Notify,
+    /// Broadcast channel for queue events.
+    pub queue_tx: broadcast::Sender<NudgeQueueEvent>,
+}
+
+/// Pending messages and the one being delivered, under one lock so a
+/// message can't be cancelled or replaced once delivery has claimed it.
+#[derive(Default)]
+struct Pending {
+    items: Vec<QueuedNudge>,
+    /// ID of the message claimed for delivery. It stays in `items` (and on
+    /// disk) until delivered, so a crash mid-delivery retries it.
+    in_flight: Option<String>,
+}
+
+/// Highest priority first, then oldest first.
+fn delivery_order(a: &QueuedNudge, b: &QueuedNudge) -> std::cmp::Ordering {
+    b.priority.cmp(&a.priority).then(a.enqueued_at_ms.cmp(&b.enqueued_at_ms))
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+impl NudgeQueue {
+    /// Create a queue, restoring pending messages from `session_dir`.
+    /// If `session_dir` is `None` (tests/attach mode), the queue is memory-only.
+    pub fn new(session_dir: Option<&std::path::Path>) -> Self {
+        let path = session_dir.map(|dir| {
+            let _ = std::fs::create_dir_all(dir);
+            dir.join(NUDGE_QUEUE_FILE)
+        });
+        let items = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str::<Vec<QueuedNudge>>(&s).ok())
+            .unwrap_or_default();
+        let (queue_tx, _) = broadcast::channel(64);
+        let pending = Mutex::new(Pending { items, in_flight: None });
+        Self { path, pending, changed: Notify::new(), queue_tx }
+    }
+
+    /// Add a message, replacing any pending one with the same dedup key
+    /// unless that one is already being delivered.
+    pub fn enqueue(&self, new: NewNudge) -> QueuedNudge {
+        let mut pending = self.pending.lock();
+        let Pending { items, in_flight } = &mut *pending;
+        let existing = new.dedup_key.as_ref().and_then(|key| {
+            items.iter().position(|n| {
+                n.dedup_key.as_ref() == Some(key) && in_flight.as_ref() != Some(&n.id)
+            })
+        });
+        // A replacement keeps the original ID and queue position.
+        let (id, enqueued_at_ms) = match existing {
+            Some(i) => (items[i].id.clone(), items[i].enqueued_at_ms),
+            None => (uuid::Uuid::new_v4().to_string(), now_ms()),
+        };
+        let nudge = QueuedNudge {
+            id,
+            message: new.message,
+            priority: new.priority,
+            not_before_ms: new.not_before_ms,
+            expires_at_ms: new.expires_at_ms,
+            dedup_key: new.dedup_key,
+            enqueued_at_ms,
+        };
+        match existing {
+            Some(i) => items[i] = nudge.clone(),
+            None => items.push(nudge.clone()),
+        }
+        self.persist(items);
+        drop(pending);
+        self.emit(NudgeQueueEventKind::Queued, nudge.clone());
+        self.changed.notify_one();
+        nudge
+    }
+
+    /// Pending messages in delivery order.
+    pub fn list(&self) -> Vec<QueuedNudge> {
+        let mut items = self.pending.lock().items.clone();
+        items.sort_by(delivery_order);
+        items
+    }
+
+    /// Remove a pending message. Fails with `BAD_REQUEST` for an unknown ID
+    /// and `AGENT_BUSY` for a message that is being delivered.
+    pub fn cancel(&self, id: &str) -> Result<QueuedNudge, (ErrorCode, String)> {
+        let mut pending = self.pending.lock();
+        if pending.in_flight.as_deref() == Some(id) {
+            return Err((ErrorCode::AgentBusy, format!("nudge {id} is being delivered")));
+        }
+        let Some(pos) = pending.items.iter().position(|n| n.id == id) else {
+            return Err((ErrorCode::BadRequest, format!("no queued nudge {id}")));
+        };
+        let nudge = pending.items.remove(pos);
+        self.persist(&pending.items);
+        drop(pending);
+        self.emit(NudgeQueueEventKind::Cancelled, nudge.clone());
+        Ok(nudge)
+    }
+
+    /// Drop expired messages and claim the next one due for delivery. Nothing
+    /// is claimed while another message is in flight.
+    pub fn claim_next(&self, now_ms: u64) -> Option<QueuedNudge> {
+        let mut pending = self.pending.lock();
+        if pending.in_flight.is_some() {
+            return None;
+        }
+        let (expired, kept): (Vec<_>, Vec<_>) =
+            pending.items.drain(..).partition(|n| n.is_expired(now_ms));
+        pending.items = kept;
+        if !expired.is_empty() {
+            self.persist(&pending.items);
+        }
+        let next = pending
+            .items
+            .iter()
+            .filter(|n| n.is_due(now_ms))
+            .min_by(|a, b| delivery_order(a, b))
+            .cloned();
+        pending.in_flight = next.as_ref().map(|n| n.id.clone());
+        drop(pending);
+        for nudge in expired {
+            self.emit(NudgeQueueEventKind::Expired, nudge);
+        }
+        next
+    }
+
+    /// Return a claimed message to the queue after a failed delivery.
+    fn release(&self, id: &str) {
+        let mut pending = self.pending.lock();
+        if pending.in_flight.as_deref() == Some(id) {
+            pending.in_flight = None;
+        }
+    }
+
+    /// Earliest future `not_before` or expiry, when the queue must be rechecked.
+    pub fn next_wake_ms(&self, now_ms: u64) -> Option<u64> {
+        self.pending
+            .lock()
+            .items
+            .iter()
+            .flat_map(|n| [n.not_before_ms, n.expires_at_ms])
+            .flatten()
+            .filter(|&t| t > now_ms)
+            .min()
+    }
+
+    /// Remove a claimed message once it has been delivered.
+    fn mark_delivered(&self, id: &str) {
+        let mut pending = self.pending.lock();
+        if pending.in_flight.as_deref() == Some(id) {
+            pending.in_flight = None;
+        }
+        let Some(pos) = pending.items.iter().position(|n| n.id == id) else {
+            return;
+        };
+        let nudge = pending.items.remove(pos);
+        self.persist(&pending.items);
+        drop(pending);
+        self.emit(NudgeQueueEventKind::Delivered, nudge);
+    }
+
+    fn emit(&self, kind: NudgeQueueEventKind, nudge: QueuedNudge) {
+        // Ignore send errors (no receivers is fine).
+        let _ = self.queue_tx.send(NudgeQueueEvent { kind, nudge });
+    }
+
+    /// Rewrite the snapshot atomically. Called with `pending` locked, so saves
+    /// land in order.
+    fn persist(&self, items: &[QueuedNudge]) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        if let Err(e) = write_json_atomic(path, items) {
+            debug!("nudge queue: failed to persist {}: {e}", path.display());
+        }
+    }
+}
+
+impl std::fmt::Debug for NudgeQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NudgeQueue").field("pending", &self.pending.lock().items.len()).finish()
+    }
+}
+
+/// Spawn the task that delivers queued nudges when the agent goes idle.
+pub fn spawn_delivery(store: Arc<Store>, shutdown: CancellationToken) {
+    let mut state_rx = store.channels.state_tx.subscribe();
+    tokio::spawn(async move {
+        // State seq at the last delivery: the next message waits until the
+        // agent has left that idle period.
+        let mut delivered_at = None;
+        loop {
+            if let Some(seq) = deliver_next(&store, delivered_at).await {
+                delivered_at = Some(seq);
+            }
+            let now = now_ms();
+            let wake = store.nudge_queue.next_wake_ms(now).map(|t| Duration::from_millis(t - now));
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                event = state_rx.recv() => {
+                    if let Err(broadcast::error::RecvError::Closed) = event {
+                        break;
+                    }
+                }
+                _ = store.nudge_queue.changed.notified() => {}
+                _ = tokio::time::sleep(wake.unwrap_or_default()), if wake.is_some() => {}
+            }
+        }
+    });
+}
+
+/// Deliver the next due message if the agent is idle. One message per idle
+/// period: the agent starts working on it and the next idle delivers the next.
+/// Returns the state seq the message was delivered at; nothing is sent while
+/// the seq still equals `delivered_at`.
+async fn deliver_next(store: &Store, delivered_at: Option<u64>) -> Option<u64> {
+    if !matches!(*store.driver.agent_state.read().await, AgentState::Idle) {
+        return None;
+    }
+    // Read after the state so a transition in between is never attributed
+    // to the idle period being delivered into.
+    let seq = store.driver.state_seq.load(Ordering::Acquire);
+    if delivered_at == Some(seq) {
+        return None;
+    }
+    let nudge = store.nudge_queue.claim_next(now_ms())?;
+    match handle_nudge(store, &nudge.message).await {
+        Ok(outcome) if outcome.delivered => {
+            store.nudge_queue.mark_delivered(&nudge.id);
+            return Some(seq);
+        }
+        Ok(outcome) => debug!("nudge queue: {} not delivered: {:?}", nudge.id, outcome.reason),
+        Err(code) => debug!("nudge queue: {} failed: {code:?}", nudge.id),
+    }
+    store.nudge_queue.release(&nudge.id);
+    None
+}
+
+#[cfg(test)]
+#[path = "nudge_queue_tests.rs"]
+mod tests;
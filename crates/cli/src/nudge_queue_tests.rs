@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use super::{spawn_delivery, NewNudge, NudgeQueue, NudgeQueueEvent, NudgeQueueEventKind};
+use crate::driver::AgentState;
+use crate::error::ErrorCode;
+use crate::event::{InputEvent, TransitionEvent};
+use crate::test_support::{StoreBuilder, StoreCtx, StubNudgeEncoder};
+
+fn nudge(message: &str) -> NewNudge {
+    NewNudge { message: message.to_owned(), ..Default::default() }
+}
+
+#[test]
+fn lists_by_priority_then_fifo() {
+    let queue = NudgeQueue::new(None);
+    queue.enqueue(nudge("first"));
+    queue.enqueue(NewNudge { priority: 5, ..nudge("urgent") });
+    queue.enqueue(nudge("second"));
+
+    let order: Vec<_> = queue.list().into_iter().map(|n| n.message).collect();
+    assert_eq!(order, vec!["urgent", "first", "second"]);
+    assert_eq!(queue.claim_next(0).map(|n| n.message).as_deref(), Some("urgent"));
+}
+
+#[test]
+fn dedup_key_replaces_pending_message() {
+    let queue = NudgeQueue::new(None);
+    let original = queue.enqueue(NewNudge { dedup_key: Some("ci".to_owned()), ..nudge("red") });
+    queue.enqueue(nudge("other"));
+    let replaced = queue.enqueue(NewNudge { dedup_key: Some("ci".to_owned()), ..nudge("green") });
+
+    assert_eq!(replaced.id, original.id);
+    let messages: Vec<_> = queue.list().into_iter().map(|n| n.message).collect();
+    assert_eq!(messages, vec!["green", "other"]);
+}
+
+#[test]
+fn not_before_and_expiry() {
+    let queue = NudgeQueue::new(None);
+    let mut rx = queue.queue_tx.subscribe();
+    queue.enqueue(NewNudge { not_before_ms: Some(2_000), ..nudge("later") });
+    queue.enqueue(NewNudge { expires_at_ms: Some(1_000), ..nudge("stale") });
+
+    assert_eq!(queue.next_wake_ms(500), Some(1_000));
+    assert!(queue.claim_next(1_500).is_none());
+    assert_eq!(queue.next_wake_ms(1_500), Some(2_000));
+    assert_eq!(queue.claim_next(2_000).map(|n| n.message).as_deref(), Some("later"));
+
+    let kinds: Vec<_> =
+        std::iter::from_fn(|| rx.try_recv().ok()).map(|e| (e.kind, e.nudge.message)).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (NudgeQueueEventKind::Queued, "later".to_owned()),
+            (NudgeQueueEventKind::Queued, "stale".to_owned()),
+            (NudgeQueueEventKind::Expired, "stale".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn cancel_removes_and_reports_unknown() {
+    let queue = NudgeQueue::new(None);
+    let queued = queue.enqueue(nudge("hello"));
+    assert!(matches!(queue.cancel("missing"), Err((ErrorCode::BadRequest, _))));
+    assert_eq!(queue.cancel(&queued.id), Ok(queued));
+    assert!(queue.list().is_empty());
+}
+
+#[test]
+fn claimed_message_cannot_be_cancelled_or_replaced() {
+    let queue = NudgeQueue::new(None);
+    let ci = || NewNudge { dedup_key: Some("ci".to_owned()), ..nudge("red") };
+    let claimed = queue.enqueue(ci());
+    assert_eq!(queue.claim_next(0).map(|n| n.id), Some(claimed.id.clone()));
+    assert!(queue.claim_next(0).is_none());
+
+    assert!(matches!(queue.cancel(&claimed.id), Err((ErrorCode::AgentBusy, _))));
+    let replacement = queue.enqueue(ci());
+    assert_ne!(replacement.id, claimed.id);
+
+    queue.mark_delivered(&claimed.id);
+    assert_eq!(queue.list(), vec![replacement.clone()]);
+    assert_eq!(queue.claim_next(0), Some(replacement.clone()));
+    queue.release(&replacement.id);
+    assert_eq!(queue.cancel(&replacement.id), Ok(replacement));
+}
+
+#[test]
+fn survives_restart() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let queued = NudgeQueue::new(Some(dir.path())).enqueue(nudge("persisted"));
+
+    let reloaded = NudgeQueue::new(Some(dir.path()));
+    assert_eq!(reloaded.list(), vec![queued.clone()]);
+    assert_eq!(reloaded.cancel(&queued.id), Ok(queued));
+    assert!(NudgeQueue::new(Some(dir.path())).list().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn delivers_on_next_idle() -> anyhow::Result<()> {
+    let StoreCtx { store, mut input_rx, .. } = StoreBuilder::new()
+        .agent_state(AgentState::Parked { reason: "test".to_owned(), resume_at_epoch_ms: 0 })
+        .nudge_encoder(Arc::new(StubNudgeEncoder))
+        .build();
+    store.ready.store(true, std::sync::atomic::Ordering::Release);
+    let mut events = store.nudge_queue.queue_tx.subscribe();
+    spawn_delivery(Arc::clone(&store), store.lifecycle.shutdown.clone());
+
+    store.nudge_queue.enqueue(nudge("pick this up"));
+    assert_eq!(events.recv().await?.kind, NudgeQueueEventKind::Queued);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(input_rx.try_recv().is_err(), "delivered while parked");
+
+    *store.driver.agent_state.write().await = AgentState::Idle;
+    let _ = store.channels.state_tx.send(TransitionEvent {
+        prev: AgentState::Working,
+        next: AgentState::Idle,
+        seq: 1,
+        cause: String::new(),
+        last_message: None,
+    });
+
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv()).await??;
+    assert_eq!(event.kind, NudgeQueueEventKind::Delivered);
+    assert_eq!(event.nudge.message, "pick this up");
+    assert!(matches!(input_rx.recv().await, Some(InputEvent::Write(_))));
+    assert!(store.nudge_queue.list().is_empty());
+    store.lifecycle.shutdown.cancel();
+    Ok(())
+}
+
+/// Wait for the next `delivered` event and return its message.
+async fn next_delivered(
+    events: &mut broadcast::Receiver<NudgeQueueEvent>,
+) -> anyhow::Result<String> {
+    loop {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv()).await??;
+        if event.kind == NudgeQueueEventKind::Delivered {
+            return Ok(event.nudge.message);
+        }
+    }
+}
+
+#[tokio::test]
+async fn delivers_one_message_per_idle_period() -> anyhow::Result<()> {
+    let StoreCtx { store, mut input_rx, .. } = StoreBuilder::new()
+        .agent_state(AgentState::Idle)
+        .nudge_encoder(Arc::new(StubNudgeEncoder))
+        .build();
+    store.ready.store(true, std::sync::atomic::Ordering::Release);
+    let mut events = store.nudge_queue.queue_tx.subscribe();
+    store.nudge_queue.enqueue(nudge("first"));
+    store.nudge_queue.enqueue(nudge("second"));
+    spawn_delivery(Arc::clone(&store), store.lifecycle.shutdown.clone());
+
+    assert_eq!(next_delivered(&mut events).await?, "first");
+    assert!(matches!(input_rx.recv().await, Some(InputEvent::Write(_))));
+
+    // Re-enqueueing wakes the task while the agent is still in the same idle period.
+    store.nudge_queue.enqueue(nudge("third"));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(store.nudge_queue.list().len(), 2, "delivered twice in one idle period");
+
+    for (seq, prev, next) in
+        [(1, AgentState::Idle, AgentState::Working), (2, AgentState::Working, AgentState::Idle)]
+    {
+        *store.driver.agent_state.write().await = next.clone();
+        store.driver.state_seq.store(seq, std::sync::atomic::Ordering::Release);
+        let _ = store.channels.state_tx.send(TransitionEvent {
+            prev,
+            next,
+            seq,
+            cause: String::new(),
+            last_message: None,
+        });
+    }
+    assert_eq!(next_delivered(&mut events).await?, "second");
+    store.lifecycle.shutdown.cancel();
+    Ok(())
+}
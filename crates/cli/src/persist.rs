@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Atomic JSON snapshots for state kept in the session directory.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use serde::Serialize;
+
+/// Serialize `value` as pretty JSON and write it atomically through a
+/// per-call temp file, so concurrent saves never share one.
+pub fn write_json_atomic<T: Serialize + ?Sized>(path: &Path, value: &T) -> anyhow::Result<()> {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    let json = serde_json::to_vec_pretty(value)?;
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let tmp_name = format!(
+        "{}.{}.{}.tmp",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        std::process::id(),
+        seq,
+    );
+    let tmp_path = path.with_file_name(tmp_name);
+    std::fs::write(&tmp_path, json)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "persist_tests.rs"]
+mod tests;
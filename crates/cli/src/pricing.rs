@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Per-model token pricing for cost estimation.
+//!
+//! Agents don't always report cost (Claude session logs, Gemini and Codex
+//! stdout only carry token counts), so every usage delta is also priced from
+//! this table. Built-in list prices cover the models coop's drivers run;
+//! `--pricing-file` adds entries or overrides them.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::usage::UsageDelta;
+
+/// USD prices per million tokens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_read: f64,
+    #[serde(default)]
+    pub cache_write: f64,
+}
+
+impl ModelPrice {
+    const fn new(input: f64, output: f64, cache_read: f64, cache_write: f64) -> Self {
+        Self { input, output, cache_read, cache_write }
+    }
+
+    /// Cost of a delta's tokens at these prices.
+    pub fn cost(&self, delta: &UsageDelta) -> f64 {
+        (delta.input_tokens as f64 * self.input
+            + delta.output_tokens as f64 * self.output
+            + delta.cache_read_input_tokens as f64 * self.cache_read
+            + delta.cache_creation_input_tokens as f64 * self.cache_write)
+            / 1_000_000.0
+    }
+}
+
+/// Built-in list prices, keyed by model id prefix. Opus prices vary by
+/// minor version, so each release is listed rather than the family, as is
+/// every cheaper variant (`-mini`, `-nano`) the family prefix would
+/// otherwise overprice.
+const BUILTIN: &[(&str, ModelPrice)] = &[
+    ("claude-opus-4-0", ModelPrice::new(15.0, 75.0, 1.5, 18.75)),
+    ("claude-opus-4-20250514", ModelPrice::new(15.0, 75.0, 1.5, 18.75)),
+    ("claude-opus-4-1", ModelPrice::new(15.0, 75.0, 1.5, 18.75)),
+    ("claude-opus-4-5", ModelPrice::new(5.0, 25.0, 0.5, 6.25)),
+    ("claude-sonnet-4", ModelPrice::new(3.0, 15.0, 0.3, 3.75)),
+    ("claude-3-7-sonnet", ModelPrice::new(3.0, 15.0, 0.3, 3.75)),
+    ("claude-haiku-4-5", ModelPrice::new(1.0, 5.0, 0.1, 1.25)),
+    ("claude-3-5-haiku", ModelPrice::new(0.8, 4.0, 0.08, 1.0)),
+    ("gemini-2.5-pro", ModelPrice::new(1.25, 10.0, 0.125, 0.0)),
+    ("gemini-2.5-flash", ModelPrice::new(0.3, 2.5, 0.03, 0.0)),
+    ("gemini-2.5-flash-lite", ModelPrice::new(0.1, 0.4, 0.01, 0.0)),
+    ("gpt-5", ModelPrice::new(1.25, 10.0, 0.125, 0.0)),
+    ("gpt-5-mini", ModelPrice::new(0.25, 2.0, 0.025, 0.0)),
+    ("gpt-5-nano", ModelPrice::new(0.05, 0.4, 0.005, 0.0)),
+    ("gpt-5-pro", ModelPrice::new(15.0, 120.0, 0.0, 0.0)),
+    ("gpt-5-codex-mini", ModelPrice::new(0.25, 2.0, 0.025, 0.0)),
+    ("gpt-5.1", ModelPrice::new(1.25, 10.0, 0.125, 0.0)),
+    ("gpt-5.1-codex-mini", ModelPrice::new(0.25, 2.0, 0.025, 0.0)),
+];
+
+/// Model prices, looked up by longest matching model id prefix so dated
+/// ids (`claude-sonnet-4-5-20250929`) resolve to their family. A prefix only
+/// matches whole `-`-separated segments: `gpt-5` covers `gpt-5-codex` but
+/// not `gpt-5.1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PricingTable {
+    #[serde(default)]
+    pub models: BTreeMap<String, ModelPrice>,
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl PricingTable {
+    /// The built-in price list.
+    pub fn builtin() -> Self {
+        Self { models: BUILTIN.iter().map(|(id, price)| ((*id).to_owned(), *price)).collect() }
+    }
+
+    /// Load a pricing file and layer it over the built-in list. Files ending
+    /// in `.toml` are parsed as TOML, everything else as JSON.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read pricing file {}", path.display()))?;
+        let is_toml = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let file: Self = if is_toml {
+            toml::from_str(&contents).map_err(anyhow::Error::from)
+        } else {
+            serde_json::from_str(&contents).map_err(anyhow::Error::from)
+        }
+        .with_context(|| format!("invalid pricing file {}", path.display()))?;
+        let mut table = Self::builtin();
+        table.models.extend(file.models);
+        Ok(table)
+    }
+
+    /// Price for `model`: an exact id, or else the longest id that is
+    /// followed by `-` in the model name.
+    pub fn price_for(&self, model: &str) -> Option<&ModelPrice> {
+        self.models
+            .iter()
+            .filter(|(id, _)| {
+                model
+                    .strip_prefix(id.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+            })
+            .max_by_key(|(id, _)| id.len())
+            .map(|(_, price)| price)
+    }
+
+    /// Estimated cost of a delta: its tokens at the model's price, or the
+    /// agent-reported cost when the model is unnamed or unpriced.
+    pub fn estimate(&self, delta: &UsageDelta) -> f64 {
+        delta
+            .model
+            .as_deref()
+            .and_then(|model| self.price_for(model))
+            .map_or(delta.cost_usd, |price| price.cost(delta))
+    }
+}
+
+#[cfg(test)]
+#[path = "pricing_tests.rs"]
+mod tests;
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+#[yare::parameterized(
+    exact = { "claude-sonnet-4", Some(3.0) },
+    dated = { "claude-sonnet-4-5-20250929", Some(3.0) },
+    longest_prefix_wins = { "claude-opus-4-5-20251101", Some(5.0) },
+    opus_4_0 = { "claude-opus-4-0", Some(15.0) },
+    opus_4_dated = { "claude-opus-4-20250514", Some(15.0) },
+    opus_4_1 = { "claude-opus-4-1-20250805", Some(15.0) },
+    unlisted_opus = { "claude-opus-4-6", None },
+    gemini_lite = { "gemini-2.5-flash-lite", Some(0.1) },
+    gpt_5_dated = { "gpt-5-2025-08-07", Some(1.25) },
+    gpt_5_codex = { "gpt-5-codex", Some(1.25) },
+    gpt_5_nano = { "gpt-5-nano-2025-08-07", Some(0.05) },
+    gpt_5_pro = { "gpt-5-pro", Some(15.0) },
+    gpt_5_1_codex_mini = { "gpt-5.1-codex-mini", Some(0.25) },
+    gpt_5_2 = { "gpt-5.2", None },
+    segment_boundary = { "claude-sonnet-40", None },
+    unknown = { "llama-3-70b", None },
+)]
+fn price_lookup(model: &str, input: Option<f64>) {
+    let table = PricingTable::builtin();
+    assert_eq!(table.price_for(model).map(|p| p.input), input, "{model}");
+}
+
+#[test]
+fn estimate_prices_cache_tokens() {
+    let delta = UsageDelta {
+        input_tokens: 2_000,
+        output_tokens: 1_000,
+        cache_read_input_tokens: 100_000,
+        cache_creation_input_tokens: 10_000,
+        model: Some("claude-haiku-4-5".to_owned()),
+        ..Default::default()
+    };
+    // 2k * $1 + 1k * $5 + 100k * $0.10 + 10k * $1.25, per million.
+    let expected = (2_000.0 + 5_000.0 + 10_000.0 + 12_500.0) / 1_000_000.0;
+    assert!((PricingTable::builtin().estimate(&delta) - expected).abs() < 1e-12);
+}
+
+#[test]
+fn estimate_falls_back_to_reported_cost() {
+    let table = PricingTable::builtin();
+    let unnamed = UsageDelta { input_tokens: 500, cost_usd: 0.02, ..Default::default() };
+    assert!((table.estimate(&unnamed) - 0.02).abs() < f64::EPSILON);
+}
+
+#[test]
+fn load_overrides_and_extends_builtin() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("pricing.toml");
+    std::fs::write(
+        &path,
+        "[models.claude-sonnet-4]\ninput = 2.5\noutput = 12.0\n\n[models.llama-3]\ninput = 0.2\noutput = 0.2\n",
+    )?;
+    let table = PricingTable::load(&path)?;
+
+    assert_eq!(table.price_for("claude-sonnet-4-5").map(|p| p.input), Some(2.5));
+    assert_eq!(table.price_for("claude-sonnet-4-5").map(|p| p.cache_read), Some(0.0));
+    assert_eq!(table.price_for("llama-3-70b").map(|p| p.output), Some(0.2));
+    assert_eq!(table.price_for("gemini-2.5-pro").map(|p| p.input), Some(1.25));
+
+    std::fs::write(dir.path().join("bad.json"), "{\"models\": {\"x\": {\"input\": \"free\"}}}")?;
+    assert!(PricingTable::load(&dir.path().join("bad.json")).is_err());
+    Ok(())
+}
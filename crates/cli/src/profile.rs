@@ -15,6 +15,7 @@ use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, RwLock};
 use tracing::debug;
+use utoipa::ToSchema;
 
 use crate::driver::AgentState;
 use crate::event::ProfileEvent;
@@ -92,7 +93,7 @@ impl std::str::FromStr for ProfileMode {
 }
 
 /// Serializable snapshot of a profile's state.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ProfileInfo {
     pub name: String,
     pub status: String,
@@ -113,7 +114,7 @@ pub struct ProfileState {
 }
 
 /// Entry in a registration request.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ProfileEntry {
     pub name: String,
     pub credentials: HashMap<String, String>,
@@ -311,6 +312,7 @@ impl ProfileState {
                     credentials: Some(next_creds),
                     force: true,
                     profile: Some(next_name),
+                    span: tracing::Span::current(),
                 })
             }
             None => {
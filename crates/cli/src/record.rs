@@ -6,6 +6,9 @@
 //! Records state transitions and hook events with full screen snapshots
 //! as JSONL to `<session-dir>/recording.jsonl`. First line is a header,
 //! subsequent lines are entries.
+//!
+//! Optionally keeps a timed raw-output track alongside it as an asciicast v2
+//! file (`<session-dir>/recording.cast`), fed from the PTY byte stream.
 
 use std::hash::{Hash, Hasher};
 use std::io::Write;
@@ -17,7 +20,9 @@ use std::time::Instant;
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, Mutex};
 use tokio_util::sync::CancellationToken;
+use utoipa::ToSchema;
 
+use crate::asciicast::{CastEvent, CastHeader};
 use crate::event::{RawHookEvent, TransitionEvent};
 use crate::screen::ScreenSnapshot;
 use crate::transport::state::TerminalState;
@@ -35,16 +40,22 @@ pub struct RecordingState {
     header_written: AtomicBool,
     cols: AtomicU16,
     rows: AtomicU16,
+    /// Raw-output track path. `None` when the track is disabled.
+    output_path: Option<PathBuf>,
+    output_header_written: AtomicBool,
+    /// Incomplete UTF-8 tail carried between output chunks.
+    output_pending: Mutex<Vec<u8>>,
     pub record_tx: broadcast::Sender<RecordingEntry>,
 }
 
 /// A single recording entry (broadcast + serialized to JSONL).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct RecordingEntry {
     pub ts: u64,
     pub seq: u64,
     pub kind: String,
     pub detail: serde_json::Value,
+    #[schema(value_type = Object)]
     pub screen: ScreenSnapshot,
 }
 
@@ -57,8 +68,17 @@ struct RecordingHeader {
     timestamp: u64,
 }
 
+/// Where a recording download's bytes come from.
+#[derive(Debug, PartialEq)]
+pub enum DownloadSource {
+    /// A recording file to stream from disk.
+    File(PathBuf),
+    /// A cast synthesized from the snapshot entries.
+    Memory(Vec<u8>),
+}
+
 /// Status snapshot returned by the status endpoint.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RecordingStatus {
     pub enabled: bool,
     pub path: Option<String>,
@@ -91,10 +111,24 @@ impl RecordingState {
             header_written: AtomicBool::new(false),
             cols: AtomicU16::new(cols),
             rows: AtomicU16::new(rows),
+            output_path: None,
+            output_header_written: AtomicBool::new(false),
+            output_pending: Mutex::new(Vec::new()),
             record_tx,
         }
     }
 
+    /// Also keep a timed raw-output track (`recording.cast`) while enabled.
+    ///
+    /// No-op without a session directory.
+    pub fn with_output_track(mut self, enabled: bool) -> Self {
+        self.output_path = match (enabled, self.path.as_ref()) {
+            (true, Some(path)) => Some(path.with_file_name("recording.cast")),
+            _ => None,
+        };
+        self
+    }
+
     /// Enable recording. Writes the header on first enable.
     pub async fn enable(&self) {
         self.enabled.store(true, Ordering::Release);
@@ -116,19 +150,22 @@ impl RecordingState {
         self.enabled.load(Ordering::Acquire)
     }
 
+    /// Milliseconds since recording was first enabled.
+    async fn elapsed_ms(&self) -> u64 {
+        let started = self.started_at.lock().await;
+        match *started {
+            Some(ref instant) => instant.elapsed().as_millis() as u64,
+            None => 0,
+        }
+    }
+
     /// Append a recording entry with the given kind, detail, and screen snapshot.
     pub async fn push(&self, kind: &str, detail: serde_json::Value, screen: &ScreenSnapshot) {
         if !self.is_enabled() {
             return;
         }
 
-        let ts = {
-            let started = self.started_at.lock().await;
-            match *started {
-                Some(ref instant) => instant.elapsed().as_millis() as u64,
-                None => 0,
-            }
-        };
+        let ts = self.elapsed_ms().await;
 
         let seq = self.seq.fetch_add(1, Ordering::Relaxed) + 1;
 
@@ -142,6 +179,38 @@ impl RecordingState {
         let _ = self.record_tx.send(entry);
     }
 
+    /// Append raw PTY output to the output track.
+    ///
+    /// Incomplete UTF-8 sequences at the end of a chunk are held back until
+    /// the next chunk so multi-byte characters are never split.
+    pub async fn push_output(&self, bytes: &[u8]) {
+        if self.output_path.is_none() || !self.is_enabled() {
+            return;
+        }
+        let ts = self.elapsed_ms().await;
+        let text = {
+            let mut pending = self.output_pending.lock().await;
+            pending.extend_from_slice(bytes);
+            let (text, rest) = crate::asciicast::split_utf8(&pending);
+            *pending = rest;
+            text
+        };
+        if !text.is_empty() {
+            self.append_output(&CastEvent::output(ts, text));
+        }
+    }
+
+    /// Record a terminal resize on the output track.
+    pub async fn push_resize(&self, cols: u16, rows: u16) {
+        self.cols.store(cols, Ordering::Relaxed);
+        self.rows.store(rows, Ordering::Relaxed);
+        if self.output_path.is_none() || !self.is_enabled() {
+            return;
+        }
+        let ts = self.elapsed_ms().await;
+        self.append_output(&CastEvent::resize(ts, cols, rows));
+    }
+
     /// Return the current recording status.
     pub fn status(&self) -> RecordingStatus {
         RecordingStatus {
@@ -172,6 +241,61 @@ impl RecordingState {
         std::fs::read(path).ok()
     }
 
+    /// Export the recording as an asciicast v2 file.
+    ///
+    /// Returns the raw-output track when one was kept; otherwise synthesizes
+    /// a cast from the snapshot entries.
+    pub fn download_asciicast(&self) -> Option<Vec<u8>> {
+        if let Some(ref path) = self.output_path {
+            if let Ok(data) = std::fs::read(path) {
+                return Some(data);
+            }
+        }
+        self.synthesize_asciicast()
+    }
+
+    /// Locate a download without reading it, so large recordings can be
+    /// streamed from disk. Mirrors [`download`](Self::download) and
+    /// [`download_asciicast`](Self::download_asciicast); only a synthesized
+    /// cast is built in memory.
+    pub fn download_source(&self, asciicast: bool) -> Option<DownloadSource> {
+        if asciicast {
+            if let Some(path) = self.output_path.as_ref().filter(|p| p.is_file()) {
+                return Some(DownloadSource::File(path.clone()));
+            }
+            return self.synthesize_asciicast().map(DownloadSource::Memory);
+        }
+        self.path.as_ref().filter(|p| p.is_file()).map(|p| DownloadSource::File(p.clone()))
+    }
+
+    /// Build an asciicast from the snapshot entries.
+    fn synthesize_asciicast(&self) -> Option<Vec<u8>> {
+        let jsonl = std::fs::read_to_string(self.path.as_ref()?).ok()?;
+        let cast = crate::asciicast::from_recording(&jsonl).ok()?;
+        Some(cast.to_text().into_bytes())
+    }
+
+    /// Append an event to the output track, writing its header first.
+    fn append_output(&self, event: &CastEvent) {
+        let Some(ref path) = self.output_path else {
+            return;
+        };
+        let mut data = String::new();
+        if !self.output_header_written.swap(true, Ordering::AcqRel) {
+            let header = CastHeader::new(
+                self.cols.load(Ordering::Relaxed),
+                self.rows.load(Ordering::Relaxed),
+                self.started_at_unix_ms.load(Ordering::Acquire),
+            );
+            data.push_str(&crate::asciicast::header_line(&header));
+        }
+        data.push_str(&crate::asciicast::event_line(event));
+        let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) else {
+            return;
+        };
+        let _ = file.write_all(data.as_bytes());
+    }
+
     /// Write the header line to the recording file (once).
     fn write_header_once(&self) {
         if self.header_written.swap(true, Ordering::AcqRel) {
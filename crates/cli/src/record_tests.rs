@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
-use crate::record::RecordingState;
+use crate::record::{DownloadSource, RecordingState};
 use crate::screen::{CursorPosition, ScreenSnapshot};
 
 fn test_snapshot() -> ScreenSnapshot {
@@ -104,3 +104,60 @@ async fn broadcast_sends_entries() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn output_track_disabled_by_default() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let state = RecordingState::new(Some(dir.path()), 80, 24);
+    state.enable().await;
+    state.push_output(b"hello").await;
+    assert!(!dir.path().join("recording.cast").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn output_track_writes_timed_cast() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let state = RecordingState::new(Some(dir.path()), 80, 24).with_output_track(true);
+
+    // Nothing is kept until recording is enabled.
+    state.push_output(b"ignored").await;
+    state.enable().await;
+    state.push_output(b"hello ").await;
+    // A multi-byte character split across chunks is held back, not mangled.
+    state.push_output(&"é".as_bytes()[..1]).await;
+    state.push_output(&"é".as_bytes()[1..]).await;
+    state.push_resize(100, 30).await;
+
+    let data = state.download_asciicast().ok_or_else(|| anyhow::anyhow!("no cast"))?;
+    let cast = crate::asciicast::Cast::parse(std::str::from_utf8(&data)?)?;
+    assert_eq!((cast.header.width, cast.header.height), (80, 24));
+    let codes: Vec<(&str, &str)> =
+        cast.events.iter().map(|e| (e.code.as_str(), e.data.as_str())).collect();
+    assert_eq!(codes, vec![("o", "hello "), ("o", "é"), ("r", "100x30")]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn asciicast_download_falls_back_to_snapshots() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let state = RecordingState::new(Some(dir.path()), 80, 24);
+    state.enable().await;
+    state.push("state", serde_json::json!({}), &test_snapshot()).await;
+
+    let data = state.download_asciicast().ok_or_else(|| anyhow::anyhow!("no cast"))?;
+    let cast = crate::asciicast::Cast::parse(std::str::from_utf8(&data)?)?;
+    assert_eq!(cast.events.len(), 2);
+    assert_eq!(cast.events[0].code, "m");
+    assert_eq!(cast.events[0].data, "state");
+    assert_eq!(cast.events[1].code, "o");
+    assert!(cast.events[1].data.contains("hello"));
+
+    // Only the synthesized cast is built in memory; the JSONL streams from disk.
+    assert_eq!(state.download_source(true), Some(DownloadSource::Memory(data)));
+    assert_eq!(
+        state.download_source(false),
+        Some(DownloadSource::File(dir.path().join("recording.jsonl")))
+    );
+    Ok(())
+}
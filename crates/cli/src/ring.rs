@@ -1,27 +1,53 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
+use crate::spill::{SpillLog, SpillReader};
+
 /// Fixed-capacity circular byte buffer for raw PTY output.
 ///
 /// Tracks the total number of bytes ever written so consumers can request
 /// replay from a global byte offset. When the buffer wraps, older data is
-/// silently discarded.
+/// discarded unless a [`SpillLog`] is attached, in which case it remains
+/// readable from disk via [`read_range`](Self::read_range).
 #[derive(Debug)]
 pub struct RingBuffer {
     buf: Vec<u8>,
     capacity: usize,
     write_pos: usize,
     total_written: u64,
+    spill: Option<SpillLog>,
 }
 
 impl RingBuffer {
     /// Create a new ring buffer with the given capacity.
     pub fn new(capacity: usize) -> Self {
-        Self { buf: vec![0u8; capacity], capacity, write_pos: 0, total_written: 0 }
+        Self { buf: vec![0u8; capacity], capacity, write_pos: 0, total_written: 0, spill: None }
+    }
+
+    /// Mirror all writes into an on-disk spill log.
+    pub fn with_spill(mut self, spill: Option<SpillLog>) -> Self {
+        self.spill = spill;
+        self
+    }
+
+    /// Detach the spill log (e.g. to reopen it for a new session).
+    pub fn take_spill(&mut self) -> Option<SpillLog> {
+        self.spill.take()
+    }
+
+    pub fn has_spill(&self) -> bool {
+        self.spill.is_some()
     }
 
     /// Append data into the circular buffer.
     pub fn write(&mut self, data: &[u8]) {
+        if let Some(ref mut spill) = self.spill {
+            if let Err(e) = spill.append(data) {
+                tracing::warn!("ring spill disabled after write error: {e:#}");
+                self.spill = None;
+            }
+        }
+
         for chunk in data.chunks(self.capacity) {
             let start = self.write_pos;
             let end = start + chunk.len();
@@ -97,6 +123,75 @@ impl RingBuffer {
     pub fn oldest_offset(&self) -> u64 {
         self.total_written.saturating_sub(self.capacity as u64)
     }
+
+    /// The oldest byte offset readable via [`read_range`](Self::read_range):
+    /// zero with a spill log, otherwise [`oldest_offset`](Self::oldest_offset).
+    pub fn oldest_replayable(&self) -> u64 {
+        if self.spill.is_some() {
+            0
+        } else {
+            self.oldest_offset()
+        }
+    }
+
+    /// Read up to `limit` bytes starting at `offset`, falling back to the
+    /// spill log for offsets older than the in-memory window.
+    ///
+    /// Returns `None` if the offset is beyond the write position or no
+    /// longer available.
+    pub fn read_range(&self, offset: u64, limit: usize) -> Option<Vec<u8>> {
+        match self.begin_read(offset, limit)? {
+            RangeRead::Memory(data) => Some(data),
+            RangeRead::Spill { reader, offset, limit } => read_spill(&reader, offset, limit),
+        }
+    }
+
+    /// Like [`read_range`](Self::read_range), but spilled history is
+    /// returned as a [`SpillReader`] to read after the ring lock is released.
+    pub fn begin_read(&self, offset: u64, limit: usize) -> Option<RangeRead> {
+        if offset < self.oldest_offset() {
+            let reader = self.spill.as_ref()?.reader();
+            return Some(RangeRead::Spill { reader, offset, limit });
+        }
+        let (a, b) = self.read_from(offset)?;
+        let mut data = Vec::with_capacity(limit.min(a.len() + b.len()));
+        data.extend_from_slice(&a[..limit.min(a.len())]);
+        data.extend_from_slice(&b[..(limit - data.len()).min(b.len())]);
+        Some(RangeRead::Memory(data))
+    }
+}
+
+/// A range read started by [`RingBuffer::begin_read`].
+pub enum RangeRead {
+    /// Copied from the in-memory window.
+    Memory(Vec<u8>),
+    /// Still to be read from disk.
+    Spill { reader: SpillReader, offset: u64, limit: usize },
+}
+
+impl RangeRead {
+    /// Finish the read, doing any disk I/O on a blocking task.
+    pub async fn finish(self) -> Option<Vec<u8>> {
+        match self {
+            RangeRead::Memory(data) => Some(data),
+            RangeRead::Spill { reader, offset, limit } => {
+                tokio::task::spawn_blocking(move || read_spill(&reader, offset, limit))
+                    .await
+                    .ok()
+                    .flatten()
+            }
+        }
+    }
+}
+
+fn read_spill(reader: &SpillReader, offset: u64, limit: usize) -> Option<Vec<u8>> {
+    match reader.read(offset, limit) {
+        Ok(data) => Some(data),
+        Err(e) => {
+            tracing::warn!("ring spill read at {offset} failed: {e:#}");
+            None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -77,3 +77,34 @@ fn read_at_total_written_returns_empty() {
     ring.write(b"hello");
     assert_eq!(collect(&ring, 5), Some(vec![]));
 }
+
+#[test]
+fn read_range_from_memory_respects_limit() {
+    let mut ring = RingBuffer::new(8);
+    ring.write(b"abcdef");
+    ring.write(b"ghij"); // wraps
+
+    assert_eq!(ring.read_range(4, 3), Some(b"efg".to_vec()));
+    assert_eq!(ring.read_range(2, 100), Some(b"cdefghij".to_vec()));
+    assert_eq!(ring.read_range(10, 4), Some(vec![]));
+    // Overwritten with no spill log.
+    assert_eq!(ring.read_range(0, 4), None);
+    assert_eq!(ring.oldest_replayable(), 2);
+}
+
+#[test]
+fn read_range_falls_back_to_spill() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let spill = crate::spill::SpillLog::open(dir.path())?.with_segment_size(5);
+    let mut ring = RingBuffer::new(8).with_spill(Some(spill));
+    ring.write(b"0123456789");
+    ring.write(b"abcdef");
+
+    assert_eq!(ring.oldest_offset(), 8);
+    assert_eq!(ring.oldest_replayable(), 0);
+    assert_eq!(ring.read_range(0, 4), Some(b"0123".to_vec()));
+    assert_eq!(ring.read_range(3, 100), Some(b"3456789abcdef".to_vec()));
+    // In-memory window is still served from memory.
+    assert_eq!(collect(&ring, 8), Some(b"89abcdef".to_vec()));
+    Ok(())
+}
@@ -3,11 +3,13 @@
 
 //! Top-level session runner — shared by `main` and integration tests.
 
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU64};
 use std::sync::Arc;
 use std::time::Instant;
 
+use bytes::Bytes;
 use tokio::net::TcpListener;
 use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio_util::sync::CancellationToken;
@@ -15,29 +17,40 @@ use tracing::{error, info};
 
 use tracing_subscriber::EnvFilter;
 
-use crate::backend::adapter::{AdapterSpec, TmuxBackend};
+use crate::audit::AuditLog;
+use crate::backend::adapter::{AdapterSpec, ScreenBackend, TmuxBackend};
 use crate::backend::spawn::NativePty;
 use crate::backend::Backend;
+use crate::budget::BudgetState;
 use crate::config::{self, Config, GroomLevel};
 use crate::driver::claude::resume;
 use crate::driver::claude::setup as claude_setup;
+use crate::driver::codex::setup as codex_setup;
 use crate::driver::gemini::setup as gemini_setup;
 use crate::driver::AgentType;
 use crate::driver::{
-    build_claude_driver, build_gemini_driver, AgentState, DetectorSinks, DriverContext,
-    SessionSetup,
+    build_claude_driver, build_codex_driver, build_gemini_driver, structured_stdout, AgentState,
+    DetectorSinks, SessionSetup,
 };
 use crate::event::InputEvent;
 use crate::event_log::EventLog;
+use crate::idle::IdleState;
+use crate::metrics::Metrics;
+use crate::nudge_queue::NudgeQueue;
+use crate::policy::RespondPolicy;
+use crate::pricing::PricingTable;
 use crate::profile::ProfileState;
 use crate::record::RecordingState;
 use crate::ring::RingBuffer;
 use crate::screen::Screen;
 use crate::session::{Session, SessionConfig, SessionOutcome};
+use crate::spill::SpillLog;
 use crate::start::StartState;
 use crate::stop::StopState;
 use crate::switch::{SwitchRequest, SwitchState};
+use crate::telemetry;
 use crate::transcript::TranscriptState;
+use crate::transport::auth::{attach_unix_peer, UnixPeer};
 #[cfg(not(debug_assertions))]
 use crate::transport::build_router;
 #[cfg(debug_assertions)]
@@ -46,6 +59,7 @@ use crate::transport::grpc::CoopGrpc;
 use crate::transport::state::{
     DetectionInfo, DriverState, LifecycleState, SessionSettings, TerminalState, TransportChannels,
 };
+use crate::transport::tokens::ApiTokens;
 use crate::transport::{build_health_router, Store};
 use crate::usage::UsageState;
 
@@ -112,6 +126,12 @@ impl PreparedSession {
 
     /// Execute a credential switch: reset store state, prepare a new agent
     /// setup, spawn a new backend, and build a new Session.
+    #[tracing::instrument(
+        name = "session.switch",
+        parent = &request.span,
+        skip_all,
+        fields(restart = request.credentials.is_none(), profile = request.profile.as_deref())
+    )]
     async fn execute_switch(&mut self, request: &SwitchRequest) -> anyhow::Result<()> {
         let agent_enum = self.config.agent_enum()?;
 
@@ -142,6 +162,7 @@ impl PreparedSession {
             AgentType::Gemini => {
                 Some(gemini_setup::prepare(&coop_url, base_settings, mcp_config, pristine)?)
             }
+            AgentType::Codex => Some(codex_setup::prepare(&coop_url)?),
             _ => None,
         };
 
@@ -183,28 +204,38 @@ impl PreparedSession {
         }
 
         // 6. Build driver (detectors only — encoders already on SessionSettings).
-        let sinks = || {
-            DetectorSinks::default()
-                .with_last_message(Arc::clone(&self.store.driver.last_message))
-                .with_hook_tx(self.store.channels.hook_tx.clone())
-                .with_message_tx(self.store.channels.message_tx.clone())
-                .with_usage(Arc::clone(&self.store.usage))
-        };
+        let (stdout_tap, stdout_rx) = stdout_channel(agent_enum, &command).unzip();
+        let mut sinks = DetectorSinks::default()
+            .with_last_message(Arc::clone(&self.store.driver.last_message))
+            .with_hook_tx(self.store.channels.hook_tx.clone())
+            .with_message_tx(self.store.channels.message_tx.clone())
+            .with_usage(Arc::clone(&self.store.usage));
+        if let Some(rx) = stdout_rx {
+            sinks = sinks.with_stdout_rx(rx);
+        }
         let driver = match agent_enum {
-            AgentType::Claude => build_claude_driver(&self.config, setup.as_ref(), 0, sinks())?,
+            AgentType::Claude => build_claude_driver(&self.config, setup.as_ref(), 0, sinks)?,
             AgentType::Gemini => build_gemini_driver(
                 &self.config,
                 setup.as_ref(),
                 self.store.terminal.child_pid_fn(),
                 self.store.terminal.ring_total_written_fn(),
-                sinks(),
+                sinks,
+            )?,
+            AgentType::Codex => build_codex_driver(
+                &self.config,
+                self.store.terminal.child_pid_fn(),
+                self.store.terminal.ring_total_written_fn(),
+                self.store.terminal.snapshot_fn(),
+                sinks,
+            )?,
+            AgentType::Unknown => crate::driver::unknown::build_driver(
+                &self.config,
+                self.store.terminal.child_pid_fn(),
+                self.store.terminal.ring_total_written_fn(),
+                self.store.terminal.snapshot_fn(),
+                sinks,
             )?,
-            _ => DriverContext {
-                nudge_encoder: None,
-                respond_encoder: None,
-                detectors: vec![],
-                option_parser: None,
-            },
         };
 
         // Add Tier 5 screen detector for Claude.
@@ -232,6 +263,9 @@ impl PreparedSession {
         if let Some(parser) = driver.option_parser {
             session_config = session_config.with_option_parser(parser);
         }
+        if let Some(tap) = stdout_tap {
+            session_config = session_config.with_stdout_tap(tap);
+        }
         self.session = Some(Session::new(&self.config, session_config));
 
         // 9. Update session log path and session ID for the next switch.
@@ -270,11 +304,23 @@ pub async fn run(config: Config) -> anyhow::Result<RunResult> {
     prepare(config).await?.run().await
 }
 
+/// Channel that tees the child's output to a Tier 3 stdout detector, when
+/// the agent runs in a structured (JSONL) output mode.
+fn stdout_channel(
+    agent: AgentType,
+    command: &[String],
+) -> Option<(mpsc::Sender<Bytes>, mpsc::Receiver<Bytes>)> {
+    structured_stdout(agent, command).then(|| mpsc::channel(1024))
+}
+
 /// Initialize tracing/logging from config.
 ///
-/// Uses `try_init` so it's safe to call multiple times (e.g. from tests).
+/// Adds an OTLP span exporter when `--otlp-endpoint` is set. Uses `try_init`
+/// so it's safe to call multiple times (e.g. from tests).
 pub fn init_tracing(config: &Config) {
-    use tracing_subscriber::fmt;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::{fmt, Layer};
 
     // Priority: --log-level / COOP_LOG_LEVEL > RUST_LOG > default ("info").
     let filter = if std::env::var("COOP_LOG_LEVEL").is_err() && config.log_level == "info" {
@@ -283,11 +329,24 @@ pub fn init_tracing(config: &Config) {
         EnvFilter::try_new(&config.log_level).unwrap_or_else(|_| EnvFilter::new("info"))
     };
 
-    let result = match config.log_format.as_str() {
-        "json" => fmt::fmt().with_env_filter(filter).json().try_init(),
-        _ => fmt::fmt().with_env_filter(filter).try_init(),
+    let fmt_layer = match config.log_format.as_str() {
+        "json" => fmt::layer().json().boxed(),
+        _ => fmt::layer().boxed(),
+    };
+    let (otel_layer, otel_err) = match config.otlp_endpoint {
+        Some(ref endpoint) => match telemetry::layer(endpoint, "coop") {
+            Ok(layer) => (Some(layer), None),
+            Err(e) => (None, Some(e)),
+        },
+        None => (None, None),
     };
+
+    let result =
+        tracing_subscriber::registry().with(filter).with(fmt_layer).with(otel_layer).try_init();
     drop(result);
+    if let Some(e) = otel_err {
+        tracing::warn!("OTLP trace export disabled: {e:#}");
+    }
 }
 
 /// Prepare a coop session: set up driver, spawn backend, start servers.
@@ -306,9 +365,29 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
         None => None,
     };
     let stop_config = agent_file_config.as_ref().and_then(|c| c.stop.clone()).unwrap_or_default();
+    if let Some(ref schema) = stop_config.schema {
+        schema.check().map_err(|e| anyhow::anyhow!("invalid stop schema: {e}"))?;
+    }
     let start_config = agent_file_config.as_ref().and_then(|c| c.start.clone()).unwrap_or_default();
+    let idle_config = agent_file_config.as_ref().and_then(|c| c.idle.clone()).unwrap_or_default();
+    let budget_config =
+        agent_file_config.as_ref().and_then(|c| c.budget.clone()).unwrap_or_default();
     let base_settings = agent_file_config.as_ref().and_then(|c| c.settings.clone());
     let mcp_config = agent_file_config.as_ref().and_then(|c| c.mcp.clone());
+    let respond_policy = agent_file_config
+        .as_ref()
+        .and_then(|c| c.policy.as_ref())
+        .map(RespondPolicy::compile)
+        .transpose()?
+        .map(Arc::new);
+    let api_tokens = match config.auth_tokens_file {
+        Some(ref path) => Some(Arc::new(ApiTokens::load(path)?)),
+        None => None,
+    };
+    let pricing = match config.pricing_file {
+        Some(ref path) => PricingTable::load(path)?,
+        None => PricingTable::builtin(),
+    };
 
     // 1. Handle --resume: discover session log and build resume state.
     let (resume_state, resume_log_path) = if let Some(ref resume_hint) = config.resume {
@@ -356,6 +435,7 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
         AgentType::Gemini => {
             Some(gemini_setup::prepare(&coop_url_for_setup, base_settings, mcp_config, pristine)?)
         }
+        AgentType::Codex => Some(codex_setup::prepare(&coop_url_for_setup)?),
         _ => None,
     };
 
@@ -365,10 +445,24 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
         command.extend(s.extra_args.clone());
     }
 
+    let session_id = setup
+        .as_ref()
+        .map(|s| s.session_id.clone())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
     // 4. Build terminal state early so driver closures can reference its atomics.
+    let spill = if config.ring_spill {
+        let session_dir = match setup {
+            Some(ref s) => s.session_dir.clone(),
+            None => crate::driver::coop_session_dir(&session_id)?,
+        };
+        Some(SpillLog::open(&session_dir.join("output"))?)
+    } else {
+        None
+    };
     let terminal = Arc::new(TerminalState {
         screen: RwLock::new(Screen::new(config.cols, config.rows)),
-        ring: RwLock::new(RingBuffer::new(config.ring_size)),
+        ring: RwLock::new(RingBuffer::new(config.ring_size).with_spill(spill)),
         ring_total_written: Arc::new(AtomicU64::new(0)),
         child_pid: AtomicU32::new(0),
         exit_status: RwLock::new(None),
@@ -381,40 +475,57 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
     let (message_tx, _) = broadcast::channel(64);
 
     let last_message: Arc<RwLock<Option<String>>> = Arc::new(RwLock::new(None));
-    let usage_state = Arc::new(UsageState::new());
-    let sinks = || {
-        DetectorSinks::default()
-            .with_last_message(Arc::clone(&last_message))
-            .with_hook_tx(hook_tx.clone())
-            .with_message_tx(message_tx.clone())
-            .with_usage(Arc::clone(&usage_state))
-    };
+    let profile_state = Arc::new(ProfileState::new());
+    // Apply --profile mode from CLI/env.
+    if let Ok(mode) = config.profile.parse::<crate::profile::ProfileMode>() {
+        profile_state.set_mode(mode);
+    }
+
+    // Usage persists in the session directory so restarts and `--resume`
+    // keep counting from the saved totals.
+    let mut usage_state =
+        UsageState::with_pricing(pricing).with_profiles(Arc::clone(&profile_state));
+    if let Some(ref s) = setup {
+        usage_state = usage_state.with_session_dir(&s.session_dir);
+    }
+    let usage_state = Arc::new(usage_state);
+    // Attached sessions only see the rendered pane, never the agent's stdout.
+    let (stdout_tap, stdout_rx) =
+        stdout_channel(agent_enum, &command).filter(|_| config.attach.is_none()).unzip();
+    let mut sinks = DetectorSinks::default()
+        .with_last_message(Arc::clone(&last_message))
+        .with_hook_tx(hook_tx.clone())
+        .with_message_tx(message_tx.clone())
+        .with_usage(Arc::clone(&usage_state));
+    if let Some(rx) = stdout_rx {
+        sinks = sinks.with_stdout_rx(rx);
+    }
     let mut driver = match agent_enum {
         AgentType::Claude => {
             let log_start_offset = resume_state.as_ref().map(|s| s.log_offset).unwrap_or(0);
-            build_claude_driver(&config, setup.as_ref(), log_start_offset, sinks())?
+            build_claude_driver(&config, setup.as_ref(), log_start_offset, sinks)?
         }
         AgentType::Gemini => build_gemini_driver(
             &config,
             setup.as_ref(),
             terminal.child_pid_fn(),
             terminal.ring_total_written_fn(),
-            sinks(),
+            sinks,
+        )?,
+        AgentType::Unknown => crate::driver::unknown::build_driver(
+            &config,
+            terminal.child_pid_fn(),
+            terminal.ring_total_written_fn(),
+            terminal.snapshot_fn(),
+            sinks,
+        )?,
+        AgentType::Codex => build_codex_driver(
+            &config,
+            terminal.child_pid_fn(),
+            terminal.ring_total_written_fn(),
+            terminal.snapshot_fn(),
+            sinks,
         )?,
-        AgentType::Unknown => DriverContext {
-            nudge_encoder: None,
-            respond_encoder: None,
-            detectors: crate::driver::unknown::build_detectors(
-                &config,
-                terminal.child_pid_fn(),
-                terminal.ring_total_written_fn(),
-                None,
-            )?,
-            option_parser: None,
-        },
-        AgentType::Codex => {
-            anyhow::bail!("{agent_enum:?} driver is not yet implemented");
-        }
     };
 
     // Tier 5: Claude screen detector for idle prompt detection.
@@ -447,8 +558,8 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
             AdapterSpec::Tmux { session } => {
                 Box::new(TmuxBackend::new(session)?.with_poll_interval(config.tmux_poll()))
             }
-            AdapterSpec::Screen { session: _ } => {
-                anyhow::bail!("screen attach is not yet implemented");
+            AdapterSpec::Screen { session } => {
+                Box::new(ScreenBackend::new(session)?.with_poll_interval(config.gnu_screen_poll()))
             }
         }
     } else {
@@ -488,24 +599,17 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
         setup.as_ref().and_then(|s| s.session_log_path.clone()),
     )?);
 
-    let session_id = setup
-        .as_ref()
-        .map(|s| s.session_id.clone())
-        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-
-    let profile_state = Arc::new(ProfileState::new());
-    // Apply --profile mode from CLI/env.
-    if let Ok(mode) = config.profile.parse::<crate::profile::ProfileMode>() {
-        profile_state.set_mode(mode);
-    }
-
     let event_log = Arc::new(EventLog::new(setup.as_ref().map(|s| s.session_dir.as_path())));
+    let audit_log = Arc::new(AuditLog::new(setup.as_ref().map(|s| s.session_dir.as_path())));
 
-    let record_state = Arc::new(RecordingState::new(
-        setup.as_ref().map(|s| s.session_dir.as_path()),
-        config.cols,
-        config.rows,
-    ));
+    let record_state = Arc::new(
+        RecordingState::new(
+            setup.as_ref().map(|s| s.session_dir.as_path()),
+            config.cols,
+            config.rows,
+        )
+        .with_output_track(config.record_output),
+    );
 
     let store = Arc::new(Store {
         terminal,
@@ -529,10 +633,12 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
             started_at: Instant::now(),
             agent: agent_enum,
             auth_token: config.auth_token.clone(),
+            api_tokens,
             nudge_encoder: driver.nudge_encoder,
             respond_encoder: driver.respond_encoder,
             nudge_timeout: config.nudge_timeout(),
             groom: config.groom_level()?,
+            respond_policy,
         },
         lifecycle: LifecycleState {
             shutdown: shutdown.clone(),
@@ -545,11 +651,16 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
         stop: stop_state,
         switch: switch_state,
         start: start_state,
+        idle: Arc::new(IdleState::new(idle_config)),
+        nudge_queue: Arc::new(NudgeQueue::new(setup.as_ref().map(|s| s.session_dir.as_path()))),
         transcript: transcript_state,
         usage: usage_state,
+        budget: Arc::new(BudgetState::new(budget_config)),
         profile: profile_state,
         input_activity: Arc::new(tokio::sync::Notify::new()),
         event_log: Arc::clone(&event_log),
+        audit_log,
+        metrics: Arc::new(Metrics::default()),
         record: Arc::clone(&record_state),
         session_dir: setup.as_ref().map(|s| s.session_dir.clone()),
     });
@@ -602,6 +713,12 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
         shutdown.clone(),
     );
 
+    // Spawn queued nudge delivery — sends pending nudges when the agent goes idle.
+    crate::nudge_queue::spawn_delivery(Arc::clone(&store), shutdown.clone());
+
+    // Spawn the budget watcher — checks each usage update against the limits.
+    crate::budget::spawn_watcher(Arc::clone(&store), shutdown.clone());
+
     // Spawn NATS publisher if configured.
     if let Some(ref nats_url) = config.nats_url {
         let nats_auth = crate::transport::nats::NatsAuth {
@@ -729,7 +846,9 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
         let sd = shutdown.clone();
         tokio::spawn(async move {
             let result =
-                axum::serve(listener, router).with_graceful_shutdown(sd.cancelled_owned()).await;
+                axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>())
+                    .with_graceful_shutdown(sd.cancelled_owned())
+                    .await;
             if let Err(e) = result {
                 error!("HTTP server error: {e}");
             }
@@ -760,6 +879,8 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
                                 let svc_future = <_ as tower::Service<_>>::call(&mut make_svc, ());
                                 tokio::spawn(async move {
                                     let Ok(svc) = svc_future.await;
+                                    let peer = UnixPeer::of(&stream);
+                                    let svc = tower::ServiceExt::map_request(svc, attach_unix_peer(peer));
                                     let io = hyper_util::rt::TokioIo::new(stream);
                                     let hyper_svc = hyper_util::service::TowerToHyperService::new(svc);
                                     let _ = hyper_util::server::conn::auto::Builder::new(
@@ -882,6 +1003,9 @@ pub async fn prepare(mut config: Config) -> anyhow::Result<PreparedSession> {
     if let Some(parser) = driver.option_parser {
         session_config = session_config.with_option_parser(parser);
     }
+    if let Some(tap) = stdout_tap {
+        session_config = session_config.with_stdout_tap(tap);
+    }
     let session = Session::new(&config, session_config);
 
     // `setup` is intentionally dropped here — session artifacts live in
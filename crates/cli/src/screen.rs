@@ -2,6 +2,7 @@
 // Copyright (c) 2026 Alfred Jean LLC
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Opaque terminal screen backed by an avt virtual terminal.
 pub struct Screen {
@@ -51,7 +52,7 @@ fn scan_alt_screen(data: &[u8], alt_screen: &mut bool) {
 /// Scans backwards from the end of `data` looking for a leading byte whose
 /// expected sequence length exceeds the bytes available.  Returns 0 when the
 /// tail is complete (or pure ASCII).
-fn incomplete_utf8_tail_len(data: &[u8]) -> usize {
+pub(crate) fn incomplete_utf8_tail_len(data: &[u8]) -> usize {
     let len = data.len();
     for i in 1..=len.min(3) {
         let byte = data[len - i];
@@ -217,7 +218,7 @@ pub struct ScreenSnapshot {
 }
 
 /// Row and column position of the terminal cursor.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct CursorPosition {
     pub row: u16,
     pub col: u16,
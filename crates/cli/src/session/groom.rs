@@ -1,10 +1,11 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
-//! Prompt enrichment and auto-dismiss logic.
+//! Prompt enrichment, auto-dismiss and respond-policy logic.
 //!
 //! These tasks run as detached spawned futures, polling the screen buffer
-//! for option labels and auto-dismissing disruption prompts.
+//! for option labels, auto-dismissing disruption prompts, and answering
+//! tool permission prompts according to the respond policy.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -15,7 +16,8 @@ use tracing::{debug, warn};
 use crate::config::{Config, GroomLevel};
 use crate::driver::{disruption_option, AgentState, NudgeStep, OptionParser, PromptKind};
 use crate::event::{InputEvent, PromptOutcome, TransitionEvent};
-use crate::transport::Store;
+use crate::policy::PolicyAction;
+use crate::transport::{resolve_permission_option, Store};
 
 /// Spawn deferred option enrichment for Permission/Plan prompts.
 ///
@@ -117,6 +119,7 @@ async fn enrich_prompt_options(app: Arc<Store>, expected_seq: u64, parser: Optio
 
 /// Spawn auto-dismiss of a disruption prompt in groom=auto mode.
 ///
+/// Prompts that are not disruptions go to the respond policy instead.
 /// The prompt state is broadcast BEFORE auto-dismiss so API clients see
 /// the action transparently.
 pub(super) fn spawn_auto_dismiss(
@@ -129,6 +132,7 @@ pub(super) fn spawn_auto_dismiss(
         return;
     }
     let Some(option) = disruption_option(prompt) else {
+        spawn_policy_response(store, prompt, config, state_seq);
         return;
     };
     if prompt.subtype.as_deref() == Some("settings_error") {
@@ -148,20 +152,77 @@ pub(super) fn spawn_auto_dismiss(
         encoder.encode_setup(option)
     };
 
-    let store = Arc::clone(store);
-    let prompt_type = prompt.kind.as_str().to_owned();
-    let prompt_subtype = prompt.subtype.clone();
-    let groom_option = if prompt.options.is_empty() { None } else { Some(option) };
-    let dismiss_delay = config.groom_dismiss_delay();
+    let outcome = PromptOutcome {
+        source: "groom".to_owned(),
+        r#type: prompt.kind.as_str().to_owned(),
+        subtype: prompt.subtype.clone(),
+        option: if prompt.options.is_empty() { None } else { Some(option) },
+        ..Default::default()
+    };
 
     tokio::spawn(auto_dismiss(
-        store,
+        Arc::clone(store),
         steps,
-        dismiss_delay,
+        config.groom_dismiss_delay(),
         state_seq,
-        prompt_type,
-        prompt_subtype,
-        groom_option,
+        outcome,
+    ));
+}
+
+/// Apply the respond policy to a tool permission prompt.
+///
+/// `allow` and `deny` decisions are delivered like an auto-dismiss; `ask`
+/// leaves the prompt for the orchestrator. Every decision is broadcast as a
+/// [`PromptOutcome`] with `source: "policy"`.
+fn spawn_policy_response(
+    store: &Arc<Store>,
+    prompt: &crate::driver::PromptContext,
+    config: &Config,
+    state_seq: u64,
+) {
+    let Some(ref policy) = store.config.respond_policy else {
+        return;
+    };
+    let Some(decision) = policy.evaluate(prompt) else {
+        return;
+    };
+    debug!(
+        tool = prompt.tool.as_deref().unwrap_or_default(),
+        action = decision.action.as_str(),
+        rule = decision.rule.as_deref().unwrap_or("default"),
+        "respond policy decision"
+    );
+
+    let mut outcome = PromptOutcome {
+        source: "policy".to_owned(),
+        r#type: prompt.kind.as_str().to_owned(),
+        subtype: prompt.subtype.clone(),
+        option: None,
+        decision: Some(decision.action.as_str().to_owned()),
+        rule: decision.rule,
+    };
+    let accept = match decision.action {
+        PolicyAction::Allow => true,
+        PolicyAction::Deny => false,
+        PolicyAction::Ask => {
+            let _ = store.channels.prompt_tx.send(outcome);
+            return;
+        }
+    };
+    let Some(ref encoder) = store.config.respond_encoder else {
+        return;
+    };
+
+    let option = resolve_permission_option(Some(accept), None);
+    let steps = encoder.encode_permission(option);
+    outcome.option = Some(option);
+
+    tokio::spawn(auto_dismiss(
+        Arc::clone(store),
+        steps,
+        config.groom_dismiss_delay(),
+        state_seq,
+        outcome,
     ));
 }
 
@@ -170,9 +231,7 @@ async fn auto_dismiss(
     steps: Vec<NudgeStep>,
     dismiss_delay: Duration,
     expected_seq: u64,
-    prompt_type: String,
-    prompt_subtype: Option<String>,
-    groom_option: Option<u32>,
+    outcome: PromptOutcome,
 ) {
     tokio::time::sleep(dismiss_delay).await;
 
@@ -224,10 +283,5 @@ async fn auto_dismiss(
             }
         }
     }
-    let _ = store.channels.prompt_tx.send(PromptOutcome {
-        source: "groom".to_owned(),
-        r#type: prompt_type,
-        subtype: prompt_subtype,
-        option: groom_option,
-    });
+    let _ = store.channels.prompt_tx.send(outcome);
 }
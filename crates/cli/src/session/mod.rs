@@ -6,6 +6,8 @@
 
 use std::sync::Arc;
 
+use bytes::Bytes;
+use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 use crate::backend::{Backend, Boxed};
@@ -28,6 +30,9 @@ pub struct SessionConfig {
     /// Driver-provided parser for extracting numbered option labels from
     /// rendered screen lines during prompt enrichment.
     pub option_parser: Option<OptionParser>,
+    /// Receives a copy of every backend output chunk, for Tier 3 stdout
+    /// detectors.
+    pub stdout_tap: Option<mpsc::Sender<Bytes>>,
 }
 
 impl SessionConfig {
@@ -38,6 +43,7 @@ impl SessionConfig {
             detectors: Vec::new(),
             shutdown: CancellationToken::new(),
             option_parser: None,
+            stdout_tap: None,
         }
     }
 
@@ -55,6 +61,11 @@ impl SessionConfig {
         self.option_parser = Some(parser);
         self
     }
+
+    pub fn with_stdout_tap(mut self, tap: mpsc::Sender<Bytes>) -> Self {
+        self.stdout_tap = Some(tap);
+        self
+    }
 }
 
 /// What happened when the session loop exited.
@@ -10,16 +10,17 @@ use bytes::Bytes;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
 use crate::backend::BackendInput;
 use crate::config::Config;
 use crate::driver::{AgentState, CompositeDetector, DetectedState, ExitStatus, OptionParser};
 use crate::event::InputEvent;
+use crate::idle::IdleStep;
 use crate::switch::SwitchRequest;
 use crate::transport::Store;
 
-use super::transition::{self, DetectAction};
+use super::transition::{self, BudgetOutcome, DetectAction, IdleOutcome};
 use super::{SessionConfig, SessionOutcome};
 
 /// Mutable state tracked across iterations of the session select-loop.
@@ -28,8 +29,37 @@ pub struct SessionState {
     pub last_state: AgentState,
     pub idle_since: Option<tokio::time::Instant>,
     pub idle_timeout: Duration,
+    /// Index of the next idle escalation step to fire.
+    pub idle_step: usize,
+    /// Set by an idle `nudge` step; cleared once the agent is idle again.
+    pub idle_nudged: bool,
+    /// When an idle `park` step ends.
+    pub idle_parked_until: Option<tokio::time::Instant>,
     pub pending_switch: Option<SwitchRequest>,
     pub drain_deadline: Option<tokio::time::Instant>,
+    /// Set when a budget park interrupts a working agent; cleared once the
+    /// detector reports it stopped.
+    pub budget_interrupting: bool,
+}
+
+impl SessionState {
+    /// Next instant the idle branch of the select-loop should wake: the end
+    /// of an idle park, the next escalation step, or `idle_timeout`.
+    pub fn next_idle_deadline(&self, steps: &[IdleStep]) -> Option<tokio::time::Instant> {
+        if self.idle_parked_until.is_some() {
+            return self.idle_parked_until;
+        }
+        let since = self.idle_since?;
+        if !matches!(self.last_state, AgentState::Idle) {
+            return None;
+        }
+        let step = steps.get(self.idle_step).map(|s| since + s.after());
+        let timeout = (self.idle_timeout > Duration::ZERO).then(|| since + self.idle_timeout);
+        match (step, timeout) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
 }
 
 /// Core session that runs the select-loop multiplexer.
@@ -42,6 +72,7 @@ pub struct Session {
     shutdown: CancellationToken,
     backend_handle: JoinHandle<anyhow::Result<ExitStatus>>,
     option_parser: Option<OptionParser>,
+    stdout_tap: Option<mpsc::Sender<Bytes>>,
 }
 
 impl Session {
@@ -53,7 +84,8 @@ impl Session {
     /// 3. Spawns backend.run() on a separate task
     /// 4. Spawns all detectors
     pub fn new(config: &Config, session: SessionConfig) -> Self {
-        let SessionConfig { mut backend, detectors, store, shutdown, option_parser } = session;
+        let SessionConfig { mut backend, detectors, store, shutdown, option_parser, stdout_tap } =
+            session;
 
         // Set initial PID (Release so signal-delivery loads with Acquire see it)
         if let Some(pid) = backend.child_pid() {
@@ -88,6 +120,7 @@ impl Session {
             shutdown,
             backend_handle,
             option_parser,
+            stdout_tap,
         }
     }
 
@@ -127,17 +160,33 @@ impl Session {
             last_state: AgentState::Starting,
             idle_since: None,
             idle_timeout: config.idle_timeout(),
+            idle_step: 0,
+            idle_nudged: false,
+            idle_parked_until: None,
             pending_switch: None,
             drain_deadline: None,
+            budget_interrupting: false,
         };
 
+        let mut budget_rx = self.store.budget.budget_tx.subscribe();
+        // The budget may have been exceeded before this loop subscribed.
+        if let Some(event) = self.store.budget.exceeded_event() {
+            match transition::handle_budget_event(&self.store, &mut state, &event).await {
+                BudgetOutcome::Shutdown => self.shutdown.cancel(),
+                BudgetOutcome::Interrupt => next_escape_at = Some(tokio::time::Instant::now()),
+                BudgetOutcome::Continue => {}
+            }
+        }
+
         loop {
+            let idle_deadline = state.next_idle_deadline(&self.store.idle.steps);
             tokio::select! {
                 // 1. Backend output → feed screen, write ring buffer, broadcast
                 data = self.backend_output_rx.recv() => {
                     match data {
                         Some(bytes) => {
                             transition::feed_output(&self.store, &bytes).await;
+                            self.tap_stdout(&bytes);
                         }
                         None => break,
                     }
@@ -178,26 +227,33 @@ impl Session {
                     }
                 }
 
-                // 5. Idle timeout → trigger shutdown when idle too long
+                // 5. Idle deadline → escalation step, park resume, or shutdown
                 _ = async {
-                    match state.idle_since {
-                        Some(since) => tokio::time::sleep_until(since + state.idle_timeout).await,
+                    match idle_deadline {
+                        Some(at) => tokio::time::sleep_until(at).await,
                         None => std::future::pending().await,
                     }
-                }, if state.idle_since.is_some() => {
-                    debug!("idle timeout reached, triggering shutdown");
-                    self.shutdown.cancel();
-                    break;
+                }, if idle_deadline.is_some() => {
+                    let outcome = transition::handle_idle_deadline(&self.store, &mut state).await;
+                    if matches!(outcome, IdleOutcome::Shutdown) {
+                        self.shutdown.cancel();
+                        break;
+                    }
                 }
 
-                // 6. Drain escape ticker — periodically send Escape during drain
+                // 6. Escape ticker — periodically send Escape during drain or a budget park
                 _ = async {
                     match next_escape_at {
                         Some(at) => tokio::time::sleep_until(at).await,
                         None => std::future::pending().await,
                     }
                 }, if next_escape_at.is_some() => {
-                    debug!("drain: sending Escape");
+                    // Escapes for a budget park stop once the agent does.
+                    if state.drain_deadline.is_none() && !state.budget_interrupting {
+                        next_escape_at = None;
+                        continue;
+                    }
+                    debug!(draining = state.drain_deadline.is_some(), "sending Escape");
                     let esc = Bytes::from_static(b"\x1b");
                     self.store.lifecycle.bytes_written.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
                     self.store.input_activity.notify_waiters();
@@ -221,6 +277,7 @@ impl Session {
                 req = switch_rx.recv(), if state.pending_switch.is_none() && switch_open => {
                     match req {
                         Some(req) => {
+                            info!(parent: &req.span, force = req.force, "switch request received");
                             if matches!(state.last_state, AgentState::Exited { .. }) {
                                 state.pending_switch = Some(req);
                                 break;
@@ -238,7 +295,21 @@ impl Session {
                     }
                 }
 
-                // 9. Shutdown signal (disabled once drain mode is active)
+                // 9. Budget events → park, open the stop gate, or shut down
+                event = budget_rx.recv() => {
+                    if let Ok(event) = event {
+                        match transition::handle_budget_event(&self.store, &mut state, &event).await {
+                            // Graceful: the shutdown branch drains a busy agent first.
+                            BudgetOutcome::Shutdown => self.shutdown.cancel(),
+                            BudgetOutcome::Interrupt => {
+                                next_escape_at = Some(tokio::time::Instant::now());
+                            }
+                            BudgetOutcome::Continue => {}
+                        }
+                    }
+                }
+
+                // 10. Shutdown signal (disabled once drain mode is active)
                 _ = self.shutdown.cancelled(), if state.drain_deadline.is_none() => {
                     debug!("shutdown signal received");
                     if graceful_timeout > Duration::ZERO
@@ -306,6 +377,22 @@ impl Session {
         &self.store
     }
 
+    /// Copy an output chunk to the stdout detector. Never blocks the loop: a
+    /// full channel drops the chunk, and a closed one (no detector consumes
+    /// stdout) removes the tap.
+    fn tap_stdout(&mut self, bytes: &Bytes) {
+        let Some(ref tap) = self.stdout_tap else {
+            return;
+        };
+        match tap.try_send(bytes.clone()) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                debug!("stdout detector lagging, dropped {} bytes", bytes.len());
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => self.stdout_tap = None,
+        }
+    }
+
     /// Handle an input event, returning `true` if the loop should break.
     async fn handle_input(&self, event: Option<InputEvent>) -> bool {
         // Notify the enter-retry monitor that input activity occurred.
@@ -315,6 +402,10 @@ impl Session {
         }
         match event {
             Some(InputEvent::Write(data)) => {
+                if self.store.budget.keeps_parked() {
+                    debug!("budget parked: dropping {} input bytes", data.len());
+                    return false;
+                }
                 let len = data.len() as u64;
                 self.store
                     .lifecycle
@@ -336,6 +427,7 @@ impl Session {
                     let mut screen = self.store.terminal.screen.write().await;
                     screen.resize(cols, rows);
                 }
+                self.store.record.push_resize(cols, rows).await;
                 let _ = self.resize_tx.try_send((cols, rows));
             }
             Some(InputEvent::Signal(sig)) => {
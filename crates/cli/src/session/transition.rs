@@ -13,15 +13,20 @@ use bytes::Bytes;
 use nix::sys::signal::{kill, Signal};
 use nix::unistd::Pid;
 use tokio::sync::mpsc;
-use tracing::debug;
+use tracing::{debug, warn};
 
+use crate::budget::{BudgetAction, BudgetEvent, BudgetEventKind, BUDGET_PARK_REASON};
 use crate::config::Config;
 use crate::driver::{
     classify_error_detail, AgentState, DetectedState, ErrorCategory, ExitStatus, OptionParser,
     PromptKind,
 };
 use crate::event::{OutputEvent, TransitionEvent};
+use crate::idle::{render_message, IdleAction};
 use crate::profile::RotateOutcome;
+use crate::stop::StopMode;
+use crate::switch::SwitchRequest;
+use crate::transport::handler::{handle_nudge, resolve_switch_profile};
 use crate::transport::Store;
 
 use super::groom;
@@ -53,6 +58,8 @@ pub async fn feed_output(store: &Store, bytes: &Bytes) {
         let mut screen = store.terminal.screen.write().await;
         screen.feed(bytes);
     }
+    // Keep the timed raw-output track (no-op unless enabled)
+    store.record.push_output(bytes).await;
     // Broadcast raw output with stamped offset
     let _ = store.channels.output_tx.send(OutputEvent::Raw { data: bytes.clone(), offset });
 }
@@ -63,13 +70,29 @@ pub async fn feed_output(store: &Store, bytes: &Bytes) {
 /// spawns prompt enrichment/auto-dismiss, and tracks idle time.
 ///
 /// Returns a [`DetectAction`] telling the select-loop whether to continue or break.
+#[tracing::instrument(
+    name = "detector.emit",
+    skip_all,
+    fields(state = detected.state.as_str(), tier = detected.tier, cause = %detected.cause)
+)]
 pub async fn process_detected_state(
     store: &Arc<Store>,
-    detected: DetectedState,
+    mut detected: DetectedState,
     session: &mut SessionState,
     option_parser: &Option<OptionParser>,
     config: &Config,
 ) -> DetectAction {
+    // An exceeded budget with the `park` action keeps the agent parked. A
+    // busy agent is interrupted until it stops working.
+    if !matches!(detected.state, AgentState::Working) {
+        session.budget_interrupting = false;
+    }
+    if matches!(detected.state, AgentState::Idle | AgentState::Working)
+        && store.budget.keeps_parked()
+    {
+        detected.state = budget_parked();
+    }
+
     session.state_seq += 1;
     let mut current = store.driver.agent_state.write().await;
     let prev = current.clone();
@@ -98,6 +121,7 @@ pub async fn process_detected_state(
 
     // Store metadata for the HTTP/gRPC API.
     store.driver.state_seq.store(session.state_seq, std::sync::atomic::Ordering::Release);
+    store.metrics.record_transition(detected.tier, &detected.cause);
     *store.driver.detection.write().await = crate::transport::state::DetectionInfo {
         tier: detected.tier,
         cause: detected.cause.clone(),
@@ -126,12 +150,24 @@ pub async fn process_detected_state(
         groom::spawn_auto_dismiss(store, prompt, config, session.state_seq);
     }
 
-    // Track idle time for idle_timeout.
-    if matches!(detected.state, AgentState::Idle) && session.idle_timeout > Duration::ZERO {
-        if session.idle_since.is_none() {
+    // Track idle time for idle_timeout and idle escalation steps. A detected
+    // state always ends an idle park.
+    session.idle_parked_until = None;
+    let tracks_idle = session.idle_timeout > Duration::ZERO || !store.idle.steps.is_empty();
+    if matches!(detected.state, AgentState::Idle) && tracks_idle {
+        if session.idle_nudged {
+            // Idle again after reacting to an idle nudge: keep the escalation
+            // position but restart the clock, so however long the agent
+            // worked, the next step is still `after_secs` away.
             session.idle_since = Some(tokio::time::Instant::now());
+        } else if session.idle_since.is_none() {
+            session.idle_since = Some(tokio::time::Instant::now());
+            session.idle_step = 0;
         }
-    } else {
+        session.idle_nudged = false;
+    } else if !session.idle_nudged {
+        // Activity caused by an idle nudge keeps the escalation position
+        // until the agent is idle again.
         session.idle_since = None;
     }
 
@@ -169,27 +205,186 @@ async fn handle_rate_limit(store: Arc<Store>, session: &mut SessionState) {
                 reason: "all_profiles_rate_limited".into(),
                 resume_at_epoch_ms: resume_at,
             };
-            session.state_seq += 1;
-            let mut current = store.driver.agent_state.write().await;
-            let prev = current.clone();
-            *current = parked.clone();
-            drop(current);
-            session.last_state = parked.clone();
-            store.driver.state_seq.store(session.state_seq, std::sync::atomic::Ordering::Release);
-            let last_message = store.driver.last_message.read().await.clone();
-            let _ = store.channels.state_tx.send(TransitionEvent {
-                prev,
-                next: parked,
-                seq: session.state_seq,
-                cause: "all_profiles_rate_limited".to_owned(),
-                last_message,
-            });
+            broadcast_state(&store, session, parked, "all_profiles_rate_limited").await;
             store.profile.schedule_retry(retry_after, store.clone());
         }
         RotateOutcome::Skipped => {}
     }
 }
 
+/// What the select-loop should do after an idle deadline fires.
+pub enum IdleOutcome {
+    /// Keep running; the next deadline is recomputed.
+    Continue,
+    /// Shut the session down.
+    Shutdown,
+}
+
+/// Handle the idle deadline computed by [`SessionState::next_idle_deadline`]:
+/// resume from an idle park, fire the next escalation step, or report that
+/// `idle_timeout` has elapsed.
+pub async fn handle_idle_deadline(store: &Arc<Store>, session: &mut SessionState) -> IdleOutcome {
+    let now = tokio::time::Instant::now();
+    if session.idle_parked_until.is_some_and(|until| until <= now) {
+        session.idle_parked_until = None;
+        broadcast_state(store, session, AgentState::Idle, "idle_resume").await;
+        return IdleOutcome::Continue;
+    }
+
+    let Some(since) = session.idle_since else {
+        return IdleOutcome::Continue;
+    };
+    let idle = now.saturating_duration_since(since);
+    let index = session.idle_step;
+    match store.idle.steps.get(index) {
+        Some(step) if step.after() <= idle => {
+            session.idle_step += 1;
+            fire_idle_step(store, session, index, &step.action, idle).await
+        }
+        _ if session.idle_timeout > Duration::ZERO && session.idle_timeout <= idle => {
+            debug!("idle timeout reached, triggering shutdown");
+            IdleOutcome::Shutdown
+        }
+        _ => IdleOutcome::Continue,
+    }
+}
+
+/// Run one idle escalation step and broadcast it as an `idle:step` event.
+#[tracing::instrument(name = "idle.step", skip_all, fields(step = index, action = action.as_str()))]
+async fn fire_idle_step(
+    store: &Arc<Store>,
+    session: &mut SessionState,
+    index: usize,
+    action: &IdleAction,
+    idle: Duration,
+) -> IdleOutcome {
+    debug!(idle_secs = idle.as_secs(), "idle step fired");
+    let mut message = None;
+    let mut outcome = IdleOutcome::Continue;
+    match action {
+        IdleAction::Nudge { message: template } => {
+            let text = render_message(template, idle);
+            message = Some(text.clone());
+            session.idle_nudged = true;
+            // Delivery goes through the session loop's input channel, so it
+            // must not be awaited from inside the loop.
+            let store = Arc::clone(store);
+            tokio::spawn(async move {
+                if let Err(code) = handle_nudge(&store, &text).await {
+                    warn!("idle nudge failed: {code:?}");
+                }
+            });
+        }
+        IdleAction::StopMode { mode, prompt } => {
+            let mut config = store.stop.config.write().await;
+            config.mode = *mode;
+            if let Some(prompt) = prompt {
+                config.prompt = Some(prompt.clone());
+            }
+        }
+        IdleAction::Park { resume_after_secs } => {
+            let resume_after = Duration::from_secs(*resume_after_secs);
+            let parked = AgentState::Parked {
+                reason: "idle".into(),
+                resume_at_epoch_ms: now_epoch_ms() + resume_after.as_millis() as u64,
+            };
+            broadcast_state(store, session, parked, "idle_park").await;
+            session.idle_parked_until = Some(tokio::time::Instant::now() + resume_after);
+        }
+        IdleAction::Alert { message: template } => {
+            message = template.as_deref().map(|t| render_message(t, idle));
+        }
+        IdleAction::Switch { profile } => {
+            let mut req = SwitchRequest {
+                credentials: None,
+                force: false,
+                profile: Some(profile.clone()),
+                span: tracing::Span::current(),
+            };
+            if resolve_switch_profile(store, &mut req).await.is_err() {
+                warn!(profile, "idle switch: unknown profile");
+            } else if store.switch.switch_tx.try_send(req).is_err() {
+                debug!("idle switch: a switch is already in progress");
+            }
+        }
+        IdleAction::Shutdown => outcome = IdleOutcome::Shutdown,
+    }
+    store.idle.emit(index, action, idle, message);
+    outcome
+}
+
+/// What the select-loop should do after a budget event.
+pub enum BudgetOutcome {
+    /// Keep running.
+    Continue,
+    /// Interrupt the busy agent with Escape (as during a graceful drain)
+    /// until `budget_interrupting` clears.
+    Interrupt,
+    /// Shut the session down.
+    Shutdown,
+}
+
+/// Run the configured action for a budget event.
+pub async fn handle_budget_event(
+    store: &Store,
+    session: &mut SessionState,
+    event: &BudgetEvent,
+) -> BudgetOutcome {
+    match (event.kind, event.action) {
+        (BudgetEventKind::Exceeded, BudgetAction::Park) => {
+            let busy = matches!(session.last_state, AgentState::Working);
+            broadcast_state(store, session, budget_parked(), BUDGET_PARK_REASON).await;
+            if busy {
+                session.budget_interrupting = true;
+                return BudgetOutcome::Interrupt;
+            }
+        }
+        (BudgetEventKind::Exceeded, BudgetAction::OpenStopGate) => {
+            store.stop.config.write().await.mode = StopMode::Allow;
+        }
+        (BudgetEventKind::Exceeded, BudgetAction::Shutdown) => {
+            debug!("budget exceeded, triggering shutdown");
+            return BudgetOutcome::Shutdown;
+        }
+        (BudgetEventKind::Cleared, _) => {
+            session.budget_interrupting = false;
+            let parked = matches!(
+                session.last_state,
+                AgentState::Parked { ref reason, .. } if reason == BUDGET_PARK_REASON
+            );
+            if parked {
+                broadcast_state(store, session, AgentState::Idle, "budget_cleared").await;
+            }
+        }
+        _ => {}
+    }
+    BudgetOutcome::Continue
+}
+
+/// Budget parks have no scheduled resume (`resume_at_epoch_ms` is 0).
+fn budget_parked() -> AgentState {
+    AgentState::Parked { reason: BUDGET_PARK_REASON.to_owned(), resume_at_epoch_ms: 0 }
+}
+
+/// Set the agent state outside the detector path and broadcast the transition.
+async fn broadcast_state(store: &Store, session: &mut SessionState, next: AgentState, cause: &str) {
+    session.state_seq += 1;
+    let mut current = store.driver.agent_state.write().await;
+    let prev = current.clone();
+    *current = next.clone();
+    drop(current);
+    session.last_state = next.clone();
+    store.driver.state_seq.store(session.state_seq, std::sync::atomic::Ordering::Release);
+    let last_message = store.driver.last_message.read().await.clone();
+    let _ = store.channels.state_tx.send(TransitionEvent {
+        prev,
+        next,
+        seq: session.state_seq,
+        cause: cause.to_owned(),
+        last_message,
+    });
+}
+
 /// Broadcast an `AgentState::Restarting` transition and update tracking state.
 pub async fn broadcast_restarting(store: &Store, session: &mut SessionState, cause: &str) {
     session.state_seq += 1;
@@ -7,9 +7,14 @@ use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 
 use crate::backend::spawn::NativePty;
+use crate::budget::{BudgetAction, BudgetConfig};
 use crate::config::{Config, GroomLevel};
 use crate::driver::{AgentState, PromptContext, PromptKind};
+use crate::event::PromptOutcome;
+use crate::idle::IdleConfig;
+use crate::policy::{PolicyConfig, RespondPolicy};
 use crate::session::{Session, SessionConfig, SessionOutcome};
+use crate::stop::StopMode;
 use crate::switch::SwitchRequest;
 use crate::test_support::{MockDetector, MockPty, StoreBuilder, StoreCtx, StubRespondEncoder};
 
@@ -54,6 +59,26 @@ async fn output_captured_in_ring_and_screen() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn stdout_tap_receives_backend_output() -> anyhow::Result<()> {
+    let config = Config::test();
+    let StoreCtx { store, mut input_rx, .. } = StoreBuilder::new().ring_size(65536).build();
+    let (tap, mut stdout_rx) = tokio::sync::mpsc::channel(64);
+
+    let line = r#"{"type":"turn.completed"}"#;
+    let backend = NativePty::spawn(&["echo".into(), line.into()], 80, 24, &[])?;
+    let session = Session::new(&config, SessionConfig::new(store, backend).with_stdout_tap(tap));
+    let _ = session.run_to_exit(&config, &mut input_rx).await?;
+
+    let mut tapped = Vec::new();
+    while let Ok(chunk) = stdout_rx.try_recv() {
+        tapped.extend_from_slice(&chunk);
+    }
+    let text = String::from_utf8_lossy(&tapped);
+    assert!(text.contains(line), "tap: {text:?}");
+    Ok(())
+}
+
 #[tokio::test]
 async fn shutdown_cancels_session() -> anyhow::Result<()> {
     let mut config = Config::test();
@@ -279,6 +304,90 @@ async fn groom_manual_does_not_dismiss() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn bash_permission_prompt(command: &str) -> AgentState {
+    AgentState::Prompt {
+        prompt: PromptContext::new(PromptKind::Permission)
+            .with_subtype("tool")
+            .with_tool("Bash")
+            .with_input(serde_json::json!({ "command": command }).to_string())
+            .with_ready(),
+    }
+}
+
+/// Run a session in groom=auto with a respond policy against a Bash
+/// permission prompt and return the captured input and broadcast outcome.
+async fn run_policy_session(command: &str) -> anyhow::Result<(Vec<Vec<u8>>, PromptOutcome)> {
+    let mut config = Config::test();
+    config.drain_timeout_ms = Some(0);
+    let policy: PolicyConfig = serde_json::from_value(serde_json::json!({
+        "rules": [
+            { "action": "deny", "name": "no-rm", "tool": "Bash", "command": { "regex": "\\brm\\b" } },
+            { "action": "allow", "name": "git", "tool": "Bash", "command": "git *" },
+        ],
+    }))?;
+    let StoreCtx { store, mut input_rx, .. } = StoreBuilder::new()
+        .ring_size(65536)
+        .groom(GroomLevel::Auto)
+        .respond_encoder(Arc::new(StubRespondEncoder))
+        .respond_policy(RespondPolicy::compile(&policy)?)
+        .build();
+    let mut prompt_rx = store.channels.prompt_tx.subscribe();
+
+    let backend = MockPty::new().drain_input();
+    let captured = backend.captured_input();
+    let detector =
+        MockDetector::new(1, vec![(Duration::from_millis(10), bash_permission_prompt(command))]);
+
+    let shutdown = CancellationToken::new();
+    let session = Session::new(
+        &config,
+        SessionConfig::new(Arc::clone(&store), backend)
+            .with_shutdown(shutdown.clone())
+            .with_detectors(vec![Box::new(detector)]),
+    );
+    let sd = shutdown.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        sd.cancel();
+    });
+    let _ = session.run_to_exit(&config, &mut input_rx).await?;
+
+    let input = captured.lock().iter().map(|b| b.to_vec()).collect();
+    Ok((input, prompt_rx.try_recv()?))
+}
+
+/// groom=Auto approves tool calls allowed by the respond policy.
+#[tokio::test]
+async fn groom_auto_policy_allows() -> anyhow::Result<()> {
+    let (input, outcome) = run_policy_session("git status").await?;
+    assert!(input.iter().any(|b| b == b"1\r"), "input: {input:?}");
+    assert_eq!(outcome.source, "policy");
+    assert_eq!(outcome.decision.as_deref(), Some("allow"));
+    assert_eq!(outcome.rule.as_deref(), Some("git"));
+    assert_eq!(outcome.option, Some(1));
+    Ok(())
+}
+
+/// groom=Auto rejects tool calls denied by the respond policy.
+#[tokio::test]
+async fn groom_auto_policy_denies() -> anyhow::Result<()> {
+    let (input, outcome) = run_policy_session("rm -rf /").await?;
+    assert!(input.iter().any(|b| b == b"3\r"), "input: {input:?}");
+    assert_eq!(outcome.decision.as_deref(), Some("deny"));
+    assert_eq!(outcome.rule.as_deref(), Some("no-rm"));
+    Ok(())
+}
+
+/// An `ask` decision leaves the prompt alone but still reports it.
+#[tokio::test]
+async fn groom_auto_policy_asks() -> anyhow::Result<()> {
+    let (input, outcome) = run_policy_session("cargo publish").await?;
+    assert!(input.is_empty(), "unexpected input: {input:?}");
+    assert_eq!(outcome.decision.as_deref(), Some("ask"));
+    assert_eq!(outcome.rule, None);
+    Ok(())
+}
+
 /// Switch request while agent is idle produces SessionOutcome::Switch.
 ///
 /// MockPty doesn't respond to SIGHUP (PID 0), so we use a shutdown token
@@ -307,8 +416,14 @@ async fn switch_when_idle_returns_switch_outcome() -> anyhow::Result<()> {
     let sd = shutdown.clone();
     tokio::spawn(async move {
         tokio::time::sleep(Duration::from_millis(50)).await;
-        let _ =
-            switch_tx.send(SwitchRequest { credentials: None, force: false, profile: None }).await;
+        let _ = switch_tx
+            .send(SwitchRequest {
+                credentials: None,
+                force: false,
+                profile: None,
+                span: tracing::Span::none(),
+            })
+            .await;
         tokio::time::sleep(Duration::from_millis(100)).await;
         sd.cancel();
     });
@@ -342,8 +457,14 @@ async fn force_switch_while_working() -> anyhow::Result<()> {
     let sd = shutdown.clone();
     tokio::spawn(async move {
         tokio::time::sleep(Duration::from_millis(50)).await;
-        let _ =
-            switch_tx.send(SwitchRequest { credentials: None, force: true, profile: None }).await;
+        let _ = switch_tx
+            .send(SwitchRequest {
+                credentials: None,
+                force: true,
+                profile: None,
+                span: tracing::Span::none(),
+            })
+            .await;
         tokio::time::sleep(Duration::from_millis(100)).await;
         sd.cancel();
     });
@@ -384,8 +505,14 @@ async fn graceful_switch_waits_for_idle() -> anyhow::Result<()> {
     let sd = shutdown.clone();
     tokio::spawn(async move {
         tokio::time::sleep(Duration::from_millis(50)).await;
-        let _ =
-            switch_tx.send(SwitchRequest { credentials: None, force: false, profile: None }).await;
+        let _ = switch_tx
+            .send(SwitchRequest {
+                credentials: None,
+                force: false,
+                profile: None,
+                span: tracing::Span::none(),
+            })
+            .await;
         tokio::time::sleep(Duration::from_millis(350)).await;
         sd.cancel();
     });
@@ -399,3 +526,246 @@ async fn graceful_switch_waits_for_idle() -> anyhow::Result<()> {
     assert!(elapsed >= Duration::from_millis(150), "returned too fast: {elapsed:?}");
     Ok(())
 }
+
+/// Idle escalation steps fire in order, park and resume, then shut down.
+#[tokio::test(start_paused = true)]
+async fn idle_steps_escalate_then_shutdown() -> anyhow::Result<()> {
+    let mut config = Config::test();
+    config.drain_timeout_ms = Some(0);
+    let idle: IdleConfig = serde_json::from_value(serde_json::json!({
+        "steps": [
+            { "after_secs": 300, "action": "shutdown" },
+            { "after_secs": 60, "action": "alert", "message": "idle for {idle_mins}m" },
+            { "after_secs": 120, "action": "stop_mode", "mode": "gate", "prompt": "report in" },
+            { "after_secs": 180, "action": "park", "resume_after_secs": 60 },
+        ],
+    }))?;
+    let StoreCtx { store, mut input_rx, .. } =
+        StoreBuilder::new().ring_size(65536).idle_config(idle).build();
+    let mut idle_rx = store.idle.idle_tx.subscribe();
+    let mut state_rx = store.channels.state_tx.subscribe();
+
+    let backend = MockPty::new().drain_input();
+    let detector = MockDetector::new(1, vec![(Duration::from_millis(10), AgentState::Idle)]);
+    let session = Session::new(
+        &config,
+        SessionConfig::new(Arc::clone(&store), backend).with_detectors(vec![Box::new(detector)]),
+    );
+    let _ = session.run_to_exit(&config, &mut input_rx).await?;
+
+    let mut fired = vec![];
+    while let Ok(event) = idle_rx.try_recv() {
+        fired.push((event.step, event.action, event.idle_secs, event.message));
+    }
+    assert_eq!(
+        fired,
+        vec![
+            (0, "alert".to_owned(), 60, Some("idle for 1m".to_owned())),
+            (1, "stop_mode".to_owned(), 120, None),
+            (2, "park".to_owned(), 180, None),
+            (3, "shutdown".to_owned(), 300, None),
+        ]
+    );
+
+    let stop = store.stop.config.read().await;
+    assert_eq!(stop.mode, StopMode::Gate);
+    assert_eq!(stop.prompt.as_deref(), Some("report in"));
+
+    let mut causes = vec![];
+    while let Ok(event) = state_rx.try_recv() {
+        causes.push((event.next.as_str().to_owned(), event.cause));
+    }
+    assert!(causes.contains(&("parked".to_owned(), "idle_park".to_owned())), "{causes:?}");
+    assert!(causes.contains(&("idle".to_owned(), "idle_resume".to_owned())), "{causes:?}");
+    Ok(())
+}
+
+/// After an idle nudge, the agent works longer than the next step's
+/// `after_secs`; the remaining steps are timed from when it is idle again.
+#[tokio::test(start_paused = true)]
+async fn idle_steps_restart_the_clock_after_a_nudge() -> anyhow::Result<()> {
+    let mut config = Config::test();
+    config.drain_timeout_ms = Some(0);
+    let idle: IdleConfig = serde_json::from_value(serde_json::json!({
+        "steps": [
+            { "after_secs": 60, "action": "nudge", "message": "still there?" },
+            { "after_secs": 120, "action": "alert" },
+            { "after_secs": 300, "action": "shutdown" },
+        ],
+    }))?;
+    let StoreCtx { store, mut input_rx, .. } =
+        StoreBuilder::new().ring_size(65536).idle_config(idle).build();
+    let mut idle_rx = store.idle.idle_tx.subscribe();
+
+    let backend = MockPty::new().drain_input();
+    let detector = MockDetector::new(
+        1,
+        vec![
+            (Duration::from_millis(10), AgentState::Idle),
+            // Reacts to the nudge at 60s and works for 200s.
+            (Duration::from_secs(70), AgentState::Working),
+            (Duration::from_secs(200), AgentState::Idle),
+        ],
+    );
+    let session = Session::new(
+        &config,
+        SessionConfig::new(Arc::clone(&store), backend).with_detectors(vec![Box::new(detector)]),
+    );
+    let started = tokio::time::Instant::now();
+    let _ = session.run_to_exit(&config, &mut input_rx).await?;
+
+    let mut fired = vec![];
+    while let Ok(event) = idle_rx.try_recv() {
+        fired.push((event.step, event.action, event.idle_secs));
+    }
+    assert_eq!(
+        fired,
+        vec![
+            (0, "nudge".to_owned(), 60),
+            (1, "alert".to_owned(), 120),
+            (2, "shutdown".to_owned(), 300),
+        ]
+    );
+    // Idle again at 270s, shut down 300s later.
+    assert!(started.elapsed() >= Duration::from_secs(570), "{:?}", started.elapsed());
+    Ok(())
+}
+
+#[tokio::test]
+async fn budget_park_holds_until_raised() -> anyhow::Result<()> {
+    let mut config = Config::test();
+    config.drain_timeout_ms = Some(0);
+    let limit = |max| BudgetConfig {
+        max_output_tokens: Some(max),
+        action: BudgetAction::Park,
+        ..Default::default()
+    };
+    let StoreCtx { store, mut input_rx, .. } =
+        StoreBuilder::new().ring_size(65536).budget_config(limit(100)).build();
+    let mut state_rx = store.channels.state_tx.subscribe();
+    let shutdown = CancellationToken::new();
+
+    let backend = MockPty::new().drain_input();
+    let detector = MockDetector::new(
+        1,
+        vec![
+            (Duration::from_millis(10), AgentState::Working),
+            (Duration::from_millis(200), AgentState::Idle),
+        ],
+    );
+    let session = Session::new(
+        &config,
+        SessionConfig::new(Arc::clone(&store), backend)
+            .with_detectors(vec![Box::new(detector)])
+            .with_shutdown(shutdown.clone()),
+    );
+
+    let driver = Arc::clone(&store);
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let used = crate::usage::SessionUsage { output_tokens: 150, ..Default::default() };
+        driver.budget.observe(&used);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        driver.budget.set_config(limit(1_000), &used);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        shutdown.cancel();
+    });
+    let _ = session.run_to_exit(&config, &mut input_rx).await?;
+
+    let mut states = vec![];
+    while let Ok(event) = state_rx.try_recv() {
+        states.push(event.next.as_str().to_owned());
+        if event.cause.starts_with("budget") {
+            states.push(event.cause);
+        }
+    }
+    assert_eq!(
+        states,
+        vec!["working", "parked", "budget_exceeded", "parked", "idle", "budget_cleared", "exited"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn budget_park_interrupts_a_working_agent_and_blocks_input() -> anyhow::Result<()> {
+    let mut config = Config::test();
+    config.drain_timeout_ms = Some(0);
+    let budget = BudgetConfig {
+        max_output_tokens: Some(100),
+        action: BudgetAction::Park,
+        ..Default::default()
+    };
+    let StoreCtx { store, mut input_rx, .. } =
+        StoreBuilder::new().ring_size(65536).budget_config(budget).build();
+    let mut state_rx = store.channels.state_tx.subscribe();
+    let shutdown = CancellationToken::new();
+
+    let backend = MockPty::new().drain_input();
+    let captured = backend.captured_input();
+    let detector = MockDetector::new(
+        1,
+        vec![
+            (Duration::from_millis(10), AgentState::Working),
+            // Stops after the Escape, then starts working again on its own.
+            (Duration::from_millis(200), AgentState::Idle),
+            (Duration::from_millis(50), AgentState::Working),
+        ],
+    );
+    let session = Session::new(
+        &config,
+        SessionConfig::new(Arc::clone(&store), backend)
+            .with_detectors(vec![Box::new(detector)])
+            .with_shutdown(shutdown.clone()),
+    );
+
+    let driver = Arc::clone(&store);
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        driver
+            .budget
+            .observe(&crate::usage::SessionUsage { output_tokens: 150, ..Default::default() });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let typed = bytes::Bytes::from_static(b"keep going\r");
+        let _ = driver.channels.input_tx.send(crate::event::InputEvent::Write(typed)).await;
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        shutdown.cancel();
+    });
+    let _ = session.run_to_exit(&config, &mut input_rx).await?;
+
+    let input = captured.lock().clone();
+    assert_eq!(input, vec![bytes::Bytes::from_static(b"\x1b")], "{input:?}");
+
+    let mut states = vec![];
+    while let Ok(event) = state_rx.try_recv() {
+        states.push(event.next.as_str().to_owned());
+    }
+    assert_eq!(states, vec!["working", "parked", "parked", "parked", "exited"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn budget_exceeded_before_start_still_shuts_down() -> anyhow::Result<()> {
+    let mut config = Config::test();
+    config.drain_timeout_ms = Some(0);
+    let budget = BudgetConfig {
+        max_output_tokens: Some(100),
+        action: BudgetAction::Shutdown,
+        ..Default::default()
+    };
+    let StoreCtx { store, mut input_rx, .. } =
+        StoreBuilder::new().ring_size(65536).budget_config(budget).build();
+    // Restored usage is over budget before any session loop is listening.
+    store.budget.observe(&crate::usage::SessionUsage { output_tokens: 150, ..Default::default() });
+
+    let backend = MockPty::new().drain_input();
+    let detector = MockDetector::new(1, vec![(Duration::from_millis(10), AgentState::Working)]);
+    let session = Session::new(
+        &config,
+        SessionConfig::new(Arc::clone(&store), backend).with_detectors(vec![Box::new(detector)]),
+    );
+
+    tokio::time::timeout(Duration::from_secs(5), session.run_to_exit(&config, &mut input_rx))
+        .await
+        .map_err(|_| anyhow::anyhow!("session ignored the exceeded budget"))??;
+    Ok(())
+}
@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Segmented on-disk log of raw PTY output.
+//!
+//! Backs [`RingBuffer`](crate::ring::RingBuffer) reads for offsets older
+//! than the in-memory window. Output is appended to an active segment file
+//! named by its starting byte offset (`<offset>.log`); once a segment reaches
+//! the size limit a new one is started and the full one is gzip-compressed
+//! (`<offset>.log.gz`) off the append path.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use parking_lot::Mutex;
+
+/// Default uncompressed size of a segment before it is sealed.
+pub const DEFAULT_SEGMENT_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Last decompressed segment, keyed by start offset. Sequential replay
+/// reads the same segment many times in small chunks.
+type Cache = Arc<Mutex<Option<(u64, Arc<Vec<u8>>)>>>;
+
+/// A full segment. It stays readable from the raw file until compression
+/// (off the append path) swaps in the `.log.gz`.
+#[derive(Debug)]
+struct Segment {
+    start: u64,
+    len: u64,
+    data: Mutex<SegmentData>,
+}
+
+#[derive(Debug)]
+enum SegmentData {
+    Raw(Arc<File>),
+    Compressed(PathBuf),
+}
+
+/// The segment currently being appended to.
+#[derive(Debug)]
+struct Active {
+    start: u64,
+    len: u64,
+    path: PathBuf,
+    file: Arc<File>,
+}
+
+#[derive(Debug)]
+pub struct SpillLog {
+    dir: PathBuf,
+    segment_size: u64,
+    sealed: Vec<Arc<Segment>>,
+    active: Active,
+    cache: Cache,
+    /// Set on drop so a compression still running for this log does not
+    /// write into a directory a new log has since taken over.
+    closed: Arc<Mutex<bool>>,
+}
+
+/// A point-in-time view of a [`SpillLog`] that reads without borrowing it,
+/// so callers can release the ring lock before doing any I/O.
+#[derive(Debug, Clone)]
+pub struct SpillReader {
+    sealed: Vec<Arc<Segment>>,
+    active_start: u64,
+    active_len: u64,
+    active_file: Arc<File>,
+    cache: Cache,
+}
+
+impl SpillLog {
+    /// Open a fresh log in `dir`, removing any segments left from a
+    /// previous session (offsets restart at zero).
+    pub fn open(dir: &Path) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            if name.ends_with(".log") || name.ends_with(".log.gz") {
+                std::fs::remove_file(&path)?;
+            }
+        }
+        let active = Active::create(dir, 0)?;
+        Ok(Self {
+            dir: dir.to_owned(),
+            segment_size: DEFAULT_SEGMENT_SIZE,
+            sealed: Vec::new(),
+            active,
+            cache: Arc::new(Mutex::new(None)),
+            closed: Arc::new(Mutex::new(false)),
+        })
+    }
+
+    pub fn with_segment_size(mut self, size: u64) -> Self {
+        self.segment_size = size.max(1);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Total bytes ever appended.
+    pub fn end_offset(&self) -> u64 {
+        self.active.start + self.active.len
+    }
+
+    /// Number of sealed segments.
+    pub fn sealed_count(&self) -> usize {
+        self.sealed.len()
+    }
+
+    /// Append data, sealing segments as they fill.
+    pub fn append(&mut self, mut data: &[u8]) -> anyhow::Result<()> {
+        while !data.is_empty() {
+            let room = (self.segment_size - self.active.len) as usize;
+            let (head, rest) = data.split_at(room.min(data.len()));
+            (&*self.active.file).write_all(head)?;
+            self.active.len += head.len() as u64;
+            data = rest;
+            if self.active.len >= self.segment_size {
+                self.seal()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Start a new active segment and compress the full one. Inside a tokio
+    /// runtime compression runs on a blocking task, since appends happen
+    /// under the ring lock.
+    fn seal(&mut self) -> anyhow::Result<()> {
+        let next = Active::create(&self.dir, self.end_offset())?;
+        let active = std::mem::replace(&mut self.active, next);
+        let segment = Arc::new(Segment {
+            start: active.start,
+            len: active.len,
+            data: Mutex::new(SegmentData::Raw(active.file)),
+        });
+        self.sealed.push(Arc::clone(&segment));
+
+        let closed = Arc::clone(&self.closed);
+        let raw_path = active.path;
+        let compress = move || {
+            if let Err(e) = compress(&segment, &raw_path, &closed) {
+                tracing::warn!("ring spill: failed to compress {}: {e:#}", raw_path.display());
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => drop(handle.spawn_blocking(compress)),
+            Err(_) => compress(),
+        }
+        Ok(())
+    }
+
+    /// A reader over everything appended so far.
+    pub fn reader(&self) -> SpillReader {
+        SpillReader {
+            sealed: self.sealed.clone(),
+            active_start: self.active.start,
+            active_len: self.active.len,
+            active_file: Arc::clone(&self.active.file),
+            cache: Arc::clone(&self.cache),
+        }
+    }
+
+    /// Read up to `limit` bytes starting at `offset`. Returns an empty
+    /// vector at or past the end.
+    pub fn read(&self, offset: u64, limit: usize) -> anyhow::Result<Vec<u8>> {
+        self.reader().read(offset, limit)
+    }
+}
+
+impl Drop for SpillLog {
+    fn drop(&mut self) {
+        *self.closed.lock() = true;
+    }
+}
+
+/// Gzip a sealed segment's raw file, then switch readers over to it. Raw
+/// file handles already taken by readers stay valid after the unlink.
+fn compress(segment: &Segment, raw_path: &Path, closed: &Mutex<bool>) -> anyhow::Result<()> {
+    let raw = {
+        let data = segment.data.lock();
+        let SegmentData::Raw(ref file) = *data else {
+            return Ok(());
+        };
+        Arc::clone(file)
+    };
+    let mut buf = vec![0u8; segment.len as usize];
+    raw.read_exact_at(&mut buf, 0)?;
+
+    let gz_path = raw_path.with_extension("log.gz");
+    let tmp_path = raw_path.with_extension("log.gz.tmp");
+    let mut encoder = GzEncoder::new(File::create(&tmp_path)?, Compression::fast());
+    encoder.write_all(&buf)?;
+    encoder.finish()?;
+
+    // Held until the raw file is gone, so a drop can't slip in between.
+    let closed = closed.lock();
+    if *closed {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Ok(());
+    }
+    std::fs::rename(&tmp_path, &gz_path)?;
+    *segment.data.lock() = SegmentData::Compressed(gz_path);
+    std::fs::remove_file(raw_path)?;
+    Ok(())
+}
+
+impl SpillReader {
+    /// Read up to `limit` bytes starting at `offset`. Returns an empty
+    /// vector at or past the end. Blocking I/O: call from `spawn_blocking`
+    /// in async code.
+    pub fn read(&self, offset: u64, limit: usize) -> anyhow::Result<Vec<u8>> {
+        let end = (self.active_start + self.active_len).min(offset.saturating_add(limit as u64));
+        let mut out = Vec::with_capacity(end.saturating_sub(offset) as usize);
+        let mut pos = offset;
+
+        let first = self.sealed.partition_point(|s| s.start + s.len <= pos);
+        for segment in &self.sealed[first..] {
+            if pos >= end {
+                break;
+            }
+            let to = end.min(segment.start + segment.len);
+            out.extend_from_slice(&self.read_segment(segment, pos, to)?);
+            pos = to;
+        }
+
+        if pos < end && pos >= self.active_start {
+            let mut buf = vec![0u8; (end - pos) as usize];
+            self.active_file.read_exact_at(&mut buf, pos - self.active_start)?;
+            out.extend_from_slice(&buf);
+        }
+        Ok(out)
+    }
+
+    /// Bytes `from..to` (absolute offsets) of a sealed segment.
+    fn read_segment(&self, segment: &Segment, from: u64, to: u64) -> anyhow::Result<Vec<u8>> {
+        let (from, to) = ((from - segment.start) as usize, (to - segment.start) as usize);
+        let gz_file = match *segment.data.lock() {
+            SegmentData::Raw(ref file) => {
+                let mut buf = vec![0u8; to - from];
+                file.read_exact_at(&mut buf, from as u64)?;
+                return Ok(buf);
+            }
+            // Open under the lock so the path is still current.
+            SegmentData::Compressed(ref path) => File::open(path)?,
+        };
+        let data = self.decompress(segment.start, segment.len, gz_file)?;
+        Ok(data[from..to.min(data.len())].to_vec())
+    }
+
+    fn decompress(&self, start: u64, len: u64, file: File) -> anyhow::Result<Arc<Vec<u8>>> {
+        let mut cache = self.cache.lock();
+        if let Some((cached, ref data)) = *cache {
+            if cached == start {
+                return Ok(Arc::clone(data));
+            }
+        }
+        let mut data = Vec::with_capacity(len as usize);
+        GzDecoder::new(file).read_to_end(&mut data)?;
+        let data = Arc::new(data);
+        *cache = Some((start, Arc::clone(&data)));
+        Ok(data)
+    }
+}
+
+impl Active {
+    fn create(dir: &Path, start: u64) -> anyhow::Result<Self> {
+        let path = dir.join(format!("{start:016x}.log"));
+        let file =
+            File::options().read(true).write(true).create(true).truncate(true).open(&path)?;
+        let file = Arc::new(file);
+        Ok(Self { start, len: 0, path, file })
+    }
+}
+
+#[cfg(test)]
+#[path = "spill_tests.rs"]
+mod tests;
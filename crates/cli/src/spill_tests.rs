@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::SpillLog;
+
+fn bytes(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn append_and_read_active_segment() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mut log = SpillLog::open(dir.path())?;
+    log.append(b"hello ")?;
+    log.append(b"world")?;
+
+    assert_eq!(log.end_offset(), 11);
+    assert_eq!(log.sealed_count(), 0);
+    assert_eq!(log.read(0, 100)?, b"hello world");
+    assert_eq!(log.read(6, 3)?, b"wor");
+    assert!(log.read(11, 10)?.is_empty());
+    assert!(log.read(50, 10)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn seals_and_compresses_full_segments() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mut log = SpillLog::open(dir.path())?.with_segment_size(100);
+    let data = bytes(350);
+    // Uneven chunks so writes straddle segment boundaries.
+    for chunk in data.chunks(37) {
+        log.append(chunk)?;
+    }
+
+    assert_eq!(log.sealed_count(), 3);
+    let mut names: Vec<String> = std::fs::read_dir(dir.path())?
+        .map(|e| e.map(|e| e.file_name().to_string_lossy().into_owned()))
+        .collect::<Result<_, _>>()?;
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "0000000000000000.log.gz",
+            "0000000000000064.log.gz",
+            "00000000000000c8.log.gz",
+            "000000000000012c.log",
+        ]
+    );
+
+    assert_eq!(log.read(0, 1000)?, data);
+    // Reads spanning sealed and active segments.
+    assert_eq!(log.read(90, 30)?, data[90..120]);
+    assert_eq!(log.read(280, 60)?, data[280..340]);
+    // Repeated reads hit the decompression cache.
+    assert_eq!(log.read(110, 5)?, data[110..115]);
+    assert_eq!(log.read(115, 5)?, data[115..120]);
+    Ok(())
+}
+
+#[test]
+fn open_discards_previous_segments() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("keep.txt"), "x")?;
+    {
+        let mut log = SpillLog::open(dir.path())?.with_segment_size(4);
+        log.append(b"0123456789")?;
+    }
+
+    let log = SpillLog::open(dir.path())?;
+    assert_eq!(log.end_offset(), 0);
+    let count = std::fs::read_dir(dir.path())?.count();
+    // keep.txt plus the fresh active segment.
+    assert_eq!(count, 2);
+    Ok(())
+}
+
+#[test]
+fn reader_outlives_sealing_and_compression() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mut log = SpillLog::open(dir.path())?.with_segment_size(100);
+    let data = bytes(250);
+    log.append(&data[..60])?;
+    // Taken while the first segment is still active and uncompressed.
+    let reader = log.reader();
+    log.append(&data[60..])?;
+
+    assert_eq!(log.sealed_count(), 2);
+    assert_eq!(reader.read(0, 1000)?, data[..60]);
+    assert_eq!(log.reader().read(40, 100)?, data[40..140]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn seals_off_the_append_path_inside_a_runtime() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mut log = SpillLog::open(dir.path())?.with_segment_size(100);
+    let data = bytes(250);
+    log.append(&data)?;
+
+    // Readable straight away, whether or not compression has finished.
+    assert_eq!(log.read(0, 1000)?, data);
+
+    let gz = dir.path().join("0000000000000064.log.gz");
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while !gz.exists() || dir.path().join("0000000000000064.log").exists() {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("segment never compressed"))?;
+    assert_eq!(log.read(0, 1000)?, data);
+    Ok(())
+}
+
+#[tokio::test]
+async fn dropped_log_abandons_pending_compression() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    {
+        let mut log = SpillLog::open(dir.path())?.with_segment_size(4);
+        log.append(b"0123456789")?;
+    }
+    let log = SpillLog::open(dir.path())?;
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    assert_eq!(log.end_offset(), 0);
+    let names: Vec<String> = std::fs::read_dir(dir.path())?
+        .map(|e| e.map(|e| e.file_name().to_string_lossy().into_owned()))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(names, vec!["0000000000000000.log"]);
+    Ok(())
+}
@@ -14,9 +14,10 @@ use base64::Engine;
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use tokio::sync::RwLock;
+use utoipa::ToSchema;
 
 /// Top-level start hook configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct StartConfig {
     /// Static text to inject (delivered as base64-decoded printf).
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -30,7 +31,7 @@ pub struct StartConfig {
 }
 
 /// Per-event override configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct StartEventConfig {
     /// Static text to inject for this event type.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -13,9 +13,12 @@ use std::sync::atomic::AtomicBool;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tokio::sync::{broadcast, RwLock};
+use utoipa::ToSchema;
+
+use crate::json_schema::{self, Violation};
 
 /// Top-level stop hook configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StopConfig {
     /// How to handle stop hook calls.
     #[serde(default)]
@@ -35,7 +38,7 @@ impl Default for StopConfig {
 }
 
 /// Stop hook mode.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum StopMode {
     /// Always allow the agent to stop (default behavior).
@@ -51,14 +54,14 @@ pub enum StopMode {
 }
 
 /// Schema describing expected fields in the signal body.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StopSchema {
     /// Named fields the signal body should contain.
     pub fields: BTreeMap<String, StopSchemaField>,
 }
 
 /// A single field in the stop schema.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StopSchemaField {
     /// Whether this field is required.
     #[serde(default)]
@@ -72,6 +75,43 @@ pub struct StopSchemaField {
     /// Field-level description.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Additional JSON Schema the field value must satisfy (e.g. `type`,
+    /// `pattern`, nested `properties`). See [`crate::json_schema`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub schema: Option<Value>,
+}
+
+impl StopSchema {
+    /// Check that every field's JSON Schema is well-formed.
+    pub fn check(&self) -> Result<(), String> {
+        for (name, field) in &self.fields {
+            if let Some(ref schema) = field.schema {
+                json_schema::check_schema(schema).map_err(|e| format!("field \"{name}\": {e}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Translate into a JSON Schema for the whole signal body.
+    pub fn to_json_schema(&self) -> Value {
+        let mut properties = serde_json::Map::new();
+        let mut required = Vec::new();
+        for (name, field) in &self.fields {
+            let mut prop = match field.schema {
+                Some(Value::Object(ref obj)) => obj.clone(),
+                _ => serde_json::Map::new(),
+            };
+            if let Some(ref values) = field.r#enum {
+                prop.insert("enum".to_owned(), Value::from(values.clone()));
+            }
+            if field.required {
+                required.push(Value::from(name.clone()));
+            }
+            properties.insert(name.clone(), Value::Object(prop));
+        }
+        serde_json::json!({ "type": "object", "required": required, "properties": properties })
+    }
 }
 
 /// Returns the default schema used in Auto mode when no custom schema is configured.
@@ -93,6 +133,7 @@ pub fn default_auto_schema() -> StopSchema {
                 d
             }),
             description: Some("Task outcome".to_owned()),
+            schema: None,
         },
     );
     fields.insert(
@@ -102,6 +143,7 @@ pub fn default_auto_schema() -> StopSchema {
             r#enum: None,
             descriptions: None,
             description: Some("Summary of completed work or what remains".to_owned()),
+            schema: None,
         },
     );
     StopSchema { fields }
@@ -198,16 +240,21 @@ impl StopState {
     /// schema when none is configured.
     ///
     /// On validation failure, emits a `Rejected` event and returns `Err` with
-    /// a descriptive message.
-    pub async fn resolve(&self, body: serde_json::Value) -> Result<(), String> {
+    /// every violation found.
+    pub async fn resolve(&self, body: serde_json::Value) -> Result<(), StopRejection> {
         let config = self.config.read().await;
-        if let Some(ref schema) = config.schema {
-            validate_signal(schema, &body)?;
+        let result = if let Some(ref schema) = config.schema {
+            validate_signal(schema, &body)
         } else if config.mode == StopMode::Auto {
-            let default = default_auto_schema();
-            validate_signal(&default, &body)?;
-        }
+            validate_signal(&default_auto_schema(), &body)
+        } else {
+            Ok(())
+        };
         drop(config);
+        if let Err(rejection) = result {
+            self.emit(StopType::Rejected, None, Some(rejection.to_string()));
+            return Err(rejection);
+        }
         *self.signal_body.write().await = Some(body);
         self.signaled.store(true, std::sync::atomic::Ordering::Release);
         Ok(())
@@ -314,35 +361,32 @@ fn generate_auto_block_reason(config: &StopConfig) -> String {
     parts.join("\n")
 }
 
-/// Validate a signal body against a stop schema.
-///
-/// Checks that required fields are present and enum fields have allowed values.
-/// Returns `Err` with a descriptive message on failure.
-pub fn validate_signal(schema: &StopSchema, body: &serde_json::Value) -> Result<(), String> {
-    let obj = body.as_object();
+/// A signal body that failed schema validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StopRejection {
+    pub violations: Vec<Violation>,
+}
 
-    for (name, field) in &schema.fields {
-        let value = obj.and_then(|o| o.get(name));
+impl std::fmt::Display for StopRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self.violations.iter().map(Violation::to_string).collect();
+        f.write_str(&parts.join("; "))
+    }
+}
 
-        // Required field check.
-        if field.required && (value.is_none() || value == Some(&serde_json::Value::Null)) {
-            return Err(format!("missing required field: {name}"));
-        }
+impl std::error::Error for StopRejection {}
 
-        // Enum validation.
-        if let (Some(ref allowed), Some(val)) = (&field.r#enum, value) {
-            if let Some(s) = val.as_str() {
-                if !allowed.contains(&s.to_owned()) {
-                    return Err(format!(
-                        "field \"{name}\": value \"{s}\" is not one of: {}",
-                        allowed.join(", ")
-                    ));
-                }
-            }
-        }
+/// Validate a signal body against a stop schema.
+///
+/// Checks required fields, enum values and each field's JSON Schema.
+/// Returns `Err` listing every violation on failure.
+pub fn validate_signal(schema: &StopSchema, body: &serde_json::Value) -> Result<(), StopRejection> {
+    let violations = json_schema::validate(&schema.to_json_schema(), body);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(StopRejection { violations })
     }
-
-    Ok(())
 }
 
 /// Build an example JSON body string from a schema.
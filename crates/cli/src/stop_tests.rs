@@ -153,6 +153,7 @@ fn generate_block_reason_with_enum_schema_expands_commands() {
                 d
             }),
             description: Some("Task outcome".to_owned()),
+            schema: None,
         },
     );
     let config = StopConfig {
@@ -184,6 +185,7 @@ fn generate_block_reason_enum_with_extra_fields() {
             r#enum: None,
             descriptions: None,
             description: Some("Optional notes".to_owned()),
+            schema: None,
         },
     );
     fields.insert(
@@ -198,6 +200,7 @@ fn generate_block_reason_enum_with_extra_fields() {
                 d
             }),
             description: Some("Outcome of the task".to_owned()),
+            schema: None,
         },
     );
     let config =
@@ -224,6 +227,7 @@ fn generate_block_reason_non_enum_schema() {
             r#enum: None,
             descriptions: None,
             description: Some("A message".to_owned()),
+            schema: None,
         },
     );
     let config =
@@ -263,6 +267,7 @@ fn generate_block_reason_gate_mode_ignores_schema() {
             r#enum: Some(vec!["done".to_owned()]),
             descriptions: None,
             description: None,
+            schema: None,
         },
     );
     let config = StopConfig {
@@ -322,6 +327,7 @@ fn validate_signal_happy_path() {
             r#enum: Some(vec!["done".to_owned(), "error".to_owned()]),
             descriptions: None,
             description: None,
+            schema: None,
         },
     );
     let schema = StopSchema { fields };
@@ -334,12 +340,18 @@ fn validate_signal_missing_required_field() {
     let mut fields = BTreeMap::new();
     fields.insert(
         "status".to_owned(),
-        StopSchemaField { required: true, r#enum: None, descriptions: None, description: None },
+        StopSchemaField {
+            required: true,
+            r#enum: None,
+            descriptions: None,
+            description: None,
+            schema: None,
+        },
     );
     let schema = StopSchema { fields };
     let body = serde_json::json!({});
     let err = validate_signal(&schema, &body).unwrap_err();
-    assert!(err.contains("missing required field: status"), "got: {err}");
+    assert!(err.to_string().contains("missing required field: status"), "got: {err}");
 }
 
 #[test]
@@ -352,13 +364,14 @@ fn validate_signal_bad_enum_value() {
             r#enum: Some(vec!["done".to_owned(), "error".to_owned()]),
             descriptions: None,
             description: None,
+            schema: None,
         },
     );
     let schema = StopSchema { fields };
     let body = serde_json::json!({"status": "bogus"});
     let err = validate_signal(&schema, &body).unwrap_err();
-    assert!(err.contains("bogus"), "got: {err}");
-    assert!(err.contains("done"), "got: {err}");
+    assert!(err.to_string().contains("bogus"), "got: {err}");
+    assert!(err.to_string().contains("done"), "got: {err}");
 }
 
 #[test]
@@ -390,7 +403,7 @@ async fn stop_state_resolve_auto_default_schema_rejects_bad_status() {
     let result = state.resolve(body).await;
     assert!(result.is_err());
     let err = result.unwrap_err();
-    assert!(err.contains("bogus"), "got: {err}");
+    assert!(err.to_string().contains("bogus"), "got: {err}");
     assert!(!state.signaled.load(std::sync::atomic::Ordering::Acquire));
 }
 
@@ -422,7 +435,7 @@ async fn stop_state_resolve_auto_default_schema_rejects_missing_status() {
     let result = state.resolve(body).await;
     assert!(result.is_err());
     let err = result.unwrap_err();
-    assert!(err.contains("missing required field: status"), "got: {err}");
+    assert!(err.to_string().contains("missing required field: status"), "got: {err}");
 }
 
 #[tokio::test]
@@ -435,6 +448,7 @@ async fn stop_state_resolve_rejected_with_event() {
             r#enum: Some(vec!["done".to_owned()]),
             descriptions: None,
             description: None,
+            schema: None,
         },
     );
     let config =
@@ -446,3 +460,50 @@ async fn stop_state_resolve_rejected_with_event() {
     // Signal should NOT be set on rejection.
     assert!(!state.signaled.load(std::sync::atomic::Ordering::Acquire));
 }
+
+#[test]
+fn validate_signal_field_schema_and_all_violations() -> anyhow::Result<()> {
+    let schema: StopSchema = serde_json::from_value(serde_json::json!({
+        "fields": {
+            "status": {"required": true, "enum": ["done", "failed"]},
+            "pr": {"required": true, "schema": {"type": "string", "pattern": "^[0-9]+$"}},
+            "report": {
+                "schema": {
+                    "type": "object",
+                    "required": ["tests_passed"],
+                    "properties": {"tests_passed": {"type": "boolean"}},
+                },
+            },
+        },
+    }))?;
+    schema.check().map_err(anyhow::Error::msg)?;
+
+    let ok = serde_json::json!({"status": "done", "pr": "42", "report": {"tests_passed": true}});
+    assert!(validate_signal(&schema, &ok).is_ok());
+
+    let bad = serde_json::json!({"status": 1, "pr": "#42", "report": {}});
+    let err = validate_signal(&schema, &bad).err().ok_or_else(|| anyhow::anyhow!("accepted"))?;
+    let paths: Vec<_> = err.violations.iter().map(|v| v.path.as_str()).collect();
+    assert_eq!(paths, vec!["/pr", "/report", "/status"]);
+    Ok(())
+}
+
+#[test]
+fn stop_schema_check_rejects_bad_pattern() -> anyhow::Result<()> {
+    let schema: StopSchema = serde_json::from_value(serde_json::json!({
+        "fields": {"pr": {"schema": {"pattern": "("}}},
+    }))?;
+    let err = schema.check().err().unwrap_or_default();
+    assert!(err.starts_with("field \"pr\""), "got: {err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn stop_state_resolve_rejection_emits_event() {
+    let config = StopConfig { mode: StopMode::Auto, prompt: None, schema: None };
+    let state = StopState::new(config, "http://test".to_owned());
+    let mut rx = state.stop_tx.subscribe();
+    assert!(state.resolve(serde_json::json!({})).await.is_err());
+    let event = rx.try_recv();
+    assert!(matches!(event, Ok(StopEvent { r#type: StopType::Rejected, .. })), "got: {event:?}");
+}
@@ -14,9 +14,10 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::{mpsc, RwLock};
+use utoipa::ToSchema;
 
 /// External switch request from the transport layer.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SwitchRequest {
     /// Environment variables to merge into the new child process.
     /// Typically contains credential keys like `CLAUDE_CODE_OAUTH_TOKEN`.
@@ -28,6 +29,10 @@ pub struct SwitchRequest {
     /// Named profile for active tracking (set by auto-rotation or manual switch).
     #[serde(default)]
     pub profile: Option<String>,
+    /// Span of the request that asked for the switch, so the drain and
+    /// respawn join the caller's trace.
+    #[serde(skip, default = "tracing::Span::current")]
+    pub span: tracing::Span,
 }
 
 /// Shared state for the switch subsystem.
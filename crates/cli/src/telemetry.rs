@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Optional OpenTelemetry trace export.
+//!
+//! When `--otlp-endpoint` is set, spans are exported over OTLP/gRPC to a
+//! collector and W3C trace context (`traceparent`) is honoured on inbound
+//! HTTP requests, so a trace started by coopmux continues into coop.
+
+use std::sync::OnceLock;
+
+use axum::http::{HeaderMap, HeaderName};
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::Context;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing_opentelemetry::OpenTelemetryLayer;
+
+/// Installed provider, kept so [`shutdown`] can flush pending spans.
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Build a tracing layer that exports spans to the OTLP collector at `endpoint`.
+///
+/// Also installs the W3C trace context propagator. Must be called from
+/// within a Tokio runtime (the gRPC exporter needs one).
+pub fn layer<S>(
+    endpoint: &str,
+    service: &'static str,
+) -> anyhow::Result<OpenTelemetryLayer<S, SdkTracer>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    let exporter = SpanExporter::builder().with_tonic().with_endpoint(endpoint).build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service).build())
+        .build();
+    let tracer = provider.tracer(service);
+    if PROVIDER.set(provider).is_err() {
+        anyhow::bail!("OTLP exporter already initialized");
+    }
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flush and stop the exporter. No-op when export is disabled.
+pub fn shutdown() {
+    if let Some(provider) = PROVIDER.get() {
+        if let Err(e) = provider.shutdown() {
+            tracing::warn!("failed to flush OTLP spans: {e}");
+        }
+    }
+}
+
+/// Extract the remote trace context carried by request headers.
+///
+/// Returns an empty context when no propagator is installed or the headers
+/// carry no `traceparent`.
+pub fn extract_context(headers: &HeaderMap) -> Context {
+    opentelemetry::global::get_text_map_propagator(|p| p.extract(&HeaderExtractor(headers)))
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "telemetry_tests.rs"]
+mod tests;
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use axum::http::{HeaderMap, HeaderValue};
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+
+use super::extract_context;
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+#[test]
+fn extracts_w3c_traceparent() -> anyhow::Result<()> {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let mut headers = HeaderMap::new();
+    headers.insert("traceparent", HeaderValue::from_static(TRACEPARENT));
+
+    let cx = extract_context(&headers);
+    let span = cx.span();
+    let sc = span.span_context();
+    assert!(sc.is_valid());
+    assert!(sc.is_remote());
+    assert_eq!(sc.trace_id().to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(sc.span_id().to_string(), "00f067aa0ba902b7");
+    Ok(())
+}
+
+#[yare::parameterized(
+    missing = { None },
+    malformed = { Some("not-a-traceparent") },
+    zero_trace_id = { Some("00-00000000000000000000000000000000-00f067aa0ba902b7-01") },
+)]
+fn ignores_invalid_traceparent(value: Option<&'static str>) {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let mut headers = HeaderMap::new();
+    if let Some(value) = value {
+        headers.insert("traceparent", HeaderValue::from_static(value));
+    }
+    assert!(!extract_context(&headers).span().span_context().is_valid());
+}
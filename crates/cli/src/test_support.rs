@@ -14,7 +14,9 @@ use bytes::Bytes;
 use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio_util::sync::CancellationToken;
 
+use crate::audit::AuditLog;
 use crate::backend::Backend;
+use crate::budget::{BudgetConfig, BudgetState};
 use crate::config::GroomLevel;
 use crate::driver::{
     AgentState, AgentType, Detector, DetectorEmission, ExitStatus, NudgeEncoder, NudgeStep,
@@ -24,6 +26,10 @@ use crate::event::{
     InputEvent, OutputEvent, PromptOutcome, RawHookEvent, RawMessageEvent, TransitionEvent,
 };
 use crate::event_log::EventLog;
+use crate::idle::{IdleConfig, IdleState};
+use crate::metrics::Metrics;
+use crate::nudge_queue::NudgeQueue;
+use crate::policy::RespondPolicy;
 use crate::profile::ProfileState;
 use crate::ring::RingBuffer;
 use crate::screen::Screen;
@@ -35,6 +41,7 @@ use crate::transport::state::{
     DetectionInfo, DriverState, LifecycleState, SessionSettings, Store, TerminalState,
     TransportChannels,
 };
+use crate::transport::tokens::ApiTokens;
 use crate::usage::UsageState;
 
 /// Test-only handle returned by [`StoreBuilder::build`], bundling the shared
@@ -51,14 +58,20 @@ pub struct StoreBuilder {
     ring_size: usize,
     child_pid: u32,
     auth_token: Option<String>,
+    api_tokens: Option<Arc<ApiTokens>>,
     agent_state: AgentState,
     nudge_encoder: Option<Arc<dyn NudgeEncoder>>,
     respond_encoder: Option<Arc<dyn RespondEncoder>>,
     stop_config: Option<StopConfig>,
     start_config: Option<StartConfig>,
+    idle_config: Option<IdleConfig>,
+    budget_config: Option<BudgetConfig>,
     transcript_state: Option<Arc<TranscriptState>>,
     groom: GroomLevel,
+    respond_policy: Option<Arc<RespondPolicy>>,
     session_dir: Option<PathBuf>,
+    event_log: Option<Arc<EventLog>>,
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 impl Default for StoreBuilder {
@@ -73,14 +86,20 @@ impl StoreBuilder {
             ring_size: 4096,
             child_pid: 0,
             auth_token: None,
+            api_tokens: None,
             agent_state: AgentState::Starting,
             nudge_encoder: None,
             respond_encoder: None,
             stop_config: None,
             start_config: None,
+            idle_config: None,
+            budget_config: None,
             transcript_state: None,
             groom: GroomLevel::Manual,
+            respond_policy: None,
             session_dir: None,
+            event_log: None,
+            audit_log: None,
         }
     }
 
@@ -99,6 +118,11 @@ impl StoreBuilder {
         self
     }
 
+    pub fn api_tokens(mut self, tokens: ApiTokens) -> Self {
+        self.api_tokens = Some(Arc::new(tokens));
+        self
+    }
+
     pub fn agent_state(mut self, s: AgentState) -> Self {
         self.agent_state = s;
         self
@@ -124,6 +148,16 @@ impl StoreBuilder {
         self
     }
 
+    pub fn idle_config(mut self, c: IdleConfig) -> Self {
+        self.idle_config = Some(c);
+        self
+    }
+
+    pub fn budget_config(mut self, c: BudgetConfig) -> Self {
+        self.budget_config = Some(c);
+        self
+    }
+
     pub fn transcript(mut self, t: Arc<TranscriptState>) -> Self {
         self.transcript_state = Some(t);
         self
@@ -134,11 +168,26 @@ impl StoreBuilder {
         self
     }
 
+    pub fn respond_policy(mut self, p: RespondPolicy) -> Self {
+        self.respond_policy = Some(Arc::new(p));
+        self
+    }
+
     pub fn session_dir(mut self, path: PathBuf) -> Self {
         self.session_dir = Some(path);
         self
     }
 
+    pub fn event_log(mut self, log: Arc<EventLog>) -> Self {
+        self.event_log = Some(log);
+        self
+    }
+
+    pub fn audit_log(mut self, log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(log);
+        self
+    }
+
     /// Build state and return a `StoreCtx` with all receiver handles.
     pub fn build(self) -> StoreCtx {
         let (input_tx, input_rx) = mpsc::channel(64);
@@ -178,10 +227,12 @@ impl StoreBuilder {
                 started_at: Instant::now(),
                 agent: AgentType::Unknown,
                 auth_token: self.auth_token,
+                api_tokens: self.api_tokens,
                 nudge_encoder: self.nudge_encoder,
                 respond_encoder: self.respond_encoder,
                 nudge_timeout: Duration::ZERO,
                 groom: self.groom,
+                respond_policy: self.respond_policy,
             },
             lifecycle: LifecycleState {
                 shutdown: CancellationToken::new(),
@@ -196,6 +247,8 @@ impl StoreBuilder {
                 "http://127.0.0.1:0/api/v1/stop/resolve".to_owned(),
             )),
             start: Arc::new(StartState::new(self.start_config.unwrap_or_default())),
+            idle: Arc::new(IdleState::new(self.idle_config.unwrap_or_default())),
+            nudge_queue: Arc::new(NudgeQueue::new(None)),
             switch: Arc::new(SwitchState {
                 switch_tx,
                 session_log_path: RwLock::new(None),
@@ -203,6 +256,7 @@ impl StoreBuilder {
                 mcp_config: None,
             }),
             usage: Arc::new(UsageState::new()),
+            budget: Arc::new(BudgetState::new(self.budget_config.unwrap_or_default())),
             profile: Arc::new(ProfileState::new()),
             transcript: self.transcript_state.unwrap_or_else(|| {
                 Arc::new({
@@ -213,8 +267,14 @@ impl StoreBuilder {
                 })
             }),
             input_activity: Arc::new(tokio::sync::Notify::new()),
-            event_log: Arc::new(EventLog::new(None)),
-            record: Arc::new(crate::record::RecordingState::new(None, 80, 24)),
+            event_log: self.event_log.unwrap_or_else(|| Arc::new(EventLog::new(None))),
+            audit_log: self.audit_log.unwrap_or_else(|| Arc::new(AuditLog::new(None))),
+            metrics: Arc::new(Metrics::default()),
+            record: Arc::new(crate::record::RecordingState::new(
+                self.session_dir.as_deref(),
+                80,
+                24,
+            )),
             session_dir: self.session_dir,
         });
 
@@ -427,7 +487,11 @@ pub async fn spawn_http_server(
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
     let addr = listener.local_addr()?;
     let handle = tokio::spawn(async move {
-        let _ = axum::serve(listener, router).await;
+        let _ = axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .await;
     });
     Ok((addr, handle))
 }
@@ -450,6 +514,11 @@ pub async fn spawn_uds_server(
             let svc_future = <_ as tower::Service<_>>::call(&mut make_svc, ());
             tokio::spawn(async move {
                 let Ok(svc) = svc_future.await;
+                let peer = crate::transport::auth::UnixPeer::of(&stream);
+                let svc = tower::ServiceExt::map_request(
+                    svc,
+                    crate::transport::auth::attach_unix_peer(peer),
+                );
                 let io = hyper_util::rt::TokioIo::new(stream);
                 let hyper_svc = hyper_util::service::TowerToHyperService::new(svc);
                 let _ = hyper_util::server::conn::auto::Builder::new(
@@ -6,18 +6,20 @@
 //! When Claude compacts its context window, the full conversation history
 //! would otherwise be lost. This module saves the JSONL session log as a
 //! numbered "transcript" before each compaction, creating a recoverable
-//! history that clients can list, fetch, and catch up from.
+//! history that clients can list, fetch, catch up from, and search.
 
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tokio::sync::broadcast;
 use tokio::sync::RwLock;
+use utoipa::ToSchema;
 
 /// Metadata for a single transcript snapshot.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TranscriptMeta {
     pub number: u32,
     pub timestamp: String,
@@ -35,7 +37,8 @@ pub struct TranscriptEvent {
 }
 
 /// Response from the catchup endpoint.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = TranscriptCatchupResponse)]
 pub struct CatchupResponse {
     pub transcripts: Vec<CatchupTranscript>,
     pub live_lines: Vec<String>,
@@ -44,13 +47,62 @@ pub struct CatchupResponse {
 }
 
 /// A transcript with its full line content (returned by catchup).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CatchupTranscript {
     pub number: u32,
     pub timestamp: String,
     pub lines: Vec<String>,
 }
 
+/// Default lines of context returned around each search match.
+pub const DEFAULT_SEARCH_CONTEXT: usize = 2;
+/// Default maximum number of search matches.
+pub const DEFAULT_SEARCH_LIMIT: usize = 100;
+
+const MAX_SEARCH_CONTEXT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 1000;
+
+/// Parameters for [`TranscriptState::search`].
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// Text to find (case-insensitive) in the entry's string values.
+    pub query: String,
+    /// Only match entries with this role (`user`, `assistant`, ...).
+    pub role: Option<String>,
+    /// Only match entries that call this tool (`Edit`, `Bash`, ...).
+    pub tool: Option<String>,
+    /// Lines of context before and after each match (at most 20).
+    pub context: usize,
+    /// Maximum number of matches (at most 1000).
+    pub limit: usize,
+}
+
+/// A single line matching a transcript search.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = TranscriptSearchMatch)]
+pub struct SearchMatch {
+    /// Transcript number, or `null` for the live session log.
+    pub transcript: Option<u32>,
+    /// 1-based line number within the transcript or live log.
+    pub line_number: u64,
+    /// Role of the matching entry, when it has one.
+    pub role: Option<String>,
+    pub line: String,
+    /// Up to `context` lines preceding the match.
+    pub before: Vec<String>,
+    /// Up to `context` lines following the match.
+    pub after: Vec<String>,
+}
+
+/// Response from the search endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = TranscriptSearchResponse)]
+pub struct SearchResponse {
+    pub matches: Vec<SearchMatch>,
+    /// Whether more matches exist beyond `limit`.
+    pub truncated: bool,
+}
+
 /// Runtime state for the transcript snapshot system.
 pub struct TranscriptState {
     transcripts_dir: PathBuf,
@@ -206,6 +258,78 @@ impl TranscriptState {
 
         Ok(CatchupResponse { transcripts, live_lines, current_transcript, current_line })
     }
+
+    /// Search all saved transcripts, oldest first, then the live session log.
+    /// Lines repeated from an earlier snapshot are only reported once.
+    pub async fn search(&self, opts: &SearchOptions) -> anyhow::Result<SearchResponse> {
+        let needle = opts.query.trim().to_lowercase();
+        if needle.is_empty() {
+            anyhow::bail!("search query must not be empty");
+        }
+        let context = opts.context.min(MAX_SEARCH_CONTEXT);
+        let limit = opts.limit.min(MAX_SEARCH_LIMIT);
+
+        let mut sources: Vec<(Option<u32>, PathBuf)> = self
+            .transcripts
+            .read()
+            .await
+            .iter()
+            .map(|m| (Some(m.number), self.transcripts_dir.join(format!("{}.jsonl", m.number))))
+            .collect();
+        if let Some(ref log_path) = self.session_log_path {
+            sources.push((None, log_path.clone()));
+        }
+
+        let mut matches = Vec::new();
+        let mut prev = String::new();
+        for (transcript, path) in sources {
+            let content = tokio::fs::read_to_string(&path).await.unwrap_or_default();
+            let lines: Vec<&str> = content.lines().collect();
+            // Each snapshot is a copy of the session log, so it usually starts
+            // with the previous one: search only the lines added since.
+            let prev_lines: Vec<&str> = prev.lines().collect();
+            let start = if lines.starts_with(&prev_lines) { prev_lines.len() } else { 0 };
+            for (i, line) in lines.iter().enumerate().skip(start) {
+                let entry: Option<Value> = serde_json::from_str(line).ok();
+                let role = entry.as_ref().and_then(entry_role);
+                if let Some(ref want) = opts.role {
+                    if !role.is_some_and(|r| r.eq_ignore_ascii_case(want)) {
+                        continue;
+                    }
+                }
+                if let Some(ref want) = opts.tool {
+                    let calls = entry.as_ref().map(tool_names).unwrap_or_default();
+                    if !calls.iter().any(|name| name.eq_ignore_ascii_case(want)) {
+                        continue;
+                    }
+                }
+                let found = match entry {
+                    Some(ref value) => contains_text(value, &needle),
+                    None => line.to_lowercase().contains(&needle),
+                };
+                if !found {
+                    continue;
+                }
+                if matches.len() == limit {
+                    return Ok(SearchResponse { matches, truncated: true });
+                }
+                let after_end = (i + 1 + context).min(lines.len());
+                matches.push(SearchMatch {
+                    transcript,
+                    line_number: i as u64 + 1,
+                    role: role.map(str::to_owned),
+                    line: (*line).to_owned(),
+                    before: lines[i.saturating_sub(context)..i]
+                        .iter()
+                        .map(|l| (*l).to_owned())
+                        .collect(),
+                    after: lines[i + 1..after_end].iter().map(|l| (*l).to_owned()).collect(),
+                });
+            }
+            prev = content;
+        }
+        Ok(SearchResponse { matches, truncated: false })
+    }
 }
 
 impl std::fmt::Debug for TranscriptState {
@@ -219,6 +343,37 @@ fn unix_timestamp_string(t: SystemTime) -> String {
     t.duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs().to_string()).unwrap_or_default()
 }
 
+/// Role of a session log entry: `message.role`, falling back to `type`.
+fn entry_role(entry: &Value) -> Option<&str> {
+    entry
+        .pointer("/message/role")
+        .or_else(|| entry.get("role"))
+        .or_else(|| entry.get("type"))
+        .and_then(Value::as_str)
+}
+
+/// Names of the tools called by a session log entry's `tool_use` blocks.
+fn tool_names(entry: &Value) -> Vec<&str> {
+    let Some(blocks) = entry.pointer("/message/content").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    blocks
+        .iter()
+        .filter(|b| b.get("type").and_then(Value::as_str) == Some("tool_use"))
+        .filter_map(|b| b.get("name").and_then(Value::as_str))
+        .collect()
+}
+
+/// Whether any string value in `value` contains `needle` (already lowercased).
+fn contains_text(value: &Value, needle: &str) -> bool {
+    match value {
+        Value::String(s) => s.to_lowercase().contains(needle),
+        Value::Array(items) => items.iter().any(|v| contains_text(v, needle)),
+        Value::Object(map) => map.values().any(|v| contains_text(v, needle)),
+        _ => false,
+    }
+}
+
 /// Count lines in a file (blocking I/O, call from spawn_blocking if needed).
 fn count_lines(path: &Path) -> std::io::Result<u64> {
     use std::io::BufRead;
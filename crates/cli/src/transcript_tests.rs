@@ -183,3 +183,105 @@ async fn broadcast_seq_increments() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn search_opts(query: &str) -> SearchOptions {
+    SearchOptions {
+        query: query.to_owned(),
+        role: None,
+        tool: None,
+        context: DEFAULT_SEARCH_CONTEXT,
+        limit: DEFAULT_SEARCH_LIMIT,
+    }
+}
+
+const SEARCH_LOG: &str = concat!(
+    r#"{"type":"user","message":{"role":"user","content":"Fix the login bug in auth.rs"}}"#,
+    "\n",
+    r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Read","input":{"file_path":"src/auth.rs"}}]}}"#,
+    "\n",
+    r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Edit","input":{"file_path":"src/Auth.rs"}}]}}"#,
+    "\n",
+    "not json: auth.rs\n",
+);
+
+#[tokio::test]
+async fn search_spans_transcripts_and_live_log() -> anyhow::Result<()> {
+    let (_tmp, transcripts_dir, log_path) = setup_dirs();
+    std::fs::write(&log_path, "{\"type\":\"user\",\"text\":\"old auth.rs\"}\n")?;
+    let state = TranscriptState::new(transcripts_dir, Some(log_path.clone()))?;
+    state.save_snapshot().await?;
+    std::fs::write(&log_path, SEARCH_LOG)?;
+
+    let resp = state.search(&search_opts("AUTH.RS")).await?;
+    let found: Vec<_> = resp.matches.iter().map(|m| (m.transcript, m.line_number)).collect();
+    assert_eq!(found, vec![(Some(1), 1), (None, 1), (None, 2), (None, 3), (None, 4)]);
+    assert!(!resp.truncated);
+
+    let edit = &resp.matches[3];
+    assert_eq!(edit.role.as_deref(), Some("assistant"));
+    assert_eq!(edit.before.len(), 2);
+    assert_eq!(edit.after, vec!["not json: auth.rs".to_owned()]);
+    assert!(resp.matches[4].role.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_reports_each_line_once_across_snapshots() -> anyhow::Result<()> {
+    let (_tmp, transcripts_dir, log_path) = setup_dirs();
+    let line = |n: u32| format!("{{\"type\":\"user\",\"text\":\"auth.rs step {n}\"}}\n");
+    std::fs::write(&log_path, line(1))?;
+    let state = TranscriptState::new(transcripts_dir, Some(log_path.clone()))?;
+    state.save_snapshot().await?;
+    std::fs::write(&log_path, line(1) + &line(2))?;
+    state.save_snapshot().await?;
+    std::fs::write(&log_path, line(1) + &line(2) + &line(3))?;
+
+    let resp = state.search(&search_opts("auth.rs")).await?;
+    let found: Vec<_> = resp.matches.iter().map(|m| (m.transcript, m.line_number)).collect();
+    assert_eq!(found, vec![(Some(1), 1), (Some(2), 2), (None, 3)]);
+    // Context still reaches back into the repeated lines.
+    assert_eq!(resp.matches[2].before, vec![line(1).trim_end(), line(2).trim_end()]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_filters_by_role_and_tool() -> anyhow::Result<()> {
+    let (_tmp, transcripts_dir, log_path) = setup_dirs();
+    std::fs::write(&log_path, SEARCH_LOG)?;
+    let state = TranscriptState::new(transcripts_dir, Some(log_path))?;
+
+    let by_role = state
+        .search(&SearchOptions { role: Some("user".to_owned()), ..search_opts("auth") })
+        .await?;
+    assert_eq!(by_role.matches.len(), 1);
+    assert_eq!(by_role.matches[0].line_number, 1);
+
+    let by_tool = state
+        .search(&SearchOptions { tool: Some("edit".to_owned()), ..search_opts("auth") })
+        .await?;
+    assert_eq!(by_tool.matches.len(), 1);
+    assert_eq!(by_tool.matches[0].line_number, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_limit_and_context() -> anyhow::Result<()> {
+    let (_tmp, transcripts_dir, log_path) = setup_dirs();
+    std::fs::write(&log_path, SEARCH_LOG)?;
+    let state = TranscriptState::new(transcripts_dir, Some(log_path))?;
+
+    let resp =
+        state.search(&SearchOptions { context: 0, limit: 2, ..search_opts("auth.rs") }).await?;
+    assert_eq!(resp.matches.len(), 2);
+    assert!(resp.truncated);
+    assert!(resp.matches[0].before.is_empty() && resp.matches[0].after.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_rejects_empty_query() -> anyhow::Result<()> {
+    let (_tmp, transcripts_dir, log_path) = setup_dirs();
+    let state = TranscriptState::new(transcripts_dir, Some(log_path))?;
+    assert!(state.search(&search_opts("  ")).await.is_err());
+    Ok(())
+}
@@ -1,15 +1,17 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 
-use axum::extract::State;
-use axum::http::{HeaderMap, Request, StatusCode};
+use axum::extract::{ConnectInfo, State};
+use axum::http::{Extensions, HeaderMap, Method, Request, StatusCode};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 
 use crate::error::ErrorCode;
-use crate::transport::state::Store;
+use crate::transport::state::{SessionSettings, Store};
+use crate::transport::tokens::{Grant, Scope};
 use crate::transport::ErrorResponse;
 
 /// Constant-time string comparison to prevent timing side-channel attacks.
@@ -79,23 +81,140 @@ pub fn validate_ws_auth(token: &str, expected: Option<&str>) -> Result<(), Error
     }
 }
 
+/// Whether any form of token auth is configured.
+pub fn auth_enabled(settings: &SessionSettings) -> bool {
+    settings.auth_token.is_some() || settings.api_tokens.is_some()
+}
+
+/// Resolve a presented token to a grant.
+///
+/// The single `--auth-token` grants every scope; named tokens from
+/// `--auth-tokens-file` grant their configured scopes.
+pub fn resolve_token(settings: &SessionSettings, token: &str) -> Option<Grant> {
+    if let Some(ref expected) = settings.auth_token {
+        if constant_time_eq(token, expected) {
+            return Some(Grant::admin("default"));
+        }
+    }
+    settings.api_tokens.as_ref().and_then(|tokens| tokens.resolve(token))
+}
+
+/// Authorize a presented token for `scope`.
+///
+/// Returns `Ok(None)` when auth is disabled, `Unauthorized` for a missing or
+/// unknown token and `Forbidden` when the token lacks the scope.
+pub fn authorize(
+    settings: &SessionSettings,
+    token: Option<&str>,
+    scope: Scope,
+) -> Result<Option<Grant>, ErrorCode> {
+    if !auth_enabled(settings) {
+        return Ok(None);
+    }
+    let grant = token.and_then(|t| resolve_token(settings, t)).ok_or(ErrorCode::Unauthorized)?;
+    if grant.allows(scope) {
+        Ok(Some(grant))
+    } else {
+        Err(ErrorCode::Forbidden)
+    }
+}
+
+/// Scope required by an HTTP route.
+pub fn route_scope(method: &Method, path: &str) -> Scope {
+    let write = method != Method::GET && method != Method::HEAD;
+    match path {
+        "/api/v1/input" | "/api/v1/input/raw" | "/api/v1/input/keys" | "/api/v1/resize"
+        | "/api/v1/upload" => Scope::Input,
+        "/api/v1/agent/nudge" | "/api/v1/agent/respond" | "/api/v1/stop/resolve" => Scope::Agent,
+        "/api/v1/config/stop" | "/api/v1/config/start" | "/api/v1/config/budget" if write => {
+            Scope::Agent
+        }
+        p if write && p.starts_with("/api/v1/agent/nudge/queue") => Scope::Agent,
+        "/api/v1/session/profiles" | "/api/v1/session/profiles/mode" if write => Scope::Credentials,
+        // Other callers' redacted payloads.
+        "/api/v1/audit/catchup" => Scope::Admin,
+        // Signals, shutdown, switch/restart, recording control and anything unknown.
+        _ if write => Scope::Admin,
+        _ => Scope::Read,
+    }
+}
+
+/// Peer identity of a Unix socket connection (`unix:pid=..,uid=..`),
+/// attached to each request served on it.
+#[derive(Debug, Clone)]
+pub struct UnixPeer(pub String);
+
+impl UnixPeer {
+    pub fn of(stream: &tokio::net::UnixStream) -> Self {
+        match stream.peer_cred() {
+            Ok(cred) => {
+                let pid = cred.pid().map(|p| p.to_string()).unwrap_or_else(|| "?".to_owned());
+                Self(format!("unix:pid={pid},uid={}", cred.uid()))
+            }
+            Err(_) => Self("unix".to_owned()),
+        }
+    }
+}
+
+/// Request mapper that attaches `peer` to every request on a Unix socket
+/// connection, for use with `tower::ServiceExt::map_request`.
+pub fn attach_unix_peer<B>(peer: UnixPeer) -> impl Fn(Request<B>) -> Request<B> + Clone {
+    move |mut req| {
+        req.extensions_mut().insert(peer.clone());
+        req
+    }
+}
+
+/// Identify the caller of a request for auditing: the token name when one
+/// was presented, otherwise the peer address.
+pub fn caller_identity(extensions: &Extensions) -> String {
+    if let Some(grant) = extensions.get::<Grant>() {
+        return grant.name.clone();
+    }
+    if let Some(peer) = extensions.get::<UnixPeer>() {
+        return peer.0.clone();
+    }
+    if let Some(ConnectInfo(addr)) = extensions.get::<ConnectInfo<SocketAddr>>() {
+        return addr.to_string();
+    }
+    "anonymous".to_owned()
+}
+
+/// Extract the Bearer token from HTTP headers, if any.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers.get("authorization")?.to_str().ok()?.strip_prefix("Bearer ")
+}
+
+/// Extract the `token` parameter from a query string, if any.
+fn query_token(query: &str) -> Option<&str> {
+    query.split('&').find_map(|pair| pair.strip_prefix("token="))
+}
+
 /// Axum middleware that enforces Bearer token authentication on all routes
 /// except `/api/v1/health` and WebSocket upgrades (`/ws`).
 ///
-/// When `auth_token` is `None` in `AppState`, all requests pass through.
+/// Each route requires the [`Scope`] given by [`route_scope`]: unknown
+/// tokens get 401, tokens lacking the scope get 403.
+///
+/// The SSE event stream also accepts `?token=`, since browser `EventSource`
+/// cannot set headers.
+///
+/// When no tokens are configured, all requests pass through.
 pub async fn auth_layer(
     state: State<Arc<Store>>,
-    req: Request<axum::body::Body>,
+    mut req: Request<axum::body::Body>,
     next: Next,
 ) -> Response {
-    let path = req.uri().path();
+    let path = req.uri().path().to_owned();
+    let path = path.as_str();
 
-    // Health, WebSocket, and hook endpoints skip HTTP auth.
+    // Health, OpenAPI, WebSocket, and hook endpoints skip HTTP auth.
     // WebSocket auth is handled in the WS handler via query param or Auth message.
     // Hook endpoints are called from inside the PTY (same machine, no token).
     if path == "/api/v1/health"
         || path == "/api/v1/livez"
         || path == "/api/v1/ready"
+        || path == "/api/v1/openapi.json"
         || path == "/ws"
         || path == "/api/v1/hooks/stop"
         || path == "/api/v1/stop/resolve"
@@ -104,13 +223,29 @@ pub async fn auth_layer(
         return next.run(req).await;
     }
 
-    if let Err(code) = validate_bearer(req.headers(), state.config.auth_token.as_deref()) {
-        let body = ErrorResponse { error: code.to_error_body("unauthorized") };
-        return (
-            StatusCode::from_u16(code.http_status()).unwrap_or(StatusCode::UNAUTHORIZED),
-            axum::Json(body),
-        )
-            .into_response();
+    let scope = route_scope(req.method(), path);
+    let mut token = bearer_token(req.headers());
+    if token.is_none() && path == "/api/v1/events/stream" {
+        token = query_token(req.uri().query().unwrap_or_default());
+    }
+
+    match authorize(&state.config, token, scope) {
+        Ok(Some(grant)) => {
+            req.extensions_mut().insert(grant);
+        }
+        Ok(None) => {}
+        Err(code) => {
+            let message = match code {
+                ErrorCode::Forbidden => format!("token lacks the {} scope", scope.as_str()),
+                _ => "unauthorized".to_owned(),
+            };
+            let body = ErrorResponse { error: code.to_error_body(message) };
+            return (
+                StatusCode::from_u16(code.http_status()).unwrap_or(StatusCode::UNAUTHORIZED),
+                axum::Json(body),
+            )
+                .into_response();
+        }
     }
 
     next.run(req).await
@@ -1,11 +1,15 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
-use axum::http::HeaderMap;
+use axum::body::Body;
+use axum::http::{HeaderMap, Method, Request, StatusCode};
+use tower::ServiceExt;
 
 use crate::error::ErrorCode;
-use crate::test_support::AnyhowExt;
-use crate::transport::auth::{validate_bearer, validate_ws_auth, validate_ws_query};
+use crate::test_support::{AnyhowExt, StoreBuilder, StoreCtx};
+use crate::transport::auth::{route_scope, validate_bearer, validate_ws_auth, validate_ws_query};
+use crate::transport::build_router;
+use crate::transport::tokens::{ApiToken, ApiTokens, Scope};
 
 #[yare::parameterized(
     no_token_allows_all = { None, None, true },
@@ -70,3 +74,57 @@ fn ws_auth_validation(
     }
     Ok(())
 }
+
+#[yare::parameterized(
+    screen          = { Method::GET, "/api/v1/screen", Scope::Read },
+    transcript      = { Method::GET, "/api/v1/transcripts/3", Scope::Read },
+    get_stop_config = { Method::GET, "/api/v1/config/stop", Scope::Read },
+    list_profiles   = { Method::GET, "/api/v1/session/profiles", Scope::Read },
+    input           = { Method::POST, "/api/v1/input", Scope::Input },
+    upload          = { Method::POST, "/api/v1/upload", Scope::Input },
+    nudge           = { Method::POST, "/api/v1/agent/nudge", Scope::Agent },
+    nudge_queue     = { Method::GET, "/api/v1/agent/nudge/queue", Scope::Read },
+    enqueue_nudge   = { Method::POST, "/api/v1/agent/nudge/queue", Scope::Agent },
+    cancel_nudge    = { Method::DELETE, "/api/v1/agent/nudge/queue/abc", Scope::Agent },
+    put_stop_config = { Method::PUT, "/api/v1/config/stop", Scope::Agent },
+    put_budget_config = { Method::PUT, "/api/v1/config/budget", Scope::Agent },
+    add_profiles    = { Method::POST, "/api/v1/session/profiles", Scope::Credentials },
+    profile_mode    = { Method::PUT, "/api/v1/session/profiles/mode", Scope::Credentials },
+    signal          = { Method::POST, "/api/v1/signal", Scope::Admin },
+    switch          = { Method::POST, "/api/v1/session/switch", Scope::Admin },
+    put_recording   = { Method::PUT, "/api/v1/recording", Scope::Admin },
+    unknown_write   = { Method::POST, "/api/v1/nope", Scope::Admin },
+)]
+fn route_scopes(method: Method, path: &str, expected: Scope) {
+    assert_eq!(route_scope(&method, path), expected);
+}
+
+#[tokio::test]
+async fn scoped_tokens_enforced_per_route() -> anyhow::Result<()> {
+    let tokens = ApiTokens {
+        tokens: vec![ApiToken {
+            name: "dashboard".to_owned(),
+            token: "read-only".to_owned(),
+            scopes: vec![Scope::Read],
+        }],
+    };
+    let StoreCtx { store, .. } = StoreBuilder::new().auth_token("root").api_tokens(tokens).build();
+    let app = build_router(store);
+
+    for (method, uri, token, expected) in [
+        (Method::GET, "/api/v1/status", "read-only", StatusCode::OK),
+        (Method::POST, "/api/v1/shutdown", "read-only", StatusCode::FORBIDDEN),
+        (Method::POST, "/api/v1/input", "read-only", StatusCode::FORBIDDEN),
+        (Method::GET, "/api/v1/status", "unknown", StatusCode::UNAUTHORIZED),
+        (Method::GET, "/api/v1/status", "root", StatusCode::OK),
+    ] {
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())?;
+        let resp = app.clone().oneshot(req).await?;
+        assert_eq!(resp.status(), expected, "{uri} with {token}");
+    }
+    Ok(())
+}
@@ -0,0 +1,291 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Shared subscription to the session's broadcast channels, used by both the
+//! `/ws` and SSE event streams so they forward the same set of events.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use base64::Engine;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+
+use crate::budget::BudgetEvent;
+use crate::event::{
+    OutputEvent, ProfileEvent, PromptOutcome, RawHookEvent, RawMessageEvent, TransitionEvent,
+};
+use crate::idle::IdleEvent;
+use crate::nudge_queue::NudgeQueueEvent;
+use crate::record::RecordingEntry;
+use crate::start::StartEvent;
+use crate::stop::StopEvent;
+use crate::transcript::TranscriptEvent;
+use crate::transport::read_ring_combined;
+use crate::transport::state::Store;
+use crate::transport::ws::{
+    budget_event_to_msg, idle_event_to_msg, nudge_queue_event_to_msg, profile_event_to_msg,
+    snapshot_to_msg, start_event_to_msg, stop_event_to_msg, transcript_event_to_msg,
+    transition_to_msg, usage_event_to_msg, ServerMessage, SubscriptionFlags,
+};
+use crate::usage::UsageEvent;
+
+/// An event received by [`EventFanout::recv`], already filtered by the
+/// subscription flags.
+pub enum Fanned {
+    /// A message ready to forward. `resumable` marks state transitions and
+    /// hook events, which advance [`EventFanout::state_seq`] / `hooks`.
+    Message { msg: Box<ServerMessage>, resumable: bool },
+    /// The screen changed; render it with [`EventFanout::resolve`].
+    Screen(u64),
+    /// PTY output lagged; replay it from the ring with [`EventFanout::resolve`].
+    Lagged,
+}
+
+/// Receivers for every broadcast channel a streaming client can subscribe to.
+pub struct EventFanout {
+    state: Arc<Store>,
+    flags: SubscriptionFlags,
+    output_rx: Receiver<OutputEvent>,
+    screen_rx: Receiver<u64>,
+    state_rx: Receiver<TransitionEvent>,
+    prompt_rx: Receiver<PromptOutcome>,
+    stop_rx: Receiver<StopEvent>,
+    start_rx: Receiver<StartEvent>,
+    idle_rx: Receiver<IdleEvent>,
+    nudge_queue_rx: Receiver<NudgeQueueEvent>,
+    hook_rx: Receiver<RawHookEvent>,
+    message_rx: Receiver<RawMessageEvent>,
+    transcript_rx: Receiver<TranscriptEvent>,
+    usage_rx: Receiver<UsageEvent>,
+    budget_rx: Receiver<BudgetEvent>,
+    record_rx: Receiver<RecordingEntry>,
+    profile_rx: Receiver<ProfileEvent>,
+    /// Next PTY byte offset to forward, for lag recovery via ring replay.
+    pub next_offset: u64,
+    /// Seq of the last state transition seen (the current seq at subscribe).
+    pub state_seq: u64,
+    /// Number of hook events seen, including ones lost to lag.
+    pub hooks: u64,
+    /// Seq of the last transition replayed from the event log. Live
+    /// transitions up to it were already sent and are skipped.
+    pub replayed_seq: Option<u64>,
+    /// Hook count covered by an event log replay. Live hook events below it
+    /// were already sent and are skipped.
+    pub replayed_hooks: Option<u64>,
+    /// Log index of the next live hook event.
+    live_hooks: u64,
+}
+
+impl EventFanout {
+    /// Subscribe to all channels. Call before sending any initial state so
+    /// no event published in between is missed.
+    pub async fn subscribe(state: Arc<Store>, flags: SubscriptionFlags) -> Self {
+        let next_offset =
+            if flags.pty { state.terminal.ring.read().await.total_written() } else { 0 };
+        Self {
+            flags,
+            output_rx: state.channels.output_tx.subscribe(),
+            screen_rx: state.channels.screen_tx.subscribe(),
+            state_rx: state.channels.state_tx.subscribe(),
+            prompt_rx: state.channels.prompt_tx.subscribe(),
+            stop_rx: state.stop.stop_tx.subscribe(),
+            start_rx: state.start.start_tx.subscribe(),
+            idle_rx: state.idle.idle_tx.subscribe(),
+            nudge_queue_rx: state.nudge_queue.queue_tx.subscribe(),
+            hook_rx: state.channels.hook_tx.subscribe(),
+            message_rx: state.channels.message_tx.subscribe(),
+            transcript_rx: state.transcript.transcript_tx.subscribe(),
+            usage_rx: state.usage.usage_tx.subscribe(),
+            budget_rx: state.budget.budget_tx.subscribe(),
+            record_rx: state.record.record_tx.subscribe(),
+            profile_rx: state.profile.profile_tx.subscribe(),
+            next_offset,
+            state_seq: state.driver.state_seq.load(Ordering::Acquire),
+            hooks: state.event_log.hook_count(),
+            replayed_seq: None,
+            replayed_hooks: None,
+            live_hooks: state.event_log.hook_count(),
+            state,
+        }
+    }
+
+    /// Wait for the next event the client is subscribed to. Returns `None`
+    /// once a channel closes. Cancel-safe: use it as a `select!` branch and
+    /// pass the result to [`resolve`](Self::resolve) outside the select.
+    pub async fn recv(&mut self) -> Option<Fanned> {
+        let flags = self.flags;
+        loop {
+            let msg = tokio::select! {
+                event = self.state_rx.recv() => match event {
+                    Ok(e) if self.replayed_seq.is_some_and(|seq| e.seq <= seq) => None,
+                    Ok(e) => {
+                        self.replayed_seq = None;
+                        self.state_seq = e.seq;
+                        if flags.state {
+                            return Some(Fanned::Message { msg: Box::new(transition_to_msg(&e)), resumable: true });
+                        }
+                        None
+                    }
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.hook_rx.recv() => match event {
+                    // Already sent by a replay.
+                    Ok(_) if self.fresh_hooks(1) == 0 => None,
+                    Ok(e) => {
+                        self.hooks += 1;
+                        if flags.hooks {
+                            let msg = Box::new(ServerMessage::HookRaw { data: e.json });
+                            return Some(Fanned::Message { msg, resumable: true });
+                        }
+                        None
+                    }
+                    Err(RecvError::Lagged(n)) => {
+                        self.hooks += self.fresh_hooks(n);
+                        None
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.prompt_rx.recv() => match event {
+                    Ok(e) => flags.state.then_some(ServerMessage::PromptOutcome {
+                        source: e.source,
+                        r#type: e.r#type,
+                        subtype: e.subtype,
+                        option: e.option,
+                        decision: e.decision,
+                        rule: e.rule,
+                    }),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.stop_rx.recv() => match event {
+                    Ok(e) => flags.state.then(|| stop_event_to_msg(&e)),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.start_rx.recv() => match event {
+                    Ok(e) => flags.state.then(|| start_event_to_msg(&e)),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.idle_rx.recv() => match event {
+                    Ok(e) => flags.state.then(|| idle_event_to_msg(&e)),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.nudge_queue_rx.recv() => match event {
+                    Ok(e) => flags.state.then(|| nudge_queue_event_to_msg(&e)),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.transcript_rx.recv() => match event {
+                    Ok(e) => flags.transcripts.then(|| transcript_event_to_msg(&e)),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.usage_rx.recv() => match event {
+                    Ok(e) => flags.usage.then(|| usage_event_to_msg(&e)),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.budget_rx.recv() => match event {
+                    Ok(e) => flags.usage.then(|| budget_event_to_msg(&e)),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.message_rx.recv() => match event {
+                    Ok(e) => flags
+                        .messages
+                        .then_some(ServerMessage::MessageRaw { data: e.json, source: e.source }),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.record_rx.recv() => match event {
+                    Ok(e) => flags.recording.then_some(ServerMessage::RecordingEntryMsg {
+                        ts: e.ts,
+                        seq: e.seq,
+                        kind: e.kind,
+                        detail: e.detail,
+                        screen: e.screen,
+                    }),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.profile_rx.recv() => match event {
+                    Ok(e) => flags.profiles.then(|| profile_event_to_msg(&e)),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                seq = self.screen_rx.recv() => match seq {
+                    Ok(seq) if flags.screen => return Some(Fanned::Screen(seq)),
+                    Ok(_) | Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+                event = self.output_rx.recv() => match event {
+                    Ok(OutputEvent::Raw { data, offset }) if flags.pty => {
+                        // Skip if already covered by a prior replay.
+                        if offset + data.len() as u64 <= self.next_offset {
+                            None
+                        } else {
+                            self.next_offset = offset + data.len() as u64;
+                            let data = base64::engine::general_purpose::STANDARD.encode(&data);
+                            Some(ServerMessage::Pty { data, offset })
+                        }
+                    }
+                    Ok(_) => None,
+                    Err(RecvError::Lagged(_)) if flags.pty => return Some(Fanned::Lagged),
+                    Err(RecvError::Lagged(_)) => None,
+                    Err(RecvError::Closed) => return None,
+                },
+            };
+            if let Some(msg) = msg {
+                return Some(Fanned::Message { msg: Box::new(msg), resumable: false });
+            }
+        }
+    }
+
+    /// Account for `n` live hook events and return how many of them were not
+    /// already sent by a replay.
+    fn fresh_hooks(&mut self, n: u64) -> u64 {
+        let start = self.live_hooks;
+        self.live_hooks += n;
+        self.live_hooks.saturating_sub(start.max(self.replayed_hooks.unwrap_or(0)))
+    }
+
+    /// Turn a received event into the message to forward, reading the
+    /// screen or ring buffer where needed.
+    pub async fn resolve(&mut self, fanned: Fanned) -> Option<ServerMessage> {
+        match fanned {
+            Fanned::Message { msg, .. } => Some(*msg),
+            Fanned::Screen(seq) => {
+                let snap = self.state.terminal.screen.read().await.snapshot();
+                Some(snapshot_to_msg(snap, seq))
+            }
+            Fanned::Lagged => {
+                let ring = self.state.terminal.ring.read().await;
+                let total_written = ring.total_written();
+                // If the ring has wrapped past next_offset, read from the oldest available.
+                let offset = self.next_offset.max(ring.oldest_offset());
+                let combined = read_ring_combined(&ring, offset);
+                drop(ring);
+                if combined.is_empty() {
+                    self.next_offset = total_written;
+                    return None;
+                }
+                self.next_offset = offset + combined.len() as u64;
+                let data = base64::engine::general_purpose::STANDARD.encode(&combined);
+                Some(ServerMessage::Replay {
+                    data,
+                    offset,
+                    next_offset: self.next_offset,
+                    total_written,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "fanout_tests.rs"]
+mod tests;
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{EventFanout, Fanned};
+use crate::driver::AgentState;
+use crate::event::{RawHookEvent, TransitionEvent};
+use crate::test_support::{StoreBuilder, StoreCtx};
+use crate::transport::ws::{ServerMessage, SubscriptionFlags};
+
+async fn next_message(fanout: &mut EventFanout) -> anyhow::Result<ServerMessage> {
+    let fanned = tokio::time::timeout(Duration::from_secs(5), fanout.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("fanout closed"))?;
+    match fanned {
+        Fanned::Message { msg, .. } => Ok(*msg),
+        _ => anyhow::bail!("expected a message"),
+    }
+}
+
+#[tokio::test]
+async fn skips_transitions_already_replayed() -> anyhow::Result<()> {
+    let StoreCtx { store, .. } = StoreBuilder::new().build();
+    let mut fanout =
+        EventFanout::subscribe(Arc::clone(&store), SubscriptionFlags::parse("state")).await;
+    fanout.state_seq = 5;
+    fanout.replayed_seq = Some(5);
+
+    for seq in [4, 5, 6] {
+        let _ = store.channels.state_tx.send(TransitionEvent {
+            prev: AgentState::Working,
+            next: AgentState::Idle,
+            seq,
+            cause: "hook".to_owned(),
+            last_message: None,
+        });
+    }
+
+    let msg = next_message(&mut fanout).await?;
+    assert!(matches!(msg, ServerMessage::Transition { seq: 6, .. }), "{msg:?}");
+    assert_eq!(fanout.state_seq, 6);
+    assert_eq!(fanout.replayed_seq, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn skips_hooks_already_replayed() -> anyhow::Result<()> {
+    let StoreCtx { store, .. } = StoreBuilder::new().build();
+    let mut fanout =
+        EventFanout::subscribe(Arc::clone(&store), SubscriptionFlags::parse("hooks")).await;
+    fanout.hooks = 2;
+    fanout.replayed_hooks = Some(2);
+
+    for event in ["first", "second", "third"] {
+        let _ =
+            store.channels.hook_tx.send(RawHookEvent { json: serde_json::json!({"event": event}) });
+    }
+
+    let msg = next_message(&mut fanout).await?;
+    assert!(matches!(&msg, ServerMessage::HookRaw { data } if data["event"] == "third"), "{msg:?}");
+    assert_eq!(fanout.hooks, 3);
+    Ok(())
+}
@@ -110,3 +110,95 @@ pub fn transition_to_proto(e: &TransitionEvent) -> proto::TransitionEvent {
         resume_at_epoch_ms,
     }
 }
+
+/// Convert a logged [`crate::event_log::TransitionEntry`] to proto.
+pub fn transition_entry_to_proto(e: crate::event_log::TransitionEntry) -> proto::StateLogEntry {
+    proto::StateLogEntry {
+        prev: e.prev,
+        next: e.next,
+        seq: e.seq,
+        cause: e.cause,
+        last_message: e.last_message,
+        timestamp_ms: e.timestamp_ms,
+    }
+}
+
+/// Convert a logged [`crate::audit::AuditEntry`] to proto.
+pub fn audit_entry_to_proto(e: crate::audit::AuditEntry) -> proto::AuditLogEntry {
+    proto::AuditLogEntry {
+        seq: e.seq,
+        timestamp_ms: e.timestamp_ms,
+        caller: e.caller,
+        transport: e.transport.as_str().to_owned(),
+        operation: e.operation,
+        payload_json: e.payload.to_string(),
+    }
+}
+
+/// Convert a [`crate::nudge_queue::QueuedNudge`] to proto.
+pub fn queued_nudge_to_proto(n: crate::nudge_queue::QueuedNudge) -> proto::QueuedNudge {
+    proto::QueuedNudge {
+        id: n.id,
+        message: n.message,
+        priority: n.priority,
+        not_before_ms: n.not_before_ms,
+        expires_at_ms: n.expires_at_ms,
+        dedup_key: n.dedup_key,
+        enqueued_at_ms: n.enqueued_at_ms,
+    }
+}
+
+/// Convert a [`crate::budget::BudgetStatus`] to proto.
+pub fn budget_status_to_proto(b: crate::budget::BudgetStatus) -> proto::BudgetStatus {
+    proto::BudgetStatus {
+        limits: b
+            .limits
+            .into_iter()
+            .map(|l| proto::BudgetLimitStatus {
+                limit: l.limit.as_str().to_owned(),
+                max: l.max,
+                used: l.used,
+                percent: l.percent,
+            })
+            .collect(),
+        action: b.action.as_str().to_owned(),
+        warned_percent: b.warned_percent,
+        exceeded: b.exceeded.map(|l| l.as_str().to_owned()),
+    }
+}
+
+/// Convert a per-model or per-profile usage breakdown to proto.
+pub fn breakdown_to_proto(
+    breakdown: &std::collections::BTreeMap<String, crate::usage::UsageCounters>,
+) -> std::collections::HashMap<String, proto::UsageCounters> {
+    breakdown
+        .iter()
+        .map(|(key, u)| {
+            let usage = proto::UsageCounters {
+                input_tokens: u.input_tokens,
+                output_tokens: u.output_tokens,
+                cache_read_tokens: u.cache_read_tokens,
+                cache_write_tokens: u.cache_write_tokens,
+                total_cost_usd: u.total_cost_usd,
+                estimated_cost_usd: u.estimated_cost_usd,
+                request_count: u.request_count,
+                total_api_ms: u.total_api_ms,
+            };
+            (key.clone(), usage)
+        })
+        .collect()
+}
+
+/// Convert a logged [`crate::event_log::HookEntry`] to proto.
+pub fn hook_entry_to_proto(e: crate::event_log::HookEntry) -> proto::HookLogEntry {
+    proto::HookLogEntry {
+        hook_seq: e.hook_seq,
+        json: e.json.to_string(),
+        timestamp_ms: e.timestamp_ms,
+    }
+}
+
+/// Convert a stop schema [`Violation`](crate::json_schema::Violation) to proto.
+pub fn violation_to_proto(v: crate::json_schema::Violation) -> proto::StopViolation {
+    proto::StopViolation { path: v.path, message: v.message }
+}
@@ -9,11 +9,18 @@ mod service;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use axum::http;
 use tokio::sync::{broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Status};
+use tower::layer::util::{Identity, Stack};
+use tower::util::MapRequestLayer;
 
+use crate::audit::AuditTransport;
+use crate::error::ErrorCode;
+use crate::transport::auth;
 use crate::transport::state::Store;
+use crate::transport::tokens::{Grant, Scope};
 
 /// Generated protobuf types for the `coop.v1` package.
 pub mod proto {
@@ -59,15 +66,28 @@ impl CoopGrpc {
         Self { state }
     }
 
+    /// Append a mutating RPC to the audit log. The caller is the token name
+    /// set by the auth interceptor, else the peer address.
+    fn audit<T: serde::Serialize>(&self, request: &Request<T>, operation: &str) {
+        let caller = match request.extensions().get::<Grant>() {
+            Some(grant) => grant.name.clone(),
+            None => request
+                .remote_addr()
+                .map_or_else(|| "anonymous".to_owned(), |addr| addr.to_string()),
+        };
+        let payload = serde_json::to_value(request.get_ref()).unwrap_or_default();
+        self.state.audit_log.record(&caller, AuditTransport::Grpc, operation, payload);
+    }
+
     /// Build a [`tonic`] router for this service.
     ///
-    /// When an auth token is configured, an interceptor validates Bearer
-    /// tokens on all RPCs except `GetHealth` and `GetReady`.
-    pub fn into_router(self) -> tonic::transport::server::Router {
-        let auth_token = self.state.config.auth_token.clone();
-        let mut server = tonic::transport::Server::builder();
-        if let Some(token) = auth_token {
-            let interceptor = GrpcAuthInterceptor { token };
+    /// When auth is configured, an interceptor validates Bearer tokens and
+    /// their scope (see [`rpc_scope`]) on every RPC.
+    pub fn into_router(self) -> GrpcRouter {
+        let mut server = tonic::transport::Server::builder()
+            .layer(MapRequestLayer::new(stash_grpc_path as StashGrpcPath));
+        if auth::auth_enabled(&self.state.config) {
+            let interceptor = GrpcAuthInterceptor { state: Arc::clone(&self.state) };
             server.add_service(proto::coop_server::CoopServer::with_interceptor(self, interceptor))
         } else {
             server.add_service(proto::coop_server::CoopServer::new(self))
@@ -75,18 +95,65 @@ impl CoopGrpc {
     }
 }
 
-/// gRPC interceptor that validates Bearer tokens on all RPCs.
+/// Router returned by [`CoopGrpc::into_router`].
+pub type GrpcRouter =
+    tonic::transport::server::Router<Stack<MapRequestLayer<StashGrpcPath>, Identity>>;
+
+type StashGrpcPath = fn(http::Request<tonic::body::Body>) -> http::Request<tonic::body::Body>;
+
+/// Request path (`/coop.v1.Coop/<Method>`), stashed in the request
+/// extensions because tonic interceptors don't see the URI.
+#[derive(Debug, Clone)]
+struct GrpcPath(String);
+
+fn stash_grpc_path(mut req: http::Request<tonic::body::Body>) -> http::Request<tonic::body::Body> {
+    let path = GrpcPath(req.uri().path().to_owned());
+    req.extensions_mut().insert(path);
+    req
+}
+
+/// Scope required by an RPC, mirroring the HTTP route it parallels.
+pub fn rpc_scope(method: &str) -> Scope {
+    match method {
+        "SendInput" | "SendInputRaw" | "SendKeys" | "Resize" | "Upload" => Scope::Input,
+        "Nudge" | "EnqueueNudge" | "CancelNudge" | "Respond" | "ResolveStop" | "PutStopConfig"
+        | "PutStartConfig" | "PutBudgetConfig" => Scope::Agent,
+        "RegisterProfiles" | "SetProfileMode" => Scope::Credentials,
+        "SendSignal" | "PutRecording" | "SwitchSession" | "RestartSession" | "Shutdown"
+        | "CatchupAudit" => Scope::Admin,
+        m if m.starts_with("Get")
+            || m.starts_with("List")
+            || m.starts_with("Stream")
+            || m.starts_with("Catchup")
+            || m.starts_with("Read")
+            || m.starts_with("Download")
+            || m.starts_with("Search") =>
+        {
+            Scope::Read
+        }
+        _ => Scope::Admin,
+    }
+}
+
+/// gRPC interceptor that validates Bearer tokens and scopes on all RPCs.
 ///
 /// Unlike the HTTP auth middleware which exempts health/ready probes,
 /// gRPC auth applies uniformly — gRPC clients are orchestration tools
 /// that always have the auth token available.
 #[derive(Clone)]
 struct GrpcAuthInterceptor {
-    token: String,
+    state: Arc<Store>,
 }
 
 impl tonic::service::Interceptor for GrpcAuthInterceptor {
-    fn call(&mut self, req: Request<()>) -> Result<Request<()>, Status> {
+    fn call(&mut self, mut req: Request<()>) -> Result<Request<()>, Status> {
+        let method = req
+            .extensions()
+            .get::<GrpcPath>()
+            .and_then(|p| p.0.rsplit('/').next())
+            .unwrap_or_default();
+        let scope = rpc_scope(method);
+
         let header = req
             .metadata()
             .get("authorization")
@@ -97,10 +164,16 @@ impl tonic::service::Interceptor for GrpcAuthInterceptor {
             .strip_prefix("Bearer ")
             .ok_or_else(|| Status::unauthenticated("invalid authorization scheme"))?;
 
-        if crate::transport::auth::constant_time_eq(bearer, &self.token) {
-            Ok(req)
-        } else {
-            Err(Status::unauthenticated("invalid token"))
+        match auth::authorize(&self.state.config, Some(bearer), scope) {
+            Ok(grant) => {
+                if let Some(grant) = grant {
+                    req.extensions_mut().insert(grant);
+                }
+                Ok(req)
+            }
+            Err(ErrorCode::Forbidden) => Err(ErrorCode::Forbidden
+                .to_grpc_status(format!("token lacks the {} scope", scope.as_str()))),
+            Err(_) => Err(Status::unauthenticated("invalid token")),
         }
     }
 }
@@ -110,6 +183,9 @@ type GrpcStream<T> = Pin<Box<dyn tokio_stream::Stream<Item = Result<T, Status>>
 #[cfg(test)]
 mod convert_tests;
 
+#[cfg(test)]
+mod parity_tests;
+
 #[cfg(test)]
 mod service_tests;
 
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::collections::BTreeSet;
+
+use utoipa::OpenApi;
+
+use crate::transport::auth::route_scope;
+use crate::transport::grpc::rpc_scope;
+use crate::transport::openapi::ApiDoc;
+
+/// gRPC counterpart of every HTTP route. `None` marks routes that are
+/// HTTP-only by design.
+const HTTP_TO_GRPC: &[(&str, &str, Option<&str>)] = &[
+    ("GET", "/api/v1/health", Some("GetHealth")),
+    ("GET", "/api/v1/ready", Some("GetReady")),
+    ("GET", "/api/v1/livez", Some("GetLivez")),
+    // Describes the HTTP API itself; gRPC clients use coop.proto.
+    ("GET", "/api/v1/openapi.json", None),
+    // Scraped by Prometheus over HTTP.
+    ("GET", "/metrics", None),
+    ("GET", "/api/v1/screen", Some("GetScreen")),
+    ("GET", "/api/v1/screen/text", Some("GetScreen")),
+    ("GET", "/api/v1/output", Some("ReadOutput")),
+    ("GET", "/api/v1/status", Some("GetStatus")),
+    ("POST", "/api/v1/input", Some("SendInput")),
+    ("POST", "/api/v1/input/raw", Some("SendInputRaw")),
+    ("POST", "/api/v1/input/keys", Some("SendKeys")),
+    ("POST", "/api/v1/resize", Some("Resize")),
+    ("POST", "/api/v1/signal", Some("SendSignal")),
+    ("POST", "/api/v1/upload", Some("Upload")),
+    ("GET", "/api/v1/agent", Some("GetAgent")),
+    ("POST", "/api/v1/agent/nudge", Some("Nudge")),
+    ("POST", "/api/v1/agent/nudge/queue", Some("EnqueueNudge")),
+    ("GET", "/api/v1/agent/nudge/queue", Some("ListNudgeQueue")),
+    ("DELETE", "/api/v1/agent/nudge/queue/{id}", Some("CancelNudge")),
+    ("POST", "/api/v1/agent/respond", Some("Respond")),
+    // Hook callbacks are posted by the agent's own hook scripts.
+    ("POST", "/api/v1/hooks/stop", None),
+    ("POST", "/api/v1/hooks/start", None),
+    ("POST", "/api/v1/stop/resolve", Some("ResolveStop")),
+    ("GET", "/api/v1/config/stop", Some("GetStopConfig")),
+    ("PUT", "/api/v1/config/stop", Some("PutStopConfig")),
+    ("GET", "/api/v1/config/budget", Some("GetBudgetConfig")),
+    ("PUT", "/api/v1/config/budget", Some("PutBudgetConfig")),
+    ("GET", "/api/v1/config/start", Some("GetStartConfig")),
+    ("PUT", "/api/v1/config/start", Some("PutStartConfig")),
+    ("GET", "/api/v1/transcripts", Some("ListTranscripts")),
+    ("GET", "/api/v1/transcripts/catchup", Some("CatchupTranscripts")),
+    ("GET", "/api/v1/transcripts/search", Some("SearchTranscripts")),
+    ("GET", "/api/v1/transcripts/{number}", Some("GetTranscript")),
+    ("GET", "/api/v1/events/catchup", Some("CatchupEvents")),
+    ("GET", "/api/v1/audit/catchup", Some("CatchupAudit")),
+    // SSE multiplexes the per-channel Stream* RPCs.
+    ("GET", "/api/v1/events/stream", Some("StreamAgent")),
+    ("GET", "/api/v1/recording", Some("GetRecording")),
+    ("PUT", "/api/v1/recording", Some("PutRecording")),
+    ("GET", "/api/v1/recording/catchup", Some("CatchupRecording")),
+    ("GET", "/api/v1/recording/download", Some("DownloadRecording")),
+    ("GET", "/api/v1/session/usage", Some("GetSessionUsage")),
+    ("POST", "/api/v1/session/profiles", Some("RegisterProfiles")),
+    ("GET", "/api/v1/session/profiles", Some("ListProfiles")),
+    ("GET", "/api/v1/session/profiles/mode", Some("GetProfileMode")),
+    ("PUT", "/api/v1/session/profiles/mode", Some("SetProfileMode")),
+    ("POST", "/api/v1/session/switch", Some("SwitchSession")),
+    ("POST", "/api/v1/session/restart", Some("RestartSession")),
+    ("POST", "/api/v1/shutdown", Some("Shutdown")),
+    // WebSocket multiplexes the per-channel Stream* RPCs.
+    ("GET", "/ws", Some("StreamOutput")),
+];
+
+/// `(METHOD, path)` pairs from the OpenAPI document, which
+/// `every_route_is_documented` keeps in sync with the router.
+fn http_routes() -> anyhow::Result<BTreeSet<(String, String)>> {
+    let doc = serde_json::to_value(ApiDoc::openapi())?;
+    let paths = doc["paths"].as_object().ok_or_else(|| anyhow::anyhow!("no paths"))?;
+    let mut routes = BTreeSet::new();
+    for (path, item) in paths {
+        for method in item.as_object().into_iter().flat_map(|m| m.keys()) {
+            if ["get", "post", "put", "delete", "patch"].contains(&method.as_str()) {
+                routes.insert((method.to_uppercase(), path.clone()));
+            }
+        }
+    }
+    Ok(routes)
+}
+
+fn grpc_methods() -> anyhow::Result<BTreeSet<String>> {
+    let proto = include_str!("../../../../../proto/coop/v1/coop.proto");
+    let rpc_re = regex::Regex::new(r"(?m)^\s*rpc\s+(\w+)\s*\(")?;
+    Ok(rpc_re.captures_iter(proto).map(|c| c[1].to_owned()).collect())
+}
+
+#[test]
+fn every_http_route_has_grpc_counterpart() -> anyhow::Result<()> {
+    let routes = http_routes()?;
+    assert!(routes.len() > 35, "route extraction looks broken: {routes:?}");
+    let mapped: BTreeSet<(String, String)> =
+        HTTP_TO_GRPC.iter().map(|(m, p, _)| ((*m).to_owned(), (*p).to_owned())).collect();
+
+    let missing: Vec<_> = routes.difference(&mapped).collect();
+    assert!(missing.is_empty(), "HTTP routes without a gRPC counterpart: {missing:?}");
+    let stale: Vec<_> = mapped.difference(&routes).collect();
+    assert!(stale.is_empty(), "HTTP_TO_GRPC lists unknown routes: {stale:?}");
+    Ok(())
+}
+
+#[test]
+fn mapped_rpcs_exist() -> anyhow::Result<()> {
+    let methods = grpc_methods()?;
+    assert!(methods.len() > 40, "rpc extraction looks broken: {methods:?}");
+    for (method, path, rpc) in HTTP_TO_GRPC {
+        if let Some(rpc) = rpc {
+            assert!(methods.contains(*rpc), "{method} {path} maps to unknown rpc {rpc}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn rpc_scopes_match_http_routes() -> anyhow::Result<()> {
+    for (method, path, rpc) in HTTP_TO_GRPC {
+        let Some(rpc) = rpc else { continue };
+        let method = axum::http::Method::from_bytes(method.as_bytes())?;
+        assert_eq!(
+            route_scope(&method, path),
+            rpc_scope(rpc),
+            "{method} {path} and {rpc} require different scopes"
+        );
+    }
+    Ok(())
+}
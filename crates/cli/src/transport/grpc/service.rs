@@ -6,25 +6,39 @@
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use tokio::io::AsyncReadExt;
 use tokio::sync::{broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
 use super::convert::{
-    profile_event_to_proto, prompt_to_proto, screen_snapshot_to_proto, screen_snapshot_to_response,
-    transition_to_proto,
+    audit_entry_to_proto, breakdown_to_proto, budget_status_to_proto, hook_entry_to_proto,
+    profile_event_to_proto, prompt_to_proto, queued_nudge_to_proto, screen_snapshot_to_proto,
+    screen_snapshot_to_response, transition_entry_to_proto, transition_to_proto,
+    violation_to_proto,
 };
 use super::{proto, spawn_broadcast_stream, CoopGrpc, GrpcStream};
+use crate::budget::BudgetConfig;
 use crate::error::ErrorCode;
 use crate::event::OutputEvent;
+use crate::nudge_queue::NewNudge;
+use crate::record::DownloadSource;
 use crate::start::StartConfig;
 use crate::stop::StopConfig;
+use crate::transcript::{SearchOptions, DEFAULT_SEARCH_CONTEXT, DEFAULT_SEARCH_LIMIT};
 use crate::transport::handler::{
-    compute_health, compute_status, error_message, extract_parked_fields, handle_input,
-    handle_input_raw, handle_keys, handle_nudge, handle_resize, handle_respond, handle_signal,
-    resolve_switch_profile, TransportQuestionAnswer,
+    compute_health, compute_status, error_message, extract_parked_fields, handle_enqueue_nudge,
+    handle_input, handle_input_raw, handle_keys, handle_nudge, handle_resize, handle_respond,
+    handle_signal, resolve_switch_profile, TransportQuestionAnswer,
 };
-use crate::transport::read_ring_combined;
+use crate::transport::http::save_upload;
+use crate::transport::{read_ring_combined, read_ring_range};
+
+/// Chunk size for streaming spilled output history.
+const SPILL_REPLAY_CHUNK: usize = 64 * 1024;
+
+/// Chunk size for streaming recording downloads.
+const DOWNLOAD_CHUNK: usize = 64 * 1024;
 
 #[tonic::async_trait]
 impl proto::coop_server::Coop for CoopGrpc {
@@ -56,6 +70,18 @@ impl proto::coop_server::Coop for CoopGrpc {
         Ok(Response::new(proto::GetReadyResponse { ready }))
     }
 
+    async fn get_livez(
+        &self,
+        _request: Request<proto::GetLivezRequest>,
+    ) -> Result<Response<proto::GetLivezResponse>, Status> {
+        let pid = self.state.terminal.child_pid.load(Ordering::Relaxed);
+        Ok(Response::new(proto::GetLivezResponse {
+            status: "alive".to_owned(),
+            pid: if pid == 0 { None } else { Some(pid as i32) },
+            uptime_secs: self.state.config.started_at.elapsed().as_secs() as i64,
+        }))
+    }
+
     async fn get_screen(
         &self,
         request: Request<proto::GetScreenRequest>,
@@ -93,6 +119,30 @@ impl proto::coop_server::Coop for CoopGrpc {
         let from_offset = request.into_inner().from_offset;
         let (tx, rx) = mpsc::channel(64);
 
+        // Replay history older than the in-memory window from the spill log
+        // in chunks, reading each outside the lock so output isn't stalled.
+        let mut from_offset = from_offset;
+        loop {
+            let ring = self.state.terminal.ring.read().await;
+            if from_offset >= ring.oldest_offset() {
+                break;
+            }
+            let read = ring.begin_read(from_offset, SPILL_REPLAY_CHUNK);
+            drop(ring);
+            let data = match read {
+                Some(read) => read.finish().await.unwrap_or_default(),
+                None => Vec::new(),
+            };
+            if data.is_empty() {
+                break;
+            }
+            let len = data.len() as u64;
+            if tx.send(Ok(proto::OutputChunk { data, offset: from_offset })).await.is_err() {
+                break;
+            }
+            from_offset += len;
+        }
+
         // Replay buffered data from ring buffer
         {
             let ring = self.state.terminal.ring.read().await;
@@ -128,6 +178,22 @@ impl proto::coop_server::Coop for CoopGrpc {
         Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
     }
 
+    async fn read_output(
+        &self,
+        request: Request<proto::ReadOutputRequest>,
+    ) -> Result<Response<proto::ReadOutputResponse>, Status> {
+        let req = request.into_inner();
+        let limit = req.limit.map(|n| usize::try_from(n).unwrap_or(usize::MAX));
+        let (offset, data, total_written) =
+            read_ring_range(&self.state.terminal.ring, req.offset, limit).await;
+        Ok(Response::new(proto::ReadOutputResponse {
+            next_offset: offset + data.len() as u64,
+            data,
+            offset,
+            total_written,
+        }))
+    }
+
     type StreamScreenStream = GrpcStream<proto::ScreenSnapshot>;
 
     async fn stream_screen(
@@ -163,8 +229,11 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::SendInputRequest>,
     ) -> Result<Response<proto::SendInputResponse>, Status> {
+        self.audit(&request, "SendInput");
         let req = request.into_inner();
-        let len = handle_input(&self.state, req.text, req.enter).await;
+        let len = handle_input(&self.state, req.text, req.enter)
+            .await
+            .map_err(|code| code.to_grpc_status(error_message(code)))?;
         Ok(Response::new(proto::SendInputResponse { bytes_written: len }))
     }
 
@@ -172,8 +241,11 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::SendInputRawRequest>,
     ) -> Result<Response<proto::SendInputRawResponse>, Status> {
+        self.audit(&request, "SendInputRaw");
         let req = request.into_inner();
-        let len = handle_input_raw(&self.state, req.data).await;
+        let len = handle_input_raw(&self.state, req.data)
+            .await
+            .map_err(|code| code.to_grpc_status(error_message(code)))?;
         Ok(Response::new(proto::SendInputRawResponse { bytes_written: len }))
     }
 
@@ -181,10 +253,11 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::SendKeysRequest>,
     ) -> Result<Response<proto::SendKeysResponse>, Status> {
+        self.audit(&request, "SendKeys");
         let req = request.into_inner();
-        let len = handle_keys(&self.state, &req.keys).await.map_err(|bad_key| {
-            ErrorCode::BadRequest.to_grpc_status(format!("unknown key: {bad_key}"))
-        })?;
+        let len = handle_keys(&self.state, &req.keys)
+            .await
+            .map_err(|(code, message)| code.to_grpc_status(message))?;
         Ok(Response::new(proto::SendKeysResponse { bytes_written: len }))
     }
 
@@ -192,6 +265,7 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::ResizeRequest>,
     ) -> Result<Response<proto::ResizeResponse>, Status> {
+        self.audit(&request, "Resize");
         let req = request.into_inner();
         let cols: u16 = req
             .cols
@@ -211,6 +285,7 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::SendSignalRequest>,
     ) -> Result<Response<proto::SendSignalResponse>, Status> {
+        self.audit(&request, "SendSignal");
         let req = request.into_inner();
         handle_signal(&self.state, &req.signal).await.map_err(|bad_signal| {
             ErrorCode::BadRequest.to_grpc_status(format!("unknown signal: {bad_signal}"))
@@ -218,6 +293,21 @@ impl proto::coop_server::Coop for CoopGrpc {
         Ok(Response::new(proto::SendSignalResponse { delivered: true }))
     }
 
+    async fn upload(
+        &self,
+        request: Request<proto::UploadRequest>,
+    ) -> Result<Response<proto::UploadResponse>, Status> {
+        self.audit(&request, "Upload");
+        let req = request.into_inner();
+        let resp = save_upload(&self.state, &req.filename, &req.data)
+            .await
+            .map_err(|(code, message)| code.to_grpc_status(message))?;
+        Ok(Response::new(proto::UploadResponse {
+            path: resp.path,
+            bytes_written: resp.bytes_written as u64,
+        }))
+    }
+
     // -- Agent ----------------------------------------------------------------
 
     async fn get_agent(
@@ -259,6 +349,7 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::NudgeRequest>,
     ) -> Result<Response<proto::NudgeResponse>, Status> {
+        self.audit(&request, "Nudge");
         let req = request.into_inner();
         match handle_nudge(&self.state, &req.message).await {
             Ok(outcome) => Ok(Response::new(proto::NudgeResponse {
@@ -270,10 +361,66 @@ impl proto::coop_server::Coop for CoopGrpc {
         }
     }
 
+    async fn enqueue_nudge(
+        &self,
+        request: Request<proto::EnqueueNudgeRequest>,
+    ) -> Result<Response<proto::QueuedNudge>, Status> {
+        self.audit(&request, "EnqueueNudge");
+        let req = request.into_inner();
+        let nudge = NewNudge {
+            message: req.message,
+            priority: req.priority,
+            not_before_ms: req.not_before_ms,
+            expires_at_ms: req.expires_at_ms,
+            dedup_key: req.dedup_key,
+        };
+        match handle_enqueue_nudge(&self.state, nudge) {
+            Ok(queued) => Ok(Response::new(queued_nudge_to_proto(queued))),
+            Err(code) => Err(code.to_grpc_status(error_message(code))),
+        }
+    }
+
+    async fn list_nudge_queue(
+        &self,
+        _request: Request<proto::ListNudgeQueueRequest>,
+    ) -> Result<Response<proto::ListNudgeQueueResponse>, Status> {
+        let nudges = self.state.nudge_queue.list().into_iter().map(queued_nudge_to_proto).collect();
+        Ok(Response::new(proto::ListNudgeQueueResponse { nudges }))
+    }
+
+    async fn cancel_nudge(
+        &self,
+        request: Request<proto::CancelNudgeRequest>,
+    ) -> Result<Response<proto::QueuedNudge>, Status> {
+        self.audit(&request, "CancelNudge");
+        let id = request.into_inner().id;
+        match self.state.nudge_queue.cancel(&id) {
+            Ok(nudge) => Ok(Response::new(queued_nudge_to_proto(nudge))),
+            Err((code, message)) => Err(code.to_grpc_status(message)),
+        }
+    }
+
+    type StreamNudgeQueueEventsStream = GrpcStream<proto::NudgeQueueEvent>;
+
+    async fn stream_nudge_queue_events(
+        &self,
+        _request: Request<proto::StreamNudgeQueueEventsRequest>,
+    ) -> Result<Response<Self::StreamNudgeQueueEventsStream>, Status> {
+        let queue_rx = self.state.nudge_queue.queue_tx.subscribe();
+        let stream = spawn_broadcast_stream(queue_rx, |event| {
+            Some(proto::NudgeQueueEvent {
+                kind: event.kind.as_str().to_owned(),
+                nudge: Some(queued_nudge_to_proto(event.nudge)),
+            })
+        });
+        Ok(Response::new(stream))
+    }
+
     async fn respond(
         &self,
         request: Request<proto::RespondRequest>,
     ) -> Result<Response<proto::RespondResponse>, Status> {
+        self.audit(&request, "Respond");
         let req = request.into_inner();
         let answers: Vec<TransportQuestionAnswer> = req
             .answers
@@ -316,6 +463,8 @@ impl proto::coop_server::Coop for CoopGrpc {
                 r#type: event.r#type,
                 subtype: event.subtype,
                 option: event.option,
+                decision: event.decision,
+                rule: event.rule,
             })
         });
         Ok(Response::new(stream))
@@ -349,6 +498,44 @@ impl proto::coop_server::Coop for CoopGrpc {
         Ok(Response::new(stream))
     }
 
+    // -- Event log ------------------------------------------------------------
+
+    async fn catchup_events(
+        &self,
+        request: Request<proto::CatchupEventsRequest>,
+    ) -> Result<Response<proto::CatchupEventsResponse>, Status> {
+        let req = request.into_inner();
+        let log = &self.state.event_log;
+        Ok(Response::new(proto::CatchupEventsResponse {
+            state_events: log
+                .catchup_state(req.since_seq)
+                .into_iter()
+                .map(transition_entry_to_proto)
+                .collect(),
+            hook_events: log
+                .catchup_hooks(req.since_hook_seq)
+                .into_iter()
+                .map(hook_entry_to_proto)
+                .collect(),
+        }))
+    }
+
+    async fn catchup_audit(
+        &self,
+        request: Request<proto::CatchupAuditRequest>,
+    ) -> Result<Response<proto::CatchupAuditResponse>, Status> {
+        let req = request.into_inner();
+        Ok(Response::new(proto::CatchupAuditResponse {
+            entries: self
+                .state
+                .audit_log
+                .catchup(req.since_seq)
+                .into_iter()
+                .map(audit_entry_to_proto)
+                .collect(),
+        }))
+    }
+
     // -- Transcripts ----------------------------------------------------------
 
     async fn list_transcripts(
@@ -409,6 +596,41 @@ impl proto::coop_server::Coop for CoopGrpc {
         }))
     }
 
+    async fn search_transcripts(
+        &self,
+        request: Request<proto::SearchTranscriptsRequest>,
+    ) -> Result<Response<proto::SearchTranscriptsResponse>, Status> {
+        let req = request.into_inner();
+        let opts = SearchOptions {
+            query: req.query,
+            role: req.role,
+            tool: req.tool,
+            context: req.context.map_or(DEFAULT_SEARCH_CONTEXT, |c| c as usize),
+            limit: req.limit.map_or(DEFAULT_SEARCH_LIMIT, |l| l as usize),
+        };
+        let resp = self
+            .state
+            .transcript
+            .search(&opts)
+            .await
+            .map_err(|e| Status::invalid_argument(format!("{e}")))?;
+        Ok(Response::new(proto::SearchTranscriptsResponse {
+            matches: resp
+                .matches
+                .into_iter()
+                .map(|m| proto::TranscriptSearchMatch {
+                    transcript: m.transcript,
+                    line_number: m.line_number,
+                    role: m.role,
+                    line: m.line,
+                    before: m.before,
+                    after: m.after,
+                })
+                .collect(),
+            truncated: resp.truncated,
+        }))
+    }
+
     type StreamTranscriptEventsStream = GrpcStream<proto::TranscriptEvent>;
 
     async fn stream_transcript_events(
@@ -443,9 +665,15 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::PutStopConfigRequest>,
     ) -> Result<Response<proto::PutStopConfigResponse>, Status> {
+        self.audit(&request, "PutStopConfig");
         let req = request.into_inner();
         let new_config: StopConfig = serde_json::from_str(&req.config_json)
             .map_err(|e| Status::invalid_argument(format!("invalid config JSON: {e}")))?;
+        if let Some(ref schema) = new_config.schema {
+            schema
+                .check()
+                .map_err(|e| Status::invalid_argument(format!("invalid stop schema: {e}")))?;
+        }
         *self.state.stop.config.write().await = new_config;
         Ok(Response::new(proto::PutStopConfigResponse { updated: true }))
     }
@@ -454,11 +682,18 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::ResolveStopRequest>,
     ) -> Result<Response<proto::ResolveStopResponse>, Status> {
+        self.audit(&request, "ResolveStop");
         let req = request.into_inner();
         let body: serde_json::Value = serde_json::from_str(&req.body_json)
             .map_err(|e| Status::invalid_argument(format!("invalid JSON: {e}")))?;
-        self.state.stop.resolve(body).await.map_err(Status::invalid_argument)?;
-        Ok(Response::new(proto::ResolveStopResponse { accepted: true }))
+        let violations = match self.state.stop.resolve(body).await {
+            Ok(()) => vec![],
+            Err(rejection) => rejection.violations.into_iter().map(violation_to_proto).collect(),
+        };
+        Ok(Response::new(proto::ResolveStopResponse {
+            accepted: violations.is_empty(),
+            violations,
+        }))
     }
 
     type StreamStopEventsStream = GrpcStream<proto::StopEvent>;
@@ -495,6 +730,7 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::PutStartConfigRequest>,
     ) -> Result<Response<proto::PutStartConfigResponse>, Status> {
+        self.audit(&request, "PutStartConfig");
         let req = request.into_inner();
         let new_config: StartConfig = serde_json::from_str(&req.config_json)
             .map_err(|e| Status::invalid_argument(format!("invalid config JSON: {e}")))?;
@@ -538,6 +774,7 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::PutRecordingRequest>,
     ) -> Result<Response<proto::PutRecordingResponse>, Status> {
+        self.audit(&request, "PutRecording");
         let req = request.into_inner();
         if req.enabled {
             self.state.record.enable().await;
@@ -589,6 +826,58 @@ impl proto::coop_server::Coop for CoopGrpc {
         Ok(Response::new(stream))
     }
 
+    type DownloadRecordingStream = GrpcStream<proto::RecordingChunk>;
+
+    async fn download_recording(
+        &self,
+        request: Request<proto::DownloadRecordingRequest>,
+    ) -> Result<Response<Self::DownloadRecordingStream>, Status> {
+        let format = request.into_inner().format;
+        let source = match format.as_str() {
+            "" | "jsonl" => self.state.record.download_source(false),
+            "asciicast" | "cast" => self.state.record.download_source(true),
+            other => {
+                return Err(ErrorCode::BadRequest
+                    .to_grpc_status(format!("unknown recording format: {other}")))
+            }
+        };
+        let not_found = || Status::not_found("no recording file available");
+        let path = match source.ok_or_else(not_found)? {
+            DownloadSource::File(path) => path,
+            DownloadSource::Memory(data) => {
+                let chunks: Vec<_> = data
+                    .chunks(DOWNLOAD_CHUNK)
+                    .map(|c| Ok(proto::RecordingChunk { data: c.to_vec() }))
+                    .collect();
+                return Ok(Response::new(Box::pin(tokio_stream::iter(chunks))));
+            }
+        };
+
+        // Read the file a chunk at a time so large recordings are never
+        // held in memory whole.
+        let mut file = tokio::fs::File::open(&path).await.map_err(|_| not_found())?;
+        let (tx, rx) = mpsc::channel(4);
+        tokio::spawn(async move {
+            loop {
+                let mut data = vec![0; DOWNLOAD_CHUNK];
+                match file.read(&mut data).await {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        data.truncate(n);
+                        if tx.send(Ok(proto::RecordingChunk { data })).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(Status::internal(format!("{e}")))).await;
+                        break;
+                    }
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+
     // -- Usage tracking -------------------------------------------------------
 
     async fn get_session_usage(
@@ -602,12 +891,37 @@ impl proto::coop_server::Coop for CoopGrpc {
             cache_read_tokens: snap.cache_read_tokens,
             cache_write_tokens: snap.cache_write_tokens,
             total_cost_usd: snap.total_cost_usd,
+            estimated_cost_usd: snap.estimated_cost_usd,
             request_count: snap.request_count,
             total_api_ms: snap.total_api_ms,
             uptime_secs: self.state.config.started_at.elapsed().as_secs() as i64,
+            budget: self.state.budget.status(&snap).map(budget_status_to_proto),
+            by_model: breakdown_to_proto(&snap.by_model),
+            by_profile: breakdown_to_proto(&snap.by_profile),
         }))
     }
 
+    async fn get_budget_config(
+        &self,
+        _request: Request<proto::GetBudgetConfigRequest>,
+    ) -> Result<Response<proto::GetBudgetConfigResponse>, Status> {
+        let json = serde_json::to_string(&self.state.budget.config())
+            .map_err(|e| Status::internal(format!("serialize error: {e}")))?;
+        Ok(Response::new(proto::GetBudgetConfigResponse { config_json: json }))
+    }
+
+    async fn put_budget_config(
+        &self,
+        request: Request<proto::PutBudgetConfigRequest>,
+    ) -> Result<Response<proto::PutBudgetConfigResponse>, Status> {
+        self.audit(&request, "PutBudgetConfig");
+        let req = request.into_inner();
+        let config: BudgetConfig = serde_json::from_str(&req.config_json)
+            .map_err(|e| Status::invalid_argument(format!("invalid config JSON: {e}")))?;
+        self.state.budget.set_config(config, &self.state.usage.snapshot().await);
+        Ok(Response::new(proto::PutBudgetConfigResponse { updated: true }))
+    }
+
     type StreamUsageEventsStream = GrpcStream<proto::UsageEvent>;
 
     async fn stream_usage_events(
@@ -623,9 +937,12 @@ impl proto::coop_server::Coop for CoopGrpc {
                 cache_read_tokens: snap.cache_read_tokens,
                 cache_write_tokens: snap.cache_write_tokens,
                 total_cost_usd: snap.total_cost_usd,
+                estimated_cost_usd: snap.estimated_cost_usd,
                 request_count: snap.request_count,
                 total_api_ms: snap.total_api_ms,
                 seq: event.seq,
+                by_model: breakdown_to_proto(&snap.by_model),
+                by_profile: breakdown_to_proto(&snap.by_profile),
             })
         });
         Ok(Response::new(stream))
@@ -637,6 +954,7 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::RegisterProfilesRequest>,
     ) -> Result<Response<proto::RegisterProfilesResponse>, Status> {
+        self.audit(&request, "RegisterProfiles");
         let req = request.into_inner();
         let entries: Vec<crate::profile::ProfileEntry> = req
             .profiles
@@ -681,6 +999,7 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::SetProfileModeRequest>,
     ) -> Result<Response<proto::ProfileModeResponse>, Status> {
+        self.audit(&request, "SetProfileMode");
         let req = request.into_inner();
         let mode: crate::profile::ProfileMode = req
             .mode
@@ -708,11 +1027,13 @@ impl proto::coop_server::Coop for CoopGrpc {
         &self,
         request: Request<proto::SwitchSessionRequest>,
     ) -> Result<Response<proto::SwitchSessionResponse>, Status> {
+        self.audit(&request, "SwitchSession");
         let req = request.into_inner();
         let mut switch_req = crate::switch::SwitchRequest {
             credentials: if req.credentials.is_empty() { None } else { Some(req.credentials) },
             force: req.force,
             profile: req.profile,
+            span: tracing::Span::current(),
         };
         resolve_switch_profile(&self.state, &mut switch_req)
             .await
@@ -732,9 +1053,15 @@ impl proto::coop_server::Coop for CoopGrpc {
 
     async fn restart_session(
         &self,
-        _request: Request<proto::RestartSessionRequest>,
+        request: Request<proto::RestartSessionRequest>,
     ) -> Result<Response<proto::RestartSessionResponse>, Status> {
-        let req = crate::switch::SwitchRequest { credentials: None, force: true, profile: None };
+        self.audit(&request, "RestartSession");
+        let req = crate::switch::SwitchRequest {
+            credentials: None,
+            force: true,
+            profile: None,
+            span: tracing::Span::current(),
+        };
         match self.state.switch.switch_tx.try_send(req) {
             Ok(()) => Ok(Response::new(proto::RestartSessionResponse { scheduled: true })),
             Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => {
@@ -748,8 +1075,9 @@ impl proto::coop_server::Coop for CoopGrpc {
 
     async fn shutdown(
         &self,
-        _request: Request<proto::ShutdownRequest>,
+        request: Request<proto::ShutdownRequest>,
     ) -> Result<Response<proto::ShutdownResponse>, Status> {
+        self.audit(&request, "Shutdown");
         self.state.lifecycle.shutdown.cancel();
         Ok(Response::new(proto::ShutdownResponse { accepted: true }))
     }
@@ -129,3 +129,160 @@ async fn get_ready_returns_readiness() -> anyhow::Result<()> {
     assert!(resp.into_inner().ready);
     Ok(())
 }
+
+#[tokio::test]
+async fn get_livez_reports_pid() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = StoreBuilder::new().child_pid(1234).build();
+    let svc = CoopGrpc::new(state);
+
+    let req = tonic::Request::new(proto::GetLivezRequest {});
+    let resp = proto::coop_server::Coop::get_livez(&svc, req).await?.into_inner();
+    assert_eq!(resp.status, "alive");
+    assert_eq!(resp.pid, Some(1234));
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_output_pages_with_limit() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = StoreBuilder::new().child_pid(1234).build();
+    state.terminal.ring.write().await.write(b"hello world");
+    let svc = CoopGrpc::new(state);
+
+    let req = tonic::Request::new(proto::ReadOutputRequest { offset: 0, limit: Some(5) });
+    let resp = proto::coop_server::Coop::read_output(&svc, req).await?.into_inner();
+    assert_eq!(resp.data, b"hello");
+    assert_eq!((resp.offset, resp.next_offset, resp.total_written), (0, 5, 11));
+
+    let req = tonic::Request::new(proto::ReadOutputRequest { offset: 5, limit: None });
+    let resp = proto::coop_server::Coop::read_output(&svc, req).await?.into_inner();
+    assert_eq!(resp.data, b" world");
+    assert_eq!(resp.next_offset, 11);
+    Ok(())
+}
+
+#[tokio::test]
+async fn upload_writes_file() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let StoreCtx { store: state, .. } =
+        StoreBuilder::new().session_dir(tmp.path().to_path_buf()).build();
+    let svc = CoopGrpc::new(state);
+
+    let req = tonic::Request::new(proto::UploadRequest {
+        filename: "../notes.txt".to_owned(),
+        data: b"hello".to_vec(),
+    });
+    let resp = proto::coop_server::Coop::upload(&svc, req).await?.into_inner();
+    assert_eq!(resp.bytes_written, 5);
+    assert!(resp.path.ends_with("uploads/notes.txt"), "{}", resp.path);
+    assert_eq!(std::fs::read(&resp.path)?, b"hello");
+    Ok(())
+}
+
+#[tokio::test]
+async fn upload_without_session_dir_is_invalid() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = StoreBuilder::new().build();
+    let svc = CoopGrpc::new(state);
+
+    let req = tonic::Request::new(proto::UploadRequest {
+        filename: "notes.txt".to_owned(),
+        data: b"hello".to_vec(),
+    });
+    let result = proto::coop_server::Coop::upload(&svc, req).await;
+    assert_eq!(result.err().map(|s| s.code()), Some(tonic::Code::InvalidArgument));
+    Ok(())
+}
+
+#[tokio::test]
+async fn catchup_events_returns_logged_events() -> anyhow::Result<()> {
+    use crate::driver::AgentState;
+    use crate::event::{RawHookEvent, TransitionEvent};
+    use crate::event_log::EventLog;
+
+    let tmp = tempfile::tempdir()?;
+    let log = Arc::new(EventLog::new(Some(tmp.path())));
+    for (seq, prev, next) in
+        [(1, AgentState::Starting, AgentState::Working), (2, AgentState::Working, AgentState::Idle)]
+    {
+        log.push_transition(&TransitionEvent {
+            prev,
+            next,
+            seq,
+            cause: "hook".to_owned(),
+            last_message: None,
+        });
+    }
+    log.push_hook(&RawHookEvent { json: serde_json::json!({"event": "first"}) });
+    log.push_hook(&RawHookEvent { json: serde_json::json!({"event": "second"}) });
+
+    let StoreCtx { store: state, .. } = StoreBuilder::new().event_log(log).build();
+    let svc = CoopGrpc::new(state);
+
+    let req = tonic::Request::new(proto::CatchupEventsRequest { since_seq: 1, since_hook_seq: 0 });
+    let resp = proto::coop_server::Coop::catchup_events(&svc, req).await?.into_inner();
+    assert_eq!(resp.state_events.len(), 1);
+    assert_eq!(resp.state_events[0].next, "idle");
+    assert_eq!(resp.hook_events.len(), 1);
+    let json: serde_json::Value = serde_json::from_str(&resp.hook_events[0].json)?;
+    assert_eq!(json["event"], "second");
+    Ok(())
+}
+
+#[tokio::test]
+async fn download_recording_errors() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = StoreBuilder::new().build();
+    let svc = CoopGrpc::new(state);
+
+    for (format, code) in [("", tonic::Code::NotFound), ("gif", tonic::Code::InvalidArgument)] {
+        let req =
+            tonic::Request::new(proto::DownloadRecordingRequest { format: format.to_owned() });
+        let result = proto::coop_server::Coop::download_recording(&svc, req).await;
+        assert_eq!(result.err().map(|s| s.code()), Some(code), "format {format:?}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn download_recording_streams_file_in_chunks() -> anyhow::Result<()> {
+    use tokio_stream::StreamExt;
+
+    let dir = tempfile::tempdir()?;
+    let StoreCtx { store: state, .. } =
+        StoreBuilder::new().session_dir(dir.path().to_path_buf()).build();
+    let contents: Vec<u8> = (0..150_000u32).map(|i| b'a' + (i % 26) as u8).collect();
+    std::fs::write(dir.path().join("recording.jsonl"), &contents)?;
+    let svc = CoopGrpc::new(state);
+
+    let req = tonic::Request::new(proto::DownloadRecordingRequest { format: String::new() });
+    let mut stream = proto::coop_server::Coop::download_recording(&svc, req).await?.into_inner();
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk?.data);
+    }
+    assert_eq!(chunks.iter().map(Vec::len).collect::<Vec<_>>(), [65_536, 65_536, 18_928]);
+    assert_eq!(chunks.concat(), contents);
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolve_stop_lists_violations() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = StoreBuilder::new().build();
+    *state.stop.config.write().await = serde_json::from_value(serde_json::json!({
+        "mode": "auto",
+        "schema": { "fields": { "status": { "required": true, "enum": ["done"] } } },
+    }))?;
+    let svc = CoopGrpc::new(state);
+
+    let body_json = serde_json::json!({"status": "bogus"}).to_string();
+    let req = tonic::Request::new(proto::ResolveStopRequest { body_json });
+    let resp = proto::coop_server::Coop::resolve_stop(&svc, req).await?.into_inner();
+    assert!(!resp.accepted);
+    assert_eq!(resp.violations.len(), 1);
+    assert_eq!(resp.violations[0].path, "/status");
+
+    let body_json = serde_json::json!({"status": "done"}).to_string();
+    let req = tonic::Request::new(proto::ResolveStopRequest { body_json });
+    let resp = proto::coop_server::Coop::resolve_stop(&svc, req).await?.into_inner();
+    assert!(resp.accepted);
+    assert!(resp.violations.is_empty());
+    Ok(())
+}
@@ -92,3 +92,34 @@ async fn catchup_transcripts_returns_data() -> anyhow::Result<()> {
     assert_eq!(inner.current_line, 2);
     Ok(())
 }
+
+#[tokio::test]
+async fn search_transcripts_returns_matches() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let ts_dir = tmp.path().join("transcripts");
+    let log = tmp.path().join("session.jsonl");
+    std::fs::write(&log, "{\"text\":\"edit auth.rs\"}\n")?;
+    let ts = std::sync::Arc::new(TranscriptState::new(ts_dir, Some(log.clone()))?);
+
+    ts.save_snapshot().await?;
+    std::fs::write(&log, "{\"text\":\"edit auth.rs\"}\n{\"text\":\"auth.rs done\"}\n")?;
+
+    let StoreCtx { store: state, .. } = StoreBuilder::new().child_pid(1234).transcript(ts).build();
+    let svc = CoopGrpc::new(state);
+
+    let req = tonic::Request::new(proto::SearchTranscriptsRequest {
+        query: "Auth.rs".to_owned(),
+        ..Default::default()
+    });
+    let resp = proto::coop_server::Coop::search_transcripts(&svc, req).await?;
+    let inner = resp.into_inner();
+
+    let found: Vec<_> = inner.matches.iter().map(|m| (m.transcript, m.line_number)).collect();
+    assert_eq!(found, vec![(Some(1), 1), (None, 2)]);
+    assert_eq!(inner.matches[1].before, vec!["{\"text\":\"edit auth.rs\"}".to_owned()]);
+
+    let req = tonic::Request::new(proto::SearchTranscriptsRequest::default());
+    let err = proto::coop_server::Coop::search_transcripts(&svc, req).await.err();
+    assert_eq!(err.map(|s| s.code()), Some(tonic::Code::InvalidArgument));
+    Ok(())
+}
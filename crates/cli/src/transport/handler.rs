@@ -8,15 +8,20 @@
 
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use utoipa::ToSchema;
 
 use crate::driver::AgentType;
 use crate::driver::{classify_error_detail, AgentState, QuestionAnswer};
 use crate::error::ErrorCode;
 use crate::event::InputEvent;
 use crate::event::PtySignal;
+use crate::metrics::NudgeResult;
+use crate::nudge_queue::{NewNudge, QueuedNudge};
 use crate::switch::SwitchRequest;
 use crate::transport::state::Store;
 use crate::transport::{
@@ -37,7 +42,7 @@ pub struct HealthInfo {
 }
 
 /// Session status result.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SessionStatus {
     pub session_id: String,
     pub state: String,
@@ -51,7 +56,7 @@ pub struct SessionStatus {
 }
 
 /// Nudge delivery result.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct NudgeOutcome {
     pub delivered: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -61,7 +66,7 @@ pub struct NudgeOutcome {
 }
 
 /// Respond delivery result.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RespondOutcome {
     pub delivered: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -71,7 +76,7 @@ pub struct RespondOutcome {
 }
 
 /// Transport-agnostic question answer (shared across HTTP, WS, gRPC).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TransportQuestionAnswer {
     pub option: Option<i32>,
     pub text: Option<String>,
@@ -103,6 +108,7 @@ pub fn error_message(code: ErrorCode) -> &'static str {
     match code {
         ErrorCode::NotReady => "agent is still starting",
         ErrorCode::NoDriver => "no agent driver configured",
+        ErrorCode::BudgetExceeded => "budget exceeded; input is blocked until the limit is raised",
         _ => "request failed",
     }
 }
@@ -197,7 +203,21 @@ pub async fn compute_status(state: &Store) -> SessionStatus {
 ///
 /// Returns `Err` only for genuine errors (not ready, no driver).
 /// Agent-busy is a soft failure returned as `Ok(NudgeOutcome { delivered: false })`.
+#[tracing::instrument(name = "nudge", skip_all, fields(result = tracing::field::Empty))]
 pub async fn handle_nudge(state: &Store, message: &str) -> Result<NudgeOutcome, ErrorCode> {
+    let started = Instant::now();
+    let result = deliver_nudge(state, message).await;
+    let outcome = match result {
+        Ok(ref o) if o.delivered => NudgeResult::Delivered,
+        Ok(_) => NudgeResult::Rejected,
+        Err(_) => NudgeResult::Error,
+    };
+    tracing::Span::current().record("result", outcome.as_str());
+    state.metrics.record_nudge(outcome, started.elapsed());
+    result
+}
+
+async fn deliver_nudge(state: &Store, message: &str) -> Result<NudgeOutcome, ErrorCode> {
     if !state.ready.load(Ordering::Acquire) {
         return Err(ErrorCode::NotReady);
     }
@@ -211,7 +231,8 @@ pub async fn handle_nudge(state: &Store, message: &str) -> Result<NudgeOutcome,
     // the Working transition that confirms Enter was processed.
     let state_rx = state.channels.state_tx.subscribe();
 
-    let mut _delivery = state.input_gate.acquire().await;
+    let mut _delivery =
+        state.input_gate.acquire().instrument(tracing::info_span!("input_gate.wait")).await;
 
     let agent = state.driver.agent_state.read().await;
     let state_before = agent.as_str().to_owned();
@@ -280,6 +301,7 @@ pub async fn handle_nudge(state: &Store, message: &str) -> Result<NudgeOutcome,
             state_rx,
             Arc::clone(&state.input_activity),
             nudge_timeout,
+            Arc::clone(&state.metrics),
         );
         _delivery.set_retry_cancel(cancel);
     }
@@ -287,10 +309,22 @@ pub async fn handle_nudge(state: &Store, message: &str) -> Result<NudgeOutcome,
     Ok(NudgeOutcome { delivered: true, state_before: Some(state_before), reason: None })
 }
 
+/// Queue a nudge for delivery the next time the agent is idle.
+///
+/// Unlike [`handle_nudge`] this works before the agent is ready; it only
+/// fails when there is no driver to deliver with.
+pub fn handle_enqueue_nudge(state: &Store, nudge: NewNudge) -> Result<QueuedNudge, ErrorCode> {
+    if state.config.nudge_encoder.is_none() {
+        return Err(ErrorCode::NoDriver);
+    }
+    Ok(state.nudge_queue.enqueue(nudge))
+}
+
 /// Respond to an active prompt.
 ///
 /// Returns `Err` only for genuine errors (not ready, no driver).
 /// No-prompt is a soft failure returned as `Ok(RespondOutcome { delivered: false })`.
+#[tracing::instrument(name = "respond", skip_all, fields(delivered = tracing::field::Empty))]
 pub async fn handle_respond(
     state: &Store,
     accept: Option<bool>,
@@ -307,10 +341,20 @@ pub async fn handle_respond(
         None => return Err(ErrorCode::NoDriver),
     };
 
+    // Input is dropped while an exceeded budget keeps the agent parked.
+    if state.budget.keeps_parked() {
+        return Ok(RespondOutcome {
+            delivered: false,
+            prompt_type: None,
+            reason: Some("budget exceeded".to_owned()),
+        });
+    }
+
     let domain_answers = to_domain_answers(answers);
     let resolved_option = option.map(|o| o as u32);
 
-    let _delivery = state.input_gate.acquire().await;
+    let _delivery =
+        state.input_gate.acquire().instrument(tracing::info_span!("input_gate.wait")).await;
 
     let agent = state.driver.agent_state.read().await;
     let prompt_type = agent.prompt().map(|p| p.kind.as_str().to_owned());
@@ -326,6 +370,7 @@ pub async fn handle_respond(
     ) {
         Ok(r) => r,
         Err(_code) => {
+            tracing::Span::current().record("delivered", false);
             return Ok(RespondOutcome {
                 delivered: false,
                 prompt_type: None,
@@ -347,34 +392,50 @@ pub async fn handle_respond(
         r#type: prompt_type.clone().unwrap_or_default(),
         subtype: prompt_subtype,
         option: resolved_option,
+        ..Default::default()
     });
 
+    tracing::Span::current().record("delivered", true);
     Ok(RespondOutcome { delivered: true, prompt_type, reason: None })
 }
 
+/// Refuse PTY input while an exceeded budget keeps the agent parked; the
+/// session loop would drop it anyway.
+fn check_budget(state: &Store) -> Result<(), ErrorCode> {
+    if state.budget.keeps_parked() {
+        return Err(ErrorCode::BudgetExceeded);
+    }
+    Ok(())
+}
+
 /// Write text to the PTY, optionally followed by a carriage return.
-pub async fn handle_input(state: &Store, text: String, enter: bool) -> i32 {
+pub async fn handle_input(state: &Store, text: String, enter: bool) -> Result<i32, ErrorCode> {
+    check_budget(state)?;
     let mut data = text.into_bytes();
     if enter {
         data.push(b'\r');
     }
     let len = data.len() as i32;
     let _ = state.channels.input_tx.send(InputEvent::Write(Bytes::from(data))).await;
-    len
+    Ok(len)
 }
 
 /// Write raw bytes to the PTY.
-pub async fn handle_input_raw(state: &Store, data: Vec<u8>) -> i32 {
+pub async fn handle_input_raw(state: &Store, data: Vec<u8>) -> Result<i32, ErrorCode> {
+    check_budget(state)?;
     let len = data.len() as i32;
     let _ = state.channels.input_tx.send(InputEvent::Write(Bytes::from(data))).await;
-    len
+    Ok(len)
 }
 
 /// Send named key sequences to the PTY.
 ///
-/// Returns the byte count on success, or the unrecognised key name on failure.
-pub async fn handle_keys(state: &Store, keys: &[String]) -> Result<i32, String> {
-    let data = keys_to_bytes(keys)?;
+/// Returns the byte count on success, or the error code and message on failure
+/// (`BAD_REQUEST` naming an unrecognised key, or `BUDGET_EXCEEDED`).
+pub async fn handle_keys(state: &Store, keys: &[String]) -> Result<i32, (ErrorCode, String)> {
+    check_budget(state).map_err(|code| (code, error_message(code).to_owned()))?;
+    let data = keys_to_bytes(keys)
+        .map_err(|bad| (ErrorCode::BadRequest, format!("unknown key: {bad}")))?;
     let len = data.len() as i32;
     let _ = state.channels.input_tx.send(InputEvent::Write(Bytes::from(data))).await;
     Ok(len)
@@ -3,7 +3,9 @@
 
 use std::sync::Arc;
 
+use crate::budget::{BudgetAction, BudgetConfig};
 use crate::driver::{AgentState, ExitStatus};
+use crate::error::ErrorCode;
 use crate::event::InputEvent;
 use crate::test_support::{StoreBuilder, StoreCtx, StubNudgeEncoder, StubRespondEncoder};
 use crate::transport::handler::{
@@ -11,6 +13,7 @@ use crate::transport::handler::{
     handle_resize, handle_respond, handle_signal, session_state_str, to_domain_answers,
     TransportQuestionAnswer,
 };
+use crate::usage::SessionUsage;
 
 #[test]
 fn session_state_exited() {
@@ -296,7 +299,9 @@ async fn respond_no_prompt_returns_soft_failure() -> anyhow::Result<()> {
 #[tokio::test]
 async fn input_writes_text() -> anyhow::Result<()> {
     let StoreCtx { store: state, mut input_rx, .. } = StoreBuilder::new().build();
-    let len = handle_input(&state, "hello".to_owned(), false).await;
+    let len = handle_input(&state, "hello".to_owned(), false)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
     assert_eq!(len, 5);
     let event = input_rx.recv().await;
     assert!(matches!(event, Some(InputEvent::Write(data)) if data == &b"hello"[..]));
@@ -306,7 +311,8 @@ async fn input_writes_text() -> anyhow::Result<()> {
 #[tokio::test]
 async fn input_with_enter_appends_cr() -> anyhow::Result<()> {
     let StoreCtx { store: state, mut input_rx, .. } = StoreBuilder::new().build();
-    let len = handle_input(&state, "hi".to_owned(), true).await;
+    let len =
+        handle_input(&state, "hi".to_owned(), true).await.map_err(|e| anyhow::anyhow!("{e}"))?;
     assert_eq!(len, 3); // "hi\r"
     let event = input_rx.recv().await;
     assert!(matches!(event, Some(InputEvent::Write(data)) if data == &b"hi\r"[..]));
@@ -316,7 +322,9 @@ async fn input_with_enter_appends_cr() -> anyhow::Result<()> {
 #[tokio::test]
 async fn input_raw_writes_bytes() -> anyhow::Result<()> {
     let StoreCtx { store: state, mut input_rx, .. } = StoreBuilder::new().build();
-    let len = handle_input_raw(&state, vec![0x1b, 0x5b, 0x41]).await;
+    let len = handle_input_raw(&state, vec![0x1b, 0x5b, 0x41])
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
     assert_eq!(len, 3);
     let event = input_rx.recv().await;
     assert!(matches!(event, Some(InputEvent::Write(data)) if data == &[0x1b, 0x5b, 0x41][..]));
@@ -328,7 +336,7 @@ async fn keys_valid() -> anyhow::Result<()> {
     let StoreCtx { store: state, mut input_rx, .. } = StoreBuilder::new().build();
     let len = handle_keys(&state, &["Enter".to_owned(), "Tab".to_owned()])
         .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+        .map_err(|(_, message)| anyhow::anyhow!(message))?;
     assert_eq!(len, 2); // \r + \t
     let event = input_rx.recv().await;
     assert!(matches!(event, Some(InputEvent::Write(_))));
@@ -339,7 +347,28 @@ async fn keys_valid() -> anyhow::Result<()> {
 async fn keys_invalid_returns_error() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = StoreBuilder::new().build();
     let result = handle_keys(&state, &["SuperKey".to_owned()]).await;
-    assert_eq!(result.unwrap_err(), "SuperKey");
+    assert_eq!(result.unwrap_err(), (ErrorCode::BadRequest, "unknown key: SuperKey".to_owned()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn input_refused_while_budget_parks_the_agent() -> anyhow::Result<()> {
+    let StoreCtx { store: state, mut input_rx, .. } = StoreBuilder::new()
+        .budget_config(BudgetConfig {
+            max_output_tokens: Some(100),
+            action: BudgetAction::Park,
+            ..Default::default()
+        })
+        .build();
+    state.budget.observe(&SessionUsage { output_tokens: 150, ..Default::default() });
+
+    let text = handle_input(&state, "hi".to_owned(), true).await;
+    assert_eq!(text, Err(ErrorCode::BudgetExceeded));
+    let raw = handle_input_raw(&state, b"hi".to_vec()).await;
+    assert_eq!(raw, Err(ErrorCode::BudgetExceeded));
+    let keys = handle_keys(&state, &["Enter".to_owned()]).await;
+    assert!(matches!(keys, Err((ErrorCode::BudgetExceeded, _))));
+    assert!(input_rx.try_recv().is_err());
     Ok(())
 }
 
@@ -4,8 +4,10 @@
 //! HTTP request/response types and axum handler implementations.
 
 mod agent;
+mod audit;
 mod events;
 mod hooks;
+mod metrics;
 mod record;
 mod screen;
 mod switch;
@@ -14,8 +16,10 @@ mod upload;
 mod usage;
 
 pub use agent::*;
+pub use audit::*;
 pub use events::*;
 pub use hooks::*;
+pub use metrics::*;
 pub use record::*;
 pub use screen::*;
 pub use switch::*;
@@ -28,14 +32,37 @@ use std::sync::Arc;
 use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::transport::state::Store;
+use crate::transport::ErrorResponse;
+
+/// Acknowledgement body (`{"accepted": true}`).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AcceptedResponse {
+    pub accepted: bool,
+}
 
 // -- Lifecycle ----------------------------------------------------------------
 
 /// `POST /api/v1/session/restart` — kill and respawn the agent process (202 Accepted).
+#[utoipa::path(
+    post,
+    path = "/api/v1/session/restart",
+    tag = "session",
+    responses(
+        (status = 202, description = "Restart scheduled"),
+        (status = 409, description = "A switch is already in progress", body = ErrorResponse),
+    )
+)]
 pub async fn restart_session(State(s): State<Arc<Store>>) -> impl IntoResponse {
-    let req = crate::switch::SwitchRequest { credentials: None, force: true, profile: None };
+    let req = crate::switch::SwitchRequest {
+        credentials: None,
+        force: true,
+        profile: None,
+        span: tracing::Span::current(),
+    };
     match s.switch.switch_tx.try_send(req) {
         Ok(()) => axum::http::StatusCode::ACCEPTED.into_response(),
         Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => {
@@ -50,9 +77,15 @@ pub async fn restart_session(State(s): State<Arc<Store>>) -> impl IntoResponse {
 }
 
 /// `POST /api/v1/shutdown` — initiate graceful coop shutdown.
+#[utoipa::path(
+    post,
+    path = "/api/v1/shutdown",
+    tag = "lifecycle",
+    responses((status = 200, body = AcceptedResponse))
+)]
 pub async fn shutdown(State(s): State<Arc<Store>>) -> impl IntoResponse {
     s.lifecycle.shutdown.cancel();
-    Json(serde_json::json!({ "accepted": true }))
+    Json(AcceptedResponse { accepted: true })
 }
 
 #[cfg(test)]
@@ -67,6 +100,9 @@ mod hooks_tests;
 #[cfg(test)]
 mod transcript_tests;
 
+#[cfg(test)]
+mod events_tests;
+
 #[cfg(test)]
 mod profile_tests;
 
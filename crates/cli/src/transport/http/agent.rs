@@ -10,16 +10,20 @@ use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::driver::PromptContext;
+use crate::nudge_queue::{NewNudge, QueuedNudge};
 use crate::transport::handler::{
-    error_message, extract_parked_fields, handle_nudge, handle_respond, TransportQuestionAnswer,
+    error_message, extract_parked_fields, handle_enqueue_nudge, handle_nudge, handle_respond,
+    NudgeOutcome, RespondOutcome, TransportQuestionAnswer,
 };
 use crate::transport::state::Store;
+use crate::transport::ErrorResponse;
 
 // -- Types --------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AgentResponse {
     pub agent: String,
     pub session_id: String,
@@ -41,12 +45,18 @@ pub struct AgentResponse {
     pub last_message: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct NudgeRequest {
     pub message: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NudgeQueueResponse {
+    /// Pending nudges in delivery order.
+    pub nudges: Vec<QueuedNudge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RespondRequest {
     pub accept: Option<bool>,
     pub text: Option<String>,
@@ -58,6 +68,12 @@ pub struct RespondRequest {
 // -- Handlers -----------------------------------------------------------------
 
 /// `GET /api/v1/agent`
+#[utoipa::path(
+    get,
+    path = "/api/v1/agent",
+    tag = "agent",
+    responses((status = 200, body = AgentResponse))
+)]
 pub async fn agent(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let state = s.driver.agent_state.read().await;
     let screen = s.terminal.screen.read().await;
@@ -91,6 +107,17 @@ pub async fn agent(State(s): State<Arc<Store>>) -> impl IntoResponse {
 }
 
 /// `POST /api/v1/agent/nudge`
+#[utoipa::path(
+    post,
+    path = "/api/v1/agent/nudge",
+    tag = "agent",
+    request_body = NudgeRequest,
+    responses(
+        (status = 200, body = NudgeOutcome),
+        (status = 404, description = "No agent driver configured", body = ErrorResponse),
+        (status = 503, description = "Agent is still starting", body = ErrorResponse),
+    )
+)]
 pub async fn agent_nudge(
     State(s): State<Arc<Store>>,
     Json(req): Json<NudgeRequest>,
@@ -101,7 +128,72 @@ pub async fn agent_nudge(
     }
 }
 
+/// `POST /api/v1/agent/nudge/queue` — queue a nudge for the next idle.
+#[utoipa::path(
+    post,
+    path = "/api/v1/agent/nudge/queue",
+    tag = "agent",
+    request_body = NewNudge,
+    responses(
+        (status = 200, body = QueuedNudge),
+        (status = 404, description = "No agent driver configured", body = ErrorResponse),
+    )
+)]
+pub async fn enqueue_nudge(
+    State(s): State<Arc<Store>>,
+    Json(req): Json<NewNudge>,
+) -> impl IntoResponse {
+    match handle_enqueue_nudge(&s, req) {
+        Ok(nudge) => Json(nudge).into_response(),
+        Err(code) => code.to_http_response(error_message(code)).into_response(),
+    }
+}
+
+/// `GET /api/v1/agent/nudge/queue` — pending nudges.
+#[utoipa::path(
+    get,
+    path = "/api/v1/agent/nudge/queue",
+    tag = "agent",
+    responses((status = 200, body = NudgeQueueResponse))
+)]
+pub async fn list_nudge_queue(State(s): State<Arc<Store>>) -> impl IntoResponse {
+    Json(NudgeQueueResponse { nudges: s.nudge_queue.list() })
+}
+
+/// `DELETE /api/v1/agent/nudge/queue/{id}` — cancel a pending nudge.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/agent/nudge/queue/{id}",
+    tag = "agent",
+    params(("id" = String, Path, description = "Queued nudge ID")),
+    responses(
+        (status = 200, description = "The cancelled nudge", body = QueuedNudge),
+        (status = 400, description = "No pending nudge with this ID", body = ErrorResponse),
+        (status = 409, description = "The nudge is being delivered", body = ErrorResponse),
+    )
+)]
+pub async fn cancel_nudge(
+    State(s): State<Arc<Store>>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    match s.nudge_queue.cancel(&id) {
+        Ok(nudge) => Json(nudge).into_response(),
+        Err((code, message)) => code.to_http_response(message).into_response(),
+    }
+}
+
 /// `POST /api/v1/agent/respond`
+#[utoipa::path(
+    post,
+    path = "/api/v1/agent/respond",
+    tag = "agent",
+    request_body = RespondRequest,
+    responses(
+        (status = 200, body = RespondOutcome),
+        (status = 404, description = "No agent driver configured", body = ErrorResponse),
+        (status = 503, description = "Agent is still starting", body = ErrorResponse),
+    )
+)]
 pub async fn agent_respond(
     State(s): State<Arc<Store>>,
     Json(req): Json<RespondRequest>,
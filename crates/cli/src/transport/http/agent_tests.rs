@@ -142,6 +142,51 @@ async fn agent_nudge_delivered_when_waiting() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn nudge_queue_enqueue_list_cancel() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = StoreBuilder::new()
+        .agent_state(AgentState::Working)
+        .nudge_encoder(Arc::new(StubNudgeEncoder))
+        .build();
+    let app = build_router(state);
+    let server = axum_test::TestServer::new(app).anyhow()?;
+
+    let resp = server
+        .post("/api/v1/agent/nudge/queue")
+        .json(&serde_json::json!({"message": "later", "priority": 2, "dedup_key": "ci"}))
+        .await;
+    resp.assert_status(StatusCode::OK);
+    let queued: serde_json::Value = resp.json();
+    let id = queued["id"].as_str().ok_or_else(|| anyhow::anyhow!("no id: {queued}"))?;
+
+    let list: serde_json::Value = server.get("/api/v1/agent/nudge/queue").await.json();
+    assert_eq!(list["nudges"][0]["message"], "later");
+    assert_eq!(list["nudges"][0]["priority"], 2);
+
+    server.delete(&format!("/api/v1/agent/nudge/queue/{id}")).await.assert_status(StatusCode::OK);
+    server
+        .delete(&format!("/api/v1/agent/nudge/queue/{id}"))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    let list: serde_json::Value = server.get("/api/v1/agent/nudge/queue").await.json();
+    assert_eq!(list["nudges"], serde_json::json!([]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn nudge_queue_no_driver_404() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = test_state();
+    let app = build_router(state);
+    let server = axum_test::TestServer::new(app).anyhow()?;
+
+    let resp = server
+        .post("/api/v1/agent/nudge/queue")
+        .json(&serde_json::json!({"message": "hello"}))
+        .await;
+    resp.assert_status(StatusCode::NOT_FOUND);
+    Ok(())
+}
+
 #[tokio::test]
 async fn auth_rejects_without_token() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } =
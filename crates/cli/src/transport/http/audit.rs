@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Audit log middleware and catchup HTTP handler.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::{header, Method, Request};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::audit::{AuditCatchupResponse, AuditTransport};
+use crate::error::ErrorCode;
+use crate::transport::auth::caller_identity;
+use crate::transport::state::Store;
+
+/// Request bodies larger than this are audited by size only (matches axum's
+/// default body limit, so handlers would reject them anyway).
+const MAX_AUDITED_BODY: usize = 2 * 1024 * 1024;
+
+/// Query parameters for the audit catchup endpoint.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditCatchupQuery {
+    /// Return entries with `seq >= since_seq`.
+    #[serde(default)]
+    pub since_seq: u64,
+}
+
+/// `GET /api/v1/audit/catchup` — audited mutating calls since a sequence number.
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit/catchup",
+    tag = "events",
+    params(AuditCatchupQuery),
+    responses((status = 200, body = AuditCatchupResponse))
+)]
+pub async fn catchup_audit(
+    State(s): State<Arc<Store>>,
+    Query(q): Query<AuditCatchupQuery>,
+) -> impl IntoResponse {
+    Json(AuditCatchupResponse { entries: s.audit_log.catchup(q.since_seq) })
+}
+
+/// Whether a request is a mutating API call worth auditing. Hook callbacks
+/// come from the agent's own hook scripts and are not audited.
+fn is_audited(method: &Method, path: &str) -> bool {
+    method != Method::GET
+        && method != Method::HEAD
+        && method != Method::OPTIONS
+        && path.starts_with("/api/v1/")
+        && !path.starts_with("/api/v1/hooks/")
+}
+
+/// Axum middleware that appends every mutating call to the audit log.
+///
+/// Runs inside [`auth_layer`](crate::transport::auth::auth_layer) so the
+/// caller's token name is available. The body is buffered, logged (redacted)
+/// and handed on unchanged.
+pub async fn audit_layer(State(s): State<Arc<Store>>, req: Request<Body>, next: Next) -> Response {
+    if !is_audited(req.method(), req.uri().path()) {
+        return next.run(req).await;
+    }
+    let caller = caller_identity(req.extensions());
+    let operation = format!("{} {}", req.method(), req.uri().path());
+
+    let (parts, body) = req.into_parts();
+    let declared_len = parts
+        .headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if let Some(len) = declared_len.filter(|len| *len > MAX_AUDITED_BODY) {
+        let payload = serde_json::json!({ "bytes": len });
+        s.audit_log.record(&caller, AuditTransport::Http, operation, payload);
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+
+    let bytes = match axum::body::to_bytes(body, MAX_AUDITED_BODY).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return ErrorCode::BadRequest.to_http_response("request body too large").into_response()
+        }
+    };
+    let payload = if bytes.is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| serde_json::json!({ "bytes": bytes.len() }))
+    };
+    s.audit_log.record(&caller, AuditTransport::Http, operation, payload);
+    next.run(Request::from_parts(parts, Body::from(bytes))).await
+}
+
+#[cfg(test)]
+#[path = "audit_tests.rs"]
+mod tests;
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+
+use axum::http::{header, HeaderValue, StatusCode};
+
+use crate::audit::{AuditLog, AuditTransport};
+use crate::test_support::{AnyhowExt, StoreBuilder, StoreCtx};
+use crate::transport::build_router;
+use crate::transport::tokens::{ApiToken, ApiTokens, Scope};
+
+#[tokio::test]
+async fn mutating_calls_are_audited_with_token_name() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let tokens = ApiTokens {
+        tokens: vec![ApiToken {
+            name: "ops".to_owned(),
+            token: "ops-token".to_owned(),
+            scopes: vec![Scope::Read, Scope::Input, Scope::Credentials],
+        }],
+    };
+    let StoreCtx { store, .. } = StoreBuilder::new()
+        .child_pid(1234)
+        .auth_token("root")
+        .api_tokens(tokens)
+        .audit_log(Arc::new(AuditLog::new(Some(dir.path()))))
+        .build();
+    let server = axum_test::TestServer::new(build_router(store.clone())).anyhow()?;
+
+    server
+        .post("/api/v1/input")
+        .add_header(header::AUTHORIZATION, HeaderValue::from_static("Bearer ops-token"))
+        .json(&serde_json::json!({ "text": "ls", "enter": true }))
+        .await
+        .assert_status(StatusCode::OK);
+    server
+        .post("/api/v1/session/profiles")
+        .add_header(header::AUTHORIZATION, HeaderValue::from_static("Bearer ops-token"))
+        .json(&serde_json::json!({
+            "profiles": [{ "name": "main", "credentials": { "ANTHROPIC_API_KEY": "sk-1" } }]
+        }))
+        .await;
+    // Reads and rejected calls are not audited.
+    server
+        .get("/api/v1/status")
+        .add_header(header::AUTHORIZATION, HeaderValue::from_static("Bearer ops-token"))
+        .await
+        .assert_status(StatusCode::OK);
+    server
+        .post("/api/v1/shutdown")
+        .add_header(header::AUTHORIZATION, HeaderValue::from_static("Bearer ops-token"))
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    let entries = store.audit_log.catchup(0);
+    assert_eq!(entries.len(), 2, "{entries:?}");
+    assert_eq!(entries[0].caller, "ops");
+    assert_eq!(entries[0].transport, AuditTransport::Http);
+    assert_eq!(entries[0].operation, "POST /api/v1/input");
+    assert_eq!(entries[0].payload, serde_json::json!({ "text": "ls", "enter": true }));
+    assert_eq!(entries[1].operation, "POST /api/v1/session/profiles");
+    assert_eq!(
+        entries[1].payload,
+        serde_json::json!({ "profiles": [{ "name": "main", "credentials": "[redacted]" }] })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn catchup_requires_admin_and_filters_by_seq() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let tokens = ApiTokens {
+        tokens: vec![ApiToken {
+            name: "dashboard".to_owned(),
+            token: "read-only".to_owned(),
+            scopes: vec![Scope::Read],
+        }],
+    };
+    let StoreCtx { store, .. } = StoreBuilder::new()
+        .auth_token("root")
+        .api_tokens(tokens)
+        .audit_log(Arc::new(AuditLog::new(Some(dir.path()))))
+        .build();
+    store.audit_log.record("a", AuditTransport::Ws, "input:send", serde_json::json!({}));
+    store.audit_log.record("b", AuditTransport::Grpc, "Nudge", serde_json::json!({}));
+    let server = axum_test::TestServer::new(build_router(store)).anyhow()?;
+
+    server
+        .get("/api/v1/audit/catchup")
+        .add_header(header::AUTHORIZATION, HeaderValue::from_static("Bearer read-only"))
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    let resp = server
+        .get("/api/v1/audit/catchup?since_seq=1")
+        .add_header(header::AUTHORIZATION, HeaderValue::from_static("Bearer root"))
+        .await;
+    resp.assert_status(StatusCode::OK);
+    let body: serde_json::Value = resp.json();
+    let entries = body["entries"].as_array().cloned().unwrap_or_default();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["caller"], "b");
+    assert_eq!(entries[0]["transport"], "grpc");
+    Ok(())
+}
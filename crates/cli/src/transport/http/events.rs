@@ -1,20 +1,32 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
-//! Event log catchup HTTP handler.
+//! Event log catchup and Server-Sent Events stream HTTP handlers.
 
+use std::convert::Infallible;
 use std::sync::Arc;
 
 use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use utoipa::IntoParams;
 
+use crate::event::TransitionEvent;
 use crate::event_log::CatchupResponse;
+use crate::transport::fanout::{EventFanout, Fanned};
 use crate::transport::state::Store;
+use crate::transport::ws::{
+    hook_entry_to_msg, transition_entry_to_msg, transition_to_msg, ServerMessage, SubscriptionFlags,
+};
 
 /// Query parameters for the event log catchup endpoint.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct EventCatchupQuery {
     #[serde(default)]
     pub since_seq: u64,
@@ -23,6 +35,13 @@ pub struct EventCatchupQuery {
 }
 
 /// `GET /api/v1/events/catchup` — catch up on missed state and hook events.
+#[utoipa::path(
+    get,
+    path = "/api/v1/events/catchup",
+    tag = "events",
+    params(EventCatchupQuery),
+    responses((status = 200, body = CatchupResponse))
+)]
 pub async fn catchup_events(
     State(s): State<Arc<Store>>,
     Query(q): Query<EventCatchupQuery>,
@@ -33,3 +52,157 @@ pub async fn catchup_events(
     };
     Json(resp)
 }
+
+/// Query parameters for the SSE event stream.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventStreamQuery {
+    /// Comma-separated subscription flags, as for `/ws` (default: `state`).
+    pub subscribe: Option<String>,
+}
+
+/// Resume position carried in SSE event ids as `<seq>-<hooks>`: the last
+/// state transition seq and the number of hook events seen so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventCursor {
+    pub seq: u64,
+    pub hooks: u64,
+}
+
+impl EventCursor {
+    /// Parse a `Last-Event-ID` value. Returns `None` for malformed ids.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (seq, hooks) = s.trim().split_once('-')?;
+        Some(Self { seq: seq.parse().ok()?, hooks: hooks.parse().ok()? })
+    }
+
+    pub fn id(&self) -> String {
+        format!("{}-{}", self.seq, self.hooks)
+    }
+}
+
+type SseItem = Result<Event, Infallible>;
+
+/// `GET /api/v1/events/stream` — push events as Server-Sent Events.
+///
+/// Emits the same [`ServerMessage`] payloads as `/ws`, with the message's
+/// `event` tag as the SSE event name. State transitions and hook events
+/// carry an [`EventCursor`] id; a reconnecting client's `Last-Event-ID`
+/// replays missed transitions and hooks from the event log.
+#[utoipa::path(
+    get,
+    path = "/api/v1/events/stream",
+    tag = "events",
+    params(
+        EventStreamQuery,
+        ("token" = Option<String>, Query, description = "Auth token (alternative to the Authorization header)"),
+        ("Last-Event-ID" = Option<String>, Header, description = "Resume cursor (`<seq>-<hooks>`)"),
+    ),
+    responses((status = 200, description = "Stream of `ServerMessage` events", body = String, content_type = "text/event-stream"))
+)]
+pub async fn stream_events(
+    State(s): State<Arc<Store>>,
+    Query(q): Query<EventStreamQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let flags = SubscriptionFlags::parse(q.subscribe.as_deref().unwrap_or("state"));
+    let resume =
+        headers.get("last-event-id").and_then(|v| v.to_str().ok()).and_then(EventCursor::parse);
+    let (tx, rx) = mpsc::channel(64);
+    spawn_event_pump(s, flags, resume, tx).await;
+    Sse::new(ReceiverStream::new(rx)).keep_alive(KeepAlive::default())
+}
+
+/// Subscribe to the broadcast channels, then spawn a task forwarding events
+/// to the SSE stream until the client disconnects.
+async fn spawn_event_pump(
+    state: Arc<Store>,
+    flags: SubscriptionFlags,
+    resume: Option<EventCursor>,
+    tx: mpsc::Sender<SseItem>,
+) {
+    let mut fanout = EventFanout::subscribe(Arc::clone(&state), flags).await;
+    if let Some(resume) = resume {
+        fanout.state_seq = resume.seq;
+        fanout.hooks = resume.hooks;
+    }
+
+    tokio::spawn(async move {
+        // Initial state: replay from the event log on resume, otherwise a
+        // synthetic current-state snapshot (as `/ws` does without a cursor).
+        if flags.state {
+            if resume.is_some() {
+                for entry in state.event_log.catchup_state(fanout.state_seq) {
+                    fanout.state_seq = entry.seq;
+                    fanout.replayed_seq = Some(entry.seq);
+                    let msg = transition_entry_to_msg(&entry);
+                    if !send_sse(&tx, &msg, Some(cursor(&fanout))).await {
+                        return;
+                    }
+                }
+            } else {
+                let agent = state.driver.agent_state.read().await.clone();
+                let last_message = state.driver.last_message.read().await.clone();
+                let initial = TransitionEvent {
+                    prev: agent.clone(),
+                    next: agent,
+                    seq: fanout.state_seq,
+                    cause: String::new(),
+                    last_message,
+                };
+                if !send_sse(&tx, &transition_to_msg(&initial), Some(cursor(&fanout))).await {
+                    return;
+                }
+            }
+        }
+        if flags.hooks && resume.is_some() {
+            for entry in state.event_log.hooks_from(fanout.hooks) {
+                fanout.hooks = entry.hook_seq + 1;
+                fanout.replayed_hooks = Some(fanout.hooks);
+                if !send_sse(&tx, &hook_entry_to_msg(&entry), Some(cursor(&fanout))).await {
+                    return;
+                }
+            }
+        }
+
+        loop {
+            let fanned = tokio::select! {
+                _ = tx.closed() => break,
+                fanned = fanout.recv() => match fanned {
+                    Some(f) => f,
+                    None => break,
+                },
+            };
+            let id =
+                matches!(fanned, Fanned::Message { resumable: true, .. }).then(|| cursor(&fanout));
+            if let Some(msg) = fanout.resolve(fanned).await {
+                if !send_sse(&tx, &msg, id).await {
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// The resume cursor for the events seen so far.
+fn cursor(fanout: &EventFanout) -> EventCursor {
+    EventCursor { seq: fanout.state_seq, hooks: fanout.hooks }
+}
+
+/// Send a message as an SSE event named after its `event` tag. Returns
+/// `false` once the client has gone away.
+async fn send_sse(
+    tx: &mpsc::Sender<SseItem>,
+    msg: &ServerMessage,
+    cursor: Option<EventCursor>,
+) -> bool {
+    let Ok(value) = serde_json::to_value(msg) else {
+        return true;
+    };
+    let name = value.get("event").and_then(|v| v.as_str()).unwrap_or("message").to_owned();
+    let mut event = Event::default().event(name).data(value.to_string());
+    if let Some(cursor) = cursor {
+        event = event.id(cursor.id());
+    }
+    tx.send(Ok(event)).await.is_ok()
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use futures_util::StreamExt;
+use tower::ServiceExt;
+
+use crate::budget::{BudgetAction, BudgetEvent, BudgetEventKind};
+use crate::driver::AgentState;
+use crate::event::{RawHookEvent, TransitionEvent};
+use crate::event_log::EventLog;
+use crate::idle::IdleEvent;
+use crate::nudge_queue::{NudgeQueueEvent, NudgeQueueEventKind, QueuedNudge};
+use crate::test_support::{StoreBuilder, StoreCtx};
+use crate::transport::build_router;
+use crate::transport::http::EventCursor;
+use crate::transport::ws::{
+    budget_event_to_msg, idle_event_to_msg, nudge_queue_event_to_msg, ServerMessage,
+};
+
+/// Read SSE frames from a streaming body until `needle` appears.
+async fn read_until(
+    body: &mut axum::body::BodyDataStream,
+    text: &mut String,
+    needle: &str,
+) -> anyhow::Result<()> {
+    while !text.contains(needle) {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out waiting for {needle:?} in {text:?}"))?
+            .ok_or_else(|| anyhow::anyhow!("stream ended waiting for {needle:?}"))??;
+        text.push_str(&String::from_utf8_lossy(&chunk));
+    }
+    Ok(())
+}
+
+fn transition(prev: AgentState, next: AgentState, seq: u64) -> TransitionEvent {
+    TransitionEvent { prev, next, seq, cause: "hook".to_owned(), last_message: None }
+}
+
+#[yare::parameterized(
+    plain = { "12-3", Some(EventCursor { seq: 12, hooks: 3 }) },
+    padded = { " 0-0 ", Some(EventCursor { seq: 0, hooks: 0 }) },
+    missing_hooks = { "12", None },
+    garbage = { "a-b", None },
+)]
+fn cursor_parse(input: &str, expected: Option<EventCursor>) {
+    assert_eq!(EventCursor::parse(input), expected);
+}
+
+#[tokio::test]
+async fn stream_sends_snapshot_then_live_events() -> anyhow::Result<()> {
+    let StoreCtx { store, .. } = StoreBuilder::new().agent_state(AgentState::Working).build();
+    let app = build_router(Arc::clone(&store));
+
+    let req = Request::get("/api/v1/events/stream?subscribe=state,hooks").body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let content_type = resp.headers().get("content-type").and_then(|v| v.to_str().ok());
+    assert_eq!(content_type, Some("text/event-stream"));
+
+    let mut body = resp.into_body().into_data_stream();
+    let mut text = String::new();
+    read_until(&mut body, &mut text, "\"next\":\"working\"").await?;
+    assert!(text.contains("event: transition"), "{text}");
+    assert!(text.contains("id: 0-0"), "{text}");
+
+    let _ = store.channels.hook_tx.send(RawHookEvent { json: serde_json::json!({"event": "x"}) });
+    read_until(&mut body, &mut text, "event: hook:raw").await?;
+    assert!(text.contains("id: 0-1"), "{text}");
+
+    let _ = store.channels.state_tx.send(transition(AgentState::Working, AgentState::Idle, 7));
+    read_until(&mut body, &mut text, "\"next\":\"idle\"").await?;
+    assert!(text.contains("id: 7-1"), "{text}");
+    Ok(())
+}
+
+/// The SSE `data:` line `/ws` would send for the same event.
+fn sse_data(msg: &ServerMessage) -> anyhow::Result<String> {
+    Ok(format!("data: {}", serde_json::to_value(msg)?))
+}
+
+#[tokio::test]
+async fn stream_forwards_the_same_events_as_ws() -> anyhow::Result<()> {
+    let StoreCtx { store, .. } = StoreBuilder::new().agent_state(AgentState::Idle).build();
+    let app = build_router(Arc::clone(&store));
+
+    let req = Request::get("/api/v1/events/stream?subscribe=state,usage").body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    let mut body = resp.into_body().into_data_stream();
+    let mut text = String::new();
+    read_until(&mut body, &mut text, "event: transition").await?;
+
+    let idle = IdleEvent {
+        step: 0,
+        action: "nudge".to_owned(),
+        idle_secs: 60,
+        message: Some("still there?".to_owned()),
+    };
+    let _ = store.idle.idle_tx.send(idle.clone());
+    read_until(&mut body, &mut text, "event: idle:step").await?;
+    assert!(text.contains(&sse_data(&idle_event_to_msg(&idle))?), "{text}");
+
+    let queued = NudgeQueueEvent {
+        kind: NudgeQueueEventKind::Queued,
+        nudge: QueuedNudge {
+            id: "n-1".to_owned(),
+            message: "next task".to_owned(),
+            priority: 0,
+            not_before_ms: None,
+            expires_at_ms: None,
+            dedup_key: None,
+            enqueued_at_ms: 1,
+        },
+    };
+    let _ = store.nudge_queue.queue_tx.send(queued.clone());
+    read_until(&mut body, &mut text, "event: nudge:queue:event").await?;
+    assert!(text.contains(&sse_data(&nudge_queue_event_to_msg(&queued))?), "{text}");
+
+    let budget = BudgetEvent {
+        kind: BudgetEventKind::Exceeded,
+        limit: None,
+        threshold: None,
+        action: BudgetAction::Warn,
+    };
+    let _ = store.budget.budget_tx.send(budget.clone());
+    read_until(&mut body, &mut text, "event: budget:event").await?;
+    assert!(text.contains(&sse_data(&budget_event_to_msg(&budget))?), "{text}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn stream_resumes_from_last_event_id() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let log = Arc::new(EventLog::new(Some(tmp.path())));
+    log.push_transition(&transition(AgentState::Starting, AgentState::Working, 1));
+    log.push_transition(&transition(AgentState::Working, AgentState::Idle, 2));
+    log.push_hook(&RawHookEvent { json: serde_json::json!({"event": "first"}) });
+    log.push_hook(&RawHookEvent { json: serde_json::json!({"event": "second"}) });
+
+    let StoreCtx { store, .. } = StoreBuilder::new().event_log(log).build();
+    let app = build_router(store);
+
+    let req = Request::get("/api/v1/events/stream?subscribe=state,hooks")
+        .header("last-event-id", "1-1")
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    let mut body = resp.into_body().into_data_stream();
+    let mut text = String::new();
+    read_until(&mut body, &mut text, "\"event\":\"second\"").await?;
+
+    assert!(!text.contains("\"seq\":1,"), "seq 1 should not be replayed: {text}");
+    assert!(text.contains("\"next\":\"idle\""), "{text}");
+    assert!(text.contains("id: 2-1"), "{text}");
+    assert!(!text.contains("\"event\":\"first\""), "{text}");
+    assert!(text.contains("id: 2-2"), "{text}");
+    Ok(())
+}
+
+/// EventSource cannot set headers, so the stream also accepts `?token=`.
+#[tokio::test]
+async fn stream_accepts_query_token() -> anyhow::Result<()> {
+    let StoreCtx { store, .. } = StoreBuilder::new().auth_token("secret").build();
+    let app = build_router(store);
+    for (uri, expected) in [
+        ("/api/v1/events/stream", StatusCode::UNAUTHORIZED),
+        ("/api/v1/events/stream?token=nope", StatusCode::UNAUTHORIZED),
+        ("/api/v1/events/stream?token=secret", StatusCode::OK),
+        ("/api/v1/status?token=secret", StatusCode::UNAUTHORIZED),
+    ] {
+        let resp = app.clone().oneshot(Request::get(uri).body(Body::empty())?).await?;
+        assert_eq!(resp.status(), expected, "{uri}");
+    }
+    Ok(())
+}
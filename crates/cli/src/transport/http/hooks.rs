@@ -9,11 +9,16 @@ use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::driver::ErrorCategory;
+use crate::error::ErrorCode;
+use crate::json_schema::Violation;
 use crate::start::{compose_start_script, StartConfig};
-use crate::stop::{generate_block_reason, StopConfig, StopMode, StopType};
+use crate::stop::{generate_block_reason, StopConfig, StopMode, StopSchema, StopType};
+use crate::transport::http::AcceptedResponse;
 use crate::transport::state::Store;
+use crate::transport::ErrorResponse;
 use axum::http::StatusCode;
 
 // -- Stop hook types ----------------------------------------------------------
@@ -22,7 +27,7 @@ use axum::http::StatusCode;
 ///
 /// Matches the same `{"event":"stop","data":{...}}` envelope that hooks
 /// write to the FIFO pipe, so the endpoint receives the same format.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StopHookInput {
     // NOTE(compat): Maintain consistent structure for all hook payloads
     #[allow(dead_code)]
@@ -32,7 +37,7 @@ pub struct StopHookInput {
 }
 
 /// Inner data carried inside the stop-event envelope.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct StopHookData {
     /// When `true`, this is a safety-valve invocation that must be allowed.
     #[serde(default)]
@@ -43,7 +48,7 @@ pub struct StopHookData {
 ///
 /// Empty object `{}` means "allow" (no `decision` field).
 /// `{"decision":"block","reason":"..."}` means "block".
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StopHookVerdict {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub decision: Option<String>,
@@ -53,13 +58,27 @@ pub struct StopHookVerdict {
     pub last_message: Option<String>,
 }
 
+/// Body returned when a stop signal is rejected (422).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StopRejectedResponse {
+    pub error: String,
+    /// Every schema violation found in the body.
+    pub violations: Vec<Violation>,
+}
+
+/// Acknowledgement for config updates (`{"updated": true}`).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdatedResponse {
+    pub updated: bool,
+}
+
 // -- Start hook types ---------------------------------------------------------
 
 /// Event-wrapped input from the start hook (piped from stdin via curl).
 ///
 /// Matches the `{"event":"start","data":{...}}` envelope that hooks
 /// write to the FIFO pipe.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StartHookInput {
     // NOTE(compat): Maintain consistent structure for all hook payloads
     #[allow(dead_code)]
@@ -71,6 +90,14 @@ pub struct StartHookInput {
 // -- Stop hook handlers -------------------------------------------------------
 
 /// `POST /api/v1/hooks/stop` — called by the hook script, returns verdict.
+#[utoipa::path(
+    post,
+    path = "/api/v1/hooks/stop",
+    tag = "stop",
+    security(()),
+    request_body = StopHookInput,
+    responses((status = 200, body = StopHookVerdict))
+)]
 pub async fn hooks_stop(
     State(s): State<Arc<Store>>,
     Json(input): Json<StopHookInput>,
@@ -131,38 +158,81 @@ pub async fn hooks_stop(
 }
 
 /// `POST /api/v1/stop/resolve` — validate, store signal body, set flag.
+#[utoipa::path(
+    post,
+    path = "/api/v1/stop/resolve",
+    tag = "stop",
+    security(()),
+    request_body(content = Object, description = "Signal body matching the stop schema"),
+    responses(
+        (status = 200, body = AcceptedResponse),
+        (status = 422, description = "Body does not match the stop schema", body = StopRejectedResponse),
+    )
+)]
 pub async fn resolve_stop(
     State(s): State<Arc<Store>>,
     Json(body): Json<serde_json::Value>,
 ) -> impl IntoResponse {
     match s.stop.resolve(body).await {
-        Ok(()) => Json(serde_json::json!({ "accepted": true })).into_response(),
-        Err(msg) => {
-            s.stop.emit(StopType::Rejected, None, Some(msg.clone()));
-            (StatusCode::UNPROCESSABLE_ENTITY, Json(serde_json::json!({ "error": msg })))
-                .into_response()
-        }
+        Ok(()) => Json(AcceptedResponse { accepted: true }).into_response(),
+        Err(rejection) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(StopRejectedResponse {
+                error: rejection.to_string(),
+                violations: rejection.violations,
+            }),
+        )
+            .into_response(),
     }
 }
 
 /// `GET /api/v1/config/stop` — read current stop config.
+#[utoipa::path(
+    get,
+    path = "/api/v1/config/stop",
+    tag = "stop",
+    responses((status = 200, body = StopConfig))
+)]
 pub async fn get_stop_config(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let config = s.stop.config.read().await;
     Json(config.clone())
 }
 
 /// `PUT /api/v1/config/stop` — update stop config.
+#[utoipa::path(
+    put,
+    path = "/api/v1/config/stop",
+    tag = "stop",
+    request_body = StopConfig,
+    responses(
+        (status = 200, body = UpdatedResponse),
+        (status = 400, description = "Invalid field schema", body = ErrorResponse),
+    )
+)]
 pub async fn put_stop_config(
     State(s): State<Arc<Store>>,
     Json(new_config): Json<StopConfig>,
 ) -> impl IntoResponse {
+    if let Some(Err(e)) = new_config.schema.as_ref().map(StopSchema::check) {
+        return ErrorCode::BadRequest
+            .to_http_response(format!("invalid stop schema: {e}"))
+            .into_response();
+    }
     *s.stop.config.write().await = new_config;
-    Json(serde_json::json!({ "updated": true }))
+    Json(UpdatedResponse { updated: true }).into_response()
 }
 
 // -- Start hook handlers ------------------------------------------------------
 
 /// `POST /api/v1/hooks/start` — called by the hook script, returns shell script.
+#[utoipa::path(
+    post,
+    path = "/api/v1/hooks/start",
+    tag = "start",
+    security(()),
+    request_body = StartHookInput,
+    responses((status = 200, description = "Shell script to evaluate (may be empty)", body = String, content_type = "text/plain"))
+)]
 pub async fn hooks_start(
     State(s): State<Arc<Store>>,
     Json(input): Json<StartHookInput>,
@@ -215,16 +285,29 @@ pub async fn hooks_start(
 }
 
 /// `GET /api/v1/config/start` — read current start config.
+#[utoipa::path(
+    get,
+    path = "/api/v1/config/start",
+    tag = "start",
+    responses((status = 200, body = StartConfig))
+)]
 pub async fn get_start_config(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let config = s.start.config.read().await;
     Json(config.clone())
 }
 
 /// `PUT /api/v1/config/start` — update start config.
+#[utoipa::path(
+    put,
+    path = "/api/v1/config/start",
+    tag = "start",
+    request_body = StartConfig,
+    responses((status = 200, body = UpdatedResponse))
+)]
 pub async fn put_start_config(
     State(s): State<Arc<Store>>,
     Json(new_config): Json<StartConfig>,
 ) -> impl IntoResponse {
     *s.start.config.write().await = new_config;
-    Json(serde_json::json!({ "updated": true }))
+    Json(UpdatedResponse { updated: true })
 }
@@ -288,6 +288,7 @@ async fn resolve_stop_rejects_invalid_body() -> anyhow::Result<()> {
             r#enum: Some(vec!["done".to_owned(), "error".to_owned()]),
             descriptions: None,
             description: None,
+            schema: None,
         },
     );
     let config =
@@ -302,6 +303,22 @@ async fn resolve_stop_rejects_invalid_body() -> anyhow::Result<()> {
     resp.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
     let body: serde_json::Value = serde_json::from_str(&resp.text())?;
     assert!(body["error"].as_str().unwrap_or("").contains("bogus"));
+    assert_eq!(body["violations"][0]["path"], "/status");
+    Ok(())
+}
+
+#[tokio::test]
+async fn put_stop_config_rejects_invalid_field_schema() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = stop_state(StopConfig::default());
+    let app = build_router(state);
+    let server = axum_test::TestServer::new(app).anyhow()?;
+
+    let config = serde_json::json!({
+        "mode": "auto",
+        "schema": {"fields": {"pr": {"schema": {"type": "text"}}}},
+    });
+    let resp = server.put("/api/v1/config/stop").json(&config).await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
     Ok(())
 }
 
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Prometheus metrics HTTP handler.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+
+use crate::metrics::Exposition;
+use crate::transport::state::Store;
+
+/// Wire names of every agent state, so the state gauge always has a full set.
+const AGENT_STATES: &[&str] = &[
+    "starting",
+    "working",
+    "idle",
+    "prompt",
+    "error",
+    "parked",
+    "restarting",
+    "exited",
+    "unknown",
+];
+
+/// `GET /metrics` — Prometheus text exposition of session metrics.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "lifecycle",
+    responses((status = 200, description = "Prometheus text format", content_type = "text/plain"))
+)]
+pub async fn metrics(State(s): State<Arc<Store>>) -> impl IntoResponse {
+    let mut out = Exposition::default();
+
+    let agent = s.driver.agent_state.read().await.as_str();
+    out.header("coop_agent_state", "gauge", "Current agent state (1 for the active state).");
+    for state in AGENT_STATES {
+        out.sample("coop_agent_state", &[("state", state)], u8::from(*state == agent));
+    }
+    out.single(
+        "coop_ready",
+        "gauge",
+        "Whether the agent has left the starting state.",
+        u8::from(s.ready.load(Ordering::Acquire)),
+    );
+    out.single(
+        "coop_ring_bytes_total",
+        "counter",
+        "Bytes of PTY output written to the ring buffer.",
+        s.terminal.ring_total_written.load(Ordering::Relaxed),
+    );
+    out.single(
+        "coop_input_bytes_total",
+        "counter",
+        "Bytes written to the PTY by API clients.",
+        s.lifecycle.bytes_written.load(Ordering::Relaxed),
+    );
+    out.single(
+        "coop_ws_clients",
+        "gauge",
+        "Connected WebSocket clients.",
+        s.lifecycle.ws_client_count.load(Ordering::Relaxed),
+    );
+
+    let usage = s.usage.snapshot().await;
+    out.header("coop_usage_tokens_total", "counter", "API tokens used by the agent.");
+    for (kind, count) in [
+        ("input", usage.input_tokens),
+        ("output", usage.output_tokens),
+        ("cache_read", usage.cache_read_tokens),
+        ("cache_write", usage.cache_write_tokens),
+    ] {
+        out.sample("coop_usage_tokens_total", &[("kind", kind)], count);
+    }
+    out.single(
+        "coop_usage_cost_usd_total",
+        "counter",
+        "Reported API cost in USD.",
+        usage.total_cost_usd,
+    );
+    out.single(
+        "coop_usage_estimated_cost_usd_total",
+        "counter",
+        "API cost in USD priced from token counts.",
+        usage.estimated_cost_usd,
+    );
+    out.single(
+        "coop_usage_requests_total",
+        "counter",
+        "API requests made by the agent.",
+        usage.request_count,
+    );
+
+    s.metrics.write_to(&mut out);
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")], out.finish())
+}
+
+#[cfg(test)]
+#[path = "metrics_tests.rs"]
+mod tests;
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use axum::http::{header, HeaderValue, StatusCode};
+
+use crate::driver::AgentState;
+use crate::test_support::{AnyhowExt, StoreBuilder};
+use crate::transport::{build_health_router, build_router};
+use crate::usage::UsageDelta;
+
+#[tokio::test]
+async fn metrics_reports_state_and_usage() -> anyhow::Result<()> {
+    let ctx = StoreBuilder::new().child_pid(1234).agent_state(AgentState::Idle).build();
+    ctx.store
+        .usage
+        .accumulate(UsageDelta { input_tokens: 120, cost_usd: 0.5, ..Default::default() })
+        .await;
+    ctx.store.metrics.record_transition(1, "hook:idle");
+    let server = axum_test::TestServer::new(build_router(ctx.store)).anyhow()?;
+
+    let resp = server.get("/metrics").await;
+    resp.assert_status(StatusCode::OK);
+    assert!(resp.header(header::CONTENT_TYPE).to_str()?.starts_with("text/plain; version=0.0.4"));
+    let text = resp.text();
+    assert!(text.contains("coop_agent_state{state=\"idle\"} 1\n"), "{text}");
+    assert!(text.contains("coop_agent_state{state=\"working\"} 0\n"));
+    assert!(text.contains("coop_usage_tokens_total{kind=\"input\"} 120\n"));
+    assert!(text.contains("coop_usage_cost_usd_total 0.5\n"));
+    assert!(text.contains("coop_usage_estimated_cost_usd_total 0.5\n"));
+    assert!(text.contains("coop_usage_requests_total 1\n"));
+    assert!(text.contains("coop_ws_clients 0\n"));
+    assert!(text.contains("coop_state_transitions_total{tier=\"1\",cause=\"hook:idle\"} 1\n"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn metrics_requires_auth_except_on_health_port() -> anyhow::Result<()> {
+    let ctx = StoreBuilder::new().auth_token("secret").build();
+
+    let server = axum_test::TestServer::new(build_router(ctx.store.clone())).anyhow()?;
+    server.get("/metrics").await.assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .get("/metrics")
+        .add_header(header::AUTHORIZATION, HeaderValue::from_static("Bearer secret"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let health = axum_test::TestServer::new(build_health_router(ctx.store)).anyhow()?;
+    health.get("/metrics").await.assert_status(StatusCode::OK);
+    Ok(())
+}
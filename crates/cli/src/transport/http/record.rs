@@ -9,22 +9,46 @@ use axum::extract::{Query, State};
 use axum::http::header;
 use axum::response::IntoResponse;
 use axum::Json;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
+use crate::error::ErrorCode;
+use crate::record::{RecordingEntry, RecordingStatus};
 use crate::transport::state::Store;
+use crate::transport::ErrorResponse;
 
 /// `GET /api/v1/recording` — recording status.
+#[utoipa::path(
+    get,
+    path = "/api/v1/recording",
+    tag = "recording",
+    responses((status = 200, body = RecordingStatus))
+)]
 pub async fn get_recording(State(s): State<Arc<Store>>) -> impl IntoResponse {
     Json(s.record.status())
 }
 
 /// Request body for PUT /api/v1/recording.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct PutRecordingBody {
     pub enabled: bool,
 }
 
+/// Response for PUT /api/v1/recording.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PutRecordingResponse {
+    pub enabled: bool,
+    pub path: Option<String>,
+}
+
 /// `PUT /api/v1/recording` — toggle recording on/off.
+#[utoipa::path(
+    put,
+    path = "/api/v1/recording",
+    tag = "recording",
+    request_body = PutRecordingBody,
+    responses((status = 200, body = PutRecordingResponse))
+)]
 pub async fn put_recording(
     State(s): State<Arc<Store>>,
     Json(body): Json<PutRecordingBody>,
@@ -35,32 +59,95 @@ pub async fn put_recording(
         s.record.disable();
     }
     let status = s.record.status();
-    Json(serde_json::json!({
-        "enabled": status.enabled,
-        "path": status.path,
-    }))
+    Json(PutRecordingResponse { enabled: status.enabled, path: status.path })
 }
 
 /// Query parameters for recording catchup endpoint.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RecordingCatchupQuery {
     #[serde(default)]
     pub since_seq: u64,
 }
 
+/// Response for the recording catchup endpoint.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecordingCatchupResponse {
+    pub entries: Vec<RecordingEntry>,
+}
+
 /// `GET /api/v1/recording/catchup` — catch up on missed recording entries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/recording/catchup",
+    tag = "recording",
+    params(RecordingCatchupQuery),
+    responses((status = 200, body = RecordingCatchupResponse))
+)]
 pub async fn catchup_recording(
     State(s): State<Arc<Store>>,
     Query(q): Query<RecordingCatchupQuery>,
 ) -> impl IntoResponse {
-    let entries = s.record.catchup(q.since_seq);
-    Json(serde_json::json!({ "entries": entries }))
+    Json(RecordingCatchupResponse { entries: s.record.catchup(q.since_seq) })
+}
+
+/// Query parameters for the recording download endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RecordingDownloadQuery {
+    /// `jsonl` (default) or `asciicast`.
+    #[serde(default)]
+    pub format: Option<String>,
 }
 
 /// `GET /api/v1/recording/download` — download full recording file.
-pub async fn download_recording(State(s): State<Arc<Store>>) -> impl IntoResponse {
-    match s.record.download() {
-        Some(data) => ([(header::CONTENT_TYPE, "application/jsonl")], data).into_response(),
-        None => (axum::http::StatusCode::NOT_FOUND, "no recording file available").into_response(),
+///
+/// `?format=asciicast` returns an asciicast v2 `.cast` file instead of the
+/// native JSONL.
+#[utoipa::path(
+    get,
+    path = "/api/v1/recording/download",
+    tag = "recording",
+    params(RecordingDownloadQuery),
+    responses(
+        (
+            status = 200,
+            description = "Recording file",
+            content(
+                (String = "application/jsonl"),
+                (String = "application/x-asciicast"),
+            )
+        ),
+        (status = 400, description = "Unknown format", body = ErrorResponse),
+        (status = 404, description = "No recording file available"),
+    )
+)]
+pub async fn download_recording(
+    State(s): State<Arc<Store>>,
+    Query(q): Query<RecordingDownloadQuery>,
+) -> impl IntoResponse {
+    match q.format.as_deref().unwrap_or("jsonl") {
+        "jsonl" => match s.record.download() {
+            Some(data) => ([(header::CONTENT_TYPE, "application/jsonl")], data).into_response(),
+            None => {
+                (axum::http::StatusCode::NOT_FOUND, "no recording file available").into_response()
+            }
+        },
+        "asciicast" | "cast" => match s.record.download_asciicast() {
+            Some(data) => (
+                [
+                    (header::CONTENT_TYPE, "application/x-asciicast"),
+                    (header::CONTENT_DISPOSITION, "attachment; filename=\"recording.cast\""),
+                ],
+                data,
+            )
+                .into_response(),
+            None => {
+                (axum::http::StatusCode::NOT_FOUND, "no recording file available").into_response()
+            }
+        },
+        other => ErrorCode::BadRequest
+            .to_http_response(format!("unknown recording format: {other}"))
+            .into_response(),
     }
 }
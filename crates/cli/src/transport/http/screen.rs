@@ -11,19 +11,21 @@ use axum::response::IntoResponse;
 use axum::Json;
 use base64::Engine;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::error::ErrorCode;
 use crate::screen::CursorPosition;
+use crate::transport::handler::SessionStatus;
 use crate::transport::handler::{
-    compute_health, compute_status, handle_input, handle_input_raw, handle_keys, handle_resize,
-    handle_signal,
+    compute_health, compute_status, error_message, handle_input, handle_input_raw, handle_keys,
+    handle_resize, handle_signal,
 };
-use crate::transport::read_ring_replay;
 use crate::transport::state::Store;
+use crate::transport::{read_ring_replay, ErrorResponse};
 
 // -- Types --------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
     pub status: String,
     pub session_id: String,
@@ -40,33 +42,34 @@ pub struct HealthResponse {
     pub ready: bool,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
 pub struct TerminalSize {
     pub cols: u16,
     pub rows: u16,
 }
 
 /// Response for the readiness probe.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ReadyResponse {
     pub ready: bool,
 }
 
 /// Response for the liveness probe — fully lock-free.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LivezResponse {
     pub status: String,
     pub uptime_secs: i64,
     pub pid: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ScreenQuery {
     #[serde(default, alias = "cursor")]
     pub cursor: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ScreenResponse {
     pub lines: Vec<String>,
     pub ansi: Vec<String>,
@@ -77,14 +80,15 @@ pub struct ScreenResponse {
     pub seq: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct OutputQuery {
     #[serde(default)]
     pub offset: u64,
     pub limit: Option<usize>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct OutputResponse {
     pub data: String,
     pub offset: u64,
@@ -92,46 +96,46 @@ pub struct OutputResponse {
     pub total_written: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct InputRequest {
     pub text: String,
     #[serde(default)]
     pub enter: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct InputRawRequest {
     pub data: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct InputResponse {
     pub bytes_written: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct KeysRequest {
     pub keys: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
 pub struct ResizeRequest {
     pub cols: u16,
     pub rows: u16,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
 pub struct ResizeResponse {
     pub cols: u16,
     pub rows: u16,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SignalRequest {
     pub signal: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SignalResponse {
     pub delivered: bool,
 }
@@ -139,6 +143,13 @@ pub struct SignalResponse {
 // -- Handlers -----------------------------------------------------------------
 
 /// `GET /api/v1/health`
+#[utoipa::path(
+    get,
+    path = "/api/v1/health",
+    tag = "terminal",
+    security(()),
+    responses((status = 200, body = HealthResponse))
+)]
 pub async fn health(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let h = compute_health(&s).await;
     Json(HealthResponse {
@@ -156,6 +167,16 @@ pub async fn health(State(s): State<Arc<Store>>) -> impl IntoResponse {
 }
 
 /// `GET /api/v1/ready` — readiness probe (200 when ready, 503 otherwise).
+#[utoipa::path(
+    get,
+    path = "/api/v1/ready",
+    tag = "terminal",
+    security(()),
+    responses(
+        (status = 200, body = ReadyResponse),
+        (status = 503, description = "Agent not ready yet", body = ReadyResponse),
+    )
+)]
 pub async fn ready(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let is_ready = s.ready.load(Ordering::Acquire);
     let status = if is_ready {
@@ -169,6 +190,13 @@ pub async fn ready(State(s): State<Arc<Store>>) -> impl IntoResponse {
 /// `GET /api/v1/livez` — liveness probe. Fully lock-free: only reads atomics
 /// and computes elapsed time. Use this for K8s liveness probes to avoid
 /// spurious kills when RwLocks are contended under heavy terminal I/O.
+#[utoipa::path(
+    get,
+    path = "/api/v1/livez",
+    tag = "terminal",
+    security(()),
+    responses((status = 200, body = LivezResponse))
+)]
 pub async fn livez(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let pid = s.terminal.child_pid.load(Ordering::Relaxed);
     let uptime = s.config.started_at.elapsed().as_secs() as i64;
@@ -180,6 +208,13 @@ pub async fn livez(State(s): State<Arc<Store>>) -> impl IntoResponse {
 }
 
 /// `GET /api/v1/screen`
+#[utoipa::path(
+    get,
+    path = "/api/v1/screen",
+    tag = "terminal",
+    params(ScreenQuery),
+    responses((status = 200, body = ScreenResponse))
+)]
 pub async fn screen(
     State(s): State<Arc<Store>>,
     Query(q): Query<ScreenQuery>,
@@ -198,6 +233,12 @@ pub async fn screen(
 }
 
 /// `GET /api/v1/screen/text`
+#[utoipa::path(
+    get,
+    path = "/api/v1/screen/text",
+    tag = "terminal",
+    responses((status = 200, description = "Screen lines joined by newlines", body = String, content_type = "text/plain"))
+)]
 pub async fn screen_text(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let snap = s.terminal.screen.read().await.snapshot();
     let text = snap.lines.join("\n");
@@ -205,12 +246,18 @@ pub async fn screen_text(State(s): State<Arc<Store>>) -> impl IntoResponse {
 }
 
 /// `GET /api/v1/output`
+#[utoipa::path(
+    get,
+    path = "/api/v1/output",
+    tag = "terminal",
+    params(OutputQuery),
+    responses((status = 200, body = OutputResponse))
+)]
 pub async fn output(
     State(s): State<Arc<Store>>,
     Query(q): Query<OutputQuery>,
 ) -> impl IntoResponse {
-    let ring = s.terminal.ring.read().await;
-    let r = read_ring_replay(&ring, q.offset, q.limit);
+    let r = read_ring_replay(&s.terminal.ring, q.offset, q.limit).await;
 
     Json(OutputResponse {
         data: r.data,
@@ -221,20 +268,49 @@ pub async fn output(
 }
 
 /// `GET /api/v1/status`
+#[utoipa::path(
+    get,
+    path = "/api/v1/status",
+    tag = "terminal",
+    responses((status = 200, body = SessionStatus))
+)]
 pub async fn status(State(s): State<Arc<Store>>) -> impl IntoResponse {
     Json(compute_status(&s).await)
 }
 
 /// `POST /api/v1/input`
+#[utoipa::path(
+    post,
+    path = "/api/v1/input",
+    tag = "terminal",
+    request_body = InputRequest,
+    responses(
+        (status = 200, body = InputResponse),
+        (status = 409, description = "Budget exceeded; the agent is parked", body = ErrorResponse),
+    )
+)]
 pub async fn input(
     State(s): State<Arc<Store>>,
     Json(req): Json<InputRequest>,
 ) -> impl IntoResponse {
-    let len = handle_input(&s, req.text, req.enter).await;
-    Json(InputResponse { bytes_written: len }).into_response()
+    match handle_input(&s, req.text, req.enter).await {
+        Ok(len) => Json(InputResponse { bytes_written: len }).into_response(),
+        Err(code) => code.to_http_response(error_message(code)).into_response(),
+    }
 }
 
 /// `POST /api/v1/input/raw`
+#[utoipa::path(
+    post,
+    path = "/api/v1/input/raw",
+    tag = "terminal",
+    request_body = InputRawRequest,
+    responses(
+        (status = 200, body = InputResponse),
+        (status = 400, description = "Invalid base64 data", body = ErrorResponse),
+        (status = 409, description = "Budget exceeded; the agent is parked", body = ErrorResponse),
+    )
+)]
 pub async fn input_raw(
     State(s): State<Arc<Store>>,
     Json(req): Json<InputRawRequest>,
@@ -245,24 +321,45 @@ pub async fn input_raw(
             return ErrorCode::BadRequest.to_http_response("invalid base64 data").into_response()
         }
     };
-    let len = handle_input_raw(&s, decoded).await;
-    Json(InputResponse { bytes_written: len }).into_response()
+    match handle_input_raw(&s, decoded).await {
+        Ok(len) => Json(InputResponse { bytes_written: len }).into_response(),
+        Err(code) => code.to_http_response(error_message(code)).into_response(),
+    }
 }
 
 /// `POST /api/v1/input/keys`
+#[utoipa::path(
+    post,
+    path = "/api/v1/input/keys",
+    tag = "terminal",
+    request_body = KeysRequest,
+    responses(
+        (status = 200, body = InputResponse),
+        (status = 400, description = "Unknown key name", body = ErrorResponse),
+        (status = 409, description = "Budget exceeded; the agent is parked", body = ErrorResponse),
+    )
+)]
 pub async fn input_keys(
     State(s): State<Arc<Store>>,
     Json(req): Json<KeysRequest>,
 ) -> impl IntoResponse {
     match handle_keys(&s, &req.keys).await {
         Ok(len) => Json(InputResponse { bytes_written: len }).into_response(),
-        Err(bad_key) => ErrorCode::BadRequest
-            .to_http_response(format!("unknown key: {bad_key}"))
-            .into_response(),
+        Err((code, message)) => code.to_http_response(message).into_response(),
     }
 }
 
 /// `POST /api/v1/resize`
+#[utoipa::path(
+    post,
+    path = "/api/v1/resize",
+    tag = "terminal",
+    request_body = ResizeRequest,
+    responses(
+        (status = 200, body = ResizeResponse),
+        (status = 400, description = "Zero cols or rows", body = ErrorResponse),
+    )
+)]
 pub async fn resize(
     State(s): State<Arc<Store>>,
     Json(req): Json<ResizeRequest>,
@@ -276,6 +373,16 @@ pub async fn resize(
 }
 
 /// `POST /api/v1/signal`
+#[utoipa::path(
+    post,
+    path = "/api/v1/signal",
+    tag = "terminal",
+    request_body = SignalRequest,
+    responses(
+        (status = 200, body = SignalResponse),
+        (status = 400, description = "Unknown signal name", body = ErrorResponse),
+    )
+)]
 pub async fn signal(
     State(s): State<Arc<Store>>,
     Json(req): Json<SignalRequest>,
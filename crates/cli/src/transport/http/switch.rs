@@ -9,16 +9,29 @@ use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::error::ErrorCode;
 use crate::profile::{ProfileEntry, ProfileInfo, ProfileMode};
 use crate::switch::SwitchRequest;
 use crate::transport::handler::resolve_switch_profile;
 use crate::transport::state::Store;
+use crate::transport::ErrorResponse;
 
 // -- Switch -------------------------------------------------------------------
 
 /// `POST /api/v1/session/switch` — schedule a credential switch (202 Accepted).
+#[utoipa::path(
+    post,
+    path = "/api/v1/session/switch",
+    tag = "session",
+    request_body = SwitchRequest,
+    responses(
+        (status = 202, description = "Switch scheduled"),
+        (status = 400, description = "Unknown profile", body = ErrorResponse),
+        (status = 409, description = "A switch is already in progress", body = ErrorResponse),
+    )
+)]
 pub async fn switch_session(
     State(s): State<Arc<Store>>,
     Json(mut req): Json<SwitchRequest>,
@@ -40,13 +53,19 @@ pub async fn switch_session(
 // -- Profiles -----------------------------------------------------------------
 
 /// Request body for `POST /api/v1/session/profiles`.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct RegisterProfilesRequest {
     pub profiles: Vec<ProfileEntry>,
 }
 
+/// Response for `POST /api/v1/session/profiles`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RegisterProfilesResponse {
+    pub registered: usize,
+}
+
 /// Response for `GET /api/v1/session/profiles`.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ProfileListResponse {
     pub profiles: Vec<ProfileInfo>,
     pub mode: String,
@@ -54,16 +73,29 @@ pub struct ProfileListResponse {
 }
 
 /// `POST /api/v1/session/profiles` — register credential profiles.
+#[utoipa::path(
+    post,
+    path = "/api/v1/session/profiles",
+    tag = "session",
+    request_body = RegisterProfilesRequest,
+    responses((status = 200, body = RegisterProfilesResponse))
+)]
 pub async fn register_profiles(
     State(s): State<Arc<Store>>,
     Json(req): Json<RegisterProfilesRequest>,
 ) -> impl IntoResponse {
-    let count = req.profiles.len();
+    let registered = req.profiles.len();
     s.profile.register(req.profiles).await;
-    Json(serde_json::json!({ "registered": count }))
+    Json(RegisterProfilesResponse { registered })
 }
 
 /// `GET /api/v1/session/profiles` — list all profiles with status.
+#[utoipa::path(
+    get,
+    path = "/api/v1/session/profiles",
+    tag = "session",
+    responses((status = 200, body = ProfileListResponse))
+)]
 pub async fn list_profiles(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let profiles = s.profile.list().await;
     let mode = s.profile.mode().as_str().to_owned();
@@ -74,24 +106,40 @@ pub async fn list_profiles(State(s): State<Arc<Store>>) -> impl IntoResponse {
 // -- Profile Mode -------------------------------------------------------------
 
 /// Request body for `PUT /api/v1/session/profiles/mode`.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ProfileModeRequest {
     pub mode: String,
 }
 
 /// Response for `GET/PUT /api/v1/session/profiles/mode`.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ProfileModeResponse {
     pub mode: String,
 }
 
 /// `GET /api/v1/session/profiles/mode` — get the current profile rotation mode.
+#[utoipa::path(
+    get,
+    path = "/api/v1/session/profiles/mode",
+    tag = "session",
+    responses((status = 200, body = ProfileModeResponse))
+)]
 pub async fn get_profile_mode(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let mode = s.profile.mode().as_str().to_owned();
     Json(ProfileModeResponse { mode })
 }
 
 /// `PUT /api/v1/session/profiles/mode` — set the profile rotation mode.
+#[utoipa::path(
+    put,
+    path = "/api/v1/session/profiles/mode",
+    tag = "session",
+    request_body = ProfileModeRequest,
+    responses(
+        (status = 200, body = ProfileModeResponse),
+        (status = 400, description = "Invalid mode", body = ErrorResponse),
+    )
+)]
 pub async fn put_profile_mode(
     State(s): State<Arc<Store>>,
     Json(req): Json<ProfileModeRequest>,
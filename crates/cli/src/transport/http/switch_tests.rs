@@ -30,7 +30,12 @@ async fn switch_rejects_when_in_progress() -> anyhow::Result<()> {
     state
         .switch
         .switch_tx
-        .try_send(SwitchRequest { credentials: None, force: false, profile: None })
+        .try_send(SwitchRequest {
+            credentials: None,
+            force: false,
+            profile: None,
+            span: tracing::Span::none(),
+        })
         .ok();
 
     let app = build_router(state);
@@ -9,15 +9,22 @@ use axum::extract::{Query, State};
 use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::error::ErrorCode;
+use crate::transcript::{
+    CatchupResponse, SearchOptions, SearchResponse, TranscriptMeta, DEFAULT_SEARCH_CONTEXT,
+    DEFAULT_SEARCH_LIMIT,
+};
 use crate::transport::state::Store;
+use crate::transport::ErrorResponse;
 
 // -- Types --------------------------------------------------------------------
 
 /// Query parameters for the transcript catchup endpoint.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CatchupQuery {
     #[serde(default)]
     pub since_transcript: u32,
@@ -25,18 +32,77 @@ pub struct CatchupQuery {
     pub since_line: u64,
 }
 
+/// Query parameters for the transcript search endpoint.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Text to find (case-insensitive).
+    #[serde(default)]
+    pub q: String,
+    /// Only match entries with this role (`user`, `assistant`, ...).
+    pub role: Option<String>,
+    /// Only match entries that call this tool (`Edit`, `Bash`, ...).
+    pub tool: Option<String>,
+    /// Lines of context before and after each match (max 20).
+    #[serde(default = "default_search_context")]
+    pub context: usize,
+    /// Maximum number of matches (max 1000).
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+}
+
+fn default_search_context() -> usize {
+    DEFAULT_SEARCH_CONTEXT
+}
+
+fn default_search_limit() -> usize {
+    DEFAULT_SEARCH_LIMIT
+}
+
+/// Response for the transcript list endpoint.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TranscriptListResponse {
+    pub transcripts: Vec<TranscriptMeta>,
+}
+
+/// JSON response for a single transcript.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TranscriptContentResponse {
+    pub number: u32,
+    pub content: String,
+}
+
 // -- Handlers -----------------------------------------------------------------
 
 /// `GET /api/v1/transcripts` — list all transcript snapshots.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transcripts",
+    tag = "transcripts",
+    responses((status = 200, body = TranscriptListResponse))
+)]
 pub async fn list_transcripts(State(s): State<Arc<Store>>) -> impl IntoResponse {
-    let list = s.transcript.list().await;
-    Json(serde_json::json!({ "transcripts": list }))
+    Json(TranscriptListResponse { transcripts: s.transcript.list().await })
 }
 
 /// `GET /api/v1/transcripts/catchup` — catch up from a cursor.
 ///
 /// If the `Accept` header is `text/plain`, returns plain text with download headers.
 /// Otherwise, returns JSON.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transcripts/catchup",
+    tag = "transcripts",
+    params(CatchupQuery),
+    responses(
+        (
+            status = 200,
+            description = "Transcripts since the cursor (plain text when `Accept: text/plain`)",
+            content((CatchupResponse = "application/json"), (String = "text/plain"))
+        ),
+        (status = 500, body = ErrorResponse),
+    )
+)]
 pub async fn catchup_transcripts(
     State(s): State<Arc<Store>>,
     Query(q): Query<CatchupQuery>,
@@ -74,10 +140,52 @@ pub async fn catchup_transcripts(
     }
 }
 
+/// `GET /api/v1/transcripts/search` — search saved transcripts and the live log.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transcripts/search",
+    tag = "transcripts",
+    params(SearchQuery),
+    responses(
+        (status = 200, body = SearchResponse),
+        (status = 400, description = "Empty query", body = ErrorResponse),
+    )
+)]
+pub async fn search_transcripts(
+    State(s): State<Arc<Store>>,
+    Query(q): Query<SearchQuery>,
+) -> impl IntoResponse {
+    let opts = SearchOptions {
+        query: q.q,
+        role: q.role,
+        tool: q.tool,
+        context: q.context,
+        limit: q.limit,
+    };
+    match s.transcript.search(&opts).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => ErrorCode::BadRequest.to_http_response(format!("{e}")).into_response(),
+    }
+}
+
 /// `GET /api/v1/transcripts/{number}` — get a single transcript's content.
 ///
 /// If the `Accept` header is `text/plain`, returns plain text with download headers.
 /// Otherwise, returns JSON.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transcripts/{number}",
+    tag = "transcripts",
+    params(("number" = u32, Path, description = "Transcript number")),
+    responses(
+        (
+            status = 200,
+            description = "Transcript content (plain text when `Accept: text/plain`)",
+            content((TranscriptContentResponse = "application/json"), (String = "text/plain"))
+        ),
+        (status = 400, description = "Transcript not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_transcript(
     State(s): State<Arc<Store>>,
     axum::extract::Path(number): axum::extract::Path<u32>,
@@ -100,7 +208,7 @@ pub async fn get_transcript(
                 }
                 (StatusCode::OK, response_headers, content).into_response()
             } else {
-                Json(serde_json::json!({ "number": number, "content": content })).into_response()
+                Json(TranscriptContentResponse { number, content }).into_response()
             }
         }
         Err(_) => ErrorCode::BadRequest
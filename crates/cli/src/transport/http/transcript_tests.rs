@@ -195,3 +195,45 @@ async fn hooks_start_non_compact_does_not_trigger_snapshot() -> anyhow::Result<(
     assert_eq!(list.len(), 0, "non-compact source should not trigger a snapshot");
     Ok(())
 }
+
+#[tokio::test]
+async fn search_transcripts_filters_by_tool() -> anyhow::Result<()> {
+    let (StoreCtx { store: state, .. }, _tmp) = transcript_state();
+
+    let log_path = _tmp.path().join("session.jsonl");
+    std::fs::write(
+        &log_path,
+        concat!(
+            r#"{"type":"user","message":{"role":"user","content":"update auth.rs"}}"#,
+            "\n",
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Edit","input":{"file_path":"auth.rs"}}]}}"#,
+            "\n",
+        ),
+    )?;
+
+    let app = build_router(state);
+    let server = axum_test::TestServer::new(app).anyhow()?;
+
+    let resp = server.get("/api/v1/transcripts/search?q=auth.rs&tool=Edit&context=1").await;
+    resp.assert_status(StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_str(&resp.text())?;
+    let matches = body["matches"].as_array().ok_or_else(|| anyhow::anyhow!("matches array"))?;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0]["transcript"], serde_json::Value::Null);
+    assert_eq!(matches[0]["line_number"], 2);
+    assert_eq!(matches[0]["role"], "assistant");
+    assert_eq!(matches[0]["before"].as_array().map(|a| a.len()), Some(1));
+    assert_eq!(body["truncated"], false);
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_transcripts_requires_query() -> anyhow::Result<()> {
+    let (StoreCtx { store: state, .. }, _tmp) = transcript_state();
+    let app = build_router(state);
+    let server = axum_test::TestServer::new(app).anyhow()?;
+
+    let resp = server.get("/api/v1/transcripts/search").await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
+    Ok(())
+}
@@ -10,20 +10,22 @@ use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::error::ErrorCode;
 use crate::transport::state::Store;
+use crate::transport::ErrorResponse;
 
 /// Maximum decoded file size: 10 MiB.
 const MAX_FILE_SIZE: usize = 10 * 1024 * 1024;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UploadRequest {
     pub filename: String,
     pub data: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UploadResponse {
     pub path: String,
     pub bytes_written: usize,
@@ -31,42 +33,64 @@ pub struct UploadResponse {
 
 /// `POST /api/v1/upload` — accept a base64-encoded file and write it to the
 /// session uploads directory.
+#[utoipa::path(
+    post,
+    path = "/api/v1/upload",
+    tag = "terminal",
+    request_body = UploadRequest,
+    responses(
+        (status = 200, body = UploadResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 500, body = ErrorResponse),
+    )
+)]
 pub async fn upload(
     State(s): State<Arc<Store>>,
     Json(req): Json<UploadRequest>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
+    let decoded = base64_decode(&req.data)
+        .map_err(|e| ErrorCode::BadRequest.to_http_response(format!("invalid base64: {e}")))?;
+    save_upload(&s, &req.filename, &decoded)
+        .await
+        .map(Json)
+        .map_err(|(code, message)| code.to_http_response(message))
+}
+
+/// Write decoded upload content to the session uploads directory. Shared by
+/// the HTTP and gRPC transports.
+pub async fn save_upload(
+    s: &Store,
+    filename: &str,
+    data: &[u8],
+) -> Result<UploadResponse, (ErrorCode, String)> {
     let session_dir = s.session_dir.as_ref().ok_or_else(|| {
-        ErrorCode::BadRequest.to_http_response("upload not available (no session directory)")
+        (ErrorCode::BadRequest, "upload not available (no session directory)".to_owned())
     })?;
 
-    let sanitized = sanitize_filename(&req.filename)
-        .ok_or_else(|| ErrorCode::BadRequest.to_http_response("invalid filename"))?;
+    let sanitized = sanitize_filename(filename)
+        .ok_or_else(|| (ErrorCode::BadRequest, "invalid filename".to_owned()))?;
 
-    let decoded = base64_decode(&req.data)
-        .map_err(|e| ErrorCode::BadRequest.to_http_response(format!("invalid base64: {e}")))?;
-
-    if decoded.len() > MAX_FILE_SIZE {
-        return Err(ErrorCode::BadRequest.to_http_response(format!(
-            "file too large: {} bytes (max {})",
-            decoded.len(),
-            MAX_FILE_SIZE,
-        )));
+    if data.len() > MAX_FILE_SIZE {
+        return Err((
+            ErrorCode::BadRequest,
+            format!("file too large: {} bytes (max {})", data.len(), MAX_FILE_SIZE),
+        ));
     }
 
     let uploads_dir = session_dir.join("uploads");
-    tokio::fs::create_dir_all(&uploads_dir).await.map_err(|e| {
-        ErrorCode::Internal.to_http_response(format!("failed to create uploads dir: {e}"))
-    })?;
+    tokio::fs::create_dir_all(&uploads_dir)
+        .await
+        .map_err(|e| (ErrorCode::Internal, format!("failed to create uploads dir: {e}")))?;
 
     let dest = resolve_unique_path(&uploads_dir, &sanitized).await;
 
-    tokio::fs::write(&dest, &decoded)
+    tokio::fs::write(&dest, data)
         .await
-        .map_err(|e| ErrorCode::Internal.to_http_response(format!("failed to write file: {e}")))?;
+        .map_err(|e| (ErrorCode::Internal, format!("failed to write file: {e}")))?;
 
     let abs_path = dest.canonicalize().unwrap_or(dest).to_string_lossy().into_owned();
 
-    Ok(Json(UploadResponse { path: abs_path, bytes_written: decoded.len() }))
+    Ok(UploadResponse { path: abs_path, bytes_written: data.len() })
 }
 
 /// Decode base64 (standard or URL-safe, with or without padding).
@@ -1,35 +1,57 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
-//! Session usage HTTP handler.
+//! Session usage and budget HTTP handlers.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
+use crate::budget::{BudgetConfig, BudgetStatus};
+use crate::transport::http::UpdatedResponse;
 use crate::transport::state::Store;
+use crate::usage::UsageCounters;
 
 // -- Types --------------------------------------------------------------------
 
 /// Response for the session usage endpoint.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UsageResponse {
     pub input_tokens: u64,
     pub output_tokens: u64,
     pub cache_read_tokens: u64,
     pub cache_write_tokens: u64,
     pub total_cost_usd: f64,
+    /// Cost priced from token counts (see `--pricing-file`).
+    pub estimated_cost_usd: f64,
     pub request_count: u64,
     pub total_api_ms: u64,
     pub uptime_secs: i64,
+    /// Budget progress (absent when no limit is configured).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetStatus>,
+    /// Breakdown by model name (absent until a model-tagged response is seen).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_model: BTreeMap<String, UsageCounters>,
+    /// Breakdown by credential profile (absent until profiles are registered).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_profile: BTreeMap<String, UsageCounters>,
 }
 
 // -- Handlers -----------------------------------------------------------------
 
 /// `GET /api/v1/session/usage` — cumulative API usage for this session.
+#[utoipa::path(
+    get,
+    path = "/api/v1/session/usage",
+    tag = "session",
+    responses((status = 200, body = UsageResponse))
+)]
 pub async fn session_usage(State(s): State<Arc<Store>>) -> impl IntoResponse {
     let snap = s.usage.snapshot().await;
     let uptime = s.config.started_at.elapsed().as_secs() as i64;
@@ -39,8 +61,39 @@ pub async fn session_usage(State(s): State<Arc<Store>>) -> impl IntoResponse {
         cache_read_tokens: snap.cache_read_tokens,
         cache_write_tokens: snap.cache_write_tokens,
         total_cost_usd: snap.total_cost_usd,
+        estimated_cost_usd: snap.estimated_cost_usd,
         request_count: snap.request_count,
         total_api_ms: snap.total_api_ms,
         uptime_secs: uptime,
+        budget: s.budget.status(&snap),
+        by_model: snap.by_model,
+        by_profile: snap.by_profile,
     })
 }
+
+/// `GET /api/v1/config/budget` — read current budget limits.
+#[utoipa::path(
+    get,
+    path = "/api/v1/config/budget",
+    tag = "session",
+    responses((status = 200, body = BudgetConfig))
+)]
+pub async fn get_budget_config(State(s): State<Arc<Store>>) -> impl IntoResponse {
+    Json(s.budget.config())
+}
+
+/// `PUT /api/v1/config/budget` — replace budget limits and re-check current usage.
+#[utoipa::path(
+    put,
+    path = "/api/v1/config/budget",
+    tag = "session",
+    request_body = BudgetConfig,
+    responses((status = 200, body = UpdatedResponse))
+)]
+pub async fn put_budget_config(
+    State(s): State<Arc<Store>>,
+    Json(config): Json<BudgetConfig>,
+) -> impl IntoResponse {
+    s.budget.set_config(config, &s.usage.snapshot().await);
+    Json(UpdatedResponse { updated: true })
+}
@@ -35,6 +35,8 @@ async fn usage_reflects_accumulated_deltas() -> anyhow::Result<()> {
             cache_read_input_tokens: 20,
             cost_usd: 0.005,
             duration_api_ms: 1200,
+            model: Some("gemini-2.5-pro".to_owned()),
+            ..Default::default()
         })
         .await;
 
@@ -50,5 +52,38 @@ async fn usage_reflects_accumulated_deltas() -> anyhow::Result<()> {
     assert_eq!(body["cache_write_tokens"], 10);
     assert_eq!(body["request_count"], 1);
     assert_eq!(body["total_api_ms"], 1200);
+    assert_eq!(body["by_model"]["gemini-2.5-pro"]["output_tokens"], 50);
+    // 100 input, 50 output and 20 cache-read tokens at gemini-2.5-pro prices.
+    let estimated = body["estimated_cost_usd"].as_f64().unwrap_or_default();
+    assert!((estimated - 0.0006275).abs() < 1e-12, "{estimated}");
+    Ok(())
+}
+
+/// PUT /api/v1/config/budget applies limits that show up in the usage response.
+#[tokio::test]
+async fn budget_config_round_trips_into_usage() -> anyhow::Result<()> {
+    let StoreCtx { store, .. } = StoreBuilder::new().build();
+    store
+        .usage
+        .accumulate(UsageDelta { output_tokens: 500, cost_usd: 1.0, ..Default::default() })
+        .await;
+    let app = build_router(store);
+    let server = axum_test::TestServer::new(app).anyhow()?;
+
+    let resp = server.get("/api/v1/session/usage").await;
+    let body: serde_json::Value = serde_json::from_str(&resp.text())?;
+    assert!(body.get("budget").is_none());
+
+    let config = serde_json::json!({ "max_output_tokens": 400, "action": "park" });
+    server.put("/api/v1/config/budget").json(&config).await.assert_status_ok();
+    let resp = server.get("/api/v1/config/budget").await;
+    let body: serde_json::Value = serde_json::from_str(&resp.text())?;
+    assert_eq!(body, config);
+
+    let resp = server.get("/api/v1/session/usage").await;
+    let body: serde_json::Value = serde_json::from_str(&resp.text())?;
+    assert_eq!(body["budget"]["action"], "park");
+    assert_eq!(body["budget"]["exceeded"], "output_tokens");
+    assert_eq!(body["budget"]["limits"][0]["used"], 500.0);
     Ok(())
 }
@@ -2,7 +2,7 @@
 // Copyright (c) 2026 Alfred Jean LLC
 
 //! Inbox consumer — subscribes to INBOX_EVENTS JetStream stream and delivers
-//! messages to the local agent session via JSONL inject queue + nudge.
+//! messages to the local agent session via JSONL inject queue + queued nudge.
 //!
 //! Part of bd-xtahx.3 (Phase 2: Coop JetStream inbox subscription).
 
@@ -17,8 +17,8 @@ use nix::fcntl::Flock;
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
-use crate::driver::AgentState;
-use crate::transport::handler::handle_nudge;
+use crate::nudge_queue::NewNudge;
+use crate::transport::handler::handle_enqueue_nudge;
 use crate::transport::nats::NatsAuth;
 use crate::transport::Store;
 
@@ -92,11 +92,15 @@ impl From<&InboxItem> for InjectEntry {
     }
 }
 
+/// Parse an RFC 3339 timestamp string to epoch milliseconds.
+fn parse_epoch_ms(s: &str) -> Option<u64> {
+    s.parse::<chrono_lite::DateTime>().ok().map(|ts| ts.timestamp_millis() as u64)
+}
+
 /// Parse an RFC 3339 timestamp string to epoch milliseconds, or return current time.
 fn chrono_parse_or_now(s: &str) -> u64 {
-    // Try ISO 8601 / RFC 3339 parse
-    if let Ok(ts) = s.parse::<chrono_lite::DateTime>() {
-        return ts.timestamp_millis() as u64;
+    if let Some(ms) = parse_epoch_ms(s) {
+        return ms;
     }
     // Fallback: current time
     std::time::SystemTime::now()
@@ -246,6 +250,20 @@ impl InboxConsumer {
             }
         };
 
+        // An expiry we can't read would otherwise expire the nudge on arrival.
+        let expires_at_ms = match item.expires_at.as_deref().map(parse_epoch_ms) {
+            Some(None) => {
+                tracing::warn!(
+                    "inbox: rejecting item {} with invalid expires_at {:?}",
+                    item.id,
+                    item.expires_at.as_deref().unwrap_or_default(),
+                );
+                let _ = msg.ack().await;
+                return;
+            }
+            parsed => parsed.flatten(),
+        };
+
         tracing::info!(
             "inbox: received item {} (type={}, from={}, to={})",
             item.id,
@@ -271,38 +289,32 @@ impl InboxConsumer {
             tracing::warn!("inbox: ack failed: {e}");
         }
 
-        // Nudge agent if idle
-        self.maybe_nudge(store, &item).await;
+        // Nudge the agent once it is idle
+        self.enqueue_nudge(store, &item, expires_at_ms);
     }
 
-    /// Nudge the agent if it's currently idle, so the next hook cycle picks up
-    /// the injected message.
-    async fn maybe_nudge(&self, store: &Store, item: &InboxItem) {
-        let agent = store.driver.agent_state.read().await;
-        if !matches!(&*agent, AgentState::Idle) {
-            tracing::debug!("inbox: agent not idle, skipping nudge");
-            return;
-        }
-        drop(agent);
-
-        let nudge_msg = format!(
-            "Inbox: {} from {} — {}",
-            item.item_type,
-            item.source,
-            truncate(&item.content, 80),
-        );
-
-        match handle_nudge(store, &nudge_msg).await {
-            Ok(outcome) => {
-                if outcome.delivered {
-                    tracing::info!("inbox: nudged agent for item {}", item.id);
-                } else {
-                    tracing::debug!("inbox: nudge not delivered (agent busy)");
-                }
-            }
-            Err(e) => {
-                tracing::debug!("inbox: nudge failed: {e:?}");
-            }
+    /// Queue a nudge so the next idle picks up the injected message. The
+    /// item's dedup key (or ID) collapses JetStream redeliveries.
+    fn enqueue_nudge(&self, store: &Store, item: &InboxItem, expires_at_ms: Option<u64>) {
+        let nudge = NewNudge {
+            message: format!(
+                "Inbox: {} from {} — {}",
+                item.item_type,
+                item.source,
+                truncate(&item.content, 80),
+            ),
+            priority: item.priority,
+            not_before_ms: None,
+            expires_at_ms,
+            dedup_key: Some(if item.dedup_key.is_empty() {
+                format!("inbox:{}", item.id)
+            } else {
+                format!("inbox:{}", item.dedup_key)
+            }),
+        };
+        match handle_enqueue_nudge(store, nudge) {
+            Ok(queued) => tracing::info!("inbox: queued nudge {} for item {}", queued.id, item.id),
+            Err(e) => tracing::debug!("inbox: nudge not queued: {e:?}"),
         }
     }
 
@@ -434,3 +446,7 @@ mod chrono_lite {
         (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
     }
 }
+
+#[cfg(test)]
+#[path = "inbox_tests.rs"]
+mod tests;
@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::parse_epoch_ms;
+
+#[yare::parameterized(
+    utc = { "2026-02-15T10:30:00Z", Some(1_771_151_400_000) },
+    fractional = { "2026-02-15T10:30:00.123Z", Some(1_771_151_400_123) },
+    epoch = { "1970-01-01T00:00:00Z", Some(0) },
+    empty = { "", None },
+    garbage = { "next tuesday, probably", None },
+    date_only = { "2026-02-15", None },
+)]
+fn parses_expiry(input: &str, expected: Option<u64>) {
+    assert_eq!(parse_epoch_ms(input), expected);
+}
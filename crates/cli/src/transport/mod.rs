@@ -5,13 +5,17 @@
 
 pub mod auth;
 pub mod compat;
+pub mod fanout;
 pub mod grpc;
 pub mod handler;
 pub mod http;
 pub mod inbox;
 pub mod nats;
 pub mod nats_relay;
+pub mod openapi;
 pub mod state;
+pub mod tokens;
+pub mod trace;
 pub mod ws;
 
 pub use state::Store;
@@ -23,18 +27,20 @@ use axum::middleware;
 use axum::response::Html;
 #[cfg(debug_assertions)]
 use axum::response::IntoResponse;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::{Json, Router};
 use base64::Engine;
 use serde::{Deserialize, Serialize};
 use tower_http::cors::CorsLayer;
+use utoipa::ToSchema;
 
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, RwLock};
 use tokio_util::sync::CancellationToken;
 
 use crate::driver::{AgentState, NudgeStep, PromptKind, QuestionAnswer, RespondEncoder};
 use crate::error::ErrorCode;
 use crate::event::{InputEvent, TransitionEvent};
+use crate::metrics::Metrics;
 
 /// Translate a named key to its terminal escape sequence (case-insensitive).
 pub fn encode_key(name: &str) -> Option<Vec<u8>> {
@@ -88,6 +94,7 @@ pub fn encode_key(name: &str) -> Option<Vec<u8>> {
 /// and wait for the backend to confirm the write completed before starting
 /// the delay timer.  This prevents the channel-timing bug where large
 /// writes block in the backend while the delay runs concurrently.
+#[tracing::instrument(name = "pty.deliver", skip_all, fields(steps = steps.len()))]
 pub async fn deliver_steps(
     input_tx: &tokio::sync::mpsc::Sender<InputEvent>,
     steps: Vec<NudgeStep>,
@@ -124,6 +131,7 @@ pub fn spawn_enter_retry(
     mut state_rx: broadcast::Receiver<TransitionEvent>,
     input_activity: Arc<tokio::sync::Notify>,
     timeout: std::time::Duration,
+    metrics: Arc<Metrics>,
 ) -> CancellationToken {
     let cancel = CancellationToken::new();
     let cancel_clone = cancel.clone();
@@ -145,6 +153,7 @@ pub fn spawn_enter_retry(
             _ = tokio::time::sleep(timeout) => {
                 // Timeout — retry Enter once
                 tracing::debug!("nudge enter-retry: timeout reached, resending \\r");
+                metrics.record_enter_retry();
                 let _ = input_tx.send(InputEvent::Write(bytes::Bytes::from_static(b"\r"))).await;
             }
         }
@@ -268,6 +277,35 @@ pub fn read_ring_combined(ring: &crate::ring::RingBuffer, offset: u64) -> Vec<u8
     [a, b].concat()
 }
 
+/// Read raw bytes for a replay request, returning the clamped start offset
+/// and the ring's `total_written` along with the data. See
+/// [`read_ring_replay`] for the clamping rules. Spilled history is read from
+/// disk after the ring lock is released.
+pub async fn read_ring_range(
+    ring: &RwLock<crate::ring::RingBuffer>,
+    offset: u64,
+    limit: Option<usize>,
+) -> (u64, Vec<u8>, u64) {
+    let ring = ring.read().await;
+    let total_written = ring.total_written();
+    match limit {
+        Some(limit) => {
+            let offset = offset.max(ring.oldest_replayable());
+            let read = ring.begin_read(offset, limit);
+            drop(ring);
+            let data = match read {
+                Some(read) => read.finish().await.unwrap_or_default(),
+                None => Vec::new(),
+            };
+            (offset, data, total_written)
+        }
+        None => {
+            let offset = offset.max(ring.oldest_offset());
+            (offset, read_ring_combined(&ring, offset), total_written)
+        }
+    }
+}
+
 /// Result of reading and encoding a ring buffer replay.
 pub struct ReplayData {
     pub data: String,
@@ -278,17 +316,16 @@ pub struct ReplayData {
 
 /// Clamp offset to the oldest available position, read from the ring buffer,
 /// optionally truncate, and base64-encode.  Shared by WS and HTTP handlers.
-pub fn read_ring_replay(
-    ring: &crate::ring::RingBuffer,
+///
+/// With a `limit`, offsets older than the in-memory window are served from
+/// the spill log (when enabled) so clients can page through the full
+/// history. Without one, the offset is clamped to the in-memory window.
+pub async fn read_ring_replay(
+    ring: &RwLock<crate::ring::RingBuffer>,
     offset: u64,
     limit: Option<usize>,
 ) -> ReplayData {
-    let total_written = ring.total_written();
-    let offset = offset.max(ring.oldest_offset());
-    let mut combined = read_ring_combined(ring, offset);
-    if let Some(limit) = limit {
-        combined.truncate(limit);
-    }
+    let (offset, combined, total_written) = read_ring_range(ring, offset, limit).await;
     let read_len = combined.len() as u64;
     ReplayData {
         data: base64::engine::general_purpose::STANDARD.encode(&combined),
@@ -299,13 +336,13 @@ pub fn read_ring_replay(
 }
 
 /// Top-level error response envelope shared across HTTP and WebSocket.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: ErrorBody,
 }
 
 /// Error body containing a machine-readable code and human-readable message.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ErrorBody {
     pub code: String,
     pub message: String,
@@ -387,6 +424,7 @@ fn build_router_inner(
         .route("/api/v1/health", get(http::health))
         .route("/api/v1/ready", get(http::ready))
         .route("/api/v1/livez", get(http::livez))
+        .route("/api/v1/openapi.json", get(openapi::openapi_json))
         .route("/api/v1/screen", get(http::screen))
         .route("/api/v1/screen/text", get(http::screen_text))
         .route("/api/v1/output", get(http::output))
@@ -398,6 +436,8 @@ fn build_router_inner(
         .route("/api/v1/signal", post(http::signal))
         .route("/api/v1/agent", get(http::agent))
         .route("/api/v1/agent/nudge", post(http::agent_nudge))
+        .route("/api/v1/agent/nudge/queue", post(http::enqueue_nudge).get(http::list_nudge_queue))
+        .route("/api/v1/agent/nudge/queue/{id}", delete(http::cancel_nudge))
         .route("/api/v1/agent/respond", post(http::agent_respond))
         .route("/api/v1/hooks/stop", post(http::hooks_stop))
         .route("/api/v1/stop/resolve", post(http::resolve_stop))
@@ -411,19 +451,26 @@ fn build_router_inner(
         .route("/api/v1/session/restart", post(http::restart_session))
         .route("/api/v1/shutdown", post(http::shutdown))
         .route("/api/v1/config/stop", get(http::get_stop_config).put(http::put_stop_config))
+        .route("/api/v1/config/budget", get(http::get_budget_config).put(http::put_budget_config))
         .route("/api/v1/hooks/start", post(http::hooks_start))
         .route("/api/v1/config/start", get(http::get_start_config).put(http::put_start_config))
         .route("/api/v1/transcripts", get(http::list_transcripts))
         .route("/api/v1/transcripts/catchup", get(http::catchup_transcripts))
+        .route("/api/v1/transcripts/search", get(http::search_transcripts))
         .route("/api/v1/events/catchup", get(http::catchup_events))
+        .route("/api/v1/events/stream", get(http::stream_events))
+        .route("/api/v1/audit/catchup", get(http::catchup_audit))
         .route("/api/v1/recording", get(http::get_recording).put(http::put_recording))
         .route("/api/v1/recording/catchup", get(http::catchup_recording))
         .route("/api/v1/recording/download", get(http::download_recording))
         .route("/api/v1/upload", post(http::upload))
         .route("/api/v1/transcripts/{number}", get(http::get_transcript))
         .route("/ws", get(ws::ws_handler))
+        .route("/metrics", get(http::metrics))
+        .layer(middleware::from_fn_with_state(state.clone(), http::audit_layer))
         .layer(middleware::from_fn_with_state(state.clone(), auth::auth_layer))
         .layer(middleware::from_fn(compat::http_compat_layer))
+        .layer(middleware::from_fn(trace::trace_layer))
         .layer(CorsLayer::permissive())
         .with_state(state)
 }
@@ -435,6 +482,7 @@ pub fn build_health_router(state: Arc<Store>) -> Router {
         .route("/api/v1/ready", get(http::ready))
         .route("/api/v1/livez", get(http::livez))
         .route("/api/v1/agent", get(http::agent))
+        .route("/metrics", get(http::metrics))
         .with_state(state)
 }
 
@@ -7,6 +7,7 @@ use super::{encode_response, resolve_permission_option, resolve_plan_option, spa
 use crate::driver::claude::encoding::ClaudeRespondEncoder;
 use crate::driver::{AgentState, PromptContext, PromptKind};
 use crate::event::TransitionEvent;
+use crate::metrics::{Exposition, Metrics};
 
 #[test]
 fn permission_option_takes_precedence_over_accept() {
@@ -123,8 +124,15 @@ async fn enter_retry_sends_cr_on_timeout() -> anyhow::Result<()> {
     let state_rx = state_tx.subscribe();
     let activity = Arc::new(tokio::sync::Notify::new());
 
-    let _cancel =
-        spawn_enter_retry(input_tx, state_rx, activity, std::time::Duration::from_millis(50));
+    let metrics = Arc::new(Metrics::default());
+
+    let _cancel = spawn_enter_retry(
+        input_tx,
+        state_rx,
+        activity,
+        std::time::Duration::from_millis(50),
+        Arc::clone(&metrics),
+    );
 
     // Wait for the retry to fire
     let event =
@@ -136,6 +144,9 @@ async fn enter_retry_sends_cr_on_timeout() -> anyhow::Result<()> {
         }
         other => panic!("expected Write(\\r), got {other:?}"),
     }
+    let mut out = Exposition::default();
+    metrics.write_to(&mut out);
+    assert!(out.finish().contains("coop_nudge_enter_retries_total 1\n"));
     Ok(())
 }
 
@@ -147,8 +158,13 @@ async fn enter_retry_cancelled_by_state_transition() -> anyhow::Result<()> {
     let state_rx = state_tx.subscribe();
     let activity = Arc::new(tokio::sync::Notify::new());
 
-    let _cancel =
-        spawn_enter_retry(input_tx, state_rx, activity, std::time::Duration::from_millis(100));
+    let _cancel = spawn_enter_retry(
+        input_tx,
+        state_rx,
+        activity,
+        std::time::Duration::from_millis(100),
+        Arc::new(Metrics::default()),
+    );
 
     // Send a Working state transition — should cancel the retry
     let _ = state_tx.send(TransitionEvent {
@@ -179,6 +195,7 @@ async fn enter_retry_cancelled_by_input_activity() -> anyhow::Result<()> {
         state_rx,
         Arc::clone(&activity),
         std::time::Duration::from_millis(100),
+        Arc::new(Metrics::default()),
     );
 
     // Give the spawned task time to register the notified() future
@@ -202,8 +219,13 @@ async fn enter_retry_cancelled_by_token() -> anyhow::Result<()> {
     let state_rx = state_tx.subscribe();
     let activity = Arc::new(tokio::sync::Notify::new());
 
-    let cancel =
-        spawn_enter_retry(input_tx, state_rx, activity, std::time::Duration::from_millis(100));
+    let cancel = spawn_enter_retry(
+        input_tx,
+        state_rx,
+        activity,
+        std::time::Duration::from_millis(100),
+        Arc::new(Metrics::default()),
+    );
 
     // Cancel via the token (simulates next InputGate::acquire)
     cancel.cancel();
@@ -214,3 +236,37 @@ async fn enter_retry_cancelled_by_token() -> anyhow::Result<()> {
     assert!(result.is_err(), "expected timeout (no retry), got {result:?}");
     Ok(())
 }
+
+#[tokio::test]
+async fn ring_replay_clamps_without_limit() {
+    let mut ring = crate::ring::RingBuffer::new(4);
+    ring.write(b"abcdefgh");
+    let ring = tokio::sync::RwLock::new(ring);
+    let r = super::read_ring_replay(&ring, 0, None).await;
+    assert_eq!(r.offset, 4);
+    assert_eq!(r.next_offset, 8);
+    assert_eq!(r.total_written, 8);
+}
+
+#[tokio::test]
+async fn ring_replay_pages_through_spill_with_limit() -> anyhow::Result<()> {
+    use base64::Engine;
+
+    let dir = tempfile::tempdir()?;
+    let spill = crate::spill::SpillLog::open(dir.path())?;
+    let mut ring = crate::ring::RingBuffer::new(4).with_spill(Some(spill));
+    ring.write(b"abcdefgh");
+    let ring = tokio::sync::RwLock::new(ring);
+
+    let r = super::read_ring_replay(&ring, 0, Some(3)).await;
+    assert_eq!(base64::engine::general_purpose::STANDARD.decode(&r.data)?, b"abc");
+    assert_eq!((r.offset, r.next_offset), (0, 3));
+
+    let r = super::read_ring_replay(&ring, r.next_offset, Some(3)).await;
+    assert_eq!(base64::engine::general_purpose::STANDARD.decode(&r.data)?, b"def");
+
+    // Without a limit the in-memory window is still the default.
+    let r = super::read_ring_replay(&ring, 0, None).await;
+    assert_eq!(r.offset, 4);
+    Ok(())
+}
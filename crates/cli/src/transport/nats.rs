@@ -10,8 +10,8 @@ use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 
 use crate::transport::ws::{
-    profile_event_to_msg, start_event_to_msg, stop_event_to_msg, transition_to_msg,
-    usage_event_to_msg, ServerMessage,
+    budget_event_to_msg, idle_event_to_msg, nudge_queue_event_to_msg, profile_event_to_msg,
+    start_event_to_msg, stop_event_to_msg, transition_to_msg, usage_event_to_msg, ServerMessage,
 };
 use crate::transport::Store;
 
@@ -61,7 +61,10 @@ impl NatsPublisher {
         let mut hook_rx = store.channels.hook_tx.subscribe();
         let mut stop_rx = store.stop.stop_tx.subscribe();
         let mut start_rx = store.start.start_tx.subscribe();
+        let mut idle_rx = store.idle.idle_tx.subscribe();
+        let mut nudge_queue_rx = store.nudge_queue.queue_tx.subscribe();
         let mut usage_rx = store.usage.usage_tx.subscribe();
+        let mut budget_rx = store.budget.budget_tx.subscribe();
         let mut profile_rx = store.profile.profile_tx.subscribe();
 
         loop {
@@ -79,6 +82,8 @@ impl NatsPublisher {
                             r#type: e.r#type,
                             subtype: e.subtype,
                             option: e.option,
+                            decision: e.decision,
+                            rule: e.rule,
                         }
                     }).await;
                 }
@@ -97,11 +102,26 @@ impl NatsPublisher {
                         start_event_to_msg(&e)
                     }).await;
                 }
+                event = idle_rx.recv() => {
+                    self.handle_with(store, event, &format!("{}.idle", self.prefix), |e| {
+                        idle_event_to_msg(&e)
+                    }).await;
+                }
+                event = nudge_queue_rx.recv() => {
+                    self.handle_with(store, event, &format!("{}.nudge", self.prefix), |e| {
+                        nudge_queue_event_to_msg(&e)
+                    }).await;
+                }
                 event = usage_rx.recv() => {
                     self.handle_with(store, event, &format!("{}.usage", self.prefix), |e| {
                         usage_event_to_msg(&e)
                     }).await;
                 }
+                event = budget_rx.recv() => {
+                    self.handle_with(store, event, &format!("{}.budget", self.prefix), |e| {
+                        budget_event_to_msg(&e)
+                    }).await;
+                }
                 event = profile_rx.recv() => {
                     self.handle_with(store, event, &format!("{}.profile", self.prefix), |e| {
                         profile_event_to_msg(&e)
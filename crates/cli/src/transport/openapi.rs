@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! OpenAPI document for the HTTP API, generated from the handler annotations.
+
+use axum::response::IntoResponse;
+use axum::Json;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::transport::{http, ws};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "coop", description = "HTTP API for a coop-managed agent session."),
+    paths(
+        http::health,
+        http::ready,
+        http::livez,
+        http::screen,
+        http::screen_text,
+        http::output,
+        http::status,
+        http::input,
+        http::input_raw,
+        http::input_keys,
+        http::resize,
+        http::signal,
+        http::upload,
+        http::agent,
+        http::agent_nudge,
+        http::enqueue_nudge,
+        http::list_nudge_queue,
+        http::cancel_nudge,
+        http::agent_respond,
+        http::hooks_stop,
+        http::resolve_stop,
+        http::get_stop_config,
+        http::put_stop_config,
+        http::hooks_start,
+        http::get_start_config,
+        http::put_start_config,
+        http::session_usage,
+        http::get_budget_config,
+        http::put_budget_config,
+        http::register_profiles,
+        http::list_profiles,
+        http::get_profile_mode,
+        http::put_profile_mode,
+        http::switch_session,
+        http::restart_session,
+        http::shutdown,
+        http::metrics,
+        http::list_transcripts,
+        http::catchup_transcripts,
+        http::search_transcripts,
+        http::get_transcript,
+        http::catchup_events,
+        http::catchup_audit,
+        http::stream_events,
+        http::get_recording,
+        http::put_recording,
+        http::catchup_recording,
+        http::download_recording,
+        ws::ws_handler,
+        openapi_json,
+    ),
+    modifiers(&BearerAuth),
+    security(("bearer" = [])),
+    tags(
+        (name = "terminal", description = "Screen, output, and terminal input"),
+        (name = "agent", description = "Agent state, nudges, and prompt responses"),
+        (name = "stop", description = "Stop hook gating"),
+        (name = "start", description = "Start hook context injection"),
+        (name = "session", description = "Usage, credential profiles, and switching"),
+        (name = "transcripts", description = "Transcript snapshots"),
+        (name = "events", description = "Push events and catchup"),
+        (name = "recording", description = "Session recording"),
+        (name = "lifecycle", description = "Process lifecycle"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the `--auth-token` Bearer scheme.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// `GET /api/v1/openapi.json` — this document.
+#[utoipa::path(
+    get,
+    path = "/api/v1/openapi.json",
+    tag = "lifecycle",
+    security(()),
+    responses((status = 200, description = "OpenAPI 3.1 document", body = Object))
+)]
+pub async fn openapi_json() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+#[path = "openapi_tests.rs"]
+mod tests;
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::collections::BTreeSet;
+
+use utoipa::OpenApi;
+
+use super::ApiDoc;
+use crate::test_support::{AnyhowExt, StoreBuilder, StoreCtx};
+use crate::transport::build_router;
+
+/// Extract `(METHOD, path)` pairs from the `.route(...)` calls in
+/// `build_router_inner`.
+fn router_routes() -> anyhow::Result<BTreeSet<(String, String)>> {
+    let source = include_str!("mod.rs");
+    let start = source
+        .find("fn build_router_inner(")
+        .ok_or_else(|| anyhow::anyhow!("build_router_inner not found"))?;
+    let body = &source[start..];
+    let body = &body[..body.find("\n}\n").unwrap_or(body.len())];
+
+    let body = &body[..body.find(".layer(").unwrap_or(body.len())];
+
+    let path_re = regex::Regex::new(r#"^\s*"([^"]+)""#)?;
+    let method_re = regex::Regex::new(r"\b(get|post|put|delete|patch)\(")?;
+    let mut routes = BTreeSet::new();
+    for segment in body.split(".route(").skip(1) {
+        let path = path_re
+            .captures(segment)
+            .map(|c| c[1].to_owned())
+            .ok_or_else(|| anyhow::anyhow!("unparsed route: {segment}"))?;
+        // The web terminal page, not part of the API.
+        if path == "/" {
+            continue;
+        }
+        for method in method_re.captures_iter(segment) {
+            routes.insert((method[1].to_uppercase(), path.clone()));
+        }
+    }
+    Ok(routes)
+}
+
+fn documented_routes() -> anyhow::Result<BTreeSet<(String, String)>> {
+    let doc = serde_json::to_value(ApiDoc::openapi())?;
+    let paths = doc["paths"].as_object().ok_or_else(|| anyhow::anyhow!("no paths"))?;
+    let mut routes = BTreeSet::new();
+    for (path, item) in paths {
+        for method in item.as_object().into_iter().flat_map(|m| m.keys()) {
+            routes.insert((method.to_uppercase(), path.clone()));
+        }
+    }
+    Ok(routes)
+}
+
+#[test]
+fn every_route_is_documented() -> anyhow::Result<()> {
+    let routes = router_routes()?;
+    assert!(routes.len() > 35, "route extraction looks broken: {routes:?}");
+    let documented = documented_routes()?;
+
+    let missing: Vec<_> = routes.difference(&documented).collect();
+    assert!(missing.is_empty(), "routes missing from the OpenAPI document: {missing:?}");
+    let stale: Vec<_> = documented.difference(&routes).collect();
+    assert!(stale.is_empty(), "documented routes not in build_router: {stale:?}");
+    Ok(())
+}
+
+#[test]
+fn schemas_resolve() -> anyhow::Result<()> {
+    let doc = serde_json::to_value(ApiDoc::openapi())?;
+    let schemas = doc["components"]["schemas"]
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("no component schemas"))?;
+    for name in ["NudgeRequest", "SessionStatus", "StopConfig", "PromptContext", "ErrorResponse"] {
+        assert!(schemas.contains_key(name), "missing schema {name}");
+    }
+
+    // Every $ref points at a defined component.
+    let text = doc.to_string();
+    let ref_re = regex::Regex::new(r##""\$ref":"#/components/schemas/([^"]+)""##)?;
+    for cap in ref_re.captures_iter(&text) {
+        assert!(schemas.contains_key(&cap[1]), "dangling $ref to {}", &cap[1]);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn served_without_auth() -> anyhow::Result<()> {
+    let StoreCtx { store, .. } = StoreBuilder::new().auth_token("secret").build();
+    let server = axum_test::TestServer::new(build_router(store)).anyhow()?;
+
+    let resp = server.get("/api/v1/openapi.json").await;
+    resp.assert_status_ok();
+    let body: serde_json::Value = serde_json::from_str(&resp.text())?;
+    assert!(body["openapi"].as_str().is_some_and(|v| v.starts_with("3.")));
+    assert!(body["paths"]["/api/v1/agent/nudge"]["post"].is_object());
+    assert_eq!(body["components"]["securitySchemes"]["bearer"]["scheme"], "bearer");
+    Ok(())
+}
@@ -9,6 +9,8 @@ use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio_util::sync::CancellationToken;
 
+use crate::audit::AuditLog;
+use crate::budget::BudgetState;
 use crate::config::GroomLevel;
 use crate::driver::{
     AgentState, AgentType, ErrorCategory, ExitStatus, NudgeEncoder, RespondEncoder,
@@ -17,14 +19,20 @@ use crate::event::{
     InputEvent, OutputEvent, PromptOutcome, RawHookEvent, RawMessageEvent, TransitionEvent,
 };
 use crate::event_log::EventLog;
+use crate::idle::IdleState;
+use crate::metrics::Metrics;
+use crate::nudge_queue::NudgeQueue;
+use crate::policy::RespondPolicy;
 use crate::profile::ProfileState;
 use crate::record::RecordingState;
 use crate::ring::RingBuffer;
 use crate::screen::Screen;
+use crate::spill::SpillLog;
 use crate::start::StartState;
 use crate::stop::StopState;
 use crate::switch::SwitchState;
 use crate::transcript::TranscriptState;
+use crate::transport::tokens::ApiTokens;
 use crate::usage::UsageState;
 
 /// Shared application state passed to all handlers via axum `State` extractor.
@@ -52,10 +60,16 @@ pub struct Store {
     pub switch: Arc<SwitchState>,
     /// Start hook state. Always present (defaults to empty config).
     pub start: Arc<StartState>,
+    /// Idle escalation steps. Always present (defaults to no steps).
+    pub idle: Arc<IdleState>,
+    /// Queued nudges awaiting an idle agent. Always present.
+    pub nudge_queue: Arc<NudgeQueue>,
     /// Transcript snapshot state. Always present.
     pub transcript: Arc<TranscriptState>,
     /// Per-session API usage tracking. Always present.
     pub usage: Arc<UsageState>,
+    /// Usage budget limits and their progress. Always present (defaults to no limits).
+    pub budget: Arc<BudgetState>,
     /// Named credential profiles for rotation. Always present (defaults to empty).
     pub profile: Arc<ProfileState>,
     /// Serializes structured input delivery (nudge, respond) and enforces
@@ -67,6 +81,10 @@ pub struct Store {
     pub input_activity: Arc<tokio::sync::Notify>,
     /// File-backed event log for state/hook event catchup on WS reconnect.
     pub event_log: Arc<EventLog>,
+    /// File-backed audit log of mutating API calls.
+    pub audit_log: Arc<AuditLog>,
+    /// Event counters exported on `GET /metrics`.
+    pub metrics: Arc<Metrics>,
     /// Session recording state. Always present (defaults to disabled).
     pub record: Arc<RecordingState>,
     /// Session directory for file uploads. `None` in attach mode.
@@ -137,7 +155,16 @@ impl TerminalState {
         *self.screen.write().await = Screen::new(cols, rows);
         {
             let mut ring = self.ring.write().await;
-            *ring = RingBuffer::new(ring_size);
+            // Offsets restart at zero, so the spill log starts over too.
+            let spill = ring.take_spill().and_then(|old| {
+                // Drop the old log first so its pending compression stands down.
+                let dir = old.dir().to_owned();
+                drop(old);
+                SpillLog::open(&dir)
+                    .map_err(|e| tracing::warn!("failed to reopen ring spill: {e:#}"))
+                    .ok()
+            });
+            *ring = RingBuffer::new(ring_size).with_spill(spill);
             self.ring_total_written.store(0, std::sync::atomic::Ordering::Relaxed);
         }
         self.child_pid.store(0, std::sync::atomic::Ordering::Release);
@@ -217,6 +244,8 @@ pub struct SessionSettings {
     pub started_at: Instant,
     pub agent: AgentType,
     pub auth_token: Option<String>,
+    /// Named, scoped tokens from `--auth-tokens-file`.
+    pub api_tokens: Option<Arc<ApiTokens>>,
     pub nudge_encoder: Option<Arc<dyn NudgeEncoder>>,
     pub respond_encoder: Option<Arc<dyn RespondEncoder>>,
     /// Timeout for the enter-retry safety net after nudge delivery.
@@ -224,6 +253,8 @@ pub struct SessionSettings {
     pub nudge_timeout: Duration,
     /// How aggressively coop auto-responds to agent prompts.
     pub groom: GroomLevel,
+    /// Auto-respond rules for tool permission prompts (groom=auto only).
+    pub respond_policy: Option<Arc<RespondPolicy>>,
 }
 
 /// Runtime lifecycle primitives.
@@ -310,6 +341,7 @@ impl std::fmt::Debug for Store {
         f.debug_struct("AppState")
             .field("agent", &self.config.agent)
             .field("auth_token", &self.config.auth_token.is_some())
+            .field("api_tokens", &self.config.api_tokens.as_ref().map(|t| t.tokens.len()))
             .finish()
     }
 }
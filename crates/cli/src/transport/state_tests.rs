@@ -111,3 +111,24 @@ fn snapshot_fn_returns_dimensions() {
     assert_eq!(snap.cols, 80);
     assert_eq!(snap.rows, 24);
 }
+
+#[tokio::test]
+async fn terminal_reset_restarts_spill_log() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let terminal = test_terminal();
+    {
+        let mut ring = terminal.ring.write().await;
+        let spill = crate::spill::SpillLog::open(dir.path())?;
+        *ring = RingBuffer::new(4).with_spill(Some(spill));
+        ring.write(b"old session output");
+    }
+
+    terminal.reset(80, 24, 4).await;
+
+    let mut ring = terminal.ring.write().await;
+    assert!(ring.has_spill());
+    ring.write(b"new!more");
+    // Offset 0 is older than the window and comes from the new log only.
+    assert_eq!(ring.read_range(0, 100), Some(b"new!more".to_vec()));
+    Ok(())
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Named API tokens with per-route permission scopes.
+//!
+//! Loaded from `--auth-tokens-file` alongside (or instead of) the single
+//! `--auth-token`, which keeps granting every scope.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::transport::auth::constant_time_eq;
+
+/// Permission scope required by an API operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Screen, output, status, agent state, transcripts, usage, event streams.
+    Read,
+    /// Terminal input, keys, resize and file upload.
+    Input,
+    /// Nudge, respond, stop resolution and stop/start hook config.
+    Agent,
+    /// Registering credential profiles and changing the profile mode.
+    Credentials,
+    /// Signals, shutdown, session switch/restart and recording control.
+    /// Implies every other scope.
+    Admin,
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Input => "input",
+            Self::Agent => "agent",
+            Self::Credentials => "credentials",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+/// The identity and scopes an authenticated caller holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grant {
+    pub name: String,
+    pub scopes: Vec<Scope>,
+}
+
+impl Grant {
+    /// A grant holding every scope.
+    pub fn admin(name: impl Into<String>) -> Self {
+        Self { name: name.into(), scopes: vec![Scope::Admin] }
+    }
+
+    /// Whether this grant permits `scope`. `admin` permits everything.
+    pub fn allows(&self, scope: Scope) -> bool {
+        self.scopes.iter().any(|s| *s == scope || *s == Scope::Admin)
+    }
+}
+
+/// A single named token from the tokens file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiToken {
+    pub name: String,
+    pub token: String,
+    pub scopes: Vec<Scope>,
+}
+
+/// Contents of the tokens file.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ApiTokens {
+    #[serde(default)]
+    pub tokens: Vec<ApiToken>,
+}
+
+impl ApiTokens {
+    /// Load a tokens file. Files ending in `.toml` are parsed as TOML,
+    /// everything else as JSON.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read tokens file {}", path.display()))?;
+        let is_toml = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let tokens: Self = if is_toml {
+            toml::from_str(&contents).map_err(anyhow::Error::from)
+        } else {
+            serde_json::from_str(&contents).map_err(anyhow::Error::from)
+        }
+        .with_context(|| format!("invalid tokens file {}", path.display()))?;
+        tokens.validate().with_context(|| format!("invalid tokens file {}", path.display()))?;
+        Ok(tokens)
+    }
+
+    /// Reject empty tokens, tokens without scopes, and duplicate names or values.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut names = HashSet::new();
+        let mut values = HashSet::new();
+        for t in &self.tokens {
+            anyhow::ensure!(!t.name.is_empty(), "token name must not be empty");
+            anyhow::ensure!(!t.token.is_empty(), "token {:?} has an empty value", t.name);
+            anyhow::ensure!(!t.scopes.is_empty(), "token {:?} has no scopes", t.name);
+            anyhow::ensure!(names.insert(t.name.as_str()), "duplicate token name {:?}", t.name);
+            anyhow::ensure!(values.insert(t.token.as_str()), "token {:?} reuses a value", t.name);
+        }
+        Ok(())
+    }
+
+    /// Resolve a presented token to its grant.
+    pub fn resolve(&self, token: &str) -> Option<Grant> {
+        self.tokens
+            .iter()
+            .find(|t| constant_time_eq(&t.token, token))
+            .map(|t| Grant { name: t.name.clone(), scopes: t.scopes.clone() })
+    }
+}
+
+#[cfg(test)]
+#[path = "tokens_tests.rs"]
+mod tests;
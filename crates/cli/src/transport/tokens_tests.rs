@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::{ApiToken, ApiTokens, Grant, Scope};
+
+fn token(name: &str, value: &str, scopes: &[Scope]) -> ApiToken {
+    ApiToken { name: name.to_owned(), token: value.to_owned(), scopes: scopes.to_vec() }
+}
+
+#[test]
+fn load_json_and_toml() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let json = dir.path().join("tokens.json");
+    std::fs::write(
+        &json,
+        r#"{"tokens": [{"name": "dashboard", "token": "d-123", "scopes": ["read"]}]}"#,
+    )?;
+    let toml = dir.path().join("tokens.toml");
+    std::fs::write(
+        &toml,
+        "[[tokens]]\nname = \"ops\"\ntoken = \"o-456\"\nscopes = [\"read\", \"input\", \"agent\"]\n",
+    )?;
+
+    let loaded = ApiTokens::load(&json)?;
+    assert_eq!(
+        loaded.resolve("d-123"),
+        Some(Grant { name: "dashboard".to_owned(), scopes: vec![Scope::Read] })
+    );
+    let loaded = ApiTokens::load(&toml)?;
+    assert_eq!(
+        loaded.resolve("o-456").map(|g| g.scopes),
+        Some(vec![Scope::Read, Scope::Input, Scope::Agent])
+    );
+    assert_eq!(loaded.resolve("d-123"), None);
+    Ok(())
+}
+
+#[test]
+fn load_rejects_unknown_scope() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("tokens.json");
+    std::fs::write(&path, r#"{"tokens": [{"name": "x", "token": "t", "scopes": ["root"]}]}"#)?;
+    assert!(ApiTokens::load(&path).is_err());
+    Ok(())
+}
+
+#[test]
+fn validate_rejects_bad_entries() {
+    for tokens in [
+        vec![token("", "t", &[Scope::Read])],
+        vec![token("a", "", &[Scope::Read])],
+        vec![token("a", "t", &[])],
+        vec![token("a", "t1", &[Scope::Read]), token("a", "t2", &[Scope::Read])],
+        vec![token("a", "t", &[Scope::Read]), token("b", "t", &[Scope::Admin])],
+    ] {
+        assert!(ApiTokens { tokens }.validate().is_err());
+    }
+}
+
+#[test]
+fn admin_implies_every_scope() {
+    let all = [Scope::Read, Scope::Input, Scope::Agent, Scope::Credentials, Scope::Admin];
+    let admin = Grant::admin("root");
+    assert!(all.iter().all(|s| admin.allows(*s)));
+
+    let read = Grant { name: "dash".to_owned(), scopes: vec![Scope::Read] };
+    assert!(read.allows(Scope::Read));
+    assert!(!read.allows(Scope::Input));
+    assert!(!read.allows(Scope::Admin));
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! HTTP tracing middleware: opens a server span per request, parented to the
+//! caller's `traceparent` when one is present.
+
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::telemetry::extract_context;
+
+/// Middleware that wraps each request in an `http.request` span so handler
+/// spans (nudge, respond, switch) join the caller's trace.
+pub async fn trace_layer(req: Request<axum::body::Body>, next: Next) -> Response {
+    let span = tracing::info_span!(
+        "http.request",
+        otel.name = %format!("{} {}", req.method(), req.uri().path()),
+        otel.kind = "server",
+        http.request.method = %req.method(),
+        url.path = %req.uri().path(),
+        http.response.status_code = tracing::field::Empty,
+    );
+    let _ = span.set_parent(extract_context(req.headers()));
+
+    let response = next.run(req).instrument(span.clone()).await;
+    span.record("http.response.status_code", response.status().as_u16());
+    response
+}
+
+#[cfg(test)]
+#[path = "trace_tests.rs"]
+mod tests;
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use opentelemetry::trace::{SpanId, TracerProvider as _};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+use crate::test_support::{StoreBuilder, StoreCtx};
+use crate::transport::build_router;
+
+#[tokio::test]
+async fn nudge_span_joins_caller_trace() -> anyhow::Result<()> {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let StoreCtx { store, .. } = StoreBuilder::new().build();
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/v1/agent/nudge")
+        .header("content-type", "application/json")
+        .header("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        .body(Body::from(r#"{"message":"hi"}"#))?;
+    let resp = build_router(store).oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let spans = exporter.get_finished_spans()?;
+    let request = spans
+        .iter()
+        .find(|s| s.name == "POST /api/v1/agent/nudge")
+        .ok_or_else(|| anyhow::anyhow!("no request span in {spans:?}"))?;
+    let nudge = spans
+        .iter()
+        .find(|s| s.name == "nudge")
+        .ok_or_else(|| anyhow::anyhow!("no nudge span in {spans:?}"))?;
+
+    assert_eq!(request.span_context.trace_id().to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(request.parent_span_id, SpanId::from_hex("00f067aa0ba902b7")?);
+    assert_eq!(nudge.span_context.trace_id(), request.span_context.trace_id());
+    assert_eq!(nudge.parent_span_id, request.span_context.span_id());
+    Ok(())
+}
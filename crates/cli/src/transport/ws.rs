@@ -16,63 +16,87 @@ use std::sync::Arc;
 
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{Query, State, WebSocketUpgrade};
+use axum::http::Extensions;
 use axum::response::IntoResponse;
 use base64::Engine;
 use futures_util::{SinkExt, StreamExt};
 
-use tokio::sync::broadcast::error::RecvError;
-
+use crate::audit::AuditTransport;
+use crate::budget::BudgetConfig;
 use crate::error::ErrorCode;
-use crate::event::{OutputEvent, TransitionEvent};
+use crate::event::TransitionEvent;
 use crate::start::StartConfig;
-use crate::stop::StopConfig;
+use crate::stop::{StopConfig, StopSchema};
 use crate::transport::auth;
+use crate::transport::fanout::{EventFanout, Fanned};
 use crate::transport::handler::{
-    compute_health, compute_status, error_message, extract_parked_fields, handle_input,
-    handle_input_raw, handle_keys, handle_nudge, handle_resize, handle_respond, handle_signal,
-    resolve_switch_profile,
+    compute_health, compute_status, error_message, extract_parked_fields, handle_enqueue_nudge,
+    handle_input, handle_input_raw, handle_keys, handle_nudge, handle_resize, handle_respond,
+    handle_signal, resolve_switch_profile,
 };
+use crate::transport::read_ring_replay;
 use crate::transport::state::Store;
-use crate::transport::{read_ring_combined, read_ring_replay};
+use crate::transport::tokens::{Grant, Scope};
 
-/// Short-circuit: return an auth error if the client has not authenticated.
-macro_rules! require_auth {
-    ($authed:expr) => {
-        if !*$authed {
-            return Some(ws_error(ErrorCode::Unauthorized, "not authenticated"));
+/// Short-circuit: return an auth error if the client has not authenticated
+/// or its token lacks the required scope.
+macro_rules! require_scope {
+    ($grant:expr, $scope:expr) => {
+        match $grant.as_ref() {
+            None => return Some(ws_error(ErrorCode::Unauthorized, "not authenticated")),
+            Some(g) if !g.allows($scope) => {
+                let message = format!("token lacks the {} scope", $scope.as_str());
+                return Some(ws_error(ErrorCode::Forbidden, &message));
+            }
+            Some(_) => {}
         }
     };
 }
 
 /// WebSocket upgrade handler. Validates auth from query params if configured.
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = "events",
+    security(()),
+    params(WsQuery),
+    responses(
+        (status = 101, description = "WebSocket upgrade (see websocket.md for the protocol)"),
+        (status = 401, description = "Invalid `token` query parameter"),
+    )
+)]
 pub async fn ws_handler(
     State(state): State<Arc<Store>>,
     Query(query): Query<WsQuery>,
+    extensions: Extensions,
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     // Validate auth token from query param if one is required.
-    if state.config.auth_token.is_some() {
-        if let Some(ref token) = query.token {
-            if let Err(_code) = auth::validate_ws_auth(token, state.config.auth_token.as_deref()) {
-                return axum::http::Response::builder()
-                    .status(401)
-                    .body(axum::body::Body::from("unauthorized"))
-                    .unwrap_or_default()
-                    .into_response();
-            }
+    let grant = if auth::auth_enabled(&state.config) {
+        match query.token {
+            Some(ref token) => match auth::resolve_token(&state.config, token) {
+                Some(grant) => Some(grant),
+                None => {
+                    return axum::http::Response::builder()
+                        .status(401)
+                        .body(axum::body::Body::from("unauthorized"))
+                        .unwrap_or_default()
+                        .into_response();
+                }
+            },
+            // If no token provided in query, the client can still auth via Auth message.
+            // We'll track auth state per-connection.
+            None => None,
         }
-        // If no token provided in query, the client can still auth via Auth message.
-        // We'll track auth state per-connection.
-    }
+    } else {
+        Some(Grant::admin("anonymous"))
+    };
 
-    let flags = query.flags();
-    let needs_auth = state.config.auth_token.is_some() && query.token.is_none();
-    let since_seq = query.since_seq;
-    let since_hook_seq = query.since_hook_seq;
+    let peer = auth::caller_identity(&extensions);
 
     ws.on_upgrade(move |socket| {
         let client_id = format!("ws-{}", next_client_id());
-        handle_connection(state, flags, socket, client_id, needs_auth, since_seq, since_hook_seq)
+        handle_connection(state, query, socket, client_id, grant, peer)
     })
     .into_response()
 }
@@ -80,273 +104,42 @@ pub async fn ws_handler(
 /// Per-connection event loop.
 async fn handle_connection(
     state: Arc<Store>,
-    flags: SubscriptionFlags,
+    query: WsQuery,
     socket: WebSocket,
     client_id: String,
-    needs_auth: bool,
-    since_seq: Option<u64>,
-    since_hook_seq: Option<u64>,
+    mut grant: Option<Grant>,
+    peer: String,
 ) {
+    let flags = query.flags();
     state.lifecycle.ws_client_count.fetch_add(1, Ordering::Relaxed);
 
     let (mut ws_tx, mut ws_rx) = socket.split();
-    let mut output_rx = state.channels.output_tx.subscribe();
-    let mut screen_rx = state.channels.screen_tx.subscribe();
-    let mut state_rx = state.channels.state_tx.subscribe();
-    let mut prompt_rx = state.channels.prompt_tx.subscribe();
-    let mut stop_rx = state.stop.stop_tx.subscribe();
-    let mut start_rx = state.start.start_tx.subscribe();
-    let mut hook_rx = state.channels.hook_tx.subscribe();
-    let mut message_rx = state.channels.message_tx.subscribe();
-    let mut transcript_rx = state.transcript.transcript_tx.subscribe();
-    let mut usage_rx = state.usage.usage_tx.subscribe();
-    let mut record_rx = state.record.record_tx.subscribe();
-    let mut profile_rx = state.profile.profile_tx.subscribe();
-    let mut authed = !needs_auth;
-
-    // Track byte offset for PTY lag recovery via ring buffer replay.
-    let mut next_offset: u64 =
-        if flags.pty { state.terminal.ring.read().await.total_written() } else { 0 };
-
-    // Send initial state: either replay from event log or current-state snapshot.
-    if flags.state && authed {
-        if let Some(seq) = since_seq {
-            // Replay missed transitions from the event log.
-            let entries = state.event_log.catchup_state(seq);
-            for entry in &entries {
-                let msg = transition_entry_to_msg(entry);
-                let _ = send_json(&mut ws_tx, &msg).await;
-            }
-        } else {
-            // No cursor: send synthetic current-state snapshot.
-            let agent = state.driver.agent_state.read().await;
-            let seq = state.driver.state_seq.load(Ordering::Acquire);
-            let last_message = state.driver.last_message.read().await.clone();
-            let initial = TransitionEvent {
-                prev: agent.clone(),
-                next: agent.clone(),
-                seq,
-                cause: String::new(),
-                last_message,
-            };
-            let _ = send_json(&mut ws_tx, &transition_to_msg(&initial)).await;
-        }
-    }
-
-    // Replay missed hook events from the event log.
-    if flags.hooks && authed {
-        if let Some(hseq) = since_hook_seq {
-            let entries = state.event_log.catchup_hooks(hseq);
-            for entry in &entries {
-                let msg = hook_entry_to_msg(entry);
-                let _ = send_json(&mut ws_tx, &msg).await;
-            }
-        }
+    // Events stream only to sockets allowed to read. Unauthenticated sockets
+    // (auth configured, no token yet) start streaming once an Auth message
+    // grants the read scope.
+    let mut fanout = None;
+    if can_read(&grant) {
+        fanout = Some(start_stream(&state, &query, flags, &mut ws_tx).await);
+    } else if grant.is_some() && flags != SubscriptionFlags::default() {
+        let _ = send_json(&mut ws_tx, &ws_error(ErrorCode::Forbidden, NO_READ_SCOPE)).await;
     }
 
     loop {
         tokio::select! {
-            event = transcript_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.transcripts {
-                    let msg = transcript_event_to_msg(&event);
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
-                }
-            }
-            event = usage_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.usage {
-                    let msg = usage_event_to_msg(&event);
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
-                }
-            }
-            event = prompt_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.state {
-                    let msg = ServerMessage::PromptOutcome {
-                        source: event.source,
-                        r#type: event.r#type,
-                        subtype: event.subtype,
-                        option: event.option,
-                    };
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
-                }
-            }
-            event = stop_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.state {
-                    let msg = stop_event_to_msg(&event);
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
-                }
-            }
-            event = start_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.state {
-                    let msg = start_event_to_msg(&event);
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
-                }
-            }
-            event = output_rx.recv() => {
-                match event {
-                    Ok(OutputEvent::Raw { data, offset: msg_offset }) if flags.pty => {
-                        // Skip if already covered by a prior replay.
-                        if msg_offset + data.len() as u64 <= next_offset {
-                            continue;
-                        }
-                        let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
-                        let msg = ServerMessage::Pty { data: encoded, offset: msg_offset };
-                        next_offset = msg_offset + data.len() as u64;
-                        if send_json(&mut ws_tx, &msg).await.is_err() {
-                            break;
-                        }
-                    }
-                    Ok(_) => {}
-                    Err(RecvError::Lagged(n)) => {
-                        if flags.pty {
-                            let ring = state.terminal.ring.read().await;
-                            let total_written = ring.total_written();
-                            // If ring has wrapped past next_offset, read from oldest available.
-                            let read_offset = next_offset.max(ring.oldest_offset());
-                            let combined = read_ring_combined(&ring, read_offset);
-                            drop(ring);
-                            if !combined.is_empty() {
-                                let read_len = combined.len() as u64;
-                                let encoded = base64::engine::general_purpose::STANDARD.encode(&combined);
-                                let msg = ServerMessage::Replay {
-                                    data: encoded,
-                                    offset: read_offset,
-                                    next_offset: read_offset + read_len,
-                                    total_written,
-                                };
-                                next_offset = read_offset + read_len;
-                                if send_json(&mut ws_tx, &msg).await.is_err() {
-                                    break;
-                                }
-                            } else {
-                                next_offset = total_written;
-                            }
-                            tracing::debug!(
-                                client_id = %client_id,
-                                skipped = n,
-                                "recovered from broadcast lag via ring buffer replay"
-                            );
-                        }
-                    }
-                    Err(RecvError::Closed) => break,
-                }
-            }
-            seq = screen_rx.recv() => {
-                match seq {
-                    Ok(seq) if flags.screen => {
-                        let snap = state.terminal.screen.read().await.snapshot();
-                        if send_json(&mut ws_tx, &snapshot_to_msg(snap, seq)).await.is_err() {
-                            break;
-                        }
-                    }
-                    Ok(_) => {}
-                    Err(RecvError::Lagged(_)) => {}
-                    Err(RecvError::Closed) => break,
-                }
-            }
-            event = state_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.state {
-                    let msg = transition_to_msg(&event);
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
-                }
-            }
-            event = hook_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.hooks {
-                    let msg = ServerMessage::HookRaw { data: event.json };
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
-                }
-            }
-            event = message_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.messages {
-                    let msg = ServerMessage::MessageRaw { data: event.json, source: event.source };
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
-                }
-            }
-            event = record_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.recording {
-                    let msg = ServerMessage::RecordingEntryMsg {
-                        ts: event.ts,
-                        seq: event.seq,
-                        kind: event.kind,
-                        detail: event.detail,
-                        screen: event.screen,
-                    };
+            fanned = recv_events(&mut fanout) => {
+                let Some(fanned) = fanned else { break };
+                let Some(fanout) = fanout.as_mut() else { continue };
+                let lagged = matches!(fanned, Fanned::Lagged);
+                if let Some(msg) = fanout.resolve(fanned).await {
                     if send_json(&mut ws_tx, &msg).await.is_err() {
                         break;
                     }
                 }
-            }
-            event = profile_rx.recv() => {
-                let event = match event {
-                    Ok(e) => e,
-                    Err(RecvError::Lagged(_)) => continue,
-                    Err(RecvError::Closed) => break,
-                };
-                if flags.profiles {
-                    let msg = profile_event_to_msg(&event);
-                    if send_json(&mut ws_tx, &msg).await.is_err() {
-                        break;
-                    }
+                if lagged {
+                    tracing::debug!(
+                        client_id = %client_id,
+                        "recovered from broadcast lag via ring buffer replay"
+                    );
                 }
             }
             msg = ws_rx.next() => {
@@ -371,11 +164,29 @@ async fn handle_connection(
                             }
                         };
 
-                        if let Some(reply) = handle_client_message(&state, envelope.message, &client_id, &mut authed).await {
+                        let audit = audit_payload(&envelope.message);
+                        let is_auth = matches!(envelope.message, ClientMessage::Auth { .. });
+                        let reply = handle_client_message(&state, envelope.message, &client_id, &mut grant).await;
+                        if let Some((operation, payload)) = audit {
+                            record_audit(&state, &grant, &peer, operation, payload, reply.as_ref());
+                        }
+                        if is_auth && fanout.is_none() && reply.is_none() {
+                            if can_read(&grant) {
+                                fanout = Some(start_stream(&state, &query, flags, &mut ws_tx).await);
+                            } else if flags != SubscriptionFlags::default() {
+                                let err = ws_error(ErrorCode::Forbidden, NO_READ_SCOPE);
+                                if send_json(&mut ws_tx, &err).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                        if let Some(reply) = reply {
                             // Advance next_offset after replay to avoid duplicate pty events.
-                            if let ServerMessage::Replay { next_offset: replay_next, .. } = &reply {
-                                if *replay_next > next_offset {
-                                    next_offset = *replay_next;
+                            if let (ServerMessage::Replay { next_offset: replay_next, .. }, Some(fanout)) =
+                                (&reply, fanout.as_mut())
+                            {
+                                if *replay_next > fanout.next_offset {
+                                    fanout.next_offset = *replay_next;
                                 }
                             }
                             if envelope.request_id.is_some() {
@@ -401,12 +212,113 @@ async fn handle_connection(
     state.lifecycle.ws_client_count.fetch_sub(1, Ordering::Relaxed);
 }
 
+/// Error sent when a socket subscribes to events without the read scope.
+const NO_READ_SCOPE: &str = "token lacks the read scope; events are not streamed";
+
+fn can_read(grant: &Option<Grant>) -> bool {
+    grant.as_ref().is_some_and(|g| g.allows(Scope::Read))
+}
+
+/// Subscribe to events and send the initial state: either a replay from the
+/// event log or a current-state snapshot, plus any missed hook events.
+async fn start_stream<S>(
+    state: &Arc<Store>,
+    query: &WsQuery,
+    flags: SubscriptionFlags,
+    ws_tx: &mut S,
+) -> EventFanout
+where
+    S: SinkExt<Message> + Unpin,
+{
+    let mut fanout = EventFanout::subscribe(Arc::clone(state), flags).await;
+    if flags.state {
+        if let Some(seq) = query.since_seq {
+            // Replay missed transitions from the event log.
+            let entries = state.event_log.catchup_state(seq);
+            for entry in &entries {
+                let msg = transition_entry_to_msg(entry);
+                let _ = send_json(ws_tx, &msg).await;
+            }
+            fanout.replayed_seq = entries.last().map(|e| e.seq);
+        } else {
+            // No cursor: send synthetic current-state snapshot.
+            let agent = state.driver.agent_state.read().await;
+            let seq = state.driver.state_seq.load(Ordering::Acquire);
+            let last_message = state.driver.last_message.read().await.clone();
+            let initial = TransitionEvent {
+                prev: agent.clone(),
+                next: agent.clone(),
+                seq,
+                cause: String::new(),
+                last_message,
+            };
+            let _ = send_json(ws_tx, &transition_to_msg(&initial)).await;
+        }
+    }
+
+    // Replay missed hook events from the event log.
+    if flags.hooks {
+        if let Some(hseq) = query.since_hook_seq {
+            let entries = state.event_log.catchup_hooks(hseq);
+            for entry in &entries {
+                let msg = hook_entry_to_msg(entry);
+                let _ = send_json(ws_tx, &msg).await;
+            }
+            fanout.replayed_hooks = entries.last().map(|e| e.hook_seq + 1);
+        }
+    }
+    fanout
+}
+
+/// Next event from the fanout, or never if the socket isn't streaming.
+async fn recv_events(fanout: &mut Option<EventFanout>) -> Option<Fanned> {
+    match fanout {
+        Some(fanout) => fanout.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Split a mutating client message into its event name and payload for the
+/// audit log. Returns `None` for read-only messages.
+fn audit_payload(msg: &ClientMessage) -> Option<(String, serde_json::Value)> {
+    if !msg.is_mutating() {
+        return None;
+    }
+    let Ok(serde_json::Value::Object(mut payload)) = serde_json::to_value(msg) else {
+        return None;
+    };
+    let operation = payload.remove("event")?.as_str()?.to_owned();
+    Some((operation, serde_json::Value::Object(payload)))
+}
+
+/// Append a mutating message to the audit log unless it was rejected by auth.
+/// The caller is the token name when auth is enabled, else the peer address.
+fn record_audit(
+    state: &Store,
+    grant: &Option<Grant>,
+    peer: &str,
+    operation: String,
+    payload: serde_json::Value,
+    reply: Option<&ServerMessage>,
+) {
+    if let Some(ServerMessage::Error { code, .. }) = reply {
+        if code == ErrorCode::Unauthorized.as_str() || code == ErrorCode::Forbidden.as_str() {
+            return;
+        }
+    }
+    let caller = match grant {
+        Some(g) if auth::auth_enabled(&state.config) => g.name.as_str(),
+        _ => peer,
+    };
+    state.audit_log.record(caller, AuditTransport::Ws, operation, payload);
+}
+
 /// Handle a single client message and optionally return a reply.
 async fn handle_client_message(
     state: &Store,
     msg: ClientMessage,
     _client_id: &str,
-    authed: &mut bool,
+    grant: &mut Option<Grant>,
 ) -> Option<ServerMessage> {
     match msg {
         // Terminal
@@ -431,7 +343,7 @@ async fn handle_client_message(
         }
 
         ClientMessage::GetScreen { cursor } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let snap = state.terminal.screen.read().await.snapshot();
             let seq = snap.sequence;
             Some(ServerMessage::Screen {
@@ -445,14 +357,13 @@ async fn handle_client_message(
         }
 
         ClientMessage::GetStatus {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             Some(compute_status(state).await.into())
         }
 
         ClientMessage::GetReplay { offset, limit } => {
-            require_auth!(authed);
-            let ring = state.terminal.ring.read().await;
-            let r = read_ring_replay(&ring, offset, limit);
+            require_scope!(grant, Scope::Read);
+            let r = read_ring_replay(&state.terminal.ring, offset, limit).await;
             Some(ServerMessage::Replay {
                 data: r.data,
                 offset: r.offset,
@@ -462,33 +373,35 @@ async fn handle_client_message(
         }
 
         ClientMessage::SendInput { text, enter } => {
-            require_auth!(authed);
-            let bytes_written = handle_input(state, text, enter).await;
-            Some(ServerMessage::InputSent { bytes_written })
+            require_scope!(grant, Scope::Input);
+            match handle_input(state, text, enter).await {
+                Ok(bytes_written) => Some(ServerMessage::InputSent { bytes_written }),
+                Err(code) => Some(ws_error(code, error_message(code))),
+            }
         }
 
         ClientMessage::SendInputRaw { data } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Input);
             let decoded = match base64::engine::general_purpose::STANDARD.decode(&data) {
                 Ok(d) => d,
                 Err(_) => return Some(ws_error(ErrorCode::BadRequest, "invalid base64 data")),
             };
-            let bytes_written = handle_input_raw(state, decoded).await;
-            Some(ServerMessage::InputSent { bytes_written })
+            match handle_input_raw(state, decoded).await {
+                Ok(bytes_written) => Some(ServerMessage::InputSent { bytes_written }),
+                Err(code) => Some(ws_error(code, error_message(code))),
+            }
         }
 
         ClientMessage::SendKeys { keys } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Input);
             match handle_keys(state, &keys).await {
                 Ok(bytes_written) => Some(ServerMessage::InputSent { bytes_written }),
-                Err(bad_key) => {
-                    Some(ws_error(ErrorCode::BadRequest, &format!("unknown key: {bad_key}")))
-                }
+                Err((code, message)) => Some(ws_error(code, &message)),
             }
         }
 
         ClientMessage::SendSignal { signal } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Admin);
             match handle_signal(state, &signal).await {
                 Ok(()) => Some(ServerMessage::SignalSent { delivered: true }),
                 Err(bad_signal) => {
@@ -498,7 +411,7 @@ async fn handle_client_message(
         }
 
         ClientMessage::Resize { cols, rows } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Input);
             match handle_resize(state, cols, rows).await {
                 Ok(()) => Some(ServerMessage::Resized { cols, rows }),
                 Err(_) => Some(ws_error(ErrorCode::BadRequest, "cols and rows must be positive")),
@@ -507,7 +420,7 @@ async fn handle_client_message(
 
         // Agent
         ClientMessage::GetAgent {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let agent = state.driver.agent_state.read().await;
             let screen = state.terminal.screen.read().await;
             let detection = state.driver.detection.read().await;
@@ -535,15 +448,36 @@ async fn handle_client_message(
         }
 
         ClientMessage::Nudge { message } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Agent);
             match handle_nudge(state, &message).await {
                 Ok(outcome) => Some(outcome.into()),
                 Err(code) => Some(ws_error(code, error_message(code))),
             }
         }
 
+        ClientMessage::QueueNudge { nudge } => {
+            require_scope!(grant, Scope::Agent);
+            match handle_enqueue_nudge(state, nudge) {
+                Ok(nudge) => Some(ServerMessage::NudgeQueued { nudge }),
+                Err(code) => Some(ws_error(code, error_message(code))),
+            }
+        }
+
+        ClientMessage::ListNudgeQueue {} => {
+            require_scope!(grant, Scope::Read);
+            Some(ServerMessage::NudgeQueue { nudges: state.nudge_queue.list() })
+        }
+
+        ClientMessage::CancelNudge { id } => {
+            require_scope!(grant, Scope::Agent);
+            match state.nudge_queue.cancel(&id) {
+                Ok(nudge) => Some(ServerMessage::NudgeCancelled { nudge }),
+                Err((code, message)) => Some(ws_error(code, &message)),
+            }
+        }
+
         ClientMessage::Respond { accept, text, answers, option } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Agent);
             match handle_respond(state, accept, option, text.as_deref(), &answers).await {
                 Ok(outcome) => Some(outcome.into()),
                 Err(code) => Some(ws_error(code, error_message(code))),
@@ -552,16 +486,22 @@ async fn handle_client_message(
 
         // Stop hook
         ClientMessage::GetStopConfig {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let config = state.stop.config.read().await;
             let json = serde_json::to_value(&*config).unwrap_or_default();
             Some(ServerMessage::StopConfig { config: json })
         }
 
         ClientMessage::PutStopConfig { config } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Agent);
             match serde_json::from_value::<StopConfig>(config) {
                 Ok(new_config) => {
+                    if let Some(Err(e)) = new_config.schema.as_ref().map(StopSchema::check) {
+                        return Some(ws_error(
+                            ErrorCode::BadRequest,
+                            &format!("invalid stop schema: {e}"),
+                        ));
+                    }
                     *state.stop.config.write().await = new_config;
                     Some(ServerMessage::StopConfigured { updated: true })
                 }
@@ -572,23 +512,26 @@ async fn handle_client_message(
         }
 
         ClientMessage::ResolveStop { body } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Agent);
             match state.stop.resolve(body).await {
-                Ok(()) => Some(ServerMessage::StopResolved { accepted: true }),
-                Err(msg) => Some(ws_error(ErrorCode::BadRequest, &msg)),
+                Ok(()) => Some(ServerMessage::StopResolved { accepted: true, violations: vec![] }),
+                Err(rejection) => Some(ServerMessage::StopResolved {
+                    accepted: false,
+                    violations: rejection.violations,
+                }),
             }
         }
 
         // Start hook
         ClientMessage::GetStartConfig {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let config = state.start.config.read().await;
             let json = serde_json::to_value(&*config).unwrap_or_default();
             Some(ServerMessage::StartConfig { config: json })
         }
 
         ClientMessage::PutStartConfig { config } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Agent);
             match serde_json::from_value::<StartConfig>(config) {
                 Ok(new_config) => {
                     *state.start.config.write().await = new_config;
@@ -602,13 +545,13 @@ async fn handle_client_message(
 
         // Transcripts
         ClientMessage::ListTranscripts {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let list = state.transcript.list().await;
             Some(ServerMessage::TranscriptList { transcripts: list })
         }
 
         ClientMessage::GetTranscript { number } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             match state.transcript.get_content(number).await {
                 Ok(content) => Some(ServerMessage::TranscriptContent { number, content }),
                 Err(e) => Some(ws_error(ErrorCode::BadRequest, &format!("{e}"))),
@@ -616,7 +559,7 @@ async fn handle_client_message(
         }
 
         ClientMessage::CatchupTranscripts { since_transcript, since_line } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             match state.transcript.catchup(since_transcript, since_line).await {
                 Ok(resp) => Some(ServerMessage::TranscriptCatchup {
                     transcripts: resp.transcripts,
@@ -630,7 +573,7 @@ async fn handle_client_message(
 
         // Usage
         ClientMessage::GetUsage {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let snap = state.usage.snapshot().await;
             let uptime = state.config.started_at.elapsed().as_secs() as i64;
             Some(ServerMessage::Usage {
@@ -639,22 +582,45 @@ async fn handle_client_message(
                 cache_read_tokens: snap.cache_read_tokens,
                 cache_write_tokens: snap.cache_write_tokens,
                 total_cost_usd: snap.total_cost_usd,
+                estimated_cost_usd: snap.estimated_cost_usd,
                 request_count: snap.request_count,
                 total_api_ms: snap.total_api_ms,
                 uptime_secs: uptime,
+                budget: state.budget.status(&snap),
+                by_model: snap.by_model,
+                by_profile: snap.by_profile,
             })
         }
 
+        ClientMessage::GetBudgetConfig {} => {
+            require_scope!(grant, Scope::Read);
+            let json = serde_json::to_value(state.budget.config()).unwrap_or_default();
+            Some(ServerMessage::BudgetConfig { config: json })
+        }
+
+        ClientMessage::PutBudgetConfig { config } => {
+            require_scope!(grant, Scope::Agent);
+            match serde_json::from_value::<BudgetConfig>(config) {
+                Ok(new_config) => {
+                    state.budget.set_config(new_config, &state.usage.snapshot().await);
+                    Some(ServerMessage::BudgetConfigured { updated: true })
+                }
+                Err(e) => {
+                    Some(ws_error(ErrorCode::BadRequest, &format!("invalid budget config: {e}")))
+                }
+            }
+        }
+
         // Profiles
         ClientMessage::RegisterProfiles { profiles } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Credentials);
             let count = profiles.len();
             state.profile.register(profiles).await;
             Some(ServerMessage::ProfilesRegistered { count })
         }
 
         ClientMessage::ListProfiles {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let profiles = state.profile.list().await;
             let mode = state.profile.mode().as_str().to_owned();
             let active_profile = state.profile.active_name().await;
@@ -662,13 +628,13 @@ async fn handle_client_message(
         }
 
         ClientMessage::GetProfileMode {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let mode = state.profile.mode().as_str().to_owned();
             Some(ServerMessage::ProfileMode { mode })
         }
 
         ClientMessage::SetProfileMode { mode } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Credentials);
             match mode.parse::<crate::profile::ProfileMode>() {
                 Ok(m) => {
                     state.profile.set_mode(m);
@@ -682,8 +648,13 @@ async fn handle_client_message(
 
         // Session switch
         ClientMessage::SwitchSession { credentials, force, profile } => {
-            require_auth!(authed);
-            let mut req = crate::switch::SwitchRequest { credentials, force, profile };
+            require_scope!(grant, Scope::Admin);
+            let mut req = crate::switch::SwitchRequest {
+                credentials,
+                force,
+                profile,
+                span: tracing::Span::current(),
+            };
             if let Err(code) = resolve_switch_profile(state, &mut req).await {
                 return Some(ws_error(code, "unknown profile"));
             }
@@ -700,7 +671,7 @@ async fn handle_client_message(
 
         // Recording
         ClientMessage::GetRecording {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let status = state.record.status();
             Some(ServerMessage::Recording {
                 enabled: status.enabled,
@@ -710,7 +681,7 @@ async fn handle_client_message(
         }
 
         ClientMessage::PutRecording { enabled } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Admin);
             if enabled {
                 state.record.enable().await;
             } else {
@@ -721,16 +692,20 @@ async fn handle_client_message(
         }
 
         ClientMessage::CatchupRecording { since_seq } => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Read);
             let entries = state.record.catchup(since_seq);
             Some(ServerMessage::RecordingCatchup { entries })
         }
 
         // Lifecycle
         ClientMessage::RestartSession {} => {
-            require_auth!(authed);
-            let req =
-                crate::switch::SwitchRequest { credentials: None, force: true, profile: None };
+            require_scope!(grant, Scope::Admin);
+            let req = crate::switch::SwitchRequest {
+                credentials: None,
+                force: true,
+                profile: None,
+                span: tracing::Span::current(),
+            };
             match state.switch.switch_tx.try_send(req) {
                 Ok(()) => Some(ServerMessage::SessionRestarted { scheduled: true }),
                 Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => {
@@ -743,7 +718,7 @@ async fn handle_client_message(
         }
 
         ClientMessage::Shutdown {} => {
-            require_auth!(authed);
+            require_scope!(grant, Scope::Admin);
             state.lifecycle.shutdown.cancel();
             Some(ServerMessage::Shutdown { accepted: true })
         }
@@ -752,13 +727,16 @@ async fn handle_client_message(
         ClientMessage::Ping {} => Some(ServerMessage::Pong {}),
 
         ClientMessage::Auth { token } => {
-            match auth::validate_ws_auth(&token, state.config.auth_token.as_deref()) {
-                Ok(()) => {
-                    *authed = true;
+            if !auth::auth_enabled(&state.config) {
+                return None;
+            }
+            match auth::resolve_token(&state.config, &token) {
+                Some(resolved) => {
+                    *grant = Some(resolved);
                     None
                 }
-                Err(code) => Some(ServerMessage::Error {
-                    code: code.as_str().to_owned(),
+                None => Some(ServerMessage::Error {
+                    code: ErrorCode::Unauthorized.as_str().to_owned(),
                     message: "authentication failed".to_owned(),
                 }),
             }
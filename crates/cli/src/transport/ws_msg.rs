@@ -3,11 +3,18 @@
 
 //! WebSocket message types and conversions.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
 
+use crate::budget::{BudgetEvent, BudgetStatus, LimitStatus};
 use crate::driver::{AgentState, PromptContext};
 use crate::error::ErrorCode;
 use crate::event::TransitionEvent;
+use crate::idle::IdleEvent;
+use crate::json_schema::Violation;
+use crate::nudge_queue::{NewNudge, NudgeQueueEvent, QueuedNudge};
 use crate::profile::{ProfileEntry, ProfileInfo};
 use crate::screen::{CursorPosition, ScreenSnapshot};
 use crate::start::StartEvent;
@@ -15,7 +22,7 @@ use crate::stop::StopEvent;
 use crate::transport::handler::{
     extract_error_fields, extract_parked_fields, NudgeOutcome, RespondOutcome, SessionStatus,
 };
-use crate::usage::{SessionUsage, UsageEvent};
+use crate::usage::{SessionUsage, UsageCounters, UsageEvent};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "event", rename_all = "snake_case")]
@@ -67,6 +74,17 @@ pub enum ClientMessage {
     Nudge {
         message: String,
     },
+    #[serde(rename = "nudge:queue")]
+    QueueNudge {
+        #[serde(flatten)]
+        nudge: NewNudge,
+    },
+    #[serde(rename = "nudge:queue:list")]
+    ListNudgeQueue {},
+    #[serde(rename = "nudge:queue:cancel")]
+    CancelNudge {
+        id: String,
+    },
     Respond {
         accept: Option<bool>,
         text: Option<String>,
@@ -126,6 +144,12 @@ pub enum ClientMessage {
     // Usage
     #[serde(rename = "usage:get")]
     GetUsage {},
+    #[serde(rename = "budget:config:get")]
+    GetBudgetConfig {},
+    #[serde(rename = "budget:config:put")]
+    PutBudgetConfig {
+        config: serde_json::Value,
+    },
 
     // Profiles
     #[serde(rename = "profiles:register")]
@@ -164,6 +188,34 @@ pub enum ClientMessage {
     },
 }
 
+impl ClientMessage {
+    /// Whether this message changes session state (and so is audited).
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            Self::SendInput { .. }
+                | Self::SendInputRaw { .. }
+                | Self::SendKeys { .. }
+                | Self::SendSignal { .. }
+                | Self::Resize { .. }
+                | Self::Nudge { .. }
+                | Self::QueueNudge { .. }
+                | Self::CancelNudge { .. }
+                | Self::Respond { .. }
+                | Self::PutStopConfig { .. }
+                | Self::ResolveStop { .. }
+                | Self::PutStartConfig { .. }
+                | Self::PutBudgetConfig { .. }
+                | Self::PutRecording { .. }
+                | Self::RegisterProfiles { .. }
+                | Self::SetProfileMode { .. }
+                | Self::SwitchSession { .. }
+                | Self::RestartSession {}
+                | Self::Shutdown {}
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "event", rename_all = "snake_case")]
 pub enum ServerMessage {
@@ -254,6 +306,18 @@ pub enum ServerMessage {
         #[serde(skip_serializing_if = "Option::is_none")]
         reason: Option<String>,
     },
+    #[serde(rename = "nudge:queued")]
+    NudgeQueued {
+        nudge: QueuedNudge,
+    },
+    #[serde(rename = "nudge:cancelled")]
+    NudgeCancelled {
+        nudge: QueuedNudge,
+    },
+    #[serde(rename = "nudge:queue")]
+    NudgeQueue {
+        nudges: Vec<QueuedNudge>,
+    },
     Response {
         delivered: bool,
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -291,6 +355,10 @@ pub enum ServerMessage {
         subtype: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         option: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        decision: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        rule: Option<String>,
     },
 
     // Raw streams
@@ -316,6 +384,9 @@ pub enum ServerMessage {
     #[serde(rename = "stop:resolved")]
     StopResolved {
         accepted: bool,
+        /// Every schema violation in a rejected body.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        violations: Vec<Violation>,
     },
     #[serde(rename = "stop:outcome")]
     StopOutcome {
@@ -371,6 +442,23 @@ pub enum ServerMessage {
         seq: u64,
     },
 
+    // Idle escalation
+    #[serde(rename = "idle:step")]
+    IdleStep {
+        step: usize,
+        action: String,
+        idle_secs: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+
+    // Nudge queue
+    #[serde(rename = "nudge:queue:event")]
+    NudgeQueueEvent {
+        kind: String,
+        nudge: QueuedNudge,
+    },
+
     // Recording
     #[serde(rename = "recording")]
     Recording {
@@ -404,15 +492,39 @@ pub enum ServerMessage {
         cache_read_tokens: u64,
         cache_write_tokens: u64,
         total_cost_usd: f64,
+        estimated_cost_usd: f64,
         request_count: u64,
         total_api_ms: u64,
         uptime_secs: i64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        budget: Option<BudgetStatus>,
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        by_model: BTreeMap<String, UsageCounters>,
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        by_profile: BTreeMap<String, UsageCounters>,
     },
     #[serde(rename = "usage:update")]
     UsageUpdate {
         cumulative: SessionUsage,
         seq: u64,
     },
+    #[serde(rename = "budget:config")]
+    BudgetConfig {
+        config: serde_json::Value,
+    },
+    #[serde(rename = "budget:configured")]
+    BudgetConfigured {
+        updated: bool,
+    },
+    #[serde(rename = "budget:event")]
+    BudgetEvent {
+        kind: String,
+        action: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<LimitStatus>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        threshold: Option<u32>,
+    },
 
     // Profiles
     #[serde(rename = "profiles:registered")]
@@ -544,7 +656,8 @@ impl SubscriptionFlags {
 }
 
 /// Query parameters for WebSocket upgrade.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct WsQuery {
     pub token: Option<String>,
     /// Comma-separated subscription flags (e.g. `raw,state,hooks`).
@@ -711,3 +824,31 @@ pub fn start_event_to_msg(event: &StartEvent) -> ServerMessage {
         seq: event.seq,
     }
 }
+
+/// Convert an `IdleEvent` to a `ServerMessage`.
+pub fn idle_event_to_msg(event: &IdleEvent) -> ServerMessage {
+    ServerMessage::IdleStep {
+        step: event.step,
+        action: event.action.clone(),
+        idle_secs: event.idle_secs,
+        message: event.message.clone(),
+    }
+}
+
+/// Convert a `BudgetEvent` to a `ServerMessage`.
+pub fn budget_event_to_msg(event: &BudgetEvent) -> ServerMessage {
+    ServerMessage::BudgetEvent {
+        kind: event.kind.as_str().to_owned(),
+        action: event.action.as_str().to_owned(),
+        limit: event.limit.clone(),
+        threshold: event.threshold,
+    }
+}
+
+/// Convert a `NudgeQueueEvent` to a `ServerMessage`.
+pub fn nudge_queue_event_to_msg(event: &NudgeQueueEvent) -> ServerMessage {
+    ServerMessage::NudgeQueueEvent {
+        kind: event.kind.as_str().to_owned(),
+        nudge: event.nudge.clone(),
+    }
+}
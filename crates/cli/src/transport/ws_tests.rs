@@ -5,8 +5,9 @@ use std::sync::Arc;
 
 use crate::driver::AgentState;
 use crate::test_support::{AnyhowExt, StoreBuilder, StoreCtx, StubNudgeEncoder};
+use crate::transport::tokens::{Grant, Scope};
 use crate::transport::ws::{
-    handle_client_message, ClientMessage, ServerMessage, SubscriptionFlags,
+    audit_payload, handle_client_message, ClientMessage, ServerMessage, SubscriptionFlags,
 };
 
 #[test]
@@ -262,7 +263,8 @@ async fn state_request_returns_agent_state() -> anyhow::Result<()> {
     });
 
     let msg = ClientMessage::GetAgent {};
-    let reply = handle_client_message(&state, msg, "test-client", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-client", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Agent {
             agent,
@@ -289,7 +291,8 @@ async fn state_request_returns_agent_state() -> anyhow::Result<()> {
 async fn resize_zero_cols_returns_error() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg = ClientMessage::Resize { cols: 0, rows: 24 };
-    let reply = handle_client_message(&state, msg, "test-client", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-client", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Error { code, .. }) => {
             assert_eq!(code, "BAD_REQUEST");
@@ -303,7 +306,8 @@ async fn resize_zero_cols_returns_error() -> anyhow::Result<()> {
 async fn resize_zero_rows_returns_error() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg = ClientMessage::Resize { cols: 80, rows: 0 };
-    let reply = handle_client_message(&state, msg, "test-client", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-client", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Error { code, .. }) => {
             assert_eq!(code, "BAD_REQUEST");
@@ -320,7 +324,8 @@ async fn nudge_delivered_when_agent_working() -> anyhow::Result<()> {
     let client_id = "test-ws";
 
     let msg = ClientMessage::Nudge { message: "hello".to_owned() };
-    let reply = handle_client_message(&state, msg, client_id, &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, client_id, &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Nudged { delivered, state_before, reason }) => {
             assert!(delivered);
@@ -339,7 +344,8 @@ async fn nudge_accepted_when_agent_waiting() -> anyhow::Result<()> {
     let client_id = "test-ws";
 
     let msg = ClientMessage::Nudge { message: "hello".to_owned() };
-    let reply = handle_client_message(&state, msg, client_id, &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, client_id, &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Nudged { delivered, state_before, reason }) => {
             assert!(delivered);
@@ -357,7 +363,8 @@ async fn shutdown_cancels_token() -> anyhow::Result<()> {
     assert!(!state.lifecycle.shutdown.is_cancelled());
 
     let msg = ClientMessage::Shutdown {};
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Shutdown { accepted }) => assert!(accepted),
         other => anyhow::bail!("expected Shutdown, got {other:?}"),
@@ -371,7 +378,7 @@ async fn shutdown_requires_auth() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
 
     let msg = ClientMessage::Shutdown {};
-    let reply = handle_client_message(&state, msg, "test-ws", &mut false).await;
+    let reply = handle_client_message(&state, msg, "test-ws", &mut None).await;
     match reply {
         Some(ServerMessage::Error { code, .. }) => {
             assert_eq!(code, "UNAUTHORIZED");
@@ -382,6 +389,30 @@ async fn shutdown_requires_auth() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn scoped_grant_limits_operations() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
+    let mut grant = Some(Grant { name: "dashboard".to_owned(), scopes: vec![Scope::Read] });
+
+    let reply =
+        handle_client_message(&state, ClientMessage::GetStatus {}, "test-ws", &mut grant).await;
+    assert!(matches!(reply, Some(ServerMessage::Status { .. })), "got {reply:?}");
+
+    for msg in [
+        ClientMessage::SendInput { text: "x".to_owned(), enter: false },
+        ClientMessage::Nudge { message: "hi".to_owned() },
+        ClientMessage::Shutdown {},
+    ] {
+        let reply = handle_client_message(&state, msg, "test-ws", &mut grant).await;
+        match reply {
+            Some(ServerMessage::Error { code, .. }) => assert_eq!(code, "FORBIDDEN"),
+            other => anyhow::bail!("expected Forbidden, got {other:?}"),
+        }
+    }
+    assert!(!state.lifecycle.shutdown.is_cancelled());
+    Ok(())
+}
+
 #[tokio::test]
 async fn read_operations_require_auth() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
@@ -391,7 +422,7 @@ async fn read_operations_require_auth() -> anyhow::Result<()> {
         ClientMessage::GetStatus {},
         ClientMessage::GetReplay { offset: 0, limit: None },
     ] {
-        let reply = handle_client_message(&state, msg, "test-ws", &mut false).await;
+        let reply = handle_client_message(&state, msg, "test-ws", &mut None).await;
         match reply {
             Some(ServerMessage::Error { code, .. }) => assert_eq!(code, "UNAUTHORIZED"),
             other => anyhow::bail!("expected Unauthorized, got {other:?}"),
@@ -406,7 +437,8 @@ async fn signal_delivers_sigint() -> anyhow::Result<()> {
     let client_id = "test-ws";
 
     let msg = ClientMessage::SendSignal { signal: "SIGINT".to_owned() };
-    let reply = handle_client_message(&state, msg, client_id, &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, client_id, &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::SignalSent { delivered }) => assert!(delivered),
         other => anyhow::bail!("expected SignalResult, got {other:?}"),
@@ -426,7 +458,8 @@ async fn signal_rejects_unknown() -> anyhow::Result<()> {
     let client_id = "test-ws";
 
     let msg = ClientMessage::SendSignal { signal: "SIGFOO".to_owned() };
-    let reply = handle_client_message(&state, msg, client_id, &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, client_id, &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Error { code, .. }) => {
             assert_eq!(code, "BAD_REQUEST");
@@ -442,7 +475,8 @@ async fn keys_rejects_unknown_key() -> anyhow::Result<()> {
     let client_id = "test-ws";
 
     let msg = ClientMessage::SendKeys { keys: vec!["Enter".to_owned(), "SuperKey".to_owned()] };
-    let reply = handle_client_message(&state, msg, client_id, &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, client_id, &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Error { code, message }) => {
             assert_eq!(code, "BAD_REQUEST");
@@ -590,7 +624,8 @@ fn shutdown_result_serialization() -> anyhow::Result<()> {
 async fn screen_request_excludes_cursor_by_default() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg: ClientMessage = serde_json::from_str(r#"{"event":"screen:get"}"#)?;
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Screen { cursor, .. }) => {
             assert!(cursor.is_none(), "cursor should be excluded by default");
@@ -604,7 +639,8 @@ async fn screen_request_excludes_cursor_by_default() -> anyhow::Result<()> {
 async fn screen_request_includes_cursor_when_requested() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg: ClientMessage = serde_json::from_str(r#"{"event":"screen:get","cursor":true}"#)?;
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Screen { cursor, .. }) => {
             assert!(cursor.is_some(), "cursor should be included when requested");
@@ -618,7 +654,8 @@ async fn screen_request_includes_cursor_when_requested() -> anyhow::Result<()> {
 async fn input_raw_rejects_bad_base64() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg = ClientMessage::SendInputRaw { data: "not-valid-base64!!!".to_owned() };
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Error { code, message }) => {
             assert_eq!(code, "BAD_REQUEST");
@@ -633,7 +670,8 @@ async fn input_raw_rejects_bad_base64() -> anyhow::Result<()> {
 async fn health_request_returns_health() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg = ClientMessage::GetHealth {};
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Health { status, .. }) => {
             assert_eq!(status, "running");
@@ -647,7 +685,8 @@ async fn health_request_returns_health() -> anyhow::Result<()> {
 async fn ready_request_returns_ready() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg = ClientMessage::GetReady {};
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Ready { ready }) => {
             assert!(!ready, "default ready is false");
@@ -661,7 +700,7 @@ async fn ready_request_returns_ready() -> anyhow::Result<()> {
 async fn get_stop_config_requires_auth() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg = ClientMessage::GetStopConfig {};
-    let reply = handle_client_message(&state, msg, "test-ws", &mut false).await;
+    let reply = handle_client_message(&state, msg, "test-ws", &mut None).await;
     match reply {
         Some(ServerMessage::Error { code, .. }) => assert_eq!(code, "UNAUTHORIZED"),
         other => anyhow::bail!("expected Unauthorized, got {other:?}"),
@@ -675,7 +714,8 @@ async fn stop_config_roundtrip() -> anyhow::Result<()> {
 
     // Read default config.
     let msg = ClientMessage::GetStopConfig {};
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::StopConfig { config }) => {
             assert_eq!(config["mode"], "allow");
@@ -687,7 +727,8 @@ async fn stop_config_roundtrip() -> anyhow::Result<()> {
     let msg = ClientMessage::PutStopConfig {
         config: serde_json::json!({"mode": "auto", "prompt": "wait"}),
     };
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::StopConfigured { updated }) => assert!(updated),
         other => anyhow::bail!("expected ConfigUpdated, got {other:?}"),
@@ -695,7 +736,8 @@ async fn stop_config_roundtrip() -> anyhow::Result<()> {
 
     // Verify update.
     let msg = ClientMessage::GetStopConfig {};
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::StopConfig { config }) => {
             assert_eq!(config["mode"], "auto");
@@ -709,15 +751,41 @@ async fn stop_config_roundtrip() -> anyhow::Result<()> {
 async fn resolve_stop_stores_signal() -> anyhow::Result<()> {
     let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
     let msg = ClientMessage::ResolveStop { body: serde_json::json!({"done": true}) };
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
-        Some(ServerMessage::StopResolved { accepted }) => assert!(accepted),
+        Some(ServerMessage::StopResolved { accepted, violations }) => {
+            assert!(accepted);
+            assert!(violations.is_empty());
+        }
         other => anyhow::bail!("expected StopResult, got {other:?}"),
     }
     assert!(state.stop.signaled.load(std::sync::atomic::Ordering::Acquire));
     Ok(())
 }
 
+#[tokio::test]
+async fn resolve_stop_lists_violations() -> anyhow::Result<()> {
+    let StoreCtx { store: state, .. } = ws_test_state(AgentState::Working);
+    *state.stop.config.write().await = serde_json::from_value(serde_json::json!({
+        "mode": "auto",
+        "schema": { "fields": { "status": { "required": true, "enum": ["done"] } } },
+    }))?;
+    let msg = ClientMessage::ResolveStop { body: serde_json::json!({"status": "bogus"}) };
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
+    match reply {
+        Some(ServerMessage::StopResolved { accepted, violations }) => {
+            assert!(!accepted);
+            assert_eq!(violations.len(), 1);
+            assert_eq!(violations[0].path, "/status");
+        }
+        other => anyhow::bail!("expected StopResolved, got {other:?}"),
+    }
+    assert!(!state.stop.signaled.load(std::sync::atomic::Ordering::Acquire));
+    Ok(())
+}
+
 #[test]
 fn hook_raw_serialization() -> anyhow::Result<()> {
     let msg = ServerMessage::HookRaw { data: serde_json::json!({"event": "post_tool_use"}) };
@@ -747,7 +815,8 @@ async fn start_config_roundtrip() -> anyhow::Result<()> {
     let msg = ClientMessage::PutStartConfig {
         config: serde_json::json!({"text": "hello", "shell": ["echo hi"]}),
     };
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::StartConfigured { updated }) => assert!(updated),
         other => anyhow::bail!("expected ConfigUpdated, got {other:?}"),
@@ -755,7 +824,8 @@ async fn start_config_roundtrip() -> anyhow::Result<()> {
 
     // Verify.
     let msg = ClientMessage::GetStartConfig {};
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::StartConfig { config }) => {
             assert_eq!(config["text"], "hello");
@@ -772,7 +842,8 @@ async fn replay_returns_next_offset_for_ring_data() -> anyhow::Result<()> {
     state.terminal.ring.write().await.write(data);
 
     let msg = ClientMessage::GetReplay { offset: 0, limit: None };
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match reply {
         Some(ServerMessage::Replay { offset, next_offset, total_written, .. }) => {
             assert_eq!(offset, 0);
@@ -799,7 +870,8 @@ async fn replay_next_offset_advances_past_previous() -> anyhow::Result<()> {
 
     // Client requests full replay from offset 0 (as expanded terminal does).
     let msg = ClientMessage::GetReplay { offset: 0, limit: None };
-    let reply = handle_client_message(&state, msg, "test-ws", &mut true).await;
+    let reply =
+        handle_client_message(&state, msg, "test-ws", &mut Some(Grant::admin("test"))).await;
     match &reply {
         Some(ServerMessage::Replay { next_offset: replay_next, .. }) => {
             // Apply the same logic as handle_connection: advance next_offset.
@@ -815,3 +887,17 @@ async fn replay_next_offset_advances_past_previous() -> anyhow::Result<()> {
     assert_eq!(next_offset, total);
     Ok(())
 }
+
+#[test]
+fn audit_payload_only_for_mutating_messages() {
+    let nudge = ClientMessage::Nudge { message: "continue".to_owned() };
+    assert_eq!(
+        audit_payload(&nudge),
+        Some(("nudge".to_owned(), serde_json::json!({ "message": "continue" })))
+    );
+    let keys = ClientMessage::SendKeys { keys: vec!["Enter".to_owned()] };
+    assert_eq!(audit_payload(&keys).map(|(op, _)| op), Some("keys:send".to_owned()));
+
+    assert_eq!(audit_payload(&ClientMessage::GetScreen { cursor: false }), None);
+    assert_eq!(audit_payload(&ClientMessage::Auth { token: "secret".to_owned() }), None);
+}
@@ -3,17 +3,34 @@
 
 //! Per-session API usage tracking.
 //!
-//! Extracts token counts from Claude session log JSONL `result` entries
-//! and accumulates them into a cumulative snapshot. Exposed via HTTP, WS,
-//! and gRPC.
+//! Drivers extract a [`UsageDelta`] from each structured entry that reports
+//! token counts (Claude session log and stdout entries, Gemini `stream-json`
+//! results, Codex `turn.completed` events, declarative `[stdout.usage]`
+//! mappings) and accumulate them into a cumulative snapshot with a per-model
+//! breakdown. Each delta is also attributed to the active credential
+//! profile, and the snapshot is persisted to the session directory so a
+//! restart or `--resume` picks up where the previous process left off.
+//! Exposed via HTTP, WS, and gRPC.
 
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tokio::sync::{broadcast, RwLock};
+use tracing::debug;
+use utoipa::ToSchema;
 
-/// Per-entry extraction from a single Claude API response.
+use crate::persist::write_json_atomic;
+use crate::pricing::PricingTable;
+use crate::profile::ProfileState;
+
+/// Usage snapshot file inside the session directory.
+const USAGE_FILE: &str = "usage.json";
+
+/// Per-entry extraction from a single API response.
 #[derive(Debug, Clone, Default)]
 pub struct UsageDelta {
     pub input_tokens: u64,
@@ -22,6 +39,40 @@ pub struct UsageDelta {
     pub cache_read_input_tokens: u64,
     pub cost_usd: f64,
     pub duration_api_ms: u64,
+    /// Model that served the request, when the entry names it.
+    pub model: Option<String>,
+    /// Cost priced from token counts. Filled in by [`UsageState`] from its
+    /// [`PricingTable`]; extractors leave it at zero.
+    pub estimated_cost_usd: f64,
+    /// Credential profile active when the response arrived. Filled in by
+    /// [`UsageState`].
+    pub profile: Option<String>,
+}
+
+/// Usage counters for one model or credential profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct UsageCounters {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    pub total_cost_usd: f64,
+    pub estimated_cost_usd: f64,
+    pub request_count: u64,
+    pub total_api_ms: u64,
+}
+
+impl UsageCounters {
+    fn accumulate(&mut self, delta: &UsageDelta) {
+        self.input_tokens += delta.input_tokens;
+        self.output_tokens += delta.output_tokens;
+        self.cache_read_tokens += delta.cache_read_input_tokens;
+        self.cache_write_tokens += delta.cache_creation_input_tokens;
+        self.total_cost_usd += delta.cost_usd;
+        self.estimated_cost_usd += delta.estimated_cost_usd;
+        self.total_api_ms += delta.duration_api_ms;
+        self.request_count += 1;
+    }
 }
 
 /// Cumulative session usage counters.
@@ -31,9 +82,22 @@ pub struct SessionUsage {
     pub output_tokens: u64,
     pub cache_read_tokens: u64,
     pub cache_write_tokens: u64,
+    /// Cost as reported by the agent (zero for agents that only report tokens).
     pub total_cost_usd: f64,
+    /// Cost priced from token counts, falling back to the reported cost for
+    /// models without a price. Comparable across agents and models.
+    #[serde(default)]
+    pub estimated_cost_usd: f64,
     pub request_count: u64,
     pub total_api_ms: u64,
+    /// Breakdown by model name. Deltas that don't name a model only count
+    /// toward the totals.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_model: BTreeMap<String, UsageCounters>,
+    /// Breakdown by credential profile name, for chargeback. Usage while no
+    /// profile is registered only counts toward the totals.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_profile: BTreeMap<String, UsageCounters>,
 }
 
 impl SessionUsage {
@@ -44,8 +108,15 @@ impl SessionUsage {
         self.cache_read_tokens += delta.cache_read_input_tokens;
         self.cache_write_tokens += delta.cache_creation_input_tokens;
         self.total_cost_usd += delta.cost_usd;
+        self.estimated_cost_usd += delta.estimated_cost_usd;
         self.total_api_ms += delta.duration_api_ms;
         self.request_count += 1;
+        if let Some(ref model) = delta.model {
+            self.by_model.entry(model.clone()).or_default().accumulate(delta);
+        }
+        if let Some(ref profile) = delta.profile {
+            self.by_profile.entry(profile.clone()).or_default().accumulate(delta);
+        }
     }
 }
 
@@ -59,6 +130,10 @@ pub struct UsageEvent {
 /// Shared usage state, safe to access from multiple tasks.
 pub struct UsageState {
     usage: RwLock<SessionUsage>,
+    pricing: PricingTable,
+    /// Snapshot file; `None` keeps usage in memory only.
+    path: Option<PathBuf>,
+    profiles: Option<Arc<ProfileState>>,
     pub usage_tx: broadcast::Sender<UsageEvent>,
     seq: AtomicU64,
 }
@@ -70,17 +145,57 @@ impl Default for UsageState {
 }
 
 impl UsageState {
-    /// Create with default (zero) usage and a broadcast channel.
+    /// Create with default (zero) usage, built-in pricing and a broadcast channel.
     pub fn new() -> Self {
+        Self::with_pricing(PricingTable::builtin())
+    }
+
+    /// Create with default (zero) usage, priced by `pricing`.
+    pub fn with_pricing(pricing: PricingTable) -> Self {
         let (usage_tx, _) = broadcast::channel(64);
-        Self { usage: RwLock::new(SessionUsage::default()), usage_tx, seq: AtomicU64::new(0) }
+        Self {
+            usage: RwLock::new(SessionUsage::default()),
+            pricing,
+            path: None,
+            profiles: None,
+            usage_tx,
+            seq: AtomicU64::new(0),
+        }
     }
 
-    /// Accumulate a delta, broadcast the updated snapshot.
-    pub async fn accumulate(&self, delta: UsageDelta) {
+    /// Persist to `session_dir`, restoring the counters saved there by a
+    /// previous process (restart or `--resume`).
+    pub fn with_session_dir(mut self, session_dir: &Path) -> Self {
+        let _ = std::fs::create_dir_all(session_dir);
+        let path = session_dir.join(USAGE_FILE);
+        if let Some(saved) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<SessionUsage>(&s).ok())
+        {
+            self.usage = RwLock::new(saved);
+        }
+        self.path = Some(path);
+        self
+    }
+
+    /// Attribute each delta to the active profile in `profiles`.
+    pub fn with_profiles(mut self, profiles: Arc<ProfileState>) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+
+    /// Price a delta, attribute it to the active profile, accumulate it,
+    /// persist, and broadcast the updated snapshot.
+    pub async fn accumulate(&self, mut delta: UsageDelta) {
+        delta.estimated_cost_usd = self.pricing.estimate(&delta);
+        if let Some(ref profiles) = self.profiles {
+            delta.profile = profiles.active_name().await;
+        }
         let snapshot = {
             let mut usage = self.usage.write().await;
             usage.accumulate(&delta);
+            // Save under the lock so concurrent updates land in order.
+            self.persist(&usage);
             usage.clone()
         };
         let seq = self.seq.fetch_add(1, Ordering::Relaxed) + 1;
@@ -91,13 +206,23 @@ impl UsageState {
     pub async fn snapshot(&self) -> SessionUsage {
         self.usage.read().await.clone()
     }
+
+    /// Rewrite the snapshot atomically.
+    fn persist(&self, usage: &SessionUsage) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        if let Err(e) = write_json_atomic(path, usage) {
+            debug!("usage: failed to persist {}: {e}", path.display());
+        }
+    }
 }
 
 /// Extract a [`UsageDelta`] from a Claude session log JSONL entry.
 ///
 /// Looks for usage data at `json["usage"]` (legacy/result entries) or
-/// `json["message"]["usage"]` (assistant entries). Returns `None` if
-/// the entry has no usage data.
+/// `json["message"]["usage"]` (assistant entries), and takes the model from
+/// `json["message"]["model"]`. Returns `None` if the entry has no usage data.
 pub fn extract_usage_delta(json: &Value) -> Option<UsageDelta> {
     let usage = json.get("usage").or_else(|| json.get("message").and_then(|m| m.get("usage")))?;
 
@@ -133,6 +258,12 @@ pub fn extract_usage_delta(json: &Value) -> Option<UsageDelta> {
         cache_read_input_tokens: cache_read,
         cost_usd: cost,
         duration_api_ms: duration,
+        model: json
+            .get("message")
+            .and_then(|m| m.get("model"))
+            .and_then(|v| v.as_str())
+            .map(str::to_owned),
+        ..Default::default()
     })
 }
 
@@ -1,9 +1,12 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
+use std::sync::Arc;
+
 use serde_json::json;
 
 use super::*;
+use crate::profile::{ProfileEntry, ProfileState};
 
 #[test]
 fn accumulate_single_delta() {
@@ -15,6 +18,9 @@ fn accumulate_single_delta() {
         cache_read_input_tokens: 20,
         cost_usd: 0.005,
         duration_api_ms: 1200,
+        model: None,
+        estimated_cost_usd: 0.004,
+        profile: None,
     };
     usage.accumulate(&delta);
     assert_eq!(usage.input_tokens, 100);
@@ -22,6 +28,7 @@ fn accumulate_single_delta() {
     assert_eq!(usage.cache_write_tokens, 10);
     assert_eq!(usage.cache_read_tokens, 20);
     assert!((usage.total_cost_usd - 0.005).abs() < f64::EPSILON);
+    assert!((usage.estimated_cost_usd - 0.004).abs() < f64::EPSILON);
     assert_eq!(usage.total_api_ms, 1200);
     assert_eq!(usage.request_count, 1);
 }
@@ -141,3 +148,89 @@ async fn usage_state_accumulate_and_snapshot() -> anyhow::Result<()> {
     assert_eq!(snap.output_tokens, 50);
     Ok(())
 }
+
+#[tokio::test]
+async fn usage_state_prices_deltas_per_model() -> anyhow::Result<()> {
+    let state = UsageState::new();
+    state
+        .accumulate(UsageDelta {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            model: Some("claude-sonnet-4-5-20250929".to_owned()),
+            ..Default::default()
+        })
+        .await;
+    // Unpriced model: the reported cost stands in for the estimate.
+    state
+        .accumulate(UsageDelta {
+            input_tokens: 10,
+            cost_usd: 0.25,
+            model: Some("local-llama".to_owned()),
+            ..Default::default()
+        })
+        .await;
+
+    let snap = state.snapshot().await;
+    assert!((snap.total_cost_usd - 0.25).abs() < f64::EPSILON);
+    assert!((snap.estimated_cost_usd - 4.75).abs() < 1e-9);
+    let sonnet = snap
+        .by_model
+        .get("claude-sonnet-4-5-20250929")
+        .ok_or_else(|| anyhow::anyhow!("missing model"))?;
+    assert!((sonnet.estimated_cost_usd - 4.5).abs() < 1e-9);
+    Ok(())
+}
+
+#[tokio::test]
+async fn usage_state_attributes_deltas_to_active_profile() -> anyhow::Result<()> {
+    let profiles = Arc::new(ProfileState::new());
+    let state = UsageState::new().with_profiles(Arc::clone(&profiles));
+    let delta = || UsageDelta { input_tokens: 10, output_tokens: 5, ..Default::default() };
+
+    // Nothing registered yet: only the totals move.
+    state.accumulate(delta()).await;
+    profiles
+        .register(vec![
+            ProfileEntry { name: "team-a".to_owned(), credentials: Default::default() },
+            ProfileEntry { name: "team-b".to_owned(), credentials: Default::default() },
+        ])
+        .await;
+    state.accumulate(delta()).await;
+    profiles.set_active("team-b").await;
+    state.accumulate(delta()).await;
+    state.accumulate(delta()).await;
+
+    let snap = state.snapshot().await;
+    assert_eq!(snap.request_count, 4);
+    let count = |name: &str| snap.by_profile.get(name).map(|u| u.request_count);
+    assert_eq!((count("team-a"), count("team-b")), (Some(1), Some(2)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn usage_state_restores_from_session_dir() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let delta = UsageDelta {
+        input_tokens: 300,
+        output_tokens: 40,
+        model: Some("claude-haiku-4-5".to_owned()),
+        ..Default::default()
+    };
+
+    let first = UsageState::new().with_session_dir(dir.path());
+    first.accumulate(delta.clone()).await;
+    drop(first);
+
+    // A restarted process picks up the saved totals and keeps counting.
+    let second = UsageState::new().with_session_dir(dir.path());
+    assert_eq!(second.snapshot().await.input_tokens, 300);
+    second.accumulate(delta).await;
+    let snap = second.snapshot().await;
+    assert_eq!((snap.input_tokens, snap.request_count), (600, 2));
+    assert_eq!(snap.by_model.get("claude-haiku-4-5").map(|u| u.output_tokens), Some(80));
+
+    // A corrupt snapshot starts from zero rather than failing.
+    std::fs::write(dir.path().join("usage.json"), "not json")?;
+    assert_eq!(UsageState::new().with_session_dir(dir.path()).snapshot().await.request_count, 0);
+    Ok(())
+}
@@ -9,10 +9,12 @@ use std::time::Duration;
 
 use tokio_stream::StreamExt;
 
+use coop::audit::AuditLog;
 use coop::driver::AgentState;
 use coop::event::{OutputEvent, TransitionEvent};
 use coop::test_support::{spawn_grpc_server, StoreBuilder, StoreCtx, StubNudgeEncoder};
 use coop::transport::grpc::proto;
+use coop::transport::tokens::{ApiToken, ApiTokens, Scope};
 
 async fn grpc_client(
     store: Arc<coop::transport::Store>,
@@ -280,3 +282,79 @@ async fn grpc_nudge_agent_busy() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn grpc_enforces_token_scopes() -> anyhow::Result<()> {
+    let tokens = ApiTokens {
+        tokens: vec![ApiToken {
+            name: "dashboard".to_owned(),
+            token: "read-only".to_owned(),
+            scopes: vec![Scope::Read],
+        }],
+    };
+    let StoreCtx { store, .. } = StoreBuilder::new().child_pid(42).api_tokens(tokens).build();
+    let (addr, _handle) = spawn_grpc_server(Arc::clone(&store)).await?;
+    let channel = tonic::transport::Channel::from_shared(format!("http://{addr}"))
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .connect()
+        .await?;
+
+    let with_token = |token: &'static str| {
+        proto::coop_client::CoopClient::with_interceptor(
+            channel.clone(),
+            move |mut req: tonic::Request<()>| {
+                req.metadata_mut()
+                    .insert("authorization", tonic::metadata::MetadataValue::from_static(token));
+                Ok(req)
+            },
+        )
+    };
+
+    let mut client = with_token("Bearer read-only");
+    client.get_status(proto::GetStatusRequest {}).await?;
+    let err = client
+        .send_input(proto::SendInputRequest { text: "x".to_owned(), enter: false })
+        .await
+        .err()
+        .ok_or_else(|| anyhow::anyhow!("send_input should be denied"))?;
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+    let err = client
+        .shutdown(proto::ShutdownRequest {})
+        .await
+        .err()
+        .ok_or_else(|| anyhow::anyhow!("shutdown should be denied"))?;
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+    assert!(!store.lifecycle.shutdown.is_cancelled());
+
+    let err = with_token("Bearer wrong")
+        .get_status(proto::GetStatusRequest {})
+        .await
+        .err()
+        .ok_or_else(|| anyhow::anyhow!("unknown token should be rejected"))?;
+    assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    Ok(())
+}
+
+#[tokio::test]
+async fn grpc_mutating_calls_are_audited() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let StoreCtx { store, .. } = StoreBuilder::new()
+        .child_pid(42)
+        .audit_log(Arc::new(AuditLog::new(Some(dir.path()))))
+        .build();
+    let (mut client, store) = grpc_client(store).await?;
+
+    client.send_input(proto::SendInputRequest { text: "ls".to_owned(), enter: true }).await?;
+    client.get_status(proto::GetStatusRequest {}).await?;
+
+    let resp = client.catchup_audit(proto::CatchupAuditRequest { since_seq: 0 }).await?;
+    let entries = resp.into_inner().entries;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].transport, "grpc");
+    assert_eq!(entries[0].operation, "SendInput");
+    assert!(entries[0].caller.starts_with("127.0.0.1:"), "caller: {}", entries[0].caller);
+    let payload: serde_json::Value = serde_json::from_str(&entries[0].payload_json)?;
+    assert_eq!(payload, serde_json::json!({ "text": "ls", "enter": true }));
+    assert_eq!(store.audit_log.catchup(0).len(), 1);
+    Ok(())
+}
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Integration tests for `ScreenBackend`.
+//!
+//! Each test runs an isolated GNU screen session with `SCREENDIR` pointing
+//! at a temp dir so tests run in parallel without colliding with each other
+//! or the user's own sessions. They need GNU screen installed, so they are
+//! ignored by default; run them with `cargo test --test screen_backend --
+//! --ignored`.
+
+use bytes::Bytes;
+use coop::backend::adapter::ScreenBackend;
+use coop::backend::{Backend, BackendInput};
+use coop::driver::ExitStatus;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// RAII guard that manages an isolated screen session in a temp dir.
+///
+/// On drop, quits the session and cleans up the temp directory.
+struct ScreenSession {
+    name: String,
+    socket_dir: PathBuf,
+    _tmpdir: tempfile::TempDir,
+}
+
+impl ScreenSession {
+    fn new(name: &str) -> anyhow::Result<Self> {
+        let tmpdir = tempfile::tempdir()?;
+        let socket_dir = tmpdir.path().join("sockets");
+        std::fs::create_dir(&socket_dir)?;
+        // screen refuses socket dirs readable by others.
+        std::fs::set_permissions(&socket_dir, std::fs::Permissions::from_mode(0o700))?;
+
+        let status = Command::new("screen")
+            .env("SCREENDIR", &socket_dir)
+            .args(["-dmS", name, "sh"])
+            .status()?;
+        anyhow::ensure!(status.success(), "failed to create screen session");
+
+        Ok(Self { name: name.to_string(), socket_dir, _tmpdir: tmpdir })
+    }
+
+    fn backend(&self) -> anyhow::Result<ScreenBackend> {
+        Ok(ScreenBackend::with_socket_dir(self.name.clone(), Some(self.socket_dir.clone()))?
+            .with_poll_interval(Duration::from_millis(50)))
+    }
+}
+
+impl Drop for ScreenSession {
+    fn drop(&mut self) {
+        let _ = Command::new("screen")
+            .env("SCREENDIR", &self.socket_dir)
+            .args(["-S", &self.name, "-X", "quit"])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires GNU screen"]
+async fn send_command_and_capture_output() -> anyhow::Result<()> {
+    let session = ScreenSession::new("test")?;
+    let mut backend = session.backend()?;
+
+    let (output_tx, mut output_rx) = mpsc::channel::<Bytes>(16);
+    let (input_tx, input_rx) = mpsc::channel::<BackendInput>(16);
+
+    let (_resize_tx, resize_rx) = mpsc::channel(4);
+    let run_handle = tokio::spawn(async move { backend.run(output_tx, input_rx, resize_rx).await });
+
+    // Send a command
+    input_tx.send(BackendInput::Write(Bytes::from("echo hello\r"))).await?;
+
+    // Wait for output containing "hello"
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    let mut found = false;
+    while tokio::time::Instant::now() < deadline {
+        match tokio::time::timeout(Duration::from_secs(2), output_rx.recv()).await {
+            Ok(Some(data)) => {
+                let text = String::from_utf8_lossy(&data);
+                if text.contains("hello") {
+                    found = true;
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    assert!(found, "expected output containing 'hello'");
+
+    drop(input_tx);
+    let result = tokio::time::timeout(Duration::from_secs(3), run_handle).await;
+    assert!(result.is_ok(), "run future should resolve after input closes");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires GNU screen"]
+async fn resize_succeeds() -> anyhow::Result<()> {
+    let session = ScreenSession::new("test")?;
+    let backend = session.backend()?;
+
+    backend.resize(100, 30)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires GNU screen"]
+async fn child_pid_returns_valid_pid() -> anyhow::Result<()> {
+    let session = ScreenSession::new("test")?;
+    let backend = session.backend()?;
+
+    let pid = backend.child_pid();
+    assert!(pid.is_some(), "child_pid should return Some");
+    assert!(pid.is_some_and(|p| p > 0), "pid should be > 0");
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires GNU screen"]
+async fn missing_session_is_rejected() -> anyhow::Result<()> {
+    let session = ScreenSession::new("test")?;
+
+    let result =
+        ScreenBackend::with_socket_dir("nope".to_owned(), Some(session.socket_dir.clone()));
+    assert!(result.is_err(), "attaching to a missing session should fail");
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires GNU screen"]
+async fn session_quit_resolves_run() -> anyhow::Result<()> {
+    let session = ScreenSession::new("test")?;
+    let mut backend = session.backend()?;
+
+    let (output_tx, _output_rx) = mpsc::channel::<Bytes>(16);
+    let (_input_tx, input_rx) = mpsc::channel::<BackendInput>(16);
+
+    let (_resize_tx, resize_rx) = mpsc::channel(4);
+    let run_handle = tokio::spawn(async move { backend.run(output_tx, input_rx, resize_rx).await });
+
+    // Quit the session (simulates it going away)
+    drop(session);
+
+    let result = tokio::time::timeout(Duration::from_secs(5), run_handle).await;
+    assert!(result.is_ok(), "run future should resolve after session quit");
+
+    if let Ok(Ok(Ok(exit_status))) = result {
+        assert_eq!(exit_status, ExitStatus { code: None, signal: None });
+    }
+    Ok(())
+}
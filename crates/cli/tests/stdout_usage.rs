@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! End-to-end tests for Tier 3 usage tracking: a fake agent writes
+//! structured JSONL to its PTY and the session's usage counters pick it up.
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::time::Duration;
+
+use coop::config::Config;
+use coop::run;
+use coop::usage::SessionUsage;
+
+/// Write an executable script that prints `lines` and then idles.
+fn fake_agent(dir: &Path, name: &str, lines: &[&str]) -> anyhow::Result<String> {
+    let path = dir.join(name);
+    let mut script = String::from("#!/bin/sh\n");
+    for line in lines {
+        script.push_str(&format!("echo '{line}'\n"));
+    }
+    script.push_str("sleep 30\n");
+    std::fs::write(&path, script)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(path.display().to_string())
+}
+
+/// Run a session until usage with `output_tokens` is reported.
+async fn usage_from(config: Config, output_tokens: u64) -> anyhow::Result<SessionUsage> {
+    let prepared = run::prepare(config).await?;
+    let mut rx = prepared.store.usage.usage_tx.subscribe();
+    let shutdown = prepared.store.lifecycle.shutdown.clone();
+    let handle = tokio::spawn(prepared.run());
+
+    let usage = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let event = rx.recv().await?;
+            if event.cumulative.output_tokens >= output_tokens {
+                return anyhow::Ok(event.cumulative);
+            }
+        }
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("timed out waiting for usage"))??;
+
+    shutdown.cancel();
+    handle.await??;
+    Ok(usage)
+}
+
+#[tokio::test]
+async fn codex_exec_json_reports_usage() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let codex = fake_agent(
+        tmp.path(),
+        "codex",
+        &[
+            r#"{"type":"thread.started","thread_id":"t-1"}"#,
+            r#"{"type":"turn.started"}"#,
+            r#"{"type":"turn.completed","usage":{"input_tokens":1200,"cached_input_tokens":200,"output_tokens":50}}"#,
+        ],
+    )?;
+    let config = Config {
+        agent: Some("codex".into()),
+        command: vec![codex, "exec".into(), "--json".into(), "fix the bug".into()],
+        ..Config::test()
+    };
+
+    let usage = usage_from(config, 50).await?;
+    assert_eq!(usage.input_tokens, 1000);
+    assert_eq!(usage.cache_read_tokens, 200);
+    assert_eq!(usage.request_count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn gemini_stream_json_reports_usage() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let gemini = fake_agent(
+        tmp.path(),
+        "gemini",
+        &[
+            r#"{"type":"init","session_id":"s-1","model":"gemini-2.5-pro"}"#,
+            r#"{"type":"result","status":"success","stats":{"input_tokens":900,"output_tokens":80,"duration_ms":1500}}"#,
+        ],
+    )?;
+    let config = Config {
+        agent: Some("gemini".into()),
+        command: vec![
+            gemini,
+            "--output-format".into(),
+            "stream-json".into(),
+            "-p".into(),
+            "hi".into(),
+        ],
+        ..Config::test()
+    };
+
+    let usage = usage_from(config, 80).await?;
+    assert_eq!(usage.input_tokens, 900);
+    assert!(usage.by_model.contains_key("gemini-2.5-pro"));
+    assert!(usage.estimated_cost_usd > 0.0);
+    Ok(())
+}
@@ -13,6 +13,7 @@ use tokio_tungstenite::tungstenite::Message as WsMessage;
 use coop::driver::AgentState;
 use coop::event::{OutputEvent, TransitionEvent};
 use coop::test_support::{spawn_http_server, StoreBuilder, StoreCtx};
+use coop::transport::tokens::{ApiToken, ApiTokens, Scope};
 
 type WsStream =
     tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
@@ -118,6 +119,71 @@ async fn ws_auth_message() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Push a PTY chunk and a state transition to every subscriber.
+async fn publish_events(store: &coop::transport::Store) {
+    let data = bytes::Bytes::from("secret output");
+    let offset = {
+        let mut ring = store.terminal.ring.write().await;
+        ring.write(&data);
+        ring.total_written() - data.len() as u64
+    };
+    let _ = store.channels.output_tx.send(OutputEvent::Raw { data, offset });
+    let _ = store.channels.state_tx.send(TransitionEvent {
+        prev: AgentState::Starting,
+        next: AgentState::Working,
+        seq: 1,
+        cause: String::new(),
+        last_message: None,
+    });
+}
+
+#[tokio::test]
+async fn ws_events_require_read_scope() -> anyhow::Result<()> {
+    let tokens = ApiTokens {
+        tokens: vec![
+            ApiToken {
+                name: "writer".to_owned(),
+                token: "write-only".to_owned(),
+                scopes: vec![Scope::Input, Scope::Credentials],
+            },
+            ApiToken {
+                name: "reader".to_owned(),
+                token: "read".to_owned(),
+                scopes: vec![Scope::Read],
+            },
+        ],
+    };
+    let StoreCtx { store, .. } =
+        StoreBuilder::new().ring_size(65536).auth_token("root").api_tokens(tokens).build();
+    let (addr, _handle) = spawn_http_server(Arc::clone(&store)).await?;
+
+    // A write-only token is told events are not streamed, then gets nothing.
+    let (_writer_tx, mut writer_rx) =
+        ws_connect(&addr, "token=write-only&subscribe=pty,state").await?;
+    let resp = ws_recv(&mut writer_rx, RECV_TIMEOUT).await?;
+    assert_eq!(resp.get("code").and_then(|c| c.as_str()), Some("FORBIDDEN"), "{resp}");
+
+    // An unauthenticated socket gets nothing until it authenticates.
+    let (mut anon_tx, mut anon_rx) = ws_connect(&addr, "subscribe=pty").await?;
+
+    publish_events(&store).await;
+    for rx in [&mut writer_rx, &mut anon_rx] {
+        let result =
+            tokio::time::timeout(Duration::from_millis(200), ws_recv(rx, RECV_TIMEOUT)).await;
+        assert!(result.is_err(), "received an event without the read scope: {result:?}");
+    }
+
+    // Authenticating with a read token starts the stream.
+    ws_send(&mut anon_tx, &serde_json::json!({"event": "auth", "token": "read"})).await?;
+    ws_send(&mut anon_tx, &serde_json::json!({"event": "ping"})).await?;
+    let resp = ws_recv(&mut anon_rx, RECV_TIMEOUT).await?;
+    assert_eq!(resp.get("event").and_then(|t| t.as_str()), Some("pong"));
+    publish_events(&store).await;
+    let resp = ws_recv(&mut anon_rx, RECV_TIMEOUT).await?;
+    assert_eq!(resp.get("event").and_then(|t| t.as_str()), Some("pty"), "{resp}");
+    Ok(())
+}
+
 #[tokio::test]
 async fn ws_subscription_mode_raw() -> anyhow::Result<()> {
     let StoreCtx { store, .. } = StoreBuilder::new().ring_size(65536).build();
@@ -19,8 +19,10 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use tokio::sync::{broadcast, Semaphore};
+use tracing::Instrument;
 
 use crate::credential::CredentialEvent;
+use crate::metrics::{DistributorStep, MuxMetrics};
 use crate::state::MuxState;
 use crate::upstream::client::UpstreamClient;
 
@@ -77,6 +79,7 @@ pub fn spawn_distributor(state: Arc<MuxState>, mut event_rx: broadcast::Receiver
 /// When `switch` is true, also triggers a profile switch on each session —
 /// unless the session is currently busy, in which case credentials are
 /// registered but the switch is deferred.
+#[tracing::instrument(name = "credential.distribute", skip(state, credentials))]
 pub async fn distribute_to_sessions(
     state: &MuxState,
     account: &str,
@@ -121,6 +124,7 @@ pub async fn distribute_to_sessions(
     let mut handles = Vec::with_capacity(eligible_count);
 
     for entry in eligible {
+        let metrics = Arc::clone(&state.metrics);
         let sem = Arc::clone(&semaphore);
         let ok = Arc::clone(&ok);
         let failed = Arc::clone(&failed);
@@ -128,21 +132,24 @@ pub async fn distribute_to_sessions(
         let account = account.to_owned();
         let credentials = credentials.clone();
 
-        handles.push(tokio::spawn(async move {
-            let _permit = sem.acquire().await;
-
-            match push_to_session(&entry, &account, &credentials, switch).await {
-                PushResult::Ok => {
-                    ok.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                }
-                PushResult::Deferred => {
-                    deferred.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                }
-                PushResult::Failed => {
-                    failed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        handles.push(tokio::spawn(
+            async move {
+                let _permit = sem.acquire().await;
+
+                match push_to_session(&entry, &account, &credentials, switch, &metrics).await {
+                    PushResult::Ok => {
+                        ok.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    }
+                    PushResult::Deferred => {
+                        deferred.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    }
+                    PushResult::Failed => {
+                        failed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    }
                 }
             }
-        }));
+            .in_current_span(),
+        ));
     }
 
     // Await all concurrent pushes.
@@ -153,6 +160,7 @@ pub async fn distribute_to_sessions(
     let ok = ok.load(std::sync::atomic::Ordering::Relaxed);
     let failed = failed.load(std::sync::atomic::Ordering::Relaxed);
     let deferred = deferred.load(std::sync::atomic::Ordering::Relaxed);
+    state.metrics.record_distributor_pushes(ok, deferred, failed);
     tracing::info!(account, ok, failed, deferred, "distributor: distribution complete");
 }
 
@@ -161,6 +169,7 @@ pub async fn distribute_to_sessions(
 /// Called automatically when a credential refresh fails. Finds all sessions
 /// assigned to the degraded account and reassigns them to the least-loaded
 /// healthy account, switching their active profile.
+#[tracing::instrument(name = "credential.rebalance", skip(state))]
 pub async fn rebalance_from_account(state: &MuxState, failed_account: &str) {
     let broker = match state.credential_broker.as_ref() {
         Some(b) => b,
@@ -249,6 +258,9 @@ pub async fn rebalance_from_account(state: &MuxState, failed_account: &str) {
         }
     }
 
+    if !affected.is_empty() {
+        state.persist_sessions().await;
+    }
     if reassigned > 0 {
         tracing::info!(
             from = failed_account,
@@ -270,11 +282,13 @@ enum PushResult {
 }
 
 /// Push credentials to a single session with retries and idle checking.
+#[tracing::instrument(name = "credential.push", skip_all, fields(session = %entry.id))]
 async fn push_to_session(
     entry: &crate::state::SessionEntry,
     account: &str,
     credentials: &std::collections::HashMap<String, String>,
     switch: bool,
+    metrics: &MuxMetrics,
 ) -> PushResult {
     let client = UpstreamClient::new(entry.url.clone(), entry.auth_token.clone());
     let profile_body = serde_json::json!({
@@ -305,6 +319,7 @@ async fn push_to_session(
                     session = %entry.id, account, attempt, err = %e,
                     "distributor: profile push failed, retrying"
                 );
+                metrics.record_distributor_retry(DistributorStep::Profile);
                 tokio::time::sleep(backoff).await;
                 backoff = (backoff * 2).min(MAX_BACKOFF);
             }
@@ -386,6 +401,7 @@ async fn push_to_session(
                     session = %entry.id, account, attempt, err = %e,
                     "distributor: switch failed, retrying"
                 );
+                metrics.record_distributor_retry(DistributorStep::Switch);
                 tokio::time::sleep(backoff).await;
                 backoff = (backoff * 2).min(MAX_BACKOFF);
             }
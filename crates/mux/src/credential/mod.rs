@@ -16,7 +16,6 @@ pub mod device_code;
 pub mod distributor;
 #[cfg(feature = "legacy-oauth")]
 pub mod oauth;
-#[cfg(feature = "legacy-oauth")]
 pub mod persist;
 #[cfg(feature = "legacy-oauth")]
 pub mod pkce;
@@ -43,10 +43,15 @@ pub fn load(path: &Path) -> anyhow::Result<PersistedCredentials> {
 /// concurrent saves race on the same `.tmp` file — a shorter write can leave
 /// trailing bytes from a longer previous write.
 pub fn save(path: &Path, creds: &PersistedCredentials) -> anyhow::Result<()> {
+    write_json_atomic(path, creds)
+}
+
+/// Serialize `value` as pretty JSON and write it atomically (see [`save`]).
+pub fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
     use std::sync::atomic::{AtomicU32, Ordering};
     static COUNTER: AtomicU32 = AtomicU32::new(0);
 
-    let json = serde_json::to_string_pretty(creds)?;
+    let json = serde_json::to_string_pretty(value)?;
     let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
     let tmp_name = format!(
         "{}.{}.{}.tmp",
@@ -6,7 +6,11 @@
 pub mod config;
 pub mod credential;
 pub mod error;
+pub mod metrics;
+pub mod registry;
+pub mod selector;
 pub mod state;
+pub mod telemetry;
 pub mod transport;
 pub mod upstream;
 
@@ -109,6 +113,7 @@ pub async fn run(
     }
 
     state.credential_broker = Some(Arc::clone(&broker));
+    state.registry_path = Some(config.state_dir().join(crate::registry::REGISTRY_FILE));
 
     // Spawn distributor (pushes credentials to sessions on events).
     let state = Arc::new(state);
@@ -155,6 +160,7 @@ pub async fn run(
     } else {
         tracing::info!("coopmux listening on {addr}");
     }
+    crate::registry::restore(&state).await;
     spawn_health_checker(Arc::clone(&state));
 
     // Spawn NATS relay subscriber for auto-discovering local agent sessions.
@@ -3,6 +3,8 @@
 
 use clap::{Parser, Subcommand};
 use tracing::error;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 use coopmux::config::MuxConfig;
 
@@ -38,6 +40,11 @@ struct Cli {
     #[arg(long, default_value = "coop.mux", env = "COOP_MUX_NATS_RELAY_PREFIX")]
     nats_relay_prefix: String,
 
+    /// OTLP/gRPC collector endpoint for trace export (e.g. "http://localhost:4317").
+    /// When set, spans are exported and trace context is propagated to upstream sessions.
+    #[arg(long, env = "COOP_MUX_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -68,12 +75,24 @@ async fn main() {
             std::process::exit(open_dashboard(&cli.config, &args));
         }
         None => {
-            tracing_subscriber::fmt()
-                .with_env_filter(
+            let (otel_layer, otel_err) = match cli.otlp_endpoint {
+                Some(ref endpoint) => match coopmux::telemetry::layer(endpoint, "coopmux") {
+                    Ok(layer) => (Some(layer), None),
+                    Err(e) => (None, Some(e)),
+                },
+                None => (None, None),
+            };
+            tracing_subscriber::registry()
+                .with(
                     tracing_subscriber::EnvFilter::try_from_default_env()
                         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
                 )
+                .with(tracing_subscriber::fmt::layer())
+                .with(otel_layer)
                 .init();
+            if let Some(e) = otel_err {
+                tracing::warn!("OTLP trace export disabled: {e:#}");
+            }
 
             let nats = cli.nats_url.map(|url| coopmux::NatsConfig {
                 url,
@@ -85,7 +104,9 @@ async fn main() {
                 token: cli.nats_relay_token,
                 prefix: cli.nats_relay_prefix,
             });
-            if let Err(e) = coopmux::run(cli.config, nats, nats_relay).await {
+            let result = coopmux::run(cli.config, nats, nats_relay).await;
+            coopmux::telemetry::shutdown();
+            if let Err(e) = result {
                 error!("fatal: {e:#}");
                 std::process::exit(1);
             }
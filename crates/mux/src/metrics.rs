@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Prometheus metrics for the mux `GET /metrics` endpoint.
+//!
+//! Counters for health checks and credential distribution accumulate here;
+//! per-session and credential pool gauges are read from [`MuxState`] at
+//! scrape time.
+
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::state::MuxState;
+
+/// Distributor step that was retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistributorStep {
+    /// `POST /api/v1/session/profiles` on the upstream.
+    Profile,
+    /// `POST /api/v1/session/switch` on the upstream.
+    Switch,
+}
+
+/// Event counters accumulated over the life of the mux.
+#[derive(Debug, Default)]
+pub struct MuxMetrics {
+    health_check_failures: AtomicU64,
+    health_evictions: AtomicU64,
+    distributor_profile_retries: AtomicU64,
+    distributor_switch_retries: AtomicU64,
+    distributor_pushes_ok: AtomicU64,
+    distributor_pushes_deferred: AtomicU64,
+    distributor_pushes_failed: AtomicU64,
+}
+
+impl MuxMetrics {
+    pub fn record_health_failure(&self) {
+        self.health_check_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_health_eviction(&self) {
+        self.health_evictions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_distributor_retry(&self, step: DistributorStep) {
+        let counter = match step {
+            DistributorStep::Profile => &self.distributor_profile_retries,
+            DistributorStep::Switch => &self.distributor_switch_retries,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count the per-session results of one distribution round.
+    pub fn record_distributor_pushes(&self, ok: u32, deferred: u32, failed: u32) {
+        self.distributor_pushes_ok.fetch_add(u64::from(ok), Ordering::Relaxed);
+        self.distributor_pushes_deferred.fetch_add(u64::from(deferred), Ordering::Relaxed);
+        self.distributor_pushes_failed.fetch_add(u64::from(failed), Ordering::Relaxed);
+    }
+}
+
+/// Render all mux metrics in Prometheus text exposition format.
+pub async fn render(state: &MuxState) -> String {
+    let mut out = Exposition::default();
+    let m = &state.metrics;
+
+    let sessions: Vec<_> = state.sessions.read().await.values().cloned().collect();
+    out.header("coopmux_sessions", "gauge", "Registered upstream sessions.");
+    out.sample("coopmux_sessions", &[], sessions.len());
+
+    out.header(
+        "coopmux_session_health_failures",
+        "gauge",
+        "Consecutive failed health checks per session.",
+    );
+    for entry in &sessions {
+        let failures = entry.health_failures.load(Ordering::Relaxed);
+        out.sample("coopmux_session_health_failures", &[("session", &entry.id)], failures);
+    }
+
+    out.header(
+        "coopmux_session_agent_state",
+        "gauge",
+        "Last polled agent state per session (1 for the active state).",
+    );
+    for entry in &sessions {
+        if let Some(ref status) = *entry.cached_status.read().await {
+            out.sample(
+                "coopmux_session_agent_state",
+                &[("session", &entry.id), ("state", &status.state)],
+                1,
+            );
+        }
+    }
+
+    out.header("coopmux_health_check_failures_total", "counter", "Failed upstream health checks.");
+    out.sample(
+        "coopmux_health_check_failures_total",
+        &[],
+        m.health_check_failures.load(Ordering::Relaxed),
+    );
+    out.header(
+        "coopmux_health_evictions_total",
+        "counter",
+        "Sessions evicted after consecutive health check failures.",
+    );
+    out.sample("coopmux_health_evictions_total", &[], m.health_evictions.load(Ordering::Relaxed));
+
+    if let Some(ref broker) = state.credential_broker {
+        let pool = broker.pool_status().await;
+        out.header(
+            "coopmux_credential_account_status",
+            "gauge",
+            "Credential account status (1 for the current status).",
+        );
+        for account in &pool {
+            let status = serde_json::to_value(account.status).unwrap_or_default();
+            let status = status.as_str().unwrap_or("unknown");
+            out.sample(
+                "coopmux_credential_account_status",
+                &[("account", &account.name), ("status", status)],
+                1,
+            );
+        }
+        out.header(
+            "coopmux_credential_account_sessions",
+            "gauge",
+            "Sessions assigned to each credential account.",
+        );
+        for account in &pool {
+            out.sample(
+                "coopmux_credential_account_sessions",
+                &[("account", &account.name)],
+                account.session_count,
+            );
+        }
+    }
+
+    out.header(
+        "coopmux_distributor_retries_total",
+        "counter",
+        "Credential distributor retries by step.",
+    );
+    for (step, counter) in
+        [("profile", &m.distributor_profile_retries), ("switch", &m.distributor_switch_retries)]
+    {
+        out.sample(
+            "coopmux_distributor_retries_total",
+            &[("step", step)],
+            counter.load(Ordering::Relaxed),
+        );
+    }
+    out.header(
+        "coopmux_distributor_pushes_total",
+        "counter",
+        "Per-session credential pushes by result.",
+    );
+    for (result, counter) in [
+        ("ok", &m.distributor_pushes_ok),
+        ("deferred", &m.distributor_pushes_deferred),
+        ("failed", &m.distributor_pushes_failed),
+    ] {
+        out.sample(
+            "coopmux_distributor_pushes_total",
+            &[("result", result)],
+            counter.load(Ordering::Relaxed),
+        );
+    }
+
+    out.finish()
+}
+
+/// Builder for a Prometheus text exposition document.
+#[derive(Debug, Default)]
+struct Exposition {
+    buf: String,
+}
+
+impl Exposition {
+    fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.buf, "# HELP {name} {help}");
+        let _ = writeln!(self.buf, "# TYPE {name} {kind}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        self.buf.push_str(name);
+        if !labels.is_empty() {
+            self.buf.push('{');
+            for (i, (key, val)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.buf.push(',');
+                }
+                let val = val.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+                let _ = write!(self.buf, "{key}=\"{val}\"");
+            }
+            self.buf.push('}');
+        }
+        let _ = writeln!(self.buf, " {value}");
+    }
+
+    fn finish(self) -> String {
+        self.buf
+    }
+}
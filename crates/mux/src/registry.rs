@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Session registry persistence: snapshot registered sessions to the state
+//! dir so a coopmux restart doesn't drop them until the next heartbeat.
+
+use std::path::Path;
+use std::sync::atomic::AtomicU32;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use crate::state::{epoch_ms, MuxEvent, MuxState, SessionEntry, SessionTransport};
+use crate::upstream::client::UpstreamClient;
+
+/// File name of the registry snapshot within the state dir.
+pub const REGISTRY_FILE: &str = "sessions.json";
+
+/// Timeout for the health check of each saved session on restore.
+const RESTORE_HEALTH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Persisted session registry.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PersistedRegistry {
+    pub sessions: Vec<PersistedSession>,
+}
+
+/// Persisted state for a single HTTP-transport session.
+///
+/// NATS-transport sessions are not persisted; they re-announce themselves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedSession {
+    pub id: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assigned_account: Option<String>,
+    /// Registration time as epoch millis.
+    #[serde(default)]
+    pub registered_at_ms: u64,
+}
+
+/// Load a persisted registry from a JSON file.
+pub fn load(path: &Path) -> anyhow::Result<PersistedRegistry> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Save a registry to a JSON file atomically.
+pub fn save(path: &Path, registry: &PersistedRegistry) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    crate::credential::persist::write_json_atomic(path, registry)
+}
+
+/// Capture the current registry, sorted by session ID.
+pub async fn snapshot(state: &MuxState) -> PersistedRegistry {
+    let entries: Vec<_> = state.sessions.read().await.values().map(Arc::clone).collect();
+    let mut sessions = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if matches!(entry.transport, SessionTransport::Nats { .. }) || entry.cancel.is_cancelled() {
+            continue;
+        }
+        sessions.push(PersistedSession {
+            id: entry.id.clone(),
+            url: entry.url.clone(),
+            auth_token: entry.auth_token.clone(),
+            metadata: entry.metadata.clone(),
+            assigned_account: entry.assigned_account.read().await.clone(),
+            registered_at_ms: epoch_ms()
+                .saturating_sub(entry.registered_at.elapsed().as_millis() as u64),
+        });
+    }
+    sessions.sort_by(|a, b| a.id.cmp(&b.id));
+    PersistedRegistry { sessions }
+}
+
+/// Reload the persisted registry into `state`.
+///
+/// Each saved session is health-checked; reachable ones are re-registered
+/// with their credential account assignment, unreachable ones are dropped.
+/// Returns the number of restored sessions.
+pub async fn restore(state: &MuxState) -> usize {
+    let Some(ref path) = state.registry_path else {
+        return 0;
+    };
+    if !path.exists() {
+        return 0;
+    }
+    let saved = match load(path) {
+        Ok(saved) => saved,
+        Err(e) => {
+            tracing::warn!(err = %e, "failed to load persisted session registry");
+            return 0;
+        }
+    };
+
+    let checks = saved.sessions.into_iter().map(|session| async move {
+        let client = UpstreamClient::with_timeout(
+            session.url.clone(),
+            session.auth_token.clone(),
+            RESTORE_HEALTH_TIMEOUT,
+        );
+        let healthy = client.health().await.is_ok();
+        (session, healthy)
+    });
+    let results = futures_util::future::join_all(checks).await;
+
+    let mut restored = 0;
+    for (session, healthy) in results {
+        if !healthy {
+            tracing::info!(session_id = %session.id, url = %session.url, "dropping unreachable saved session");
+            continue;
+        }
+        if let (Some(broker), Some(account)) =
+            (&state.credential_broker, session.assigned_account.as_deref())
+        {
+            broker.session_assigned(account).await;
+        }
+
+        let age = Duration::from_millis(epoch_ms().saturating_sub(session.registered_at_ms));
+        let entry = Arc::new(SessionEntry {
+            id: session.id.clone(),
+            url: session.url.clone(),
+            auth_token: session.auth_token,
+            metadata: session.metadata.clone(),
+            registered_at: Instant::now().checked_sub(age).unwrap_or_else(Instant::now),
+            cached_screen: RwLock::new(None),
+            cached_status: RwLock::new(None),
+            health_failures: AtomicU32::new(0),
+            cancel: CancellationToken::new(),
+            ws_bridge: RwLock::new(None),
+            assigned_account: RwLock::new(session.assigned_account),
+            transport: SessionTransport::Http,
+        });
+        state.sessions.write().await.insert(session.id.clone(), entry);
+        state.prewarm.lock().await.touch(&session.id);
+        let _ = state.feed.event_tx.send(MuxEvent::SessionOnline {
+            session: session.id,
+            url: session.url,
+            metadata: session.metadata,
+        });
+        restored += 1;
+    }
+
+    tracing::info!(restored, "restored persisted session registry");
+    state.persist_sessions().await;
+    restored
+}
+
+#[cfg(test)]
+#[path = "registry_tests.rs"]
+mod tests;
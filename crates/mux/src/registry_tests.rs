@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use std::sync::atomic::AtomicU32;
+use std::sync::Arc;
+use std::time::Instant;
+
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use super::{load, restore, save, PersistedRegistry, PersistedSession, REGISTRY_FILE};
+use crate::config::MuxConfig;
+use crate::state::{MuxState, SessionEntry, SessionTransport};
+
+fn test_state(dir: &std::path::Path) -> MuxState {
+    let config = MuxConfig {
+        host: "127.0.0.1".into(),
+        port: 0,
+        auth_token: None,
+        screen_poll_ms: 500,
+        status_poll_ms: 2000,
+        health_check_ms: 10000,
+        max_health_failures: 3,
+        launch: None,
+        credential_config: None,
+        prewarm_capacity: 64,
+        prewarm_poll_ms: 15000,
+        state_dir: Some(dir.to_path_buf()),
+        api_key_file: None,
+        #[cfg(debug_assertions)]
+        hot: false,
+    };
+    let mut state = MuxState::new(config, CancellationToken::new());
+    state.registry_path = Some(dir.join(REGISTRY_FILE));
+    state
+}
+
+fn entry(id: &str, url: &str, account: Option<&str>, transport: SessionTransport) -> SessionEntry {
+    SessionEntry {
+        id: id.to_owned(),
+        url: url.to_owned(),
+        auth_token: Some("tok".to_owned()),
+        metadata: serde_json::json!({"role": "worker"}),
+        registered_at: Instant::now(),
+        cached_screen: RwLock::new(None),
+        cached_status: RwLock::new(None),
+        health_failures: AtomicU32::new(0),
+        cancel: CancellationToken::new(),
+        ws_bridge: RwLock::new(None),
+        assigned_account: RwLock::new(account.map(str::to_owned)),
+        transport,
+    }
+}
+
+fn saved(id: &str, url: &str, account: Option<&str>) -> PersistedSession {
+    PersistedSession {
+        id: id.to_owned(),
+        url: url.to_owned(),
+        auth_token: None,
+        metadata: serde_json::Value::Null,
+        assigned_account: account.map(str::to_owned),
+        registered_at_ms: 0,
+    }
+}
+
+/// Serve a minimal upstream that answers health checks.
+async fn healthy_upstream() -> anyhow::Result<String> {
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let router = axum::Router::new().route(
+        "/api/v1/health",
+        axum::routing::get(|| async { axum::Json(serde_json::json!({"status": "running"})) }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    Ok(url)
+}
+
+#[tokio::test]
+async fn persist_skips_nats_sessions() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let state = test_state(tmp.path());
+    {
+        let mut sessions = state.sessions.write().await;
+        sessions.insert(
+            "b".into(),
+            Arc::new(entry("b", "http://b", Some("acct"), SessionTransport::Http)),
+        );
+        sessions.insert(
+            "n".into(),
+            Arc::new(entry("n", "nats://n", None, SessionTransport::Nats { prefix: "x".into() })),
+        );
+        sessions.insert("a".into(), Arc::new(entry("a", "http://a", None, SessionTransport::Http)));
+    }
+    state.persist_sessions().await;
+
+    let registry = load(&tmp.path().join(REGISTRY_FILE))?;
+    let ids: Vec<_> = registry.sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["a", "b"]);
+    assert_eq!(registry.sessions[1].assigned_account.as_deref(), Some("acct"));
+    assert_eq!(registry.sessions[1].auth_token.as_deref(), Some("tok"));
+    assert_eq!(registry.sessions[1].metadata["role"], "worker");
+
+    state.remove_session("a").await;
+    let registry = load(&tmp.path().join(REGISTRY_FILE))?;
+    assert_eq!(registry.sessions.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn restore_keeps_reachable_sessions() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let url = healthy_upstream().await?;
+    let path = tmp.path().join(REGISTRY_FILE);
+    save(
+        &path,
+        &PersistedRegistry {
+            sessions: vec![
+                saved("alive", &url, Some("acct")),
+                // Nothing listens on port 1.
+                saved("gone", "http://127.0.0.1:1", None),
+            ],
+        },
+    )?;
+
+    let state = test_state(tmp.path());
+    let mut events = state.feed.event_tx.subscribe();
+    assert_eq!(restore(&state).await, 1);
+
+    let sessions = state.sessions.read().await;
+    let alive = sessions.get("alive").ok_or_else(|| anyhow::anyhow!("not restored"))?;
+    assert_eq!(alive.assigned_account.read().await.as_deref(), Some("acct"));
+    assert!(!sessions.contains_key("gone"));
+    drop(sessions);
+    assert!(matches!(
+        events.try_recv()?,
+        crate::state::MuxEvent::SessionOnline { ref session, .. } if session == "alive"
+    ));
+
+    // The dropped session is pruned from the snapshot.
+    let ids: Vec<_> = load(&path)?.sessions.into_iter().map(|s| s.id).collect();
+    assert_eq!(ids, vec!["alive"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn restore_without_snapshot_is_noop() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let state = test_state(tmp.path());
+    assert_eq!(restore(&state).await, 0);
+    assert!(state.sessions.read().await.is_empty());
+    Ok(())
+}
@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Kubernetes-style label selectors over session metadata.
+//!
+//! A selector is a comma-separated list of requirements, all of which must
+//! match:
+//!
+//! - `key=value` / `key==value` — label equals value
+//! - `key!=value` — label differs from value (or is absent)
+//! - `key in (a,b)` / `key notin (a,b)` — set membership (`notin` also
+//!   matches absent labels)
+//! - `key` / `!key` — label exists / does not exist
+//!
+//! Keys are dot-separated paths into the metadata object, mirroring how
+//! `--label a.b=v` nests values (e.g. `k8s.namespace=prod`).
+
+use serde_json::Value;
+
+/// A parsed label selector. The empty selector matches everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelSelector {
+    requirements: Vec<Requirement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Requirement {
+    Eq(String, String),
+    NotEq(String, String),
+    In(String, Vec<String>),
+    NotIn(String, Vec<String>),
+    Exists(String),
+    NotExists(String),
+}
+
+impl LabelSelector {
+    /// Parse a selector string such as `team=infra,role!=mayor`.
+    pub fn parse(input: &str) -> anyhow::Result<Self> {
+        let requirements = split_top_level(input)?
+            .into_iter()
+            .map(|part| parse_requirement(part.trim()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self { requirements })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+
+    /// Whether `metadata` satisfies every requirement.
+    pub fn matches(&self, metadata: &Value) -> bool {
+        self.requirements.iter().all(|req| {
+            let label = |key: &str| lookup(metadata, key);
+            match req {
+                Requirement::Eq(k, v) => label(k).as_deref() == Some(v.as_str()),
+                Requirement::NotEq(k, v) => label(k).as_deref() != Some(v.as_str()),
+                Requirement::In(k, vs) => label(k).is_some_and(|l| vs.contains(&l)),
+                Requirement::NotIn(k, vs) => !label(k).is_some_and(|l| vs.contains(&l)),
+                Requirement::Exists(k) => label(k).is_some(),
+                Requirement::NotExists(k) => label(k).is_none(),
+            }
+        })
+    }
+}
+
+/// Split on commas outside of `(...)` value sets, dropping empty parts.
+fn split_top_level(input: &str) -> anyhow::Result<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut depth = 0u32;
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1).ok_or_else(|| anyhow::anyhow!("unbalanced ')'"))?,
+            ',' if depth == 0 => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    anyhow::ensure!(depth == 0, "unbalanced '('");
+    parts.push(&input[start..]);
+    Ok(parts.into_iter().filter(|p| !p.trim().is_empty()).collect())
+}
+
+fn parse_requirement(part: &str) -> anyhow::Result<Requirement> {
+    if let Some(key) = part.strip_prefix('!') {
+        return Ok(Requirement::NotExists(parse_key(key)?));
+    }
+    if let Some((key, value)) = part.split_once("!=") {
+        return Ok(Requirement::NotEq(parse_key(key)?, value.trim().to_owned()));
+    }
+    if let Some((key, value)) = part.split_once("==").or_else(|| part.split_once('=')) {
+        return Ok(Requirement::Eq(parse_key(key)?, value.trim().to_owned()));
+    }
+    if let Some((key, rest)) = part.split_once(" notin ") {
+        return Ok(Requirement::NotIn(parse_key(key)?, parse_set(rest)?));
+    }
+    if let Some((key, rest)) = part.split_once(" in ") {
+        return Ok(Requirement::In(parse_key(key)?, parse_set(rest)?));
+    }
+    Ok(Requirement::Exists(parse_key(part)?))
+}
+
+fn parse_key(key: &str) -> anyhow::Result<String> {
+    let key = key.trim();
+    let valid = !key.is_empty()
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '/'));
+    anyhow::ensure!(valid, "invalid label key: {key:?}");
+    Ok(key.to_owned())
+}
+
+fn parse_set(rest: &str) -> anyhow::Result<Vec<String>> {
+    let inner = rest
+        .trim()
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or_else(|| anyhow::anyhow!("expected (a,b,...) after in/notin, got {rest:?}"))?;
+    Ok(inner.split(',').map(|v| v.trim().to_owned()).filter(|v| !v.is_empty()).collect())
+}
+
+/// Resolve a dot-separated key to a scalar label value.
+fn lookup(metadata: &Value, key: &str) -> Option<String> {
+    let value = key.split('.').try_fold(metadata, |v, part| v.get(part))?;
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "selector_tests.rs"]
+mod tests;
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::LabelSelector;
+
+fn metadata() -> serde_json::Value {
+    serde_json::json!({
+        "agent": "claude",
+        "team": "infra",
+        "role": "worker",
+        "k8s": { "namespace": "prod" },
+        "shard": 3,
+    })
+}
+
+#[test]
+fn matching_selectors() -> anyhow::Result<()> {
+    let meta = metadata();
+    for input in [
+        "",
+        "team=infra",
+        "team==infra",
+        "team=infra,role!=mayor",
+        "k8s.namespace=prod",
+        "shard=3",
+        "role in (worker, reviewer)",
+        "role notin (mayor)",
+        "missing notin (x)",
+        "missing!=x",
+        "team",
+        "!missing",
+        " team = infra , agent=claude ",
+    ] {
+        assert!(LabelSelector::parse(input)?.matches(&meta), "{input:?} should match");
+    }
+    Ok(())
+}
+
+#[test]
+fn non_matching_selectors() -> anyhow::Result<()> {
+    let meta = metadata();
+    for input in [
+        "team=ops",
+        "team=infra,role=mayor",
+        "role!=worker",
+        "role in (mayor,deacon)",
+        "role notin (worker)",
+        "missing in (x)",
+        "missing",
+        "!team",
+        // Objects are not scalar labels.
+        "k8s=prod",
+    ] {
+        assert!(!LabelSelector::parse(input)?.matches(&meta), "{input:?} should not match");
+    }
+    Ok(())
+}
+
+#[test]
+fn invalid_selectors() {
+    for input in ["=x", "team in infra", "role in (a", "a)", "bad key=x", "!"] {
+        assert!(LabelSelector::parse(input).is_err(), "{input:?} should be rejected");
+    }
+}
+
+#[test]
+fn empty_selector() -> anyhow::Result<()> {
+    assert!(LabelSelector::parse(" , ")?.is_empty());
+    assert!(LabelSelector::default().matches(&serde_json::Value::Null));
+    Ok(())
+}
@@ -2,6 +2,7 @@
 // Copyright (c) 2026 Alfred Jean LLC
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
 use std::time::Instant;
@@ -12,6 +13,7 @@ use tokio_util::sync::CancellationToken;
 use crate::config::MuxConfig;
 use crate::credential::broker::CredentialBroker;
 use crate::credential::CredentialEvent;
+use crate::metrics::MuxMetrics;
 use crate::upstream::bridge::WsBridge;
 use crate::upstream::prewarm::PrewarmCache;
 
@@ -130,6 +132,12 @@ pub struct MuxState {
     /// NATS client for publishing input commands to NATS-transport sessions.
     /// Set when a NATS relay subscriber is configured.
     pub nats_client: RwLock<Option<async_nats::Client>>,
+    /// Where the session registry is persisted. `None` disables persistence.
+    pub registry_path: Option<PathBuf>,
+    /// Counters exported on `GET /metrics`.
+    pub metrics: Arc<MuxMetrics>,
+    /// Serializes registry snapshots so an older one never overwrites a newer one.
+    registry_lock: Mutex<()>,
 }
 
 impl MuxState {
@@ -143,6 +151,23 @@ impl MuxState {
             feed: SessionFeed::new(),
             credential_broker: None,
             nats_client: RwLock::new(None),
+            registry_path: None,
+            metrics: Arc::new(MuxMetrics::default()),
+            registry_lock: Mutex::new(()),
+        }
+    }
+
+    /// Snapshot the session registry to [`registry_path`](Self::registry_path).
+    ///
+    /// Call after adding or removing sessions or changing account assignments.
+    pub async fn persist_sessions(&self) {
+        let Some(ref path) = self.registry_path else {
+            return;
+        };
+        let _guard = self.registry_lock.lock().await;
+        let registry = crate::registry::snapshot(self).await;
+        if let Err(e) = crate::registry::save(path, &registry) {
+            tracing::warn!(err = %e, "failed to persist session registry");
         }
     }
 
@@ -161,6 +186,7 @@ impl MuxState {
         }
         drop(watchers);
         self.prewarm.lock().await.remove(id);
+        self.persist_sessions().await;
         Some(entry)
     }
 }
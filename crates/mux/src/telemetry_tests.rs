@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use axum::http::{HeaderMap, HeaderValue};
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+use tracing_subscriber::layer::SubscriberExt;
+
+use super::{extract_context, inject_current};
+
+#[test]
+fn inject_round_trips_current_span() -> anyhow::Result<()> {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let provider =
+        SdkTracerProvider::builder().with_simple_exporter(InMemorySpanExporter::default()).build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut inbound = HeaderMap::new();
+    inbound.insert(
+        "traceparent",
+        HeaderValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+    );
+    let span = tracing::info_span!("request");
+    let _ =
+        tracing_opentelemetry::OpenTelemetrySpanExt::set_parent(&span, extract_context(&inbound));
+
+    let mut outbound = HeaderMap::new();
+    span.in_scope(|| inject_current(&mut outbound));
+
+    let traceparent = outbound
+        .get("traceparent")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| anyhow::anyhow!("no traceparent in {outbound:?}"))?;
+    assert!(traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"), "{traceparent}");
+    assert!(!traceparent.contains("00f067aa0ba902b7"), "child span id expected: {traceparent}");
+
+    let cx = extract_context(&outbound);
+    assert!(cx.span().span_context().is_sampled());
+    Ok(())
+}
+
+#[test]
+fn inject_is_noop_without_span() {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let mut headers = HeaderMap::new();
+    inject_current(&mut headers);
+    assert!(headers.get("traceparent").is_none());
+}
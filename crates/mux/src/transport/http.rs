@@ -6,13 +6,14 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
 use crate::error::MuxError;
+use crate::selector::LabelSelector;
 use crate::state::{epoch_ms, MuxEvent, MuxState, SessionEntry};
 use crate::upstream::client::UpstreamClient;
 
@@ -52,6 +53,17 @@ pub struct SessionInfo {
     pub cached_state: Option<String>,
 }
 
+/// Query parameters for `GET /api/v1/sessions`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListSessionsQuery {
+    /// Label selector over session metadata (e.g. `team=infra,role!=mayor`).
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// Comma-separated agent states to keep (matched against the cached state).
+    #[serde(default)]
+    pub state: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct DeregisterResponse {
     pub id: String,
@@ -113,6 +125,12 @@ pub async fn health(State(s): State<Arc<MuxState>>) -> impl IntoResponse {
     Json(HealthResponse { status: "running".to_owned(), session_count: sessions.len() })
 }
 
+/// `GET /metrics` — Prometheus text exposition of mux metrics.
+pub async fn metrics(State(s): State<Arc<MuxState>>) -> impl IntoResponse {
+    let body = crate::metrics::render(&s).await;
+    ([(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")], body)
+}
+
 /// `POST /api/v1/sessions` — register a coop session.
 pub async fn register_session(
     State(s): State<Arc<MuxState>>,
@@ -222,6 +240,7 @@ pub async fn register_session(
 
         // Add to pre-warm cache for slow-poll background updates.
         s.prewarm.lock().await.touch(&id);
+        s.persist_sessions().await;
 
         // Clone metadata for credential filtering before moving into the event.
         let cred_metadata = event_metadata.clone();
@@ -247,6 +266,7 @@ pub async fn register_session(
             let session_token = cred_token;
             let session_metadata = cred_metadata;
             let entry_clone = Arc::clone(&entry);
+            let state = Arc::clone(&s);
             tokio::spawn(async move {
                 // Pick an assigned account from the pool.
                 let assigned = broker.assign_account(None).await;
@@ -257,6 +277,7 @@ pub async fn register_session(
                         account = %assigned_name,
                         "pool: assigned account to new session"
                     );
+                    state.persist_sessions().await;
                 }
 
                 // Push all healthy account profiles to the session with retries.
@@ -393,12 +414,39 @@ pub async fn deregister_session(
     }
 }
 
-/// `GET /api/v1/sessions` — list all registered sessions.
-pub async fn list_sessions(State(s): State<Arc<MuxState>>) -> impl IntoResponse {
+/// `GET /api/v1/sessions` — list registered sessions, optionally filtered by
+/// `?selector=` (label selector) and `?state=` (comma-separated agent states).
+pub async fn list_sessions(
+    State(s): State<Arc<MuxState>>,
+    Query(q): Query<ListSessionsQuery>,
+) -> impl IntoResponse {
+    let selector = match LabelSelector::parse(q.selector.as_deref().unwrap_or_default()) {
+        Ok(selector) => selector,
+        Err(e) => {
+            return MuxError::BadRequest
+                .to_http_response(format!("invalid selector: {e}"))
+                .into_response()
+        }
+    };
+    let states: Vec<&str> = q
+        .state
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|st| !st.is_empty())
+        .collect();
+
     let sessions = s.sessions.read().await;
     let mut list = Vec::with_capacity(sessions.len());
     for entry in sessions.values() {
+        if !selector.matches(&entry.metadata) {
+            continue;
+        }
         let cached_state = entry.cached_status.read().await.as_ref().map(|st| st.state.clone());
+        if !states.is_empty() && !cached_state.as_deref().is_some_and(|st| states.contains(&st)) {
+            continue;
+        }
         let registered_at_ms =
             epoch_ms().saturating_sub(entry.registered_at.elapsed().as_millis() as u64);
         list.push(SessionInfo {
@@ -410,7 +458,7 @@ pub async fn list_sessions(State(s): State<Arc<MuxState>>) -> impl IntoResponse
             cached_state,
         });
     }
-    Json(list)
+    Json(list).into_response()
 }
 
 /// `GET /api/v1/sessions/{id}/screen` — cached screen snapshot.
@@ -613,10 +661,6 @@ pub async fn launch_session(
 }
 
 /// Generic POST proxy to upstream coop.
-///
-/// For NATS-transport sessions, routes input/nudge/respond through NATS
-/// instead of HTTP. Other paths fall through to HTTP (which may fail if
-/// the session is only reachable via NATS).
 async fn proxy_post(
     state: &MuxState,
     session_id: &str,
@@ -632,8 +676,26 @@ async fn proxy_post(
     };
     drop(sessions);
 
+    match forward_post(state, &entry, path, &body).await {
+        Ok(value) => Json(value).into_response(),
+        Err(e) => MuxError::UpstreamError.to_http_response(e).into_response(),
+    }
+}
+
+/// POST `body` to a session's upstream `path` and return the response body.
+///
+/// For NATS-transport sessions, routes input/nudge/respond through NATS
+/// instead of HTTP. Other paths fall through to HTTP (which may fail if
+/// the session is only reachable via NATS).
+pub(crate) async fn forward_post(
+    state: &MuxState,
+    entry: &SessionEntry,
+    path: &str,
+    body: &serde_json::Value,
+) -> Result<serde_json::Value, String> {
     // For NATS-transport sessions, route input/nudge/respond via NATS.
     if let crate::state::SessionTransport::Nats { ref prefix } = entry.transport {
+        let session_id = &entry.id;
         let nats_subject = match path {
             "/api/v1/input" | "/api/v1/input/raw" | "/api/v1/input/keys" => {
                 Some(format!("{prefix}.session.{session_id}.input"))
@@ -645,26 +707,18 @@ async fn proxy_post(
 
         if let Some(subject) = nats_subject {
             let client_guard = state.nats_client.read().await;
-            if let Some(ref nats_client) = *client_guard {
-                let payload = serde_json::to_vec(&body).unwrap_or_default();
-                if let Err(e) = nats_client.publish(subject, payload.into()).await {
-                    return MuxError::UpstreamError
-                        .to_http_response(format!("nats publish error: {e}"))
-                        .into_response();
-                }
-                return Json(serde_json::json!({"ok": true})).into_response();
-            }
-            return MuxError::UpstreamError
-                .to_http_response("nats client not available")
-                .into_response();
+            let Some(ref nats_client) = *client_guard else {
+                return Err("nats client not available".to_owned());
+            };
+            let payload = serde_json::to_vec(body).unwrap_or_default();
+            nats_client
+                .publish(subject, payload.into())
+                .await
+                .map_err(|e| format!("nats publish error: {e}"))?;
+            return Ok(serde_json::json!({"ok": true}));
         }
     }
 
     let client = UpstreamClient::new(entry.url.clone(), entry.auth_token.clone());
-    match client.post_json(path, &body).await {
-        Ok(value) => Json(value).into_response(),
-        Err(e) => {
-            MuxError::UpstreamError.to_http_response(format!("upstream error: {e}")).into_response()
-        }
-    }
+    client.post_json(path, body).await.map_err(|e| format!("upstream error: {e}"))
 }
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! HTTP handlers for selector-based nudge/respond fan-out across sessions.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tracing::Instrument;
+
+use crate::error::MuxError;
+use crate::selector::LabelSelector;
+use crate::state::{MuxState, SessionEntry, SessionTransport};
+use crate::transport::http::forward_post;
+use crate::upstream::client::UpstreamClient;
+
+/// Maximum concurrent upstream requests per broadcast.
+const MAX_CONCURRENT_SENDS: usize = 8;
+
+/// Body fields forwarded to the upstream `/api/v1/agent/nudge`.
+const NUDGE_FIELDS: &[&str] = &["message"];
+/// Body fields forwarded to the upstream `/api/v1/agent/respond`.
+const RESPOND_FIELDS: &[&str] = &["accept", "option", "text", "answers"];
+
+/// Target selection plus the body forwarded to each matching session.
+#[derive(Debug, Deserialize)]
+pub struct BroadcastRequest {
+    /// Label selector over session metadata (e.g. `team=infra,role!=mayor`).
+    /// Required; an explicit empty string selects every session.
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// Only deliver to sessions whose agent is currently in one of these
+    /// states (e.g. `["idle"]`). Empty means any state.
+    #[serde(default)]
+    pub states: Vec<String>,
+    /// Remaining fields are the upstream nudge/respond body, limited to
+    /// [`NUDGE_FIELDS`] or [`RESPOND_FIELDS`].
+    #[serde(flatten)]
+    pub body: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BroadcastResponse {
+    /// Number of sessions matching the selector.
+    pub matched: usize,
+    pub results: Vec<BroadcastResult>,
+}
+
+/// Per-session fan-out result.
+#[derive(Debug, Serialize)]
+pub struct BroadcastResult {
+    pub session: String,
+    /// Agent state seen by the state filter (only when `states` is set).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// True when the state filter excluded this session.
+    pub skipped: bool,
+    /// Upstream response (`NudgeOutcome` / `RespondOutcome`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `POST /api/v1/sessions/nudge` — nudge every matching session.
+pub async fn broadcast_nudge(
+    State(s): State<Arc<MuxState>>,
+    Json(req): Json<BroadcastRequest>,
+) -> impl IntoResponse {
+    broadcast(s, req, "/api/v1/agent/nudge", NUDGE_FIELDS).await
+}
+
+/// `POST /api/v1/sessions/respond` — respond to the prompt on every matching session.
+pub async fn broadcast_respond(
+    State(s): State<Arc<MuxState>>,
+    Json(req): Json<BroadcastRequest>,
+) -> impl IntoResponse {
+    broadcast(s, req, "/api/v1/agent/respond", RESPOND_FIELDS).await
+}
+
+async fn broadcast(
+    state: Arc<MuxState>,
+    req: BroadcastRequest,
+    path: &'static str,
+    fields: &[&str],
+) -> axum::response::Response {
+    // A forgotten or misspelled selector must not silently target every session.
+    let Some(ref selector) = req.selector else {
+        return MuxError::BadRequest
+            .to_http_response("selector is required (use \"\" to select every session)")
+            .into_response();
+    };
+    if let Some(key) = req.body.keys().find(|k| !fields.contains(&k.as_str())) {
+        return MuxError::BadRequest
+            .to_http_response(format!("unknown field `{key}`"))
+            .into_response();
+    }
+    let selector = match LabelSelector::parse(selector) {
+        Ok(selector) => selector,
+        Err(e) => {
+            return MuxError::BadRequest
+                .to_http_response(format!("invalid selector: {e}"))
+                .into_response()
+        }
+    };
+
+    let mut targets: Vec<Arc<SessionEntry>> = {
+        let sessions = state.sessions.read().await;
+        sessions.values().filter(|e| selector.matches(&e.metadata)).map(Arc::clone).collect()
+    };
+    targets.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let body = serde_json::Value::Object(req.body);
+    let states = Arc::new(req.states);
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_SENDS));
+    let mut handles = Vec::with_capacity(targets.len());
+    for entry in &targets {
+        let state = Arc::clone(&state);
+        let entry = Arc::clone(entry);
+        let states = Arc::clone(&states);
+        let sem = Arc::clone(&semaphore);
+        let body = body.clone();
+        handles.push(tokio::spawn(
+            async move {
+                let _permit = sem.acquire().await;
+                send_one(&state, &entry, &states, path, &body).await
+            }
+            .in_current_span(),
+        ));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for (entry, handle) in targets.iter().zip(handles) {
+        results.push(handle.await.unwrap_or_else(|e| BroadcastResult {
+            session: entry.id.clone(),
+            state: None,
+            skipped: false,
+            outcome: None,
+            error: Some(format!("task failed: {e}")),
+        }));
+    }
+
+    let delivered = results.iter().filter(|r| r.outcome.is_some()).count();
+    tracing::info!(path, matched = targets.len(), delivered, "broadcast complete");
+    Json(BroadcastResponse { matched: targets.len(), results }).into_response()
+}
+
+/// Apply the state filter to one session, then forward the request.
+#[tracing::instrument(name = "broadcast.send", skip_all, fields(session = %entry.id))]
+async fn send_one(
+    state: &MuxState,
+    entry: &SessionEntry,
+    states: &[String],
+    path: &str,
+    body: &serde_json::Value,
+) -> BroadcastResult {
+    let mut result = BroadcastResult {
+        session: entry.id.clone(),
+        state: None,
+        skipped: false,
+        outcome: None,
+        error: None,
+    };
+
+    if !states.is_empty() {
+        if matches!(entry.transport, SessionTransport::Nats { .. }) {
+            result.error = Some("state filter unsupported for NATS sessions".to_owned());
+            return result;
+        }
+        let client = UpstreamClient::new(entry.url.clone(), entry.auth_token.clone());
+        let agent_state = match client.get_agent().await {
+            Ok(agent) => agent.get("state").and_then(|v| v.as_str()).unwrap_or("").to_owned(),
+            Err(e) => {
+                result.error = Some(format!("upstream error: {e}"));
+                return result;
+            }
+        };
+        result.skipped = !states.contains(&agent_state);
+        result.state = Some(agent_state);
+        if result.skipped {
+            return result;
+        }
+    }
+
+    match forward_post(state, entry, path, body).await {
+        Ok(outcome) => result.outcome = Some(outcome),
+        Err(e) => result.error = Some(e),
+    }
+    result
+}
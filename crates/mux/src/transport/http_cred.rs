@@ -339,6 +339,9 @@ pub async fn credentials_pool_rebalance(
             }
         }
     }
+    if reassigned + failed > 0 {
+        s.persist_sessions().await;
+    }
 
     Json(serde_json::json!({
         "rebalanced": reassigned,
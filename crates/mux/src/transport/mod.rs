@@ -5,10 +5,12 @@
 
 pub mod auth;
 pub mod http;
+pub mod http_broadcast;
 pub mod http_cred;
 pub mod nats_sub;
 #[cfg(feature = "legacy-oauth")]
 pub mod nats_pub;
+pub mod trace;
 pub mod ws;
 pub mod ws_mux;
 
@@ -65,6 +67,7 @@ fn build_router_inner(
     Router::new()
         // Health (no auth)
         .route("/api/v1/health", get(http::health))
+        .route("/metrics", get(http::metrics))
         // Session management
         .route("/api/v1/sessions", post(http::register_session).get(http::list_sessions))
         .route("/api/v1/sessions/{id}", delete(http::deregister_session))
@@ -79,6 +82,9 @@ fn build_router_inner(
         .route("/api/v1/sessions/{id}/input/raw", post(http::session_input_raw))
         .route("/api/v1/sessions/{id}/input/keys", post(http::session_input_keys))
         .route("/api/v1/sessions/{id}/upload", post(http::session_upload))
+        // Selector-based fan-out
+        .route("/api/v1/sessions/nudge", post(http_broadcast::broadcast_nudge))
+        .route("/api/v1/sessions/respond", post(http_broadcast::broadcast_respond))
         // Launch
         .route("/api/v1/sessions/launch", post(http::launch_session))
         .route("/api/v1/config/launch", get(http::launch_config))
@@ -99,6 +105,7 @@ fn build_router_inner(
         .route("/api/v1/credentials/pool/rebalance", post(http_cred::credentials_pool_rebalance))
         // Middleware
         .layer(middleware::from_fn_with_state(state.clone(), auth::auth_layer))
+        .layer(middleware::from_fn(trace::trace_layer))
         .layer(CorsLayer::permissive())
         .with_state(state)
 }
@@ -7,6 +7,7 @@
 //! lifecycle, sends cached screen snapshots on connect, pushes state
 //! transitions immediately + screen thumbnails periodically.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use axum::extract::ws::{Message, WebSocket};
@@ -16,6 +17,7 @@ use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
+use crate::selector::LabelSelector;
 use crate::state::{MuxEvent, MuxState, WatcherState};
 use crate::transport::auth;
 use crate::upstream::feed::spawn_event_feed;
@@ -32,10 +34,22 @@ pub struct MuxWsQuery {
 #[derive(Debug, Deserialize)]
 #[serde(tag = "event", rename_all = "snake_case")]
 enum MuxClientMessage {
-    /// Subscribe to events from specific sessions.
-    Subscribe { sessions: Vec<String> },
-    /// Unsubscribe from sessions.
-    Unsubscribe { sessions: Vec<String> },
+    /// Subscribe to events from specific sessions, and/or every session
+    /// matching a label selector (including ones registered later).
+    Subscribe {
+        #[serde(default)]
+        sessions: Vec<String>,
+        #[serde(default)]
+        selector: Option<String>,
+    },
+    /// Unsubscribe from sessions, or drop a selector subscription along with
+    /// the sessions only it was watching.
+    Unsubscribe {
+        #[serde(default)]
+        sessions: Vec<String>,
+        #[serde(default)]
+        selector: Option<String>,
+    },
     /// Forward keyboard input to a session.
     #[serde(rename = "input:send")]
     InputSend {
@@ -114,6 +128,113 @@ struct ScreenThumbnail {
     seq: u64,
 }
 
+/// Why a client watches a session.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Owner {
+    /// Subscribed to by ID.
+    Explicit,
+    /// Matched by the selector subscription with this source string.
+    Selector(String),
+}
+
+/// The sessions one `/ws/mux` client watches. Each watched session records
+/// what subscribed it, so dropping a selector keeps sessions that an ID
+/// subscription or another selector still wants.
+#[derive(Debug, Default)]
+struct Subscriptions {
+    /// Selector subscriptions, keyed by their source string.
+    selectors: Vec<(String, LabelSelector)>,
+    watched: HashMap<String, HashSet<Owner>>,
+}
+
+impl Subscriptions {
+    fn contains(&self, session: &str) -> bool {
+        self.watched.contains_key(session)
+    }
+
+    fn sessions(&self) -> impl Iterator<Item = &String> {
+        self.watched.keys()
+    }
+
+    /// Record `owner` for each session, returning the newly watched ones.
+    fn add(&mut self, owner: &Owner, sessions: impl IntoIterator<Item = String>) -> Vec<String> {
+        let mut added = Vec::new();
+        for sid in sessions {
+            let owners = self.watched.entry(sid.clone()).or_default();
+            if owners.is_empty() {
+                added.push(sid);
+            }
+            owners.insert(owner.clone());
+        }
+        added
+    }
+
+    /// Subscribe to sessions by ID, returning the newly watched ones.
+    fn add_sessions(&mut self, sessions: Vec<String>) -> Vec<String> {
+        self.add(&Owner::Explicit, sessions)
+    }
+
+    /// Add a selector subscription along with the sessions it currently
+    /// matches, returning the newly watched ones.
+    fn add_selector(
+        &mut self,
+        raw: String,
+        selector: LabelSelector,
+        matched: Vec<String>,
+    ) -> Vec<String> {
+        if !self.selectors.iter().any(|(s, _)| *s == raw) {
+            self.selectors.push((raw.clone(), selector));
+        }
+        self.add(&Owner::Selector(raw), matched)
+    }
+
+    /// A session registered: every selector matching its metadata claims it.
+    /// Returns whether it is newly watched.
+    fn session_online(&mut self, session: &str, metadata: &serde_json::Value) -> bool {
+        let owners: Vec<Owner> = self
+            .selectors
+            .iter()
+            .filter(|(_, sel)| sel.matches(metadata))
+            .map(|(raw, _)| Owner::Selector(raw.clone()))
+            .collect();
+        let mut added = false;
+        for owner in &owners {
+            added |= !self.add(owner, [session.to_owned()]).is_empty();
+        }
+        added
+    }
+
+    /// Unsubscribe from sessions by ID, whatever subscribed them. Returns the
+    /// ones that were watched.
+    fn remove_sessions(&mut self, sessions: Vec<String>) -> Vec<String> {
+        sessions.into_iter().filter(|sid| self.watched.remove(sid).is_some()).collect()
+    }
+
+    /// Drop a selector subscription. Returns the sessions nothing else owns,
+    /// which are no longer watched.
+    fn remove_selector(&mut self, raw: &str) -> Vec<String> {
+        let Some(pos) = self.selectors.iter().position(|(s, _)| s == raw) else {
+            return Vec::new();
+        };
+        self.selectors.remove(pos);
+        let owner = Owner::Selector(raw.to_owned());
+        let mut dropped = Vec::new();
+        self.watched.retain(|sid, owners| {
+            owners.remove(&owner);
+            if owners.is_empty() {
+                dropped.push(sid.clone());
+            }
+            !owners.is_empty()
+        });
+        dropped
+    }
+
+    /// Forget a deregistered session. Its watcher is already gone.
+    fn session_offline(&mut self, session: &str) {
+        self.watched.remove(session);
+    }
+}
+
 /// WebSocket upgrade handler for `/ws/mux`.
 pub async fn ws_mux_handler(
     State(state): State<Arc<MuxState>>,
@@ -145,8 +266,8 @@ async fn handle_mux_ws(state: Arc<MuxState>, socket: WebSocket) {
     let (mut ws_tx, mut ws_rx) = socket.split();
     let mut event_rx = state.feed.event_tx.subscribe();
 
-    // Track which sessions this client is watching.
-    let mut watched: std::collections::HashSet<String> = std::collections::HashSet::new();
+    // Track which sessions this client is watching, and why.
+    let mut subs = Subscriptions::default();
 
     // Send initial session list.
     {
@@ -181,6 +302,22 @@ async fn handle_mux_ws(state: Arc<MuxState>, socket: WebSocket) {
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                     Err(_) => break,
                 };
+                match &event {
+                    // Auto-subscribe newly registered sessions matching a selector.
+                    MuxEvent::SessionOnline { session, metadata, .. } => {
+                        if subs.session_online(session, metadata) {
+                            start_watching(&state, session).await;
+                        }
+                    }
+                    // A feed also goes offline when its last watcher stops;
+                    // only a deregistered session is forgotten.
+                    MuxEvent::SessionOffline { session } => {
+                        if !state.sessions.read().await.contains_key(session) {
+                            subs.session_offline(session);
+                        }
+                    }
+                    _ => {}
+                }
                 // Forward session lifecycle and credential events to all clients;
                 // state transitions only for watched sessions.
                 let should_forward = match &event {
@@ -188,7 +325,7 @@ async fn handle_mux_ws(state: Arc<MuxState>, socket: WebSocket) {
                     | MuxEvent::CredentialRefreshFailed { .. }
                     | MuxEvent::SessionOnline { .. }
                     | MuxEvent::SessionOffline { .. } => true,
-                    MuxEvent::Transition { session, .. } => watched.contains(session),
+                    MuxEvent::Transition { session, .. } => subs.contains(session),
                     // Forward any other event variants (e.g. CredentialReauthRequired
                     // when legacy-oauth is enabled).
                     #[allow(unreachable_patterns)]
@@ -204,12 +341,12 @@ async fn handle_mux_ws(state: Arc<MuxState>, socket: WebSocket) {
 
             // Screen thumbnail batch.
             _ = screen_interval.tick() => {
-                if watched.is_empty() {
+                if subs.watched.is_empty() {
                     continue;
                 }
                 let sessions = state.sessions.read().await;
                 let mut screens = Vec::new();
-                for session_id in &watched {
+                for session_id in subs.sessions() {
                     if let Some(entry) = sessions.get(session_id) {
                         if let Some(screen) = entry.cached_screen.read().await.as_ref() {
                             screens.push(ScreenThumbnail {
@@ -242,13 +379,24 @@ async fn handle_mux_ws(state: Arc<MuxState>, socket: WebSocket) {
                     Message::Text(text) => {
                         if let Ok(client_msg) = serde_json::from_str::<MuxClientMessage>(&text) {
                             match client_msg {
-                                MuxClientMessage::Subscribe { sessions } => {
-                                    let mut new_sids = Vec::new();
-                                    for sid in sessions {
-                                        if watched.insert(sid.clone()) {
-                                            start_watching(&state, &sid).await;
-                                            new_sids.push(sid);
-                                        }
+                                MuxClientMessage::Subscribe { sessions, selector } => {
+                                    let mut new_sids = subs.add_sessions(sessions);
+                                    if let Some(raw) = selector {
+                                        let sel = match LabelSelector::parse(&raw) {
+                                            Ok(sel) => sel,
+                                            Err(e) => {
+                                                let err = MuxServerMessage::Error { message: format!("invalid selector: {e}") };
+                                                if send_json(&mut ws_tx, &err).await.is_err() {
+                                                    break;
+                                                }
+                                                continue;
+                                            }
+                                        };
+                                        let matched = matching_sessions(&state, &sel).await;
+                                        new_sids.extend(subs.add_selector(raw, sel, matched));
+                                    }
+                                    for sid in &new_sids {
+                                        start_watching(&state, sid).await;
                                     }
                                     // Send immediate screen_batch for newly subscribed sessions
                                     // so clients don't wait for the next periodic tick.
@@ -278,11 +426,13 @@ async fn handle_mux_ws(state: Arc<MuxState>, socket: WebSocket) {
                                         }
                                     }
                                 }
-                                MuxClientMessage::Unsubscribe { sessions } => {
-                                    for sid in sessions {
-                                        if watched.remove(&sid) {
-                                            stop_watching(&state, &sid).await;
-                                        }
+                                MuxClientMessage::Unsubscribe { sessions, selector } => {
+                                    let mut dropped = subs.remove_sessions(sessions);
+                                    if let Some(raw) = selector {
+                                        dropped.extend(subs.remove_selector(&raw));
+                                    }
+                                    for sid in &dropped {
+                                        stop_watching(&state, sid).await;
                                     }
                                 }
                                 MuxClientMessage::InputSend { session, text, enter } => {
@@ -309,11 +459,17 @@ async fn handle_mux_ws(state: Arc<MuxState>, socket: WebSocket) {
     let _ = ws_tx.send(Message::Close(None)).await;
 
     // Cleanup: stop watching all sessions.
-    for sid in &watched {
+    for sid in subs.sessions() {
         stop_watching(&state, sid).await;
     }
 }
 
+/// IDs of registered sessions whose metadata matches `selector`.
+async fn matching_sessions(state: &MuxState, selector: &LabelSelector) -> Vec<String> {
+    let sessions = state.sessions.read().await;
+    sessions.values().filter(|e| selector.matches(&e.metadata)).map(|e| e.id.clone()).collect()
+}
+
 /// Increment watcher count for a session, starting the event feed if needed.
 async fn start_watching(state: &MuxState, session_id: &str) {
     let mut watchers = state.feed.watchers.write().await;
@@ -409,3 +565,7 @@ where
     };
     tx.send(Message::Text(text.into())).await.map_err(|_| ())
 }
+
+#[cfg(test)]
+#[path = "ws_mux_tests.rs"]
+mod tests;
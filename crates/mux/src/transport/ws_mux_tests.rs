@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use serde_json::json;
+
+use super::Subscriptions;
+use crate::selector::LabelSelector;
+
+fn ids(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| (*s).to_owned()).collect()
+}
+
+fn sorted(mut v: Vec<String>) -> Vec<String> {
+    v.sort();
+    v
+}
+
+fn add_selector(
+    subs: &mut Subscriptions,
+    raw: &str,
+    matched: &[&str],
+) -> anyhow::Result<Vec<String>> {
+    Ok(subs.add_selector(raw.to_owned(), LabelSelector::parse(raw)?, ids(matched)))
+}
+
+#[test]
+fn dropping_a_selector_keeps_explicit_subscriptions() -> anyhow::Result<()> {
+    let mut subs = Subscriptions::default();
+    assert_eq!(subs.add_sessions(ids(&["a"])), ids(&["a"]));
+    assert_eq!(add_selector(&mut subs, "team=infra", &["a", "b"])?, ids(&["b"]));
+
+    assert_eq!(subs.remove_selector("team=infra"), ids(&["b"]));
+    assert!(subs.contains("a"));
+    assert!(!subs.contains("b"));
+    Ok(())
+}
+
+#[test]
+fn dropping_a_selector_keeps_sessions_another_selector_matches() -> anyhow::Result<()> {
+    let mut subs = Subscriptions::default();
+    add_selector(&mut subs, "team=infra", &["a", "b"])?;
+    assert_eq!(add_selector(&mut subs, "role=worker", &["b", "c"])?, ids(&["c"]));
+
+    assert_eq!(subs.remove_selector("team=infra"), ids(&["a"]));
+    assert_eq!(sorted(subs.sessions().cloned().collect()), ids(&["b", "c"]));
+    assert_eq!(sorted(subs.remove_selector("role=worker")), ids(&["b", "c"]));
+    assert!(subs.remove_selector("role=worker").is_empty());
+    Ok(())
+}
+
+#[test]
+fn registered_sessions_are_owned_by_every_matching_selector() -> anyhow::Result<()> {
+    let mut subs = Subscriptions::default();
+    add_selector(&mut subs, "team=infra", &[])?;
+    add_selector(&mut subs, "role=worker", &[])?;
+
+    assert!(subs.session_online("a", &json!({"team": "infra", "role": "worker"})));
+    assert!(!subs.session_online("a", &json!({"team": "infra", "role": "worker"})));
+    assert!(!subs.session_online("b", &json!({"team": "web"})));
+
+    assert!(subs.remove_selector("team=infra").is_empty());
+    assert_eq!(subs.remove_selector("role=worker"), ids(&["a"]));
+    Ok(())
+}
+
+#[test]
+fn unsubscribing_by_id_and_going_offline_forget_the_session() -> anyhow::Result<()> {
+    let mut subs = Subscriptions::default();
+    add_selector(&mut subs, "team=infra", &["a", "b"])?;
+    subs.add_sessions(ids(&["a"]));
+
+    assert_eq!(subs.remove_sessions(ids(&["a", "missing"])), ids(&["a"]));
+    subs.session_offline("b");
+    assert_eq!(subs.sessions().count(), 0);
+    assert!(subs.remove_selector("team=infra").is_empty());
+    Ok(())
+}
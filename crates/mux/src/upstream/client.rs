@@ -3,6 +3,7 @@
 
 //! HTTP client for communicating with a single upstream coop instance.
 
+use reqwest::header::HeaderMap;
 use reqwest::Client;
 
 /// HTTP client wrapper for one upstream coop instance.
@@ -35,7 +36,11 @@ impl UpstreamClient {
         format!("{}{}", self.base_url, path)
     }
 
-    fn apply_auth(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    /// Attach the bearer token and the current trace context.
+    fn apply_headers(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let mut headers = HeaderMap::new();
+        crate::telemetry::inject_current(&mut headers);
+        let req = req.headers(headers);
         match &self.auth_token {
             Some(token) => req.bearer_auth(token),
             None => req,
@@ -52,7 +57,7 @@ impl UpstreamClient {
     /// Fetch screen snapshot from upstream.
     pub async fn get_screen(&self) -> anyhow::Result<serde_json::Value> {
         let req = self.client.get(self.url("/api/v1/screen"));
-        let resp = self.apply_auth(req).send().await?;
+        let resp = self.apply_headers(req).send().await?;
         let value = resp.error_for_status()?.json().await?;
         Ok(value)
     }
@@ -60,7 +65,7 @@ impl UpstreamClient {
     /// Fetch status from upstream.
     pub async fn get_status(&self) -> anyhow::Result<serde_json::Value> {
         let req = self.client.get(self.url("/api/v1/status"));
-        let resp = self.apply_auth(req).send().await?;
+        let resp = self.apply_headers(req).send().await?;
         let value = resp.error_for_status()?.json().await?;
         Ok(value)
     }
@@ -68,7 +73,7 @@ impl UpstreamClient {
     /// Fetch agent state from upstream.
     pub async fn get_agent(&self) -> anyhow::Result<serde_json::Value> {
         let req = self.client.get(self.url("/api/v1/agent"));
-        let resp = self.apply_auth(req).send().await?;
+        let resp = self.apply_headers(req).send().await?;
         let value = resp.error_for_status()?.json().await?;
         Ok(value)
     }
@@ -80,7 +85,7 @@ impl UpstreamClient {
         body: &serde_json::Value,
     ) -> anyhow::Result<serde_json::Value> {
         let req = self.client.post(self.url(path)).json(body);
-        let resp = self.apply_auth(req).send().await?.error_for_status()?;
+        let resp = self.apply_headers(req).send().await?.error_for_status()?;
         let bytes = resp.bytes().await?;
         if bytes.is_empty() {
             return Ok(serde_json::Value::Null);
@@ -73,6 +73,7 @@ pub fn spawn_health_checker(state: Arc<MuxState>) {
                         entry.health_failures.store(0, Ordering::Relaxed);
                     }
                     Err(e) => {
+                        state_ref.metrics.record_health_failure();
                         let prev = entry.health_failures.fetch_add(1, Ordering::Relaxed);
                         let count = prev + 1;
                         tracing::warn!(
@@ -87,6 +88,7 @@ pub fn spawn_health_checker(state: Arc<MuxState>) {
                                 session_id = %entry.id,
                                 "evicting session after {count} consecutive health failures"
                             );
+                            state_ref.metrics.record_health_eviction();
                             // Unassign from credential pool before removal.
                             if let Some(ref broker) = state_ref.credential_broker {
                                 if let Some(account) =
@@ -23,7 +23,7 @@ use coopmux::config::MuxConfig;
 use coopmux::credential::broker::CredentialBroker;
 use coopmux::credential::{AccountConfig, CredentialConfig};
 
-use coopmux::state::{MuxState, SessionEntry};
+use coopmux::state::{CachedStatus, MuxState, SessionEntry};
 use coopmux::transport::build_router;
 
 fn test_config() -> MuxConfig {
@@ -72,11 +72,21 @@ fn test_server(state: Arc<MuxState>) -> TestServer {
 
 /// Insert a fake session entry directly (bypasses upstream health check).
 async fn insert_session(state: &MuxState, id: &str, url: &str) {
+    insert_labeled_session(state, id, url, serde_json::Value::Null).await;
+}
+
+/// Insert a fake session entry with the given metadata.
+async fn insert_labeled_session(
+    state: &MuxState,
+    id: &str,
+    url: &str,
+    metadata: serde_json::Value,
+) {
     let entry = Arc::new(SessionEntry {
         id: id.to_owned(),
         url: url.to_owned(),
         auth_token: None,
-        metadata: serde_json::Value::Null,
+        metadata,
         registered_at: Instant::now(),
         cached_screen: tokio::sync::RwLock::new(None),
         cached_status: tokio::sync::RwLock::new(None),
@@ -124,6 +134,72 @@ async fn list_sessions_returns_registered() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Set the cached agent state of an inserted session.
+async fn set_cached_state(state: &MuxState, id: &str, agent_state: &str) {
+    let sessions = state.sessions.read().await;
+    if let Some(entry) = sessions.get(id) {
+        *entry.cached_status.write().await = Some(CachedStatus {
+            session_id: id.to_owned(),
+            state: agent_state.to_owned(),
+            pid: None,
+            uptime_secs: 0,
+            exit_code: None,
+            screen_seq: 0,
+            bytes_read: 0,
+            bytes_written: 0,
+            ws_clients: 0,
+            fetched_at: 0,
+        });
+    }
+}
+
+#[tokio::test]
+async fn list_sessions_filters_by_selector_and_state() -> anyhow::Result<()> {
+    let state = test_state();
+    let infra = serde_json::json!({ "team": "infra", "role": "worker" });
+    insert_labeled_session(&state, "a", "http://fake:2001", infra.clone()).await;
+    insert_labeled_session(&state, "b", "http://fake:2002", infra).await;
+    let mayor = serde_json::json!({ "team": "infra", "role": "mayor" });
+    insert_labeled_session(&state, "c", "http://fake:2003", mayor).await;
+    insert_labeled_session(&state, "d", "http://fake:2004", serde_json::json!({ "team": "ops" }))
+        .await;
+    set_cached_state(&state, "a", "idle").await;
+    set_cached_state(&state, "b", "working").await;
+    let server = test_server(state);
+
+    let ids = |resp: axum_test::TestResponse| -> Vec<String> {
+        let mut ids: Vec<String> = resp
+            .json::<Vec<serde_json::Value>>()
+            .iter()
+            .filter_map(|s| s["id"].as_str().map(str::to_owned))
+            .collect();
+        ids.sort();
+        ids
+    };
+
+    let resp = server.get("/api/v1/sessions").add_query_param("selector", "team=infra").await;
+    assert_eq!(ids(resp), ["a", "b", "c"]);
+
+    let resp =
+        server.get("/api/v1/sessions").add_query_param("selector", "team=infra,role!=mayor").await;
+    assert_eq!(ids(resp), ["a", "b"]);
+
+    // Sessions without a cached state never match a state filter.
+    let resp = server.get("/api/v1/sessions").add_query_param("state", "idle,prompt").await;
+    assert_eq!(ids(resp), ["a"]);
+
+    let resp = server
+        .get("/api/v1/sessions")
+        .add_query_param("selector", "role in (worker)")
+        .add_query_param("state", "working")
+        .await;
+    assert_eq!(ids(resp), ["b"]);
+
+    let resp = server.get("/api/v1/sessions").add_query_param("selector", "role in worker").await;
+    resp.assert_status_bad_request();
+    Ok(())
+}
+
 #[tokio::test]
 async fn deregister_session_removes_it() -> anyhow::Result<()> {
     let state = test_state();
@@ -363,3 +439,154 @@ async fn launch_with_empty_env_object() -> anyhow::Result<()> {
     assert_eq!(body["launched"], true);
     Ok(())
 }
+
+/// Serve a fake upstream coop whose agent reports `agent_state` and whose
+/// nudge/respond endpoints echo a delivered outcome.
+async fn fake_upstream(agent_state: &'static str) -> anyhow::Result<String> {
+    use axum::routing::{get, post};
+    let router = axum::Router::new()
+        .route(
+            "/api/v1/agent",
+            get(move || async move { axum::Json(serde_json::json!({ "state": agent_state })) }),
+        )
+        .route(
+            "/api/v1/agent/nudge",
+            post(move |axum::Json(body): axum::Json<serde_json::Value>| async move {
+                axum::Json(serde_json::json!({
+                    "delivered": true,
+                    "state_before": agent_state,
+                    "message": body["message"],
+                }))
+            }),
+        )
+        .route(
+            "/api/v1/agent/respond",
+            post(|| async { axum::Json(serde_json::json!({ "delivered": true })) }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    Ok(url)
+}
+
+#[tokio::test]
+async fn broadcast_nudge_filters_by_selector_and_state() -> anyhow::Result<()> {
+    let state = test_state();
+    let idle = fake_upstream("idle").await?;
+    let working = fake_upstream("working").await?;
+    insert_labeled_session(&state, "a", &idle, serde_json::json!({ "team": "infra" })).await;
+    insert_labeled_session(&state, "b", &working, serde_json::json!({ "team": "infra" })).await;
+    insert_labeled_session(&state, "c", &idle, serde_json::json!({ "team": "ops" })).await;
+    let server = test_server(state);
+
+    let resp = server
+        .post("/api/v1/sessions/nudge")
+        .json(&serde_json::json!({
+            "selector": "team=infra",
+            "states": ["idle"],
+            "message": "rebase on main",
+        }))
+        .await;
+    resp.assert_status_ok();
+
+    let body: serde_json::Value = resp.json();
+    assert_eq!(body["matched"], 2);
+    let results = &body["results"];
+    assert_eq!(results[0]["session"], "a");
+    assert_eq!(results[0]["skipped"], false);
+    assert_eq!(results[0]["outcome"]["delivered"], true);
+    assert_eq!(results[0]["outcome"]["message"], "rebase on main");
+    assert_eq!(results[1]["session"], "b");
+    assert_eq!(results[1]["skipped"], true);
+    assert_eq!(results[1]["state"], "working");
+    assert!(results[1].get("outcome").is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn broadcast_respond_reports_upstream_errors() -> anyhow::Result<()> {
+    let state = test_state();
+    let up = fake_upstream("prompt").await?;
+    insert_session(&state, "ok", &up).await;
+    // Nothing listens on port 1.
+    insert_session(&state, "down", "http://127.0.0.1:1").await;
+    let server = test_server(state);
+
+    let resp = server
+        .post("/api/v1/sessions/respond")
+        .json(&serde_json::json!({ "selector": "", "option": 1 }))
+        .await;
+    resp.assert_status_ok();
+
+    let body: serde_json::Value = resp.json();
+    assert_eq!(body["matched"], 2);
+    assert_eq!(body["results"][0]["session"], "down");
+    assert!(body["results"][0]["error"].is_string());
+    assert_eq!(body["results"][1]["session"], "ok");
+    assert_eq!(body["results"][1]["outcome"]["delivered"], true);
+    Ok(())
+}
+
+#[tokio::test]
+async fn broadcast_rejects_bad_requests() -> anyhow::Result<()> {
+    let state = test_state();
+    let up = fake_upstream("idle").await?;
+    insert_session(&state, "a", &up).await;
+    let server = test_server(state);
+
+    for body in [
+        serde_json::json!({ "selector": "team in infra", "message": "hi" }),
+        // A missing or misspelled selector must not fall back to every session.
+        serde_json::json!({ "message": "hi" }),
+        serde_json::json!({ "selecter": "team=infra", "message": "hi" }),
+        // Respond fields are not part of a nudge body.
+        serde_json::json!({ "selector": "", "message": "hi", "option": 1 }),
+    ] {
+        let resp = server.post("/api/v1/sessions/nudge").json(&body).await;
+        assert_eq!(resp.status_code(), 400, "{body}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn metrics_reports_sessions_and_credential_pool() -> anyhow::Result<()> {
+    let accounts = vec![AccountConfig {
+        name: "main".into(),
+        provider: "claude".into(),
+        env_key: None,
+        token_url: None,
+        client_id: None,
+        auth_url: None,
+        device_auth_url: None,
+        reauth: false,
+    }];
+    let state = test_state_with_broker(accounts);
+    insert_session(&state, "s1", "http://fake:3001").await;
+    insert_session(&state, "s2", "http://fake:3002").await;
+    set_cached_state(&state, "s1", "working").await;
+    if let Some(entry) = state.sessions.read().await.get("s2") {
+        entry.health_failures.store(2, std::sync::atomic::Ordering::Relaxed);
+    }
+    state.metrics.record_health_failure();
+    state.metrics.record_distributor_retry(coopmux::metrics::DistributorStep::Switch);
+    state.metrics.record_distributor_pushes(3, 1, 0);
+
+    let server = test_server(state);
+    let resp = server.get("/metrics").await;
+    resp.assert_status_ok();
+    let text = resp.text();
+    for line in [
+        "coopmux_sessions 2\n",
+        "coopmux_session_health_failures{session=\"s2\"} 2\n",
+        "coopmux_session_agent_state{session=\"s1\",state=\"working\"} 1\n",
+        "coopmux_health_check_failures_total 1\n",
+        "coopmux_credential_account_status{account=\"main\",status=\"missing\"} 1\n",
+        "coopmux_credential_account_sessions{account=\"main\"} 0\n",
+        "coopmux_distributor_retries_total{step=\"switch\"} 1\n",
+        "coopmux_distributor_pushes_total{result=\"ok\"} 3\n",
+        "coopmux_distributor_pushes_total{result=\"deferred\"} 1\n",
+    ] {
+        assert!(text.contains(line), "missing {line:?} in:\n{text}");
+    }
+    Ok(())
+}